@@ -7,7 +7,10 @@ use starcoin_vm2_types::{
     account_address::AccountAddress,
     account_config::token_code::TokenCode,
     sign_message::{SignedMessage, SigningMessage},
-    transaction::{RawUserTransaction, SignedUserTransaction},
+    transaction::{
+        authenticator::{AccountAuthenticator, Scheme},
+        RawUserTransaction, SignedUserTransaction,
+    },
 };
 use std::{sync::Arc, time::Duration};
 
@@ -22,8 +25,8 @@ impl AccountRpcProvider {
 }
 
 impl AccountProvider for AccountRpcProvider {
-    fn create_account(&self, password: String) -> anyhow::Result<AccountInfo> {
-        self.rpc.account_create2(password)
+    fn create_account(&self, password: String, key_type: Scheme) -> anyhow::Result<AccountInfo> {
+        self.rpc.account_create2(password, key_type)
     }
 
     fn get_default_account(&self) -> anyhow::Result<Option<AccountInfo>> {
@@ -59,6 +62,26 @@ impl AccountProvider for AccountRpcProvider {
         self.rpc.account_sign_txn2(raw_txn)
     }
 
+    fn sign_fee_payer_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        fee_payer_address: AccountAddress,
+        signer_address: AccountAddress,
+    ) -> anyhow::Result<AccountAuthenticator> {
+        self.rpc
+            .account_sign_fee_payer_txn2(raw_txn, fee_payer_address, signer_address)
+    }
+
+    fn sign_multi_agent_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        signer_address: AccountAddress,
+    ) -> anyhow::Result<AccountAuthenticator> {
+        self.rpc
+            .account_sign_multi_agent_txn2(raw_txn, secondary_signer_addresses, signer_address)
+    }
+
     fn unlock_account(
         &self,
         address: AccountAddress,
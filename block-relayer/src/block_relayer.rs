@@ -4,7 +4,10 @@
 use crate::metrics::BlockRelayerMetrics;
 use anyhow::{ensure, format_err, Result};
 use futures::FutureExt;
-use network_api::messages::{CompactBlockMessage, NotificationMessage, PeerCompactBlockMessage};
+use network_api::messages::{
+    CompactBlockMessage, NotificationMessage, PeerCompactBlockMessage, PeerCompactBlockV2Message,
+    PeerMessage,
+};
 use network_api::{NetworkService, PeerId, PeerProvider, PeerSelector, PeerStrategy};
 use starcoin_chain::verifier::StaticVerifier;
 use starcoin_config::NodeConfig;
@@ -12,7 +15,7 @@ use starcoin_config::G_CRATE_VERSION;
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_network::NetworkServiceRef;
-use starcoin_network_rpc_api::GetTxnsWithHash;
+use starcoin_network_rpc_api::{GetBlockTxns, GetTxnsWithHash, MAX_TXN_REQUEST_SIZE};
 use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
 use starcoin_sync::block_connector::ExecuteService;
 use starcoin_sync::sync::{CheckSyncEvent, SyncService};
@@ -21,24 +24,52 @@ use starcoin_sync_api::PeerNewBlock;
 use starcoin_time_service::TimeService;
 use starcoin_txpool::TxPoolService;
 use starcoin_txpool_api::TxPoolSyncService;
-use starcoin_types::block::ExecutedBlock;
+use starcoin_types::block::{BlockHeader, ExecutedBlock};
 use starcoin_types::sync_status::SyncStatus;
 use starcoin_types::system_events::{NewBranch, NewDagBlock, SyncStatusChangeEvent};
 use starcoin_types::{
     block::{Block, BlockBody},
-    compact_block::{CompactBlock, ShortId},
+    compact_block::{CompactBlock, CompactBlockV2, ShortId, ShortTxnId},
     multi_transaction::MultiSignedUserTransaction,
     system_events::NewHeadBlock,
 };
-use std::collections::{HashMap, HashSet};
+use std::collections::{hash_map::Entry, HashMap, HashSet, VecDeque};
 use std::convert::TryInto;
 use std::sync::Arc;
 
+/// How many recently executed blocks keep their txns as short id reconstruction candidates,
+/// parallel dag blocks often include txns already removed from the txpool.
+const RECENT_BLOCK_TXNS_CACHE_SIZE: usize = 16;
+
+/// How many recently received blocks remember the peer which delivered them first.
+const BLOCK_DELIVERERS_CACHE_SIZE: usize = 256;
+
+/// Compact block received from a peer, with full or salted short transaction ids.
+enum ReceivedCompactBlock {
+    Full(CompactBlock),
+    Short(CompactBlockV2),
+}
+
+impl ReceivedCompactBlock {
+    fn header(&self) -> &BlockHeader {
+        match self {
+            Self::Full(compact_block) => &compact_block.header,
+            Self::Short(compact_block) => &compact_block.header,
+        }
+    }
+}
+
 pub struct BlockRelayer {
     txpool: TxPoolService,
     sync_status: Option<SyncStatus>,
     time_service: Arc<dyn TimeService>,
     metrics: Option<BlockRelayerMetrics>,
+    recent_block_txns: VecDeque<Arc<Vec<MultiSignedUserTransaction>>>,
+    /// Peers which most recently delivered a new block first, most recent at the front.
+    high_bandwidth_peers: VecDeque<PeerId>,
+    max_high_bandwidth_peers: usize,
+    /// The peer which first delivered each recently received block, oldest at the front.
+    block_deliverers: VecDeque<(HashValue, PeerId)>,
 }
 
 impl ServiceFactory<Self> for BlockRelayer {
@@ -50,7 +81,12 @@ impl ServiceFactory<Self> for BlockRelayer {
             .metrics
             .registry()
             .and_then(|registry| BlockRelayerMetrics::register(registry).ok());
-        Ok(Self::new(txpool, time_service, metrics))
+        Ok(Self::new(
+            txpool,
+            time_service,
+            metrics,
+            node_config.network.high_bandwidth_relay_peers() as usize,
+        ))
     }
 }
 
@@ -59,12 +95,17 @@ impl BlockRelayer {
         txpool: TxPoolService,
         time_service: Arc<dyn TimeService>,
         metrics: Option<BlockRelayerMetrics>,
+        max_high_bandwidth_peers: usize,
     ) -> Self {
         Self {
             txpool,
             sync_status: None,
             time_service,
             metrics,
+            recent_block_txns: VecDeque::with_capacity(RECENT_BLOCK_TXNS_CACHE_SIZE),
+            high_bandwidth_peers: VecDeque::with_capacity(max_high_bandwidth_peers),
+            max_high_bandwidth_peers,
+            block_deliverers: VecDeque::with_capacity(BLOCK_DELIVERERS_CACHE_SIZE),
         }
     }
 
@@ -82,18 +123,93 @@ impl BlockRelayer {
         }
     }
 
+    fn remember_block_txns(&mut self, executed_block: &ExecutedBlock) {
+        let body = &executed_block.block().body;
+        if body.transactions.is_empty() && body.transactions2.is_empty() {
+            return;
+        }
+        let txns = body
+            .transactions
+            .iter()
+            .cloned()
+            .map(Into::into)
+            .chain(body.transactions2.iter().cloned().map(Into::into))
+            .collect();
+        if self.recent_block_txns.len() >= RECENT_BLOCK_TXNS_CACHE_SIZE {
+            self.recent_block_txns.pop_back();
+        }
+        self.recent_block_txns.push_front(Arc::new(txns));
+    }
+
+    fn block_deliverer(&self, block_id: &HashValue) -> Option<&PeerId> {
+        self.block_deliverers
+            .iter()
+            .find(|(id, _)| id == block_id)
+            .map(|(_, peer_id)| peer_id)
+    }
+
+    /// Record the peer as a high-bandwidth peer if it is the first to deliver the block,
+    /// later deliveries of the same block do not change the high-bandwidth peers.
+    fn update_high_bandwidth_peers(&mut self, peer_id: &PeerId, block_id: HashValue) {
+        if self.block_deliverer(&block_id).is_some() {
+            return;
+        }
+        if self.block_deliverers.len() >= BLOCK_DELIVERERS_CACHE_SIZE {
+            self.block_deliverers.pop_front();
+        }
+        self.block_deliverers.push_back((block_id, peer_id.clone()));
+        if self.max_high_bandwidth_peers == 0 {
+            return;
+        }
+        self.high_bandwidth_peers.retain(|peer| peer != peer_id);
+        self.high_bandwidth_peers.push_front(peer_id.clone());
+        self.high_bandwidth_peers
+            .truncate(self.max_high_bandwidth_peers);
+    }
+
+    fn observe_block_relay_time(&self, header: &BlockHeader) {
+        let current_timestamp = self.time_service.now_millis();
+        let time = current_timestamp.saturating_sub(header.timestamp());
+        let time_sec: f64 = (time as f64) / 1000_f64;
+        if let Some(metrics) = self.metrics.as_ref() {
+            metrics.block_relay_time.observe(time_sec);
+        }
+        sl_info!(
+            "{action} {hash} {time_sec}",
+            time_sec = time_sec,
+            hash = header.id().to_hex(),
+            action = "block_relay_time",
+        );
+    }
+
     fn broadcast_compact_block(
-        &self,
+        &mut self,
         network: NetworkServiceRef,
         executed_block: Arc<ExecutedBlock>,
     ) {
+        self.remember_block_txns(&executed_block);
         if !self.is_nearly_synced() {
             debug!("[block-relay] Ignore NewHeadBlock event because the node has not been synchronized yet.");
             return;
         }
+        let block_id = executed_block.block().id();
         let compact_block = executed_block.block().clone().into();
         let compact_block_msg =
             CompactBlockMessage::new(compact_block, executed_block.block_info().clone());
+        // High-bandwidth relay: the block is sent to the high-bandwidth peers once it is
+        // connected, so they can fetch its txns from us. The network marks these peers as
+        // knowing the block, and the broadcast below skips them.
+        let deliverer = self.block_deliverer(&block_id);
+        for peer_id in self
+            .high_bandwidth_peers
+            .iter()
+            .filter(|peer_id| Some(*peer_id) != deliverer)
+        {
+            network.send_peer_message(PeerMessage::new_compact_block(
+                peer_id.clone(),
+                compact_block_msg.clone(),
+            ));
+        }
         network.broadcast(NotificationMessage::CompactBlock(Box::new(
             compact_block_msg,
         )));
@@ -206,12 +322,194 @@ impl BlockRelayer {
         Ok(block)
     }
 
-    fn is_future_compact_block(
-        compact_block: &CompactBlock,
-        txpool: &TxPoolService,
-    ) -> Result<bool> {
+    /// Rebuild a block from salted short ids. The prefilled txns take their slots first, the
+    /// remaining slots map to the short ids in order. Short ids are matched against the txpool
+    /// and the recently executed blocks, missing or colliding ones are fetched from the peer, and
+    /// the whole body is fetched if the rebuilt body hash mismatches.
+    async fn fill_compact_block_v2(
+        txpool: TxPoolService,
+        recent_block_txns: Vec<Arc<Vec<MultiSignedUserTransaction>>>,
+        rpc_client: VerifiedRpcClient,
+        compact_block: CompactBlockV2,
+        peer_id: PeerId,
+        metrics: Option<BlockRelayerMetrics>,
+    ) -> Result<Block> {
+        let expect_txn_len = compact_block.txn_len();
+        let block_id = compact_block.header.id();
+        let mut filled_from_txpool: u64 = 0;
+        let mut filled_from_prefilled: u64 = 0;
+        let mut filled_from_network: u64 = 0;
+        let mut collisions: u64 = 0;
+
+        let mut txns: Vec<Option<MultiSignedUserTransaction>> = vec![None; expect_txn_len];
+        for prefilled_txn in compact_block.prefilled_txn {
+            let index = prefilled_txn.index as usize;
+            ensure!(
+                index < expect_txn_len && txns[index].is_none(),
+                "Invalid prefilled txn index {} of compact block {}",
+                index,
+                block_id
+            );
+            txns[index] = Some(prefilled_txn.tx);
+            filled_from_prefilled += 1;
+        }
+
+        let key = compact_block.key();
+        // None marks a short id shared by several candidate txns.
+        let mut candidates: HashMap<ShortTxnId, Option<MultiSignedUserTransaction>> =
+            HashMap::new();
+        let pending_txns = if compact_block.short_ids.is_empty() {
+            vec![]
+        } else {
+            txpool.get_pending_txns(None, None)?
+        };
+        for txn in pending_txns.iter().chain(
+            recent_block_txns
+                .iter()
+                .flat_map(|block_txns| block_txns.iter()),
+        ) {
+            let txn_id = txn.id();
+            match candidates.entry(key.short_id(&txn_id)) {
+                Entry::Vacant(entry) => {
+                    entry.insert(Some(txn.clone()));
+                }
+                Entry::Occupied(mut entry) => {
+                    if matches!(entry.get(), Some(exist) if exist.id() != txn_id) {
+                        entry.insert(None);
+                    }
+                }
+            }
+        }
+
+        let mut missing_indexes = vec![];
+        let mut short_ids = compact_block.short_ids.iter();
+        for (index, txn) in txns.iter_mut().enumerate() {
+            if txn.is_some() {
+                continue;
+            }
+            let short_id = short_ids.next().ok_or_else(|| {
+                format_err!("Compact block {} short ids less than txns", block_id)
+            })?;
+            match candidates.get(short_id) {
+                Some(Some(candidate)) => {
+                    *txn = Some(candidate.clone());
+                    filled_from_txpool += 1;
+                }
+                Some(None) => {
+                    collisions += 1;
+                    missing_indexes.push(index as u64);
+                }
+                None => missing_indexes.push(index as u64),
+            }
+        }
+        ensure!(
+            short_ids.next().is_none(),
+            "Compact block {} short ids more than txns",
+            block_id
+        );
+
+        let fetched_from_network = !missing_indexes.is_empty();
+        if fetched_from_network {
+            let fetched =
+                Self::fetch_block_txns(&rpc_client, &peer_id, block_id, missing_indexes.clone())
+                    .await?;
+            for (index, txn) in missing_indexes.into_iter().zip(fetched) {
+                txns[index as usize] = Some(txn);
+                filled_from_network += 1;
+            }
+        }
+
+        let mut block = Block::new(
+            compact_block.header.clone(),
+            BlockBody::new(
+                txns.into_iter().flatten().collect(),
+                compact_block.uncles.clone(),
+            ),
+        );
+        let mut body_mismatch = false;
+        if StaticVerifier::verify_body_hash(&block).is_err() {
+            // An undetected short id collision, fallback to fetch the whole body from the peer.
+            warn!(
+                "[block-relay] compact block {} rebuilt body mismatch, fetch all txns from peer {}",
+                block_id, peer_id
+            );
+            body_mismatch = true;
+            let txns = Self::fetch_block_txns(
+                &rpc_client,
+                &peer_id,
+                block_id,
+                (0..expect_txn_len as u64).collect(),
+            )
+            .await?;
+            filled_from_network = txns.len() as u64;
+            filled_from_txpool = 0;
+            filled_from_prefilled = 0;
+            block = Block::new(
+                compact_block.header,
+                BlockBody::new(txns, compact_block.uncles),
+            );
+            StaticVerifier::verify_body_hash(&block)?;
+        }
+        StaticVerifier::verify_vm1_offline(&block)?;
+
+        if let Some(metrics) = metrics {
+            metrics
+                .txns_filled_total
+                .with_label_values(&["expect"])
+                .inc_by(expect_txn_len as u64);
+            metrics
+                .txns_filled_total
+                .with_label_values(&["txpool"])
+                .inc_by(filled_from_txpool);
+            metrics
+                .txns_filled_total
+                .with_label_values(&["network"])
+                .inc_by(filled_from_network);
+            metrics
+                .txns_filled_total
+                .with_label_values(&["prefilled"])
+                .inc_by(filled_from_prefilled);
+            let result = if body_mismatch || collisions > 0 {
+                "collision"
+            } else if fetched_from_network {
+                "network"
+            } else {
+                "txpool"
+            };
+            metrics
+                .compact_block_reconstruct_total
+                .with_label_values(&[result])
+                .inc();
+        }
+        Ok(block)
+    }
+
+    async fn fetch_block_txns(
+        rpc_client: &VerifiedRpcClient,
+        peer_id: &PeerId,
+        block_id: HashValue,
+        indexes: Vec<u64>,
+    ) -> Result<Vec<MultiSignedUserTransaction>> {
+        let mut txns = Vec::with_capacity(indexes.len());
+        for chunk in indexes.chunks(MAX_TXN_REQUEST_SIZE as usize) {
+            txns.extend(
+                rpc_client
+                    .get_block_txns(
+                        peer_id.clone(),
+                        GetBlockTxns {
+                            block_id,
+                            indexes: chunk.to_vec(),
+                        },
+                    )
+                    .await?,
+            );
+        }
+        Ok(txns)
+    }
+
+    fn is_future_compact_block(header: &BlockHeader, txpool: &TxPoolService) -> Result<bool> {
         let storage = txpool.get_store();
-        for parent_id in compact_block.header.parents_hash() {
+        for parent_id in header.parents_hash() {
             let header = match storage.get_block_header_by_hash(*parent_id)? {
                 Some(header) => header,
                 None => return Ok(true),
@@ -224,8 +522,9 @@ impl BlockRelayer {
     }
 
     fn handle_block_event(
-        &self,
-        compact_block_msg: PeerCompactBlockMessage,
+        &mut self,
+        peer_id: PeerId,
+        compact_block: ReceivedCompactBlock,
         ctx: &mut ServiceContext<BlockRelayer>,
     ) -> Result<()> {
         let network = ctx.get_shared::<NetworkServiceRef>()?;
@@ -236,12 +535,16 @@ impl BlockRelayer {
         let txpool = self.txpool.clone();
         let metrics = self.metrics.clone();
         let is_synced = self.is_synced();
+        self.update_high_bandwidth_peers(&peer_id, compact_block.header().id());
+        let recent_block_txns = match compact_block {
+            ReceivedCompactBlock::Short(_) => self.recent_block_txns.iter().cloned().collect(),
+            ReceivedCompactBlock::Full(_) => vec![],
+        };
         let fut = async move {
-            let compact_block = compact_block_msg.message.compact_block;
-            let peer_id = compact_block_msg.peer_id;
             debug!("Receive peer compact block event from peer id:{}", peer_id);
-            let block_id = compact_block.header.id();
-            if !is_synced && Self::is_future_compact_block(&compact_block, &txpool)? {
+            let block_id = compact_block.header().id();
+            let is_future = Self::is_future_compact_block(compact_block.header(), &txpool)?;
+            if !is_synced && is_future {
                 info!(
                     "[block-relay] compact block {:?} is future block while node not synced, trigger sync check and skip txn fill",
                     block_id
@@ -268,18 +571,34 @@ impl BlockRelayer {
                     )
                 })?;
                 let peer_selector = PeerSelector::new(vec![peer], PeerStrategy::default(), None);
-                let rpc_client = VerifiedRpcClient::new(peer_selector, network, max_retry_times);
+                let rpc_client =
+                    VerifiedRpcClient::new(peer_selector, network.clone(), max_retry_times);
                 let _timer = metrics
                     .as_ref()
                     .map(|metrics| metrics.txns_filled_time.start_timer());
-                let block = BlockRelayer::fill_compact_block(
-                    txpool.clone(),
-                    rpc_client,
-                    compact_block,
-                    peer_id.clone(),
-                    metrics,
-                )
-                .await?;
+                let block = match compact_block {
+                    ReceivedCompactBlock::Full(compact_block) => {
+                        BlockRelayer::fill_compact_block(
+                            txpool.clone(),
+                            rpc_client,
+                            compact_block,
+                            peer_id.clone(),
+                            metrics,
+                        )
+                        .await?
+                    }
+                    ReceivedCompactBlock::Short(compact_block) => {
+                        BlockRelayer::fill_compact_block_v2(
+                            txpool.clone(),
+                            recent_block_txns,
+                            rpc_client,
+                            compact_block,
+                            peer_id.clone(),
+                            metrics,
+                        )
+                        .await?
+                    }
+                };
 
                 block_connector_service.notify(PeerNewBlock::new(peer_id, block))?;
             }
@@ -375,22 +694,15 @@ impl EventHandler<Self, PeerCompactBlockMessage> for BlockRelayer {
         compact_block_msg: PeerCompactBlockMessage,
         ctx: &mut ServiceContext<BlockRelayer>,
     ) {
-        let block_timestamp = compact_block_msg.message.compact_block.header.timestamp();
-        let current_timestamp = self.time_service.now_millis();
-        let time = current_timestamp.saturating_sub(block_timestamp);
-        let time_sec: f64 = (time as f64) / 1000_f64;
-        if let Some(metrics) = self.metrics.as_ref() {
-            metrics.block_relay_time.observe(time_sec);
-        }
-        sl_info!(
-            "{action} {hash} {time_sec}",
-            time_sec = time_sec,
-            hash = compact_block_msg.message.compact_block.header.id().to_hex(),
-            action = "block_relay_time",
-        );
+        self.observe_block_relay_time(&compact_block_msg.message.compact_block.header);
         //TODO should filter too old block?
 
-        if let Err(e) = self.handle_block_event(compact_block_msg, ctx) {
+        let message = compact_block_msg.message;
+        if let Err(e) = self.handle_block_event(
+            compact_block_msg.peer_id,
+            ReceivedCompactBlock::Full(message.compact_block),
+            ctx,
+        ) {
             if let Some(metrics) = self.metrics.as_ref() {
                 metrics.txns_filled_failed_total.inc();
             }
@@ -401,3 +713,28 @@ impl EventHandler<Self, PeerCompactBlockMessage> for BlockRelayer {
         }
     }
 }
+
+impl EventHandler<Self, PeerCompactBlockV2Message> for BlockRelayer {
+    fn handle_event(
+        &mut self,
+        compact_block_msg: PeerCompactBlockV2Message,
+        ctx: &mut ServiceContext<BlockRelayer>,
+    ) {
+        self.observe_block_relay_time(&compact_block_msg.message.compact_block.header);
+
+        let message = compact_block_msg.message;
+        if let Err(e) = self.handle_block_event(
+            compact_block_msg.peer_id,
+            ReceivedCompactBlock::Short(message.compact_block),
+            ctx,
+        ) {
+            if let Some(metrics) = self.metrics.as_ref() {
+                metrics.txns_filled_failed_total.inc();
+            }
+            error!(
+                "[block-relay] handle PeerCompactBlockV2Message error: {:?}",
+                e
+            );
+        }
+    }
+}
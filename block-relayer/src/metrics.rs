@@ -12,6 +12,7 @@ pub struct BlockRelayerMetrics {
     pub txns_filled_time: Histogram,
     pub block_relay_time: Histogram,
     pub txns_filled_failed_total: UIntCounter,
+    pub compact_block_reconstruct_total: UIntCounterVec,
 }
 
 impl BlockRelayerMetrics {
@@ -47,11 +48,23 @@ impl BlockRelayerMetrics {
             registry,
         )?;
 
+        let compact_block_reconstruct_total = register(
+            UIntCounterVec::new(
+                Opts::new(
+                    "compact_block_reconstruct_total",
+                    "Count of short id compact blocks rebuilt from txpool|network|collision",
+                ),
+                &["result"],
+            )?,
+            registry,
+        )?;
+
         Ok(Self {
             txns_filled_total,
             txns_filled_time,
             block_relay_time,
             txns_filled_failed_total,
+            compact_block_reconstruct_total,
         })
     }
 }
@@ -52,11 +52,61 @@ impl OpenedBlock {
         };
         Ok(())
     }
+
+    /// Commit the pending writes of the vm2 state and return its root, the root identifies the
+    /// state the next pushed txns execute on.
+    pub fn state_root2(&self) -> anyhow::Result<HashValue> {
+        let state_root = self
+            .state
+            .1
+            .commit()
+            .map_err(BlockExecutorError::BlockChainStateErr)?;
+        Ok(state_root)
+    }
+
     pub fn push_txns2(
         &mut self,
         user_txns: Vec<SignedUserTransaction2>,
     ) -> anyhow::Result<ExcludedTxns> {
-        let state = &self.state.1;
+        let txn_outputs = self.execute_txns2(&user_txns)?;
+        self.push_executed_txns2(user_txns, txn_outputs)
+    }
+
+    /// Execute `user_txns` on the current state of this block with the gas left, without
+    /// pushing them. The outputs of the txns which fit in the gas left are returned.
+    pub fn execute_txns2(
+        &self,
+        user_txns: &[SignedUserTransaction2],
+    ) -> anyhow::Result<Vec<TransactionOutput2>> {
+        let txns = user_txns
+            .iter()
+            .cloned()
+            .map(Transaction2::UserTransaction)
+            .collect::<Vec<_>>();
+        let outputs = do_execute_block_transactions(
+            self.state.1.as_ref(),
+            txns,
+            Some(self.checked_gas_left()?),
+            self.vm_metrics.clone(),
+        )
+        .map_err(BlockExecutorError::BlockTransactionExecuteErr)?;
+        Ok(outputs)
+    }
+
+    /// Push `user_txns` with the `txn_outputs` they got by executing on the current state of this
+    /// block with the current gas left, e.g. by `execute_txns2`.
+    pub fn push_executed_txns2(
+        &mut self,
+        user_txns: Vec<SignedUserTransaction2>,
+        txn_outputs: Vec<TransactionOutput2>,
+    ) -> anyhow::Result<ExcludedTxns> {
+        if txn_outputs.len() > user_txns.len() {
+            bail!(
+                "got {} outputs for {} txns",
+                txn_outputs.len(),
+                user_txns.len()
+            );
+        }
         let mut txns = user_txns
             .into_iter()
             .map(Transaction2::UserTransaction)
@@ -64,23 +114,6 @@ impl OpenedBlock {
         let mut discarded_txns: Vec<MultiSignedUserTransaction> = Vec::new();
         let mut untouched_txns: Vec<MultiSignedUserTransaction> = Vec::new();
 
-        let txn_outputs = {
-            let gas_left = self.gas_limit.checked_sub(self.gas_used).ok_or_else(|| {
-                format_err!(
-                    "block gas_used {} exceed block gas_limit:{}",
-                    self.gas_used,
-                    self.gas_limit
-                )
-            })?;
-            do_execute_block_transactions(
-                state,
-                txns.clone(),
-                Some(gas_left),
-                self.vm_metrics.clone(),
-            )
-            .map_err(BlockExecutorError::BlockTransactionExecuteErr)?
-        };
-
         if txn_outputs.len() < txns.len() {
             untouched_txns = txns
                 .drain(txn_outputs.len()..)
@@ -125,6 +158,16 @@ impl OpenedBlock {
         })
     }
 
+    fn checked_gas_left(&self) -> anyhow::Result<u64> {
+        self.gas_limit.checked_sub(self.gas_used).ok_or_else(|| {
+            format_err!(
+                "block gas_used {} exceed block gas_limit:{}",
+                self.gas_used,
+                self.gas_limit
+            )
+        })
+    }
+
     pub fn finalize_block_epilogue(&mut self) -> anyhow::Result<()> {
         let (_state, state) = &self.state;
         // Directly use VM2 BlockEpilogue
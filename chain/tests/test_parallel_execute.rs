@@ -0,0 +1,109 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, Result};
+use starcoin_chain::{ChainReader, ChainWriter};
+use starcoin_consensus::Consensus;
+use starcoin_state_api::ChainStateWriter;
+use starcoin_statedb::ChainStateDB;
+use starcoin_transaction_builder::{peer_to_peer_txn_sent_as_association, DEFAULT_EXPIRATION_TIME};
+use starcoin_types::account_address::AccountAddress;
+use starcoin_types::block_metadata;
+use starcoin_types::transaction::Transaction;
+use starcoin_vm_runtime::parallel_executor::ParallelStarcoinVM;
+use starcoin_vm_runtime::starcoin_vm::StarcoinVM;
+use starcoin_vm_types::account_config::association_address;
+use starcoin_vm_types::state_view::StateReaderExt;
+use starcoin_vm_types::vm_status::KeptVMStatus;
+use test_helper::chain::vm1_testnet;
+use test_helper::gen_blockchain_for_test;
+
+/// Replay the vm1 blocks committed by the chain with the block-STM executor, the outputs and the
+/// state roots must be the same as the sequential execution recorded by the chain.
+#[stest::test]
+fn test_parallel_execute_same_as_sequential() -> Result<()> {
+    let net = vm1_testnet()?;
+    let mut chain = gen_blockchain_for_test(&net)?;
+    let miner = AccountAddress::random();
+    let receivers = (0..4).map(|_| AccountAddress::random()).collect::<Vec<_>>();
+
+    // All the txns of a block are sent by the association, so they read and write the same
+    // account and conflict with each other.
+    let blocks = 3;
+    for _ in 0..blocks {
+        let sequence_number = chain
+            .chain_state_reader()
+            .get_sequence_number(association_address())?;
+        let txns = receivers
+            .iter()
+            .enumerate()
+            .map(|(index, receiver)| {
+                peer_to_peer_txn_sent_as_association(
+                    *receiver,
+                    sequence_number + index as u64,
+                    1000,
+                    net.time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+                    &net,
+                )
+                .into()
+            })
+            .collect();
+        let (template, excluded) = chain.create_block_template_simple_with_txns(miner, txns)?;
+        assert!(excluded.discarded_txns.is_empty());
+        let block = chain
+            .consensus()
+            .create_block(template, net.time_service().as_ref())?;
+        chain.apply(block)?;
+    }
+
+    let storage = chain.get_storage();
+    for number in 1..=blocks {
+        let block = chain
+            .get_block_by_number(number)?
+            .ok_or_else(|| anyhow!("block {} should exist", number))?;
+        let parent = chain
+            .get_block(block.header().parent_hash())?
+            .ok_or_else(|| anyhow!("parent of block {} should exist", number))?;
+        assert_eq!(block.transactions().len(), receivers.len());
+
+        let mut txns = vec![Transaction::BlockMetadata(block_metadata::from(
+            block.to_metadata(parent.header().gas_used(), 0),
+        ))];
+        txns.extend(
+            block
+                .transactions()
+                .iter()
+                .cloned()
+                .map(Transaction::UserTransaction),
+        );
+
+        let parent_state_root = storage.get_vm_multi_state(parent.id())?.state_root1();
+        let state = ChainStateDB::new(storage.clone().into_super_arc(), Some(parent_state_root));
+        let block_gas_limit = Some(chain.epoch().block_gas_limit());
+        let sequential_outputs = StarcoinVM::execute_block_and_keep_vm_status(
+            txns.clone(),
+            &state,
+            block_gas_limit,
+            None,
+        )
+        .map_err(|e| anyhow!("sequential execute failed: {:?}", e))?
+        .into_iter()
+        .map(|(_vm_status, output)| output)
+        .collect::<Vec<_>>();
+        let (parallel_outputs, err) =
+            ParallelStarcoinVM::execute_block(txns, &state, 4, block_gas_limit, None)
+                .map_err(|e| anyhow!("parallel execute failed: {:?}", e))?;
+        assert!(err.is_none(), "parallel execution fell back: {:?}", err);
+        assert_eq!(sequential_outputs, parallel_outputs);
+
+        for output in parallel_outputs {
+            assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+            state.apply_write_set(output.write_set().clone())?;
+        }
+        assert_eq!(
+            state.commit()?,
+            storage.get_vm_multi_state(block.id())?.state_root1()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,530 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Chunked and self-verifying block archive.
+//!
+//! An archive is a directory holding a `manifest.json` and one gzip compressed BCS file per chunk
+//! of `chunk_size` blocks. Every chunk carries the blocks with their block infos and transaction
+//! infos, and the block accumulator root after its last block. On import the chunk is replayed on
+//! top of the local block and transaction accumulators, and must match every header and the
+//! chunk root before any of its blocks is applied.
+
+use anyhow::{ensure, format_err, Result};
+use bcs_ext::BCSCodec;
+use clap::Parser;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::{Deserialize, Serialize};
+use starcoin_accumulator::{node::AccumulatorStoreType, Accumulator, MerkleAccumulator};
+use starcoin_chain::verifier::{
+    BasicVerifier, ConsensusVerifier, FullVerifier, NoneVerifier, StaticVerifier, Verifier,
+};
+use starcoin_chain::{BlockChain, ChainReader};
+use starcoin_config::{BuiltinNetworkID, ChainNetwork, RocksdbConfig};
+use starcoin_crypto::HashValue;
+use starcoin_genesis::Genesis;
+use starcoin_storage::{
+    block_info::BlockInfoStore, cache_storage::CacheStorage, db_storage::DBStorage,
+    storage::StorageInstance, BlockStore, Storage, StorageVersion, Store,
+};
+use starcoin_types::block::{Block, BlockInfo, BlockNumber};
+use starcoin_types::genesis_config::ChainId;
+use starcoin_types::startup_info::StartupInfo;
+use starcoin_types::transaction::StcRichTransactionInfo;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+pub const ARCHIVE_MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Debug, Clone, Parser)]
+#[clap(
+    name = "export-archive",
+    about = "export block range as verifiable archive chunks"
+)]
+pub struct ExportArchiveOptions {
+    #[clap(long, short = 'n')]
+    /// Chain Network, like main, proxima
+    pub net: BuiltinNetworkID,
+    #[clap(long, short = 'o', parse(from_os_str))]
+    /// output archive dir, the manifest and chunk files are written into it.
+    pub output: PathBuf,
+    #[clap(long, short = 'i', parse(from_os_str))]
+    /// starcoin node db path. like ~/.starcoin/main
+    pub db_path: PathBuf,
+    #[clap(long, short = 's', default_value = "1")]
+    /// first block number of the archive, the genesis block is never archived.
+    pub start: BlockNumber,
+    #[clap(long, short = 'e')]
+    /// last block number of the archive, default is the current head.
+    pub end: Option<BlockNumber>,
+    #[clap(long, default_value = "1000")]
+    /// block count of each chunk file.
+    pub chunk_size: u64,
+}
+
+#[derive(Debug, Parser)]
+#[clap(
+    name = "import-archive",
+    about = "verify and apply blocks from archive chunks"
+)]
+pub struct ImportArchiveOptions {
+    #[clap(long, short = 'n')]
+    /// Chain Network
+    pub net: BuiltinNetworkID,
+    #[clap(long, short = 'o', parse(from_os_str))]
+    /// starcoin node db path. like ~/.starcoin/main
+    pub to_path: PathBuf,
+    #[clap(long, short = 'i', parse(from_os_str))]
+    /// input archive dir, which contains the manifest.json
+    pub input_path: PathBuf,
+    #[clap(possible_values = Verifier::variants(), ignore_case = true)]
+    /// Verify type:  Basic, Consensus, Full, None, eg.
+    pub verifier: Option<Verifier>,
+}
+
+/// Index of an archive, lists the chunks in block number order.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub chain_id: ChainId,
+    pub chunk_size: u64,
+    pub chunks: Vec<ArchiveChunkMeta>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ArchiveChunkMeta {
+    pub file_name: String,
+    pub start_number: BlockNumber,
+    pub end_number: BlockNumber,
+    pub block_accumulator_root: HashValue,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ArchivedBlock {
+    pub block: Block,
+    pub block_info: BlockInfo,
+    pub txn_infos: Vec<StcRichTransactionInfo>,
+}
+
+impl ArchivedBlock {
+    /// Load the block info and the transaction infos of `block` from `storage`.
+    pub fn load(storage: &dyn Store, block: Block) -> Result<Self> {
+        let block_info = storage
+            .get_block_info(block.id())?
+            .ok_or_else(|| format_err!("{} get block info error", block.header().number()))?;
+        let txn_infos = storage.get_block_transaction_infos(block.id())?;
+        Ok(Self {
+            block,
+            block_info,
+            txn_infos,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ArchiveChunk {
+    pub chain_id: ChainId,
+    pub blocks: Vec<ArchivedBlock>,
+    /// Block accumulator root after the last block of the chunk.
+    pub block_accumulator_root: HashValue,
+}
+
+impl ArchiveChunk {
+    pub fn start_number(&self) -> Option<BlockNumber> {
+        self.blocks
+            .first()
+            .map(|block| block.block.header().number())
+    }
+
+    pub fn end_number(&self) -> Option<BlockNumber> {
+        self.blocks
+            .last()
+            .map(|block| block.block.header().number())
+    }
+
+    pub fn write_to(&self, path: &Path) -> Result<()> {
+        let mut encoder = GzEncoder::new(BufWriter::new(File::create(path)?), Compression::best());
+        encoder.write_all(&self.encode()?)?;
+        encoder.finish()?.flush()?;
+        Ok(())
+    }
+
+    pub fn read_from(path: &Path) -> Result<Self> {
+        let mut bytes = vec![];
+        GzDecoder::new(BufReader::new(File::open(path)?)).read_to_end(&mut bytes)?;
+        Self::decode(&bytes)
+    }
+
+    /// Replay the chunk on the accumulators of the block before it, and check every block
+    /// links to its parent and matches the roots committed in the headers.
+    pub fn verify(&self, storage: &dyn Store) -> Result<()> {
+        let first = self
+            .blocks
+            .first()
+            .ok_or_else(|| format_err!("empty archive chunk"))?;
+        let parent_id = first.block.header().parent_hash();
+        let parent_info = storage.get_block_info(parent_id)?.ok_or_else(|| {
+            format_err!(
+                "parent block {} of chunk start {} not found, import the previous chunks first",
+                parent_id,
+                first.block.header().number()
+            )
+        })?;
+        let block_accumulator = MerkleAccumulator::new_with_info(
+            parent_info.block_accumulator_info.clone(),
+            storage.get_accumulator_store(AccumulatorStoreType::Block),
+        );
+        let txn_accumulator = MerkleAccumulator::new_with_info(
+            parent_info.txn_accumulator_info.clone(),
+            storage.get_accumulator_store(AccumulatorStoreType::Transaction),
+        );
+        let mut parent_id = parent_id;
+        for archived in &self.blocks {
+            let header = archived.block.header();
+            let block_id = header.id();
+            ensure!(
+                header.parent_hash() == parent_id,
+                "block {} parent {} mismatch, expect {}",
+                block_id,
+                header.parent_hash(),
+                parent_id
+            );
+            ensure!(
+                archived.block_info.block_id == block_id,
+                "block info {} not belong to block {}",
+                archived.block_info.block_id,
+                block_id
+            );
+            StaticVerifier::verify_body_hash(&archived.block)?;
+
+            ensure!(
+                block_accumulator.root_hash() == header.block_accumulator_root(),
+                "block {} block accumulator root mismatch",
+                block_id
+            );
+            let block_accumulator_root = block_accumulator.append(&[block_id])?;
+            ensure!(
+                block_accumulator_root
+                    == archived.block_info.block_accumulator_info.accumulator_root,
+                "block {} block info block accumulator root mismatch",
+                block_id
+            );
+
+            let mut txn_infos = archived.txn_infos.iter().collect::<Vec<_>>();
+            txn_infos.sort_by_key(|info| info.transaction_global_index);
+            for (offset, info) in txn_infos.iter().enumerate() {
+                ensure!(
+                    info.block_id == block_id
+                        && info.transaction_global_index
+                            == txn_accumulator.num_leaves() + offset as u64,
+                    "block {} txn info {} out of order",
+                    block_id,
+                    info.transaction_info.id()
+                );
+            }
+            let txn_info_ids = txn_infos
+                .iter()
+                .map(|info| info.transaction_info.id())
+                .collect::<Vec<_>>();
+            let txn_accumulator_root = txn_accumulator.append(&txn_info_ids)?;
+            ensure!(
+                txn_accumulator_root == header.txn_accumulator_root()
+                    && txn_accumulator_root
+                        == archived.block_info.txn_accumulator_info.accumulator_root,
+                "block {} txn accumulator root mismatch",
+                block_id
+            );
+            parent_id = block_id;
+        }
+        ensure!(
+            block_accumulator.root_hash() == self.block_accumulator_root,
+            "chunk block accumulator root {} mismatch, expect {}",
+            block_accumulator.root_hash(),
+            self.block_accumulator_root
+        );
+        Ok(())
+    }
+}
+
+fn chunk_file_name(start: BlockNumber, end: BlockNumber) -> String {
+    format!("blocks_{:012}_{:012}.bcs.gz", start, end)
+}
+
+fn progress_bar(len: u64) -> ProgressBar {
+    let bar = ProgressBar::new(len);
+    bar.set_style(
+        ProgressStyle::default_bar()
+            .template("[{elapsed_precise}] {bar:100.cyan/blue} {percent}% {msg}"),
+    );
+    bar
+}
+
+pub fn export_archive(
+    from_dir: PathBuf,
+    output: PathBuf,
+    network: BuiltinNetworkID,
+    start: BlockNumber,
+    end: Option<BlockNumber>,
+    chunk_size: u64,
+) -> Result<()> {
+    ensure!(chunk_size > 0, "chunk size must be positive");
+    ensure!(start > 0, "the genesis block can not be archived");
+    let net = ChainNetwork::new_builtin(network);
+    let db_storage = DBStorage::open_with_cfs(
+        from_dir.join("starcoindb/db/starcoindb"),
+        StorageVersion::current_version()
+            .get_column_family_names()
+            .to_vec(),
+        true,
+        Default::default(),
+        None,
+    )?;
+    let storage = Arc::new(Storage::new(StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(None),
+        db_storage,
+    ))?);
+    let (chain_info, _) =
+        Genesis::init_and_check_storage(&net, storage.clone(), from_dir.as_ref())?;
+    let chain = BlockChain::new(
+        net.time_service(),
+        chain_info.head().id(),
+        storage.clone(),
+        None,
+    )
+    .expect("create block chain should success.");
+    let cur_num = chain.status().head().number();
+    let end = end.unwrap_or(cur_num);
+    ensure!(
+        start <= end && end <= cur_num,
+        "cur_num {} start {} end {} illegal",
+        cur_num,
+        start,
+        end
+    );
+    std::fs::create_dir_all(&output)?;
+
+    let start_time = SystemTime::now();
+    let bar = progress_bar(end - start + 1);
+    let mut chunks = vec![];
+    let mut chunk_start = start;
+    while chunk_start <= end {
+        let chunk_end = chunk_start.saturating_add(chunk_size - 1).min(end);
+        let mut blocks = Vec::with_capacity((chunk_end - chunk_start + 1) as usize);
+        for number in chunk_start..=chunk_end {
+            let block = chain
+                .get_block_by_number(number)?
+                .ok_or_else(|| format_err!("{} get block error", number))?;
+            blocks.push(ArchivedBlock::load(storage.as_ref(), block)?);
+            bar.set_message(format!("load block {}", number));
+            bar.inc(1);
+        }
+        let block_accumulator_root = blocks
+            .last()
+            .map(|block| block.block_info.block_accumulator_info.accumulator_root)
+            .expect("chunk is not empty");
+        let chunk = ArchiveChunk {
+            chain_id: net.chain_id(),
+            blocks,
+            block_accumulator_root,
+        };
+        let file_name = chunk_file_name(chunk_start, chunk_end);
+        chunk.write_to(&output.join(&file_name))?;
+        chunks.push(ArchiveChunkMeta {
+            file_name,
+            start_number: chunk_start,
+            end_number: chunk_end,
+            block_accumulator_root,
+        });
+        chunk_start = chunk_end + 1;
+    }
+    bar.finish();
+
+    let manifest = ArchiveManifest {
+        chain_id: net.chain_id(),
+        chunk_size,
+        chunks,
+    };
+    let mut file = File::create(output.join(ARCHIVE_MANIFEST_FILE))?;
+    writeln!(file, "{}", serde_json::to_string_pretty(&manifest)?)?;
+    file.flush()?;
+    let use_time = SystemTime::now().duration_since(start_time)?;
+    println!(
+        "export archive [{}..{}] in {} chunks use time: {:?}",
+        start,
+        end,
+        manifest.chunks.len(),
+        use_time.as_secs()
+    );
+    Ok(())
+}
+
+pub fn import_archive(
+    to_dir: PathBuf,
+    input_path: PathBuf,
+    network: BuiltinNetworkID,
+    verifier: Verifier,
+) -> Result<()> {
+    ::starcoin_logger::init();
+    let net = ChainNetwork::new_builtin(network);
+    let manifest: ArchiveManifest = serde_json::from_reader(BufReader::new(File::open(
+        input_path.join(ARCHIVE_MANIFEST_FILE),
+    )?))?;
+    ensure!(
+        manifest.chain_id == net.chain_id(),
+        "archive chain id {} mismatch with net {}",
+        manifest.chain_id,
+        net.chain_id()
+    );
+    let db_storage = DBStorage::new(to_dir.join("starcoindb/db"), RocksdbConfig::default(), None)?;
+    let storage = Arc::new(Storage::new(StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(None),
+        db_storage,
+    ))?);
+    let (chain_info, _) = Genesis::init_and_check_storage(&net, storage.clone(), to_dir.as_ref())?;
+    let mut chain = BlockChain::new(
+        net.time_service(),
+        chain_info.head().id(),
+        storage.clone(),
+        None,
+    )
+    .expect("create block chain should success.");
+    let cur_num = chain.status().head().number();
+    let start_time = SystemTime::now();
+    let chunks = manifest
+        .chunks
+        .into_iter()
+        .filter(|meta| meta.end_number > cur_num)
+        .collect::<Vec<_>>();
+    if chunks.is_empty() {
+        println!("archive {} has apply", input_path.display());
+        return Ok(());
+    }
+    let bar = progress_bar(chunks.len() as u64);
+    for meta in chunks {
+        let chunk = ArchiveChunk::read_from(&input_path.join(&meta.file_name))?;
+        ensure!(
+            chunk.chain_id == net.chain_id()
+                && chunk.start_number() == Some(meta.start_number)
+                && chunk.end_number() == Some(meta.end_number)
+                && chunk.block_accumulator_root == meta.block_accumulator_root,
+            "chunk file {} mismatch with manifest",
+            meta.file_name
+        );
+        chunk.verify(storage.as_ref())?;
+        for archived in chunk.blocks {
+            let block_number = archived.block.header().number();
+            if block_number <= cur_num {
+                continue;
+            }
+            let block_hash = archived.block.id();
+            let executed_block = match verifier {
+                Verifier::Basic => chain.apply_with_verifier::<BasicVerifier>(archived.block)?,
+                Verifier::Consensus => {
+                    chain.apply_with_verifier::<ConsensusVerifier>(archived.block)?
+                }
+                Verifier::Full => chain.apply_with_verifier::<FullVerifier>(archived.block)?,
+                Verifier::None => chain.apply_with_verifier::<NoneVerifier>(archived.block)?,
+            };
+            ensure!(
+                executed_block.block_info() == &archived.block_info,
+                "block {} executed block info mismatch with archive",
+                block_number
+            );
+            // apply block then flush startup_info for breakpoint resume
+            storage.save_startup_info(StartupInfo::new(block_hash))?;
+            bar.set_message(format!("apply block {}", block_number));
+        }
+        bar.inc(1);
+    }
+    bar.finish();
+    let use_time = SystemTime::now().duration_since(start_time)?;
+    println!("import archive use time: {:?}", use_time.as_secs());
+    let chain_info = storage
+        .get_chain_info()?
+        .ok_or_else(|| format_err!("{}", "get chain_info error"))?;
+    println!("chain_info {}", chain_info);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ArchiveChunk, ArchivedBlock};
+    use bcs_ext::BCSCodec;
+    use starcoin_chain::{BlockChain, ChainReader};
+    use starcoin_config::{temp_dir, ChainNetwork};
+    use starcoin_crypto::HashValue;
+    use test_helper::chain::gen_blockchain_with_blocks_for_test;
+
+    /// A chain of 3 blocks, and the chunk archiving them.
+    fn archive_chunk() -> (BlockChain, ArchiveChunk) {
+        let net = ChainNetwork::new_test();
+        let chain = gen_blockchain_with_blocks_for_test(3, &net).unwrap();
+        let blocks = (1..=3)
+            .map(|number| {
+                let block = chain.get_block_by_number(number).unwrap().unwrap();
+                ArchivedBlock::load(chain.get_storage().as_ref(), block).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let block_accumulator_root = blocks[2].block_info.block_accumulator_info.accumulator_root;
+        let chunk = ArchiveChunk {
+            chain_id: net.chain_id(),
+            blocks,
+            block_accumulator_root,
+        };
+        (chain, chunk)
+    }
+
+    fn verify_err(chain: &BlockChain, chunk: &ArchiveChunk) -> String {
+        chunk
+            .verify(chain.get_storage().as_ref())
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn test_write_and_read_chunk() {
+        let (chain, chunk) = archive_chunk();
+        let dir = temp_dir();
+        let path = dir.path().join("chunk.bcs.gz");
+        chunk.write_to(&path).unwrap();
+        let read = ArchiveChunk::read_from(&path).unwrap();
+        assert_eq!(read.encode().unwrap(), chunk.encode().unwrap());
+        assert_eq!(read.start_number(), Some(1));
+        assert_eq!(read.end_number(), Some(3));
+        read.verify(chain.get_storage().as_ref()).unwrap();
+    }
+
+    #[test]
+    fn test_verify_tampered_accumulator_root() {
+        let (chain, mut chunk) = archive_chunk();
+        chunk.block_accumulator_root = HashValue::random();
+        assert!(verify_err(&chain, &chunk).contains("chunk block accumulator root"));
+
+        let (chain, mut chunk) = archive_chunk();
+        chunk.blocks[1]
+            .block_info
+            .block_accumulator_info
+            .accumulator_root = HashValue::random();
+        assert!(verify_err(&chain, &chunk).contains("block info block accumulator root mismatch"));
+    }
+
+    #[test]
+    fn test_verify_wrong_parent() {
+        let (chain, mut chunk) = archive_chunk();
+        chunk.blocks.remove(1);
+        assert!(verify_err(&chain, &chunk).contains("parent"));
+    }
+
+    #[test]
+    fn test_verify_reordered_txn_info() {
+        let (chain, mut chunk) = archive_chunk();
+        let first = chunk.blocks[0].txn_infos.clone();
+        chunk.blocks[0].txn_infos = chunk.blocks[1].txn_infos.clone();
+        chunk.blocks[1].txn_infos = first;
+        assert!(verify_err(&chain, &chunk).contains("out of order"));
+    }
+}
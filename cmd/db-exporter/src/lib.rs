@@ -1,6 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod block_archive;
 pub mod cmd_batch_execution;
 pub mod force_deploy_output;
 pub mod verify_header;
@@ -5,6 +5,9 @@ use anyhow::{bail, format_err, Result};
 use bcs_ext::{BCSCodec, Sample};
 use clap::{IntoApp, Parser};
 use csv::Writer;
+use db_exporter::block_archive::{
+    export_archive, import_archive, ExportArchiveOptions, ImportArchiveOptions,
+};
 use db_exporter::force_deploy_output::{force_deploy_output, ForceDeployOutput};
 use db_exporter::{
     verify_header::{verify_header_via_export_file, VerifyHeaderOptions},
@@ -246,6 +249,8 @@ enum Cmd {
     SaveStartupInfo(SaveStartupInfoOptions),
     TokenSupply(TokenSupplyOptions),
     ForceDeploy(ForceDeployOutput),
+    ExportArchive(ExportArchiveOptions),
+    ImportArchive(ImportArchiveOptions),
 }
 
 #[derive(Debug, Clone, Parser)]
@@ -731,6 +736,20 @@ async fn main() -> anyhow::Result<()> {
             let result = save_startup_info(option.to_path, option.net, option.hash_value);
             return result;
         }
+        Cmd::ExportArchive(option) => {
+            return export_archive(
+                option.db_path,
+                option.output,
+                option.net,
+                option.start,
+                option.end,
+                option.chunk_size,
+            );
+        }
+        Cmd::ImportArchive(option) => {
+            let verifier = option.verifier.unwrap_or(Verifier::Basic);
+            return import_archive(option.to_path, option.input_path, option.net, verifier);
+        }
         Cmd::TokenSupply(option) => {
             let result = token_supply(
                 option.db_path,
@@ -844,7 +863,7 @@ pub fn apply_block(
         CacheStorage::new(None),
         db_storage,
     ))?);
-    // StarcoinVM::set_concurrency_level_once(num_cpus::get());
+    // StarcoinVM::set_concurrency_level(num_cpus::get());
     let (chain_info, _) = Genesis::init_and_check_storage(&net, storage.clone(), to_dir.as_ref())?;
     let mut chain = BlockChain::new(
         net.time_service(),
@@ -2197,7 +2216,7 @@ pub fn apply_turbo_stm_block(
     }
     let mut block_hash = HashValue::zero();
     let start_time = SystemTime::now();
-    StarcoinVM::set_concurrency_level_once(num_cpus::get());
+    StarcoinVM::set_concurrency_level(num_cpus::get());
     for item in blocks.iter().skip(4) {
         let block = item.clone();
         block_hash = block.header().id();
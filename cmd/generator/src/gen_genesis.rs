@@ -3,16 +3,20 @@
 
 use crate::cli_state::CliState;
 use crate::init_or_load_data_dir;
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use clap::Parser;
 use scmd::{CommandAction, ExecContext};
 use serde::{Deserialize, Serialize};
 use starcoin_account_api::AccountInfo;
-use starcoin_config::ChainNetworkID;
-use starcoin_config::StarcoinOpt;
+use starcoin_config::genesis_config::GenesisAllocations;
+use starcoin_config::{
+    ChainNetworkID, NodeConfig, StarcoinOpt, G_GENESIS_CONFIG_FILE_NAME,
+    G_GENESIS_CONFIG_FILE_NAME_2,
+};
 use starcoin_crypto::HashValue;
+use starcoin_genesis::Genesis;
 use starcoin_logger::prelude::*;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Generate starcoin config, account, storage and genesis in data_dir
 #[derive(Debug, Parser)]
@@ -21,6 +25,11 @@ pub struct GenGenesisOpt {
     ///Default account password, default is empty string.
     #[clap(long, short = 's')]
     password: Option<String>,
+
+    /// Genesis allocations file in json or toml format, the accounts in it are funded, and the
+    /// packages published, in the genesis block. Only for dev and custom networks.
+    #[clap(long)]
+    allocations: Option<PathBuf>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -48,6 +57,9 @@ impl CommandAction for GenGenesisCommand {
         if global_opt.base_data_dir.is_none() {
             warn!("data_dir option is none, use default data_dir.")
         }
+        if let Some(allocations_file) = opt.allocations.as_ref() {
+            write_genesis_allocations(global_opt, allocations_file.as_path())?;
+        }
         let (config, .., chain_info, account, _dag) =
             init_or_load_data_dir(global_opt, opt.password.clone())?;
         Ok(GenGenesisResult {
@@ -58,3 +70,34 @@ impl CommandAction for GenGenesisCommand {
         })
     }
 }
+
+/// Write the allocations into the genesis config files of the data dir, before the genesis is
+/// generated from them.
+fn write_genesis_allocations(global_opt: &StarcoinOpt, allocations_file: &Path) -> Result<()> {
+    let config = NodeConfig::load_with_opt(global_opt)?;
+    let net = config.net();
+    ensure!(
+        net.is_dev() || net.is_custom(),
+        "Genesis allocations are only supported by dev and custom networks, current is {}",
+        net
+    );
+    ensure!(
+        Genesis::load_from_dir(config.data_dir())?.is_none(),
+        "The genesis of data dir {:?} is already generated",
+        config.data_dir()
+    );
+    let allocations = GenesisAllocations::load(allocations_file)?;
+    let mut genesis_config = net.genesis_config().clone();
+    genesis_config.allocations = allocations.vm1_allocations()?;
+    genesis_config.save(config.data_dir().join(G_GENESIS_CONFIG_FILE_NAME))?;
+    let mut genesis_config2 = net.genesis_config2().clone();
+    genesis_config2.allocations = allocations.vm2_allocations()?;
+    genesis_config2.save(config.data_dir().join(G_GENESIS_CONFIG_FILE_NAME_2))?;
+    info!(
+        "Write {} vm1 and {} vm2 genesis allocations from {:?}",
+        genesis_config.allocations.len(),
+        genesis_config2.allocations.len(),
+        allocations_file
+    );
+    Ok(())
+}
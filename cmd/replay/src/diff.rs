@@ -0,0 +1,498 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Re-execute the vm1 and vm2 transactions of a block on the state of its parent, and compare the
+//! outputs with the transaction infos recorded in the source storage, to pinpoint the first
+//! diverging transaction and the state written differently.
+
+use anyhow::{format_err, Result};
+use serde::Serialize;
+use starcoin_config::upgrade_config::vm1_offline_height;
+use starcoin_crypto::HashValue;
+use starcoin_executor::execute_block_transactions;
+use starcoin_state_api::ChainStateWriter;
+use starcoin_statedb::ChainStateDB;
+use starcoin_storage::{
+    BlockStore, BlockTransactionInfoStore, IntoSuper, Storage, Storage2, Store, Store2,
+    TransactionStore,
+};
+use starcoin_types::block::Block;
+use starcoin_types::block_metadata;
+use starcoin_types::transaction::{Transaction, TransactionInfo};
+use starcoin_vm2_chain::{build_block_transactions, get_epoch_from_statedb};
+use starcoin_vm2_state_api::ChainStateReader as ChainStateReader2;
+use starcoin_vm2_statedb::ChainStateDB as ChainStateDB2;
+use starcoin_vm2_types::transaction::{
+    Transaction as Transaction2, TransactionInfo as TransactionInfo2,
+};
+use starcoin_vm_types::state_store::state_key::StateKey;
+use starcoin_vm_types::state_view::StateView;
+use starcoin_vm_types::transaction::TransactionStatus;
+use starcoin_vm_types::write_set::WriteOp;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Output of a transaction, recorded in the source storage or re-executed.
+#[derive(Clone, Debug, Serialize)]
+pub struct TxnOutputView {
+    pub txn_hash: HashValue,
+    pub status: String,
+    pub gas_used: u64,
+    pub event_root_hash: HashValue,
+    pub state_root: Option<HashValue>,
+    /// State written by the transaction, only known for the re-executed ones.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub write_set: Vec<StateWriteView>,
+}
+
+impl TxnOutputView {
+    /// Whether the outputs differ, the state roots are only compared when both are recorded.
+    fn diverges(&self, other: &Self) -> bool {
+        self.txn_hash != other.txn_hash
+            || self.status != other.status
+            || self.gas_used != other.gas_used
+            || self.event_root_hash != other.event_root_hash
+            || matches!((self.state_root, other.state_root), (Some(a), Some(b)) if a != b)
+    }
+}
+
+impl From<&TransactionInfo> for TxnOutputView {
+    fn from(info: &TransactionInfo) -> Self {
+        Self {
+            txn_hash: info.transaction_hash(),
+            status: format!("{:?}", info.status()),
+            gas_used: info.gas_used(),
+            event_root_hash: info.event_root_hash(),
+            state_root: info.state_root_hash(),
+            write_set: vec![],
+        }
+    }
+}
+
+impl From<&TransactionInfo2> for TxnOutputView {
+    fn from(info: &TransactionInfo2) -> Self {
+        Self {
+            txn_hash: info.transaction_hash(),
+            status: format!("{:?}", info.status()),
+            gas_used: info.gas_used(),
+            event_root_hash: info.event_root_hash(),
+            state_root: info.state_root_hash(),
+            write_set: vec![],
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct StateWriteView {
+    pub key: String,
+    /// Hex encoded value, none for deletion.
+    pub value: Option<String>,
+}
+
+/// A state value written by the re-execution which differs from the source state after the block.
+#[derive(Clone, Debug, Serialize)]
+pub struct StateDiff {
+    /// Index of the last re-executed transaction writing the key.
+    pub txn_index: usize,
+    pub key: String,
+    pub source: Option<String>,
+    pub replayed: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BlockReplayDiff {
+    pub block_id: HashValue,
+    pub block_number: u64,
+    /// Index of the first vm1 transaction whose re-executed output differs from the recorded one.
+    pub first_diverging_txn: Option<usize>,
+    pub state_diffs: Vec<StateDiff>,
+    /// Index of the first vm2 transaction whose re-executed output differs from the recorded one.
+    pub first_diverging_txn2: Option<usize>,
+    /// The vm2 state root after the block in the source storage.
+    pub source_state_root2: HashValue,
+    /// The vm2 state root after re-executing the vm2 transactions, none if there are none.
+    pub replayed_state_root2: Option<HashValue>,
+    #[serde(skip)]
+    pub recorded: Vec<TxnOutputView>,
+    #[serde(skip)]
+    pub replayed: Vec<TxnOutputView>,
+    #[serde(skip)]
+    pub recorded2: Vec<TxnOutputView>,
+    #[serde(skip)]
+    pub replayed2: Vec<TxnOutputView>,
+}
+
+impl BlockReplayDiff {
+    /// Whether no transaction of the block is re-executed, so nothing is compared.
+    pub fn is_nothing_replayed(&self) -> bool {
+        self.replayed.is_empty() && self.replayed2.is_empty()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.is_nothing_replayed()
+            && self.first_diverging_txn.is_none()
+            && self.state_diffs.is_empty()
+            && self.first_diverging_txn2.is_none()
+            && self
+                .replayed_state_root2
+                .map_or(true, |root| root == self.source_state_root2)
+    }
+
+    /// Dump the recorded and the re-executed outputs of the block as json files in `dir`.
+    pub fn dump(&self, dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir)?;
+        let prefix = format!("{}-{}", self.block_number, self.block_id);
+        for (name, outputs) in [
+            ("recorded", &self.recorded),
+            ("replayed", &self.replayed),
+            ("recorded2", &self.recorded2),
+            ("replayed2", &self.replayed2),
+        ] {
+            let path = dir.join(format!("{}.{}.json", prefix, name));
+            serde_json::to_writer_pretty(BufWriter::new(File::create(&path)?), outputs)?;
+            println!("dump {} outputs to {}", name, path.display());
+        }
+        Ok(())
+    }
+}
+
+impl Display for BlockReplayDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "replay diff of block {} at height {}:",
+            self.block_id, self.block_number
+        )?;
+        if self.is_nothing_replayed() {
+            return writeln!(f, "  nothing replayed, the block has no txn to re-execute");
+        }
+        for (vm, first_diverging_txn, recorded, replayed) in [
+            (
+                "vm1",
+                self.first_diverging_txn,
+                &self.recorded,
+                &self.replayed,
+            ),
+            (
+                "vm2",
+                self.first_diverging_txn2,
+                &self.recorded2,
+                &self.replayed2,
+            ),
+        ] {
+            if replayed.is_empty() {
+                writeln!(f, "  no {} txn replayed", vm)?;
+                continue;
+            }
+            match first_diverging_txn {
+                Some(index) => {
+                    writeln!(f, "  first diverging {} txn at index {}", vm, index)?;
+                    writeln!(f, "    recorded: {:?}", recorded.get(index))?;
+                    let replayed = replayed.get(index).map(|output| TxnOutputView {
+                        write_set: vec![],
+                        ..output.clone()
+                    });
+                    writeln!(f, "    replayed: {:?}", replayed)?;
+                }
+                None => writeln!(f, "  all {} txn outputs match the recorded txn infos", vm)?,
+            }
+        }
+        for diff in &self.state_diffs {
+            writeln!(
+                f,
+                "  txn {} writes {}: source {:?}, replayed {:?}",
+                diff.txn_index, diff.key, diff.source, diff.replayed
+            )?;
+        }
+        if let Some(replayed_state_root2) = self.replayed_state_root2 {
+            if replayed_state_root2 != self.source_state_root2 {
+                writeln!(
+                    f,
+                    "  vm2 state root: source {}, replayed {}",
+                    self.source_state_root2, replayed_state_root2
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn state_key_to_string(key: &StateKey) -> String {
+    match key {
+        StateKey::AccessPath(access_path) => access_path.to_string(),
+        StateKey::TableItem(item) => format!("{:?}/0x{}", item.handle, hex::encode(&item.key)),
+    }
+}
+
+fn write_op_value(op: &WriteOp) -> Option<Vec<u8>> {
+    match op {
+        WriteOp::Value(value) => Some(value.clone()),
+        WriteOp::Deletion => None,
+    }
+}
+
+fn find_first_diverging_txn(
+    recorded: &[TxnOutputView],
+    replayed: &[TxnOutputView],
+) -> Option<usize> {
+    (0..recorded.len().max(replayed.len())).find(|index| {
+        match (recorded.get(*index), replayed.get(*index)) {
+            (Some(recorded), Some(replayed)) => recorded.diverges(replayed),
+            _ => true,
+        }
+    })
+}
+
+/// Re-execute the vm1 and vm2 transactions of `block` on its parent state in `storage`, and diff
+/// the outputs with the recorded transaction infos and the recorded state after the block. The
+/// vm2 transactions are executed after the vm1 ones with the gas left by them, like the chain.
+pub fn replay_block(storage: Arc<Storage>, block: &Block) -> Result<BlockReplayDiff> {
+    let header = block.header();
+    let parent = storage
+        .get_block_header_by_hash(header.parent_hash())?
+        .ok_or_else(|| format_err!("Can not find parent block {}", header.parent_hash()))?;
+    let parent_state = storage.get_vm_multi_state(parent.id())?;
+    let block_state = storage.get_vm_multi_state(header.id())?;
+
+    let recorded_ids = storage.get_block_txn_info_ids(header.id())?;
+    let recorded_infos = storage
+        .get_transaction_infos_by_info_ids(recorded_ids, header.id())?
+        .into_iter()
+        .flatten()
+        .map(|info| info.transaction_info)
+        .collect::<Vec<_>>();
+    let recorded = recorded_infos
+        .iter()
+        .filter_map(|info| info.clone().to_v1())
+        .map(|info| TxnOutputView::from(&info))
+        .collect::<Vec<_>>();
+    let recorded2 = recorded_infos
+        .iter()
+        .filter_map(|info| info.clone().to_v2())
+        .collect::<Vec<_>>();
+
+    let txns = if header.number() < vm1_offline_height(header.chain_id().id().into()) {
+        let mut txns = vec![Transaction::BlockMetadata(block_metadata::from(
+            block.to_metadata(parent.gas_used(), 0),
+        ))];
+        txns.extend(
+            block
+                .transactions()
+                .iter()
+                .cloned()
+                .map(Transaction::UserTransaction),
+        );
+        txns
+    } else {
+        vec![]
+    };
+
+    let statedb = ChainStateDB::new(
+        storage.clone().into_super_arc(),
+        Some(parent_state.state_root1()),
+    );
+    let storage2: Arc<dyn Store2> = Arc::new(Storage2(storage.clone()));
+    let statedb2 = ChainStateDB2::new(storage2.into_super_arc(), Some(parent_state.state_root2()));
+    // the chain executes the txns of both vms with the block gas limit of the vm2 epoch.
+    let block_gas_limit = get_epoch_from_statedb(&statedb2)?.block_gas_limit();
+    let outputs = if txns.is_empty() {
+        vec![]
+    } else {
+        execute_block_transactions(&statedb, txns.clone(), block_gas_limit, None)?
+    };
+
+    let mut vm1_gas_used = 0u64;
+    let mut replayed = vec![];
+    // the last write of every key in the block, with the index of the writing txn.
+    let mut last_writes = BTreeMap::<StateKey, (usize, Option<Vec<u8>>)>::new();
+    for (index, (txn, output)) in txns.iter().zip(outputs).enumerate() {
+        let (_, write_set, events, gas_used, status) = output.into_inner();
+        let write_set_view = write_set
+            .iter()
+            .map(|(key, op)| StateWriteView {
+                key: state_key_to_string(key),
+                value: write_op_value(op).map(hex::encode),
+            })
+            .collect::<Vec<_>>();
+        let output = match status {
+            TransactionStatus::Keep(status) => {
+                for (key, op) in write_set.iter() {
+                    last_writes.insert(key.clone(), (index, write_op_value(op)));
+                }
+                vm1_gas_used = vm1_gas_used.saturating_add(gas_used);
+                statedb.apply_write_set(write_set)?;
+                let state_root = statedb.commit()?;
+                let info =
+                    TransactionInfo::new(txn.id(), Some(state_root), &events, gas_used, status);
+                TxnOutputView {
+                    write_set: write_set_view,
+                    ..TxnOutputView::from(&info)
+                }
+            }
+            status => TxnOutputView {
+                txn_hash: txn.id(),
+                status: format!("{:?}", status),
+                gas_used,
+                event_root_hash: HashValue::zero(),
+                state_root: None,
+                write_set: write_set_view,
+            },
+        };
+        replayed.push(output);
+    }
+
+    let first_diverging_txn = find_first_diverging_txn(&recorded, &replayed);
+
+    // The vm2 block metadata carries the red blocks of the dag, it is taken from the recorded
+    // transactions instead of being rebuilt.
+    let mut block_metadata2 = None;
+    for info in &recorded2 {
+        if let Some(Transaction2::BlockMetadata(metadata)) = storage
+            .get_transaction(info.transaction_hash())?
+            .and_then(|txn| txn.to_v2())
+        {
+            block_metadata2 = Some(metadata);
+            break;
+        }
+    }
+    let txns2 = match block_metadata2 {
+        Some(metadata) => build_block_transactions(block.transactions2(), Some(metadata)),
+        None => vec![],
+    };
+    let (replayed2, replayed_state_root2) = if txns2.is_empty() {
+        (vec![], None)
+    } else {
+        let executed_data2 = starcoin_vm2_chain::execute_transactions(
+            &statedb2,
+            txns2,
+            block_gas_limit.saturating_sub(vm1_gas_used),
+            None,
+        )?;
+        let replayed2 = executed_data2
+            .txn_infos
+            .iter()
+            .map(TxnOutputView::from)
+            .collect::<Vec<_>>();
+        (replayed2, Some(statedb2.state_root()))
+    };
+    let recorded2 = recorded2
+        .iter()
+        .map(TxnOutputView::from)
+        .collect::<Vec<_>>();
+    let first_diverging_txn2 = find_first_diverging_txn(&recorded2, &replayed2);
+
+    let source_statedb =
+        ChainStateDB::new(storage.into_super_arc(), Some(block_state.state_root1()));
+    let mut state_diffs = vec![];
+    for (key, (txn_index, replayed_value)) in last_writes {
+        let source_value = source_statedb.get_state_value(&key)?;
+        if source_value != replayed_value {
+            state_diffs.push(StateDiff {
+                txn_index,
+                key: state_key_to_string(&key),
+                source: source_value.map(hex::encode),
+                replayed: replayed_value.map(hex::encode),
+            });
+        }
+    }
+    state_diffs.sort_by_key(|diff| diff.txn_index);
+
+    Ok(BlockReplayDiff {
+        block_id: header.id(),
+        block_number: header.number(),
+        first_diverging_txn,
+        state_diffs,
+        first_diverging_txn2,
+        source_state_root2: block_state.state_root2(),
+        replayed_state_root2,
+        recorded,
+        replayed,
+        recorded2,
+        replayed2,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_chain::ChainReader;
+    use starcoin_chain_mock::MockChain;
+    use starcoin_config::{BuiltinNetworkID, ChainNetwork};
+
+    fn output(gas_used: u64) -> TxnOutputView {
+        TxnOutputView {
+            txn_hash: HashValue::zero(),
+            status: "Executed".to_string(),
+            gas_used,
+            event_root_hash: HashValue::zero(),
+            state_root: None,
+            write_set: vec![],
+        }
+    }
+
+    fn empty_diff() -> BlockReplayDiff {
+        BlockReplayDiff {
+            block_id: HashValue::zero(),
+            block_number: 1,
+            first_diverging_txn: None,
+            state_diffs: vec![],
+            first_diverging_txn2: None,
+            source_state_root2: HashValue::zero(),
+            replayed_state_root2: None,
+            recorded: vec![],
+            replayed: vec![],
+            recorded2: vec![],
+            replayed2: vec![],
+        }
+    }
+
+    #[stest::test]
+    fn test_replay_block_matches_source() -> Result<()> {
+        let (mut mock_chain, storage2) =
+            MockChain::new_and_get_storage2(ChainNetwork::new_builtin(BuiltinNetworkID::Test))?;
+        mock_chain.produce_and_apply_times(3)?;
+        let block = mock_chain.head().head_block().block().clone();
+        let diff = replay_block(storage2.0.clone(), &block)?;
+        assert!(!diff.replayed2.is_empty());
+        assert_eq!(diff.replayed_state_root2, Some(diff.source_state_root2));
+        assert!(diff.is_empty(), "{}", diff);
+        Ok(())
+    }
+
+    #[test]
+    fn test_nothing_replayed() {
+        let diff = empty_diff();
+        assert!(diff.is_nothing_replayed());
+        assert!(!diff.is_empty());
+        assert!(diff.to_string().contains("nothing replayed"));
+    }
+
+    #[test]
+    fn test_vm2_divergence() {
+        let recorded2 = vec![output(1), output(2)];
+        let replayed2 = vec![output(1), output(3)];
+        let diff = BlockReplayDiff {
+            first_diverging_txn2: find_first_diverging_txn(&recorded2, &replayed2),
+            replayed_state_root2: Some(HashValue::random()),
+            recorded2,
+            replayed2,
+            ..empty_diff()
+        };
+        assert_eq!(diff.first_diverging_txn2, Some(1));
+        assert!(!diff.is_empty());
+        let report = diff.to_string();
+        assert!(report.contains("first diverging vm2 txn at index 1"));
+        assert!(report.contains("vm2 state root"));
+
+        let diff = BlockReplayDiff {
+            first_diverging_txn2: None,
+            replayed_state_root2: Some(diff.source_state_root2),
+            ..diff
+        };
+        assert!(diff.is_empty());
+    }
+}
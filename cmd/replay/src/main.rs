@@ -1,6 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::bail;
 use clap::Parser;
 use sp_utils::stop_watch::start_watch;
 use starcoin_chain::verifier::Verifier;
@@ -12,12 +13,14 @@ use starcoin_genesis::Genesis;
 use starcoin_storage::cache_storage::CacheStorage;
 use starcoin_storage::db_storage::DBStorage;
 use starcoin_storage::storage::StorageInstance;
-use starcoin_storage::{BlockStore, Storage};
+use starcoin_storage::{BlockStore, Storage, Store};
 use starcoin_types::startup_info::StartupInfo;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
 
+mod diff;
+
 #[derive(Debug, Parser)]
 #[clap(name = "replay")]
 pub struct ReplayOpt {
@@ -42,6 +45,9 @@ pub struct ReplayOpt {
     #[clap(long, short = 'w')]
     /// Watch metrics logs.
     pub watch: bool,
+    #[clap(long, parse(from_os_str))]
+    /// Dump the recorded and the re-executed txn outputs of the diverging block as json to this dir.
+    pub dump_dir: Option<PathBuf>,
 }
 
 // deprecated use starcoin_db_exporter replace
@@ -80,8 +86,13 @@ fn main() -> anyhow::Result<()> {
     );
     let (chain_info, _) = Genesis::init_and_check_storage(&net, storage.clone(), from_dir.as_ref())
         .expect("init storage by genesis fail.");
-    let chain = BlockChain::new(net.time_service(), chain_info.head().id(), storage, None)
-        .expect("create block chain should success.");
+    let chain = BlockChain::new(
+        net.time_service(),
+        chain_info.head().id(),
+        storage.clone(),
+        None,
+    )
+    .expect("create block chain should success.");
 
     let storage2 = Arc::new(
         Storage::new(StorageInstance::new_cache_and_db_instance(
@@ -130,22 +141,49 @@ fn main() -> anyhow::Result<()> {
             let expected_state_root = block.header().state_root();
             let block_id = block.id();
             let block_height = block.header().number();
-            match opts.verifier {
-                Verifier::Basic => {
-                    chain2.apply_with_verifier::<BasicVerifier>(block).unwrap();
-                }
+            let applied = match opts.verifier {
+                Verifier::Basic => chain2.apply_with_verifier::<BasicVerifier>(block.clone()),
                 Verifier::Consensus => {
-                    chain2
-                        .apply_with_verifier::<ConsensusVerifier>(block)
-                        .unwrap();
+                    chain2.apply_with_verifier::<ConsensusVerifier>(block.clone())
                 }
-                Verifier::None => {
-                    chain2.apply_with_verifier::<NoneVerifier>(block).unwrap();
+                Verifier::None => chain2.apply_with_verifier::<NoneVerifier>(block.clone()),
+                Verifier::Full => chain2.apply_with_verifier::<FullVerifier>(block.clone()),
+            };
+            let source_state = storage.get_vm_multi_state(block_id)?;
+            let (source_state_root, source_state_root2) =
+                (source_state.state_root1(), source_state.state_root2());
+            let target_state_root = chain2.chain_state_reader().state_root();
+            let target_state_root2 = chain2.chain_state_reader2().state_root();
+            if let Err(e) = &applied {
+                println!(
+                    "apply block {} at height {} failed: {:?}",
+                    block_id, block_height, e
+                );
+            }
+            if applied.is_err()
+                || source_state_root != target_state_root
+                || source_state_root2 != target_state_root2
+            {
+                // the target chain is same with the source one before this block, so re-execute the
+                // block on the source parent state to find where it diverges.
+                let diff = diff::replay_block(storage.clone(), &block)?;
+                print!("{}", diff);
+                if diff.is_empty() {
+                    println!("re-executed outputs match the source, the target chain diverges");
                 }
-                Verifier::Full => {
-                    chain2.apply_with_verifier::<FullVerifier>(block).unwrap();
+                if let Some(dump_dir) = opts.dump_dir.as_ref() {
+                    diff.dump(dump_dir)?;
                 }
-            };
+                bail!(
+                    "replay diverges at block {} height {}, source state roots: {} {}, target state roots: {} {}",
+                    block_id,
+                    block_height,
+                    source_state_root,
+                    source_state_root2,
+                    target_state_root,
+                    target_state_root2
+                );
+            }
             println!(
                 "apply block {} at height: {}, time_used: {:?}, source state root: {}, target state root: {}",
                 block_id,
@@ -7,6 +7,7 @@ use clap::Parser;
 use scmd::{CommandAction, ExecContext};
 use starcoin_config::StarcoinOpt;
 use starcoin_vm2_account_api::AccountInfo;
+use starcoin_vm2_vm_types::transaction::authenticator::Scheme;
 
 /// Create a new account
 #[derive(Debug, Parser, Default)]
@@ -14,6 +15,11 @@ use starcoin_vm2_account_api::AccountInfo;
 pub struct CreateOpt {
     #[clap(short = 'p')]
     password: String,
+
+    #[clap(long = "key-type")]
+    /// scheme of the account key generated by the wallet, one of ed25519, secp256k1, secp256r1,
+    /// default to ed25519.
+    key_type: Option<Scheme>,
 }
 
 pub struct CreateCommand;
@@ -28,8 +34,12 @@ impl CommandAction for CreateCommand {
         &self,
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<AccountInfo> {
+        let opt = ctx.opt();
         let account_client = ctx.state().vm2()?.account_client();
-        let account = account_client.create_account(ctx.opt().password.clone())?;
+        let account = account_client.create_account(
+            opt.password.clone(),
+            opt.key_type.unwrap_or(Scheme::Ed25519),
+        )?;
         Ok(account)
     }
 
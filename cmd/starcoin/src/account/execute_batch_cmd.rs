@@ -0,0 +1,318 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    cli_state::CliState, view::TransactionOptions, view_vm2::ExecuteResultView, StarcoinOpt,
+};
+use anyhow::{bail, ensure, format_err, Result};
+use clap::Parser;
+use scmd::{CommandAction, ExecContext};
+use serde::Deserialize;
+use serde_json::Value;
+use starcoin_rpc_client::StateRootOption;
+use starcoin_vm2_abi_resolver::ABIResolver;
+use starcoin_vm2_abi_types::TypeInstantiation;
+use starcoin_vm2_types::view::{FunctionIdView, TypeTagView};
+use starcoin_vm2_vm_types::{
+    language_storage::CORE_CODE_ADDRESS,
+    parser::parse_transaction_argument,
+    transaction::{EntryFunction, TransactionPayload},
+    value::{MoveStruct, MoveValue},
+};
+use std::path::PathBuf;
+
+/// One entry function call of the batch file.
+#[derive(Debug, Deserialize)]
+pub struct BatchCall {
+    /// example: 0x1::transfer_scripts::peer_to_peer_v2
+    function: FunctionIdView,
+    #[serde(default)]
+    type_args: Vec<TypeTagView>,
+    /// args in json, encoded by the abi of the function, the signer params are skipped.
+    #[serde(default)]
+    args: Vec<Value>,
+}
+
+/// Execute a batch of script functions in one transaction, the transaction fails as a whole if
+/// any of the functions fails.
+/// The batch file is a json array of calls, example:
+/// [{"function": "0x1::transfer_scripts::peer_to_peer_v2", "type_args": ["0x1::starcoin_coin::STC"], "args": ["0x2", "1000"]}]
+#[derive(Debug, Parser)]
+#[clap(name = "execute-batch")]
+pub struct ExecuteBatchOpt {
+    #[clap(flatten)]
+    transaction_opts: TransactionOptions,
+
+    #[clap(name = "batch-file")]
+    /// json file of the script functions to execute.
+    batch_file: PathBuf,
+}
+
+pub struct ExecuteBatchCmd;
+
+impl CommandAction for ExecuteBatchCmd {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = ExecuteBatchOpt;
+    type ReturnItem = ExecuteResultView;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let calls: Vec<BatchCall> = serde_json::from_slice(&std::fs::read(&opt.batch_file)?)?;
+        if calls.is_empty() {
+            bail!("batch file should contain at least one script function");
+        }
+        let state_reader = ctx
+            .state()
+            .client()
+            .state_reader2(StateRootOption::Latest)?;
+        let resolver = ABIResolver::new(&state_reader);
+        let entry_functions = calls
+            .into_iter()
+            .map(|call| {
+                let function_id = call.function.0;
+                let type_args = call.type_args.into_iter().map(|t| t.0).collect::<Vec<_>>();
+                let abi = resolver.resolve_function_instantiation(
+                    &function_id.module,
+                    function_id.function.as_ident_str(),
+                    &type_args,
+                )?;
+                let params = abi
+                    .args()
+                    .iter()
+                    .filter(|p| !is_signer(p.type_abi()))
+                    .collect::<Vec<_>>();
+                if params.len() != call.args.len() {
+                    bail!(
+                        "{}::{} expects {} args, but {} given",
+                        function_id.module,
+                        function_id.function,
+                        params.len(),
+                        call.args.len()
+                    );
+                }
+                let args = params
+                    .iter()
+                    .zip(call.args.iter())
+                    .map(|(param, value)| {
+                        encode_arg(param.type_abi(), value).map_err(|e| {
+                            format_err!(
+                                "invalid arg {} of {}::{}: {}",
+                                param.name(),
+                                function_id.module,
+                                function_id.function,
+                                e
+                            )
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(EntryFunction::new(
+                    function_id.module,
+                    function_id.function,
+                    type_args,
+                    args,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        ctx.state().vm2()?.build_and_execute_transaction(
+            opt.transaction_opts.clone(),
+            TransactionPayload::EntryFunctionBatch(entry_functions),
+        )
+    }
+}
+
+fn is_signer(ty: &TypeInstantiation) -> bool {
+    match ty {
+        TypeInstantiation::Signer => true,
+        TypeInstantiation::Reference(_, inner) => is_signer(inner),
+        _ => false,
+    }
+}
+
+/// Encode the json `value` to bcs bytes by the type of the param.
+fn encode_arg(ty: &TypeInstantiation, value: &Value) -> Result<Vec<u8>> {
+    bcs_ext::to_bytes(&json_to_move_value(ty, value)?)
+}
+
+/// Convert the json `value` to a move value of the type of the param.
+fn json_to_move_value(ty: &TypeInstantiation, value: &Value) -> Result<MoveValue> {
+    let primitive = |suffix: &str| -> Result<MoveValue> {
+        let literal = match value {
+            Value::String(s) => s.clone(),
+            Value::Number(n) => n.to_string(),
+            Value::Bool(b) => b.to_string(),
+            _ => bail!("expect a primitive value, but got {}", value),
+        };
+        let literal = if suffix.is_empty() || literal.ends_with(suffix) {
+            literal
+        } else {
+            format!("{}{}", literal, suffix)
+        };
+        let move_value = MoveValue::from(parse_transaction_argument(&literal)?);
+        let matched = matches!(
+            (ty, &move_value),
+            (TypeInstantiation::Bool, MoveValue::Bool(_))
+                | (TypeInstantiation::Address, MoveValue::Address(_))
+                | (TypeInstantiation::U8, MoveValue::U8(_))
+                | (TypeInstantiation::U16, MoveValue::U16(_))
+                | (TypeInstantiation::U32, MoveValue::U32(_))
+                | (TypeInstantiation::U64, MoveValue::U64(_))
+                | (TypeInstantiation::U128, MoveValue::U128(_))
+                | (TypeInstantiation::U256, MoveValue::U256(_))
+        );
+        ensure!(matched, "expect a value of {:?}, but got {}", ty, value);
+        Ok(move_value)
+    };
+    Ok(match ty {
+        TypeInstantiation::Bool | TypeInstantiation::Address => primitive("")?,
+        TypeInstantiation::U8 => primitive("u8")?,
+        TypeInstantiation::U16 => primitive("u16")?,
+        TypeInstantiation::U32 => primitive("u32")?,
+        TypeInstantiation::U64 => primitive("u64")?,
+        TypeInstantiation::U128 => primitive("u128")?,
+        TypeInstantiation::U256 => primitive("u256")?,
+        TypeInstantiation::Vector(elem) => match (elem.as_ref(), value) {
+            (TypeInstantiation::U8, Value::String(s)) => {
+                let hex = s.strip_prefix("0x").ok_or_else(|| {
+                    format_err!("expect a hex string with 0x prefix, but got {}", s)
+                })?;
+                MoveValue::vector_u8(hex::decode(hex)?)
+            }
+            (_, Value::Array(values)) => MoveValue::Vector(
+                values
+                    .iter()
+                    .map(|v| json_to_move_value(elem, v))
+                    .collect::<Result<_>>()?,
+            ),
+            _ => bail!("expect an array, but got {}", value),
+        },
+        TypeInstantiation::Struct(s) => {
+            let struct_tag = s.struct_tag()?;
+            let is_string = struct_tag.address == CORE_CODE_ADDRESS
+                && struct_tag.module.as_str() == "string"
+                && struct_tag.name.as_str() == "String";
+            let fields = match value {
+                // strings are passed as utf8 text, not hex.
+                Value::String(text) if is_string => {
+                    vec![MoveValue::vector_u8(text.as_bytes().to_vec())]
+                }
+                Value::Object(fields) => s
+                    .fields()
+                    .iter()
+                    .map(|field| {
+                        let v = fields.get(field.name()).ok_or_else(|| {
+                            format_err!("missing field {} of {}", field.name(), struct_tag)
+                        })?;
+                        json_to_move_value(field.type_abi(), v)
+                    })
+                    .collect::<Result<_>>()?,
+                // a struct with a single field, such as Object<T>, can be passed as its field.
+                _ if s.fields().len() == 1 => {
+                    vec![json_to_move_value(s.fields()[0].type_abi(), value)?]
+                }
+                _ => bail!("expect an object of {}, but got {}", struct_tag, value),
+            };
+            MoveValue::Struct(MoveStruct::Runtime(fields))
+        }
+        TypeInstantiation::Signer
+        | TypeInstantiation::Reference(_, _)
+        | TypeInstantiation::TypeParameter(_) => {
+            bail!("unsupported arg type {:?}", ty)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::encode_arg;
+    use serde_json::json;
+    use starcoin_vm2_abi_types::{FieldABI, StructInstantiation, TypeInstantiation};
+    use starcoin_vm2_vm_types::{
+        account_address::AccountAddress,
+        file_format::AbilitySet,
+        identifier::Identifier,
+        language_storage::{ModuleId, CORE_CODE_ADDRESS},
+    };
+
+    fn struct_type(
+        module: &str,
+        name: &str,
+        fields: Vec<(&str, TypeInstantiation)>,
+    ) -> TypeInstantiation {
+        TypeInstantiation::new_struct_instantiation(StructInstantiation::new(
+            name.to_string(),
+            ModuleId::new(CORE_CODE_ADDRESS, Identifier::new(module).unwrap()),
+            String::new(),
+            vec![],
+            fields
+                .into_iter()
+                .map(|(name, ty)| FieldABI::new(name.to_string(), String::new(), ty))
+                .collect(),
+            AbilitySet::EMPTY,
+        ))
+    }
+
+    #[test]
+    fn test_encode_arg() {
+        assert_eq!(
+            encode_arg(&TypeInstantiation::U64, &json!("1000")).unwrap(),
+            bcs_ext::to_bytes(&1000u64).unwrap()
+        );
+        assert_eq!(
+            encode_arg(&TypeInstantiation::U128, &json!(7)).unwrap(),
+            bcs_ext::to_bytes(&7u128).unwrap()
+        );
+        assert_eq!(
+            encode_arg(&TypeInstantiation::Bool, &json!(true)).unwrap(),
+            vec![1]
+        );
+        assert_eq!(
+            encode_arg(&TypeInstantiation::Address, &json!("0x2")).unwrap(),
+            bcs_ext::to_bytes(&AccountAddress::from_hex_literal("0x2").unwrap()).unwrap()
+        );
+        // A value of another type is rejected.
+        assert!(encode_arg(&TypeInstantiation::Address, &json!("1000")).is_err());
+
+        let bytes = TypeInstantiation::new_vector(TypeInstantiation::U8);
+        assert_eq!(
+            encode_arg(&bytes, &json!("0x0102")).unwrap(),
+            bcs_ext::to_bytes(&vec![1u8, 2u8]).unwrap()
+        );
+        // The bytes must be hex with the 0x prefix.
+        assert!(encode_arg(&bytes, &json!("0102")).is_err());
+        assert_eq!(
+            encode_arg(
+                &TypeInstantiation::new_vector(TypeInstantiation::U64),
+                &json!(["1", 2])
+            )
+            .unwrap(),
+            bcs_ext::to_bytes(&vec![1u64, 2u64]).unwrap()
+        );
+
+        let string = struct_type(
+            "string",
+            "String",
+            vec![(
+                "bytes",
+                TypeInstantiation::new_vector(TypeInstantiation::U8),
+            )],
+        );
+        assert_eq!(
+            encode_arg(&string, &json!("hello")).unwrap(),
+            bcs_ext::to_bytes("hello").unwrap()
+        );
+        let pair = struct_type(
+            "pair",
+            "Pair",
+            vec![("a", TypeInstantiation::U8), ("b", string)],
+        );
+        assert_eq!(
+            encode_arg(&pair, &json!({"a": 1, "b": "x"})).unwrap(),
+            bcs_ext::to_bytes(&(1u8, "x")).unwrap()
+        );
+        assert!(encode_arg(&pair, &json!({"a": 1})).is_err());
+    }
+}
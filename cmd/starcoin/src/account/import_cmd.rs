@@ -9,7 +9,7 @@ use starcoin_config::StarcoinOpt;
 use starcoin_vm2_account_api::AccountInfo;
 use starcoin_vm2_crypto::{ValidCryptoMaterial, ValidCryptoMaterialStringExt};
 use starcoin_vm2_types::account_address::AccountAddress;
-use starcoin_vm2_vm_types::transaction::authenticator::AccountPrivateKey;
+use starcoin_vm2_vm_types::transaction::authenticator::{AccountPrivateKey, Scheme};
 use std::path::PathBuf;
 
 /// Import account by private key to node wallet.
@@ -29,6 +29,11 @@ pub struct ImportOpt {
     )]
     from_file: Option<PathBuf>,
 
+    #[clap(long = "key-type")]
+    /// scheme of a raw hex private key, one of ed25519, secp256k1, secp256r1, e.g. secp256k1 for
+    /// an Ethereum private key. If absent, the key should be one exported by `account export`.
+    key_type: Option<Scheme>,
+
     /// if account_address is absent, generate address by public_key.
     #[clap(name = "account_address")]
     account_address: Option<AccountAddress>,
@@ -48,11 +53,20 @@ impl CommandAction for ImportCommand {
     ) -> Result<Self::ReturnItem> {
         let opt: &ImportOpt = ctx.opt();
         let client = ctx.state().vm2()?.account_client();
+        let parse_private_key = |input: &str| -> Result<AccountPrivateKey> {
+            match opt.key_type {
+                Some(scheme) => AccountPrivateKey::from_scheme_bytes(
+                    scheme,
+                    &hex::decode(input.strip_prefix("0x").unwrap_or(input))?,
+                ),
+                None => Ok(AccountPrivateKey::from_encoded_string(input)?),
+            }
+        };
         let private_key = match (opt.from_input.as_ref(), opt.from_file.as_ref()) {
-            (Some(p), _) => AccountPrivateKey::from_encoded_string(p)?,
+            (Some(p), _) => parse_private_key(p)?,
             (None, Some(p)) => {
                 let data = std::fs::read_to_string(p)?.replace(['\n', '\r'], "");
-                AccountPrivateKey::from_encoded_string(data.as_str())?
+                parse_private_key(data.as_str())?
             }
             (None, None) => {
                 bail!("private key should be specified, use one of <input>, <from-file>")
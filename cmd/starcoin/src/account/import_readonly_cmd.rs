@@ -9,7 +9,7 @@ use starcoin_config::StarcoinOpt;
 use starcoin_vm2_account_api::AccountInfo;
 use starcoin_vm2_crypto::{ValidCryptoMaterial, ValidCryptoMaterialStringExt};
 use starcoin_vm2_types::account_address::AccountAddress;
-use starcoin_vm2_vm_types::transaction::authenticator::AccountPublicKey;
+use starcoin_vm2_vm_types::transaction::authenticator::{AccountPublicKey, Scheme};
 
 /// Import a readonly account by public key
 #[derive(Debug, Parser)]
@@ -18,6 +18,11 @@ pub struct ImportReadonlyOpt {
     #[clap(name = "input", short = 'i', help = "input of public key")]
     from_input: String,
 
+    #[clap(long = "key-type")]
+    /// scheme of a raw hex public key, one of ed25519, secp256k1, secp256r1, e.g. secp256r1 for
+    /// the public key of a passkey. If absent, the key should be one shown by `account show`.
+    key_type: Option<Scheme>,
+
     /// if account_address is absent, generate address by public_key.
     #[clap(name = "account_address")]
     account_address: Option<AccountAddress>,
@@ -38,7 +43,16 @@ impl CommandAction for ImportReadonlyCommand {
         let client = ctx.state().vm2()?.account_client();
         let opt: &ImportReadonlyOpt = ctx.opt();
 
-        let public_key = AccountPublicKey::from_encoded_string(opt.from_input.as_str())?;
+        let public_key = match opt.key_type {
+            Some(scheme) => {
+                let input = opt.from_input.as_str();
+                AccountPublicKey::from_scheme_bytes(
+                    scheme,
+                    &hex::decode(input.strip_prefix("0x").unwrap_or(input))?,
+                )?
+            }
+            None => AccountPublicKey::from_encoded_string(opt.from_input.as_str())?,
+        };
 
         let address = opt
             .account_address
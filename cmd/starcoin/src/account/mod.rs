@@ -2,6 +2,7 @@ pub mod change_password_cmd;
 pub mod create_cmd;
 pub mod default_cmd;
 pub mod derive_account_address_cmd;
+pub mod execute_batch_cmd;
 pub mod execute_script_cmd;
 pub mod execute_script_function_cmd;
 pub mod export_cmd;
@@ -16,6 +17,8 @@ pub mod remove_cmd;
 pub mod rotate_auth_key_cmd;
 pub mod show_cmd;
 pub mod sign_cmd;
+pub mod sign_fee_payer_txn_cmd;
+pub mod sign_multi_agent_txn_cmd;
 pub mod sign_multisig_txn_cmd;
 pub mod submit_txn_cmd;
 pub mod transfer_cmd;
@@ -24,9 +27,10 @@ pub mod verify_sign_cmd;
 
 pub use {
     change_password_cmd::*, create_cmd::*, default_cmd::*, derive_account_address_cmd::*,
-    execute_script_cmd::*, execute_script_function_cmd::*, export_cmd::*, generate_keypair::*,
-    import_cmd::*, import_multisig_cmd::*, import_readonly_cmd::*, list_cmd::*, lock_cmd::*,
-    receipt_identifier_cmd::*, remove_cmd::*, rotate_auth_key_cmd::*, show_cmd::*, sign_cmd::*,
+    execute_batch_cmd::*, execute_script_cmd::*, execute_script_function_cmd::*, export_cmd::*,
+    generate_keypair::*, import_cmd::*, import_multisig_cmd::*, import_readonly_cmd::*,
+    list_cmd::*, lock_cmd::*, receipt_identifier_cmd::*, remove_cmd::*, rotate_auth_key_cmd::*,
+    show_cmd::*, sign_cmd::*, sign_fee_payer_txn_cmd::*, sign_multi_agent_txn_cmd::*,
     sign_multisig_txn_cmd::*, submit_txn_cmd::*, transfer_cmd::*, unlock_cmd::*,
     verify_sign_cmd::*,
 };
@@ -7,6 +7,7 @@ use clap::Parser;
 use scmd::{CommandAction, ExecContext};
 use starcoin_rpc_client::StateRootOption;
 use starcoin_vm2_crypto::ValidCryptoMaterialStringExt;
+use starcoin_vm2_rpc_api::account_api::PortfolioOption;
 use starcoin_vm2_statedb::ChainStateReader;
 use starcoin_vm2_vm_types::{
     account_address::AccountAddress,
@@ -28,6 +29,41 @@ pub struct ShowOpt {
     #[clap(name = "state-root", long, short = 'b', alias = "block_id")]
     /// The block number or block hash for get state, if absent, use latest block state_root.
     state_root: Option<StateRootOption>,
+
+    #[clap(long = "portfolio")]
+    /// Also show the coin balances and the primary fungible stores of STC, the coins, the accepted
+    /// tokens and the given tokens and metadata with their token metadata, and the NFTs and
+    /// secondary fungible stores among the given objects. Other assets are not discovered.
+    portfolio: bool,
+
+    #[clap(
+        long = "token-code",
+        name = "token-code",
+        requires = "portfolio",
+        action = clap::ArgAction::Append
+    )]
+    /// Also look up the primary fungible stores of these tokens.
+    token_codes: Vec<TokenCode>,
+
+    #[clap(
+        long = "metadata",
+        name = "metadata",
+        requires = "portfolio",
+        action = clap::ArgAction::Append
+    )]
+    /// Also look up the primary fungible stores of these fungible asset metadata objects, for the
+    /// fungible assets without a token code.
+    fungible_asset_metadata: Vec<AccountAddress>,
+
+    #[clap(
+        long = "object",
+        name = "object",
+        requires = "portfolio",
+        action = clap::ArgAction::Append
+    )]
+    /// Only these objects are checked for NFTs and secondary fungible stores owned by the account,
+    /// other objects of the account are not discovered.
+    objects: Vec<AccountAddress>,
 }
 
 pub struct ShowCommand;
@@ -73,6 +109,7 @@ impl CommandAction for ShowCommand {
             usize::MAX,
             None,
             None,
+            None,
         )?;
 
         let mut balances: HashMap<TokenCode, u128> = resources
@@ -101,12 +138,27 @@ impl CommandAction for ShowCommand {
             }
         }
 
+        let portfolio = if opt.portfolio {
+            Some(rpc_client.account_portfolio2(
+                *account.address(),
+                Some(PortfolioOption {
+                    state_root: Some(chain_state_reader.state_root()),
+                    token_codes: opt.token_codes.clone(),
+                    fungible_asset_metadata: opt.fungible_asset_metadata.clone(),
+                    objects: opt.objects.clone(),
+                }),
+            )?)
+        } else {
+            None
+        };
+
         let auth_key = account.public_key.authentication_key();
         Ok(AccountWithStateView {
             auth_key: auth_key.to_encoded_string()?,
             account,
             sequence_number,
             balances,
+            portfolio,
         })
     }
 }
@@ -0,0 +1,327 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::convert::TryInto;
+use std::env::current_dir;
+use std::fs::File;
+use std::path::PathBuf;
+
+use anyhow::{bail, format_err, Result};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+use scmd::{CommandAction, ExecContext};
+use starcoin_rpc_client::StateRootOption;
+use starcoin_vm2_crypto::hash::PlainCryptoHash;
+use starcoin_vm2_state_api::AccountStateReader;
+use starcoin_vm2_types::transaction::{
+    parse_transaction_argument_advance, RawUserTransaction, TransactionArgument,
+};
+use starcoin_vm2_types::view::{
+    FunctionIdView, RawUserTransactionView, TransactionPayloadView,
+    TransactionStatusView as TransactionStatusView2,
+};
+
+use crate::{cli_state::CliState, StarcoinOpt};
+use starcoin_vm2_vm_types::on_chain_resource::ChainId;
+use starcoin_vm2_vm_types::{
+    account_address::AccountAddress,
+    language_storage::TypeTag,
+    parser::parse_type_tag,
+    token::stc::STC_TOKEN_CODE_STR,
+    transaction::{
+        authenticator::AccountAuthenticator, DryRunTransaction, EntryFunction,
+        SignedUserTransaction, TransactionPayload,
+    },
+    transaction_argument::convert_txn_args,
+};
+
+/// A fee payer txn waiting for the signatures of its sender and its fee payer.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct FeePayerTransaction {
+    raw_txn: RawUserTransaction,
+    fee_payer_address: AccountAddress,
+    sender_signature: Option<AccountAuthenticator>,
+    fee_payer_signature: Option<AccountAuthenticator>,
+}
+
+impl FeePayerTransaction {
+    pub fn new(raw_txn: RawUserTransaction, fee_payer_address: AccountAddress) -> Self {
+        Self {
+            raw_txn,
+            fee_payer_address,
+            sender_signature: None,
+            fee_payer_signature: None,
+        }
+    }
+
+    pub fn collect_signature(
+        &mut self,
+        signer: AccountAddress,
+        signature: AccountAuthenticator,
+    ) -> Result<()> {
+        if signer == self.raw_txn.sender() {
+            self.sender_signature = Some(signature);
+        } else if signer == self.fee_payer_address {
+            self.fee_payer_signature = Some(signature);
+        } else {
+            bail!("{} is not a signer of the fee payer txn", signer);
+        }
+        Ok(())
+    }
+
+    /// The signers whose signatures are not collected yet.
+    pub fn missing_signers(&self) -> Vec<AccountAddress> {
+        let mut missing = vec![];
+        if self.sender_signature.is_none() {
+            missing.push(self.raw_txn.sender());
+        }
+        if self.fee_payer_signature.is_none() {
+            missing.push(self.fee_payer_address);
+        }
+        missing
+    }
+
+    pub fn into_signed_txn(self) -> Result<SignedUserTransaction> {
+        let sender = self
+            .sender_signature
+            .ok_or_else(|| format_err!("sender signature is missing"))?;
+        let fee_payer = self
+            .fee_payer_signature
+            .ok_or_else(|| format_err!("fee payer signature is missing"))?;
+        Ok(SignedUserTransaction::fee_payer(
+            self.raw_txn,
+            sender,
+            self.fee_payer_address,
+            fee_payer,
+        ))
+    }
+}
+
+#[derive(Debug, Parser)]
+#[clap(name = "sign-fee-payer-txn")]
+/// Generate a txn whose gas is paid by a fee payer instead of the sender, or sign the txn
+/// generated by the other participant. The sender needs no account on chain, it is created by its
+/// first txn.
+/// Output the txn to file, waiting for the other participant to sign it, or output the signed txn
+/// when both signatures are collected, it can be submitted by `account submit-txn`.
+pub struct SignFeePayerTxnOpt {
+    #[clap(name = "fee-payer-file")]
+    /// fee payer txn data generated by the other participant.
+    fee_payer_txn_file: Option<PathBuf>,
+
+    #[clap(short = 's', required_unless_present = "fee-payer-file")]
+    /// sender address of this fee payer txn.
+    sender: Option<AccountAddress>,
+
+    #[clap(
+        long = "fee-payer",
+        name = "fee-payer",
+        required_unless_present = "fee-payer-file"
+    )]
+    /// the account paying the gas of the txn.
+    fee_payer: Option<AccountAddress>,
+
+    #[clap(long = "signer")]
+    /// the account signs the txn this time, default to the sender when generating the txn.
+    signer: Option<AccountAddress>,
+
+    #[clap(
+        long = "function",
+        name = "script-function",
+        required_unless_present = "fee-payer-file"
+    )]
+    /// script function to execute, example: 0x1::transfer_scripts::peer_to_peer_v2
+    script_function: Option<FunctionIdView>,
+
+    #[clap(
+        short = 't',
+        long = "type_tag",
+        name = "type-tag",
+        help = "can specify multi type_tag",
+        value_parser = parse_type_tag,
+    )]
+    type_tags: Option<Vec<TypeTag>>,
+
+    #[clap(long = "arg", name = "transaction-arg", value_parser = parse_transaction_argument_advance)]
+    /// transaction arguments
+    args: Option<Vec<TransactionArgument>>,
+
+    #[clap(
+        name = "expiration_time",
+        long = "timeout",
+        default_value = "3000",
+        help = "how long(in seconds) the txn stay alive"
+    )]
+    expiration_time: u64,
+
+    #[clap(
+        short = 'g',
+        long = "max-gas",
+        name = "max-gas-amount",
+        default_value = "10000000",
+        help = "max gas used to execute the script"
+    )]
+    max_gas_amount: u64,
+    #[clap(
+        long = "gas-price",
+        name = "price of gas",
+        default_value = "1",
+        help = "gas price used to execute the script"
+    )]
+    gas_price: u64,
+
+    #[clap(name = "output-dir", long = "output-dir")]
+    /// dir used to save txn data file. Default to current dir.
+    output_dir: Option<PathBuf>,
+}
+
+pub struct SignFeePayerTxnCommand;
+
+impl CommandAction for SignFeePayerTxnCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SignFeePayerTxnOpt;
+    type ReturnItem = PathBuf;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let rpc_client = ctx.state().client();
+        let account_client = ctx.state().vm2()?.account_client();
+
+        // gen fee payer txn or read from file sent by the other participant.
+        let mut fee_payer_txn = if let Some(file_input) = opt.fee_payer_txn_file.as_ref() {
+            bcs_ext::from_bytes::<FeePayerTransaction>(&std::fs::read(file_input)?)?
+        } else {
+            let sender = opt
+                .sender
+                .ok_or_else(|| format_err!("sender address should be provided"))?;
+            let fee_payer = opt
+                .fee_payer
+                .ok_or_else(|| format_err!("fee payer address should be provided"))?;
+            let function_id = opt
+                .script_function
+                .clone()
+                .ok_or_else(|| format_err!("script function should be provided"))?
+                .0;
+            let script_function = EntryFunction::new(
+                function_id.module,
+                function_id.function,
+                opt.type_tags.clone().unwrap_or_default(),
+                convert_txn_args(&opt.args.clone().unwrap_or_default()),
+            );
+            let node_info = rpc_client.node_info()?;
+            let chain_state_reader = rpc_client.state_reader2(StateRootOption::Latest)?;
+            // a sender without account on chain sends its first txn.
+            let sequence_number = AccountStateReader::new(&chain_state_reader)
+                .get_account_resource_opt(&sender)?
+                .map(|account_resource| account_resource.sequence_number())
+                .unwrap_or_default();
+            let raw_txn = RawUserTransaction::new(
+                sender,
+                sequence_number,
+                TransactionPayload::EntryFunction(script_function),
+                opt.max_gas_amount,
+                opt.gas_price,
+                opt.expiration_time + node_info.now_seconds,
+                ChainId::new(ctx.state().vm2()?.net().chain_id().id()),
+                STC_TOKEN_CODE_STR.to_string(),
+            );
+            FeePayerTransaction::new(raw_txn, fee_payer)
+        };
+        let raw_txn = fee_payer_txn.raw_txn.clone();
+        let fee_payer_address = fee_payer_txn.fee_payer_address;
+
+        let mut raw_txn_view: RawUserTransactionView = raw_txn.clone().try_into()?;
+        raw_txn_view.decoded_payload = Some(TransactionPayloadView::from(
+            ctx.state().vm2()?.decode_txn_payload(raw_txn.payload())?,
+        ));
+        // Use `eprintln` instead of `println`, for keep the cli stdout's format(such as json) is not broken by print.
+        eprintln!(
+            "Prepare to sign the transaction: \n {}",
+            serde_json::to_string_pretty(&raw_txn_view)?
+        );
+
+        // pre-run the txn when first generation, the public keys are only known if the sender and
+        // the fee payer are both in the local wallet.
+        if opt.fee_payer_txn_file.is_none() {
+            let sender = account_client.get_account(raw_txn.sender())?;
+            let fee_payer = account_client.get_account(fee_payer_address)?;
+            match sender.zip(fee_payer) {
+                Some((sender, fee_payer)) => {
+                    let output = ctx.state().vm2()?.dry_run_transaction(DryRunTransaction {
+                        raw_txn: raw_txn.clone(),
+                        public_key: sender.public_key,
+                        fee_payer: Some((fee_payer_address, fee_payer.public_key)),
+                        secondary_signers: vec![],
+                    })?;
+                    eprintln!(
+                        "Transaction dry run execute output: \n {}",
+                        serde_json::to_string_pretty(&output)?
+                    );
+                    match &output.txn_output.status {
+                        TransactionStatusView2::Discard {
+                            status_code,
+                            status_code_name,
+                        } => {
+                            bail!(
+                                "TransactionStatus is discard: {:?}, {}",
+                                status_code,
+                                status_code_name
+                            )
+                        }
+                        TransactionStatusView2::Executed => {}
+                        s => {
+                            bail!("pre-run failed, status: {:?}", s);
+                        }
+                    }
+                }
+                None => eprintln!(
+                    "skip the dry run, the sender or the fee payer is not in the local wallet"
+                ),
+            }
+        }
+
+        let signer = match opt.signer {
+            Some(signer) => signer,
+            None if opt.fee_payer_txn_file.is_none() => raw_txn.sender(),
+            None => bail!("signer address should be provided"),
+        };
+        let signature =
+            account_client.sign_fee_payer_txn(raw_txn.clone(), fee_payer_address, signer)?;
+        fee_payer_txn.collect_signature(signer, signature)?;
+
+        let mut output_file = opt.output_dir.clone().unwrap_or(current_dir()?);
+        if !output_file.exists() {
+            std::fs::create_dir_all(output_file.as_path())?;
+        }
+        // use raw txn's hash as output file name
+        output_file.push(raw_txn.crypto_hash().to_hex());
+        let missing_signers = fee_payer_txn.missing_signers();
+        let mut file = if missing_signers.is_empty() {
+            eprintln!("all signatures collected for the fee payer txn, txn can be submitted now");
+            output_file.set_extension("signed-txn");
+            let mut file = File::create(output_file.as_path())?;
+            bcs_ext::serialize_into(&mut file, &fee_payer_txn.into_signed_txn()?)?;
+            file
+        } else {
+            eprintln!(
+                "still require the signatures of {}",
+                missing_signers
+                    .iter()
+                    .map(|address| address.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            output_file.set_extension("fee-payer-txn");
+            let mut file = File::create(output_file.as_path())?;
+            bcs_ext::serialize_into(&mut file, &fee_payer_txn)?;
+            file
+        };
+        file.sync_all()?;
+        Ok(output_file)
+    }
+}
@@ -0,0 +1,346 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::convert::TryInto;
+use std::env::current_dir;
+use std::fs::File;
+use std::path::PathBuf;
+
+use anyhow::{bail, format_err, Result};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+use scmd::{CommandAction, ExecContext};
+use starcoin_rpc_client::StateRootOption;
+use starcoin_vm2_crypto::hash::PlainCryptoHash;
+use starcoin_vm2_state_api::StateReaderExt;
+use starcoin_vm2_types::transaction::{
+    parse_transaction_argument_advance, RawUserTransaction, TransactionArgument,
+};
+use starcoin_vm2_types::view::{
+    FunctionIdView, RawUserTransactionView, TransactionPayloadView,
+    TransactionStatusView as TransactionStatusView2,
+};
+
+use crate::{cli_state::CliState, StarcoinOpt};
+use starcoin_vm2_vm_types::on_chain_resource::ChainId;
+use starcoin_vm2_vm_types::{
+    account_address::AccountAddress,
+    language_storage::TypeTag,
+    parser::parse_type_tag,
+    token::stc::STC_TOKEN_CODE_STR,
+    transaction::{
+        authenticator::AccountAuthenticator, DryRunTransaction, EntryFunction,
+        SignedUserTransaction, TransactionPayload,
+    },
+    transaction_argument::convert_txn_args,
+};
+
+/// A multi-agent txn waiting for the signatures of its sender and secondary signers.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct MultiAgentTransaction {
+    raw_txn: RawUserTransaction,
+    secondary_signer_addresses: Vec<AccountAddress>,
+    sender_signature: Option<AccountAuthenticator>,
+    /// collected signatures of the secondary signers, in the order of their addresses.
+    secondary_signatures: Vec<Option<AccountAuthenticator>>,
+}
+
+impl MultiAgentTransaction {
+    pub fn new(
+        raw_txn: RawUserTransaction,
+        secondary_signer_addresses: Vec<AccountAddress>,
+    ) -> Self {
+        let secondary_signatures = vec![None; secondary_signer_addresses.len()];
+        Self {
+            raw_txn,
+            secondary_signer_addresses,
+            sender_signature: None,
+            secondary_signatures,
+        }
+    }
+
+    pub fn collect_signature(
+        &mut self,
+        signer: AccountAddress,
+        signature: AccountAuthenticator,
+    ) -> Result<()> {
+        if signer == self.raw_txn.sender() {
+            self.sender_signature = Some(signature);
+        } else if let Some(pos) = self
+            .secondary_signer_addresses
+            .iter()
+            .position(|address| *address == signer)
+        {
+            self.secondary_signatures[pos] = Some(signature);
+        } else {
+            bail!("{} is not a signer of the multi-agent txn", signer);
+        }
+        Ok(())
+    }
+
+    /// The signers whose signatures are not collected yet.
+    pub fn missing_signers(&self) -> Vec<AccountAddress> {
+        let mut missing = vec![];
+        if self.sender_signature.is_none() {
+            missing.push(self.raw_txn.sender());
+        }
+        for (address, signature) in self
+            .secondary_signer_addresses
+            .iter()
+            .zip(self.secondary_signatures.iter())
+        {
+            if signature.is_none() {
+                missing.push(*address);
+            }
+        }
+        missing
+    }
+
+    pub fn into_signed_txn(self) -> Result<SignedUserTransaction> {
+        let sender = self
+            .sender_signature
+            .ok_or_else(|| format_err!("sender signature is missing"))?;
+        let secondary_signers = self
+            .secondary_signatures
+            .into_iter()
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| format_err!("secondary signer signature is missing"))?;
+        Ok(SignedUserTransaction::multi_agent(
+            self.raw_txn,
+            sender,
+            self.secondary_signer_addresses,
+            secondary_signers,
+        ))
+    }
+}
+
+#[derive(Debug, Parser)]
+#[clap(name = "sign-multi-agent-txn")]
+/// Generate a multi-agent txn whose entry function takes the sender and the secondary signers as
+/// signers, or sign the txn generated by other participants.
+/// Output the txn to file, waiting for other signers to sign it, or output the signed txn when all
+/// signatures are collected, it can be submitted by `account submit-txn`.
+pub struct SignMultiAgentTxnOpt {
+    #[clap(name = "multi-agent-file")]
+    /// multi-agent txn data generated by other participants.
+    multi_agent_txn_file: Option<PathBuf>,
+
+    #[clap(short = 's', required_unless_present = "multi-agent-file")]
+    /// sender address of this multi-agent txn.
+    sender: Option<AccountAddress>,
+
+    #[clap(
+        long = "secondary-signer",
+        name = "secondary-signer",
+        required_unless_present = "multi-agent-file"
+    )]
+    /// the secondary signers of the txn, in the order of the signer params of the function.
+    secondary_signers: Option<Vec<AccountAddress>>,
+
+    #[clap(long = "signer")]
+    /// the account signs the txn this time, default to the sender when generating the txn.
+    signer: Option<AccountAddress>,
+
+    #[clap(
+        long = "function",
+        name = "script-function",
+        required_unless_present = "multi-agent-file"
+    )]
+    /// script function to execute, example: 0x1::TransferScripts::peer_to_peer_v2
+    script_function: Option<FunctionIdView>,
+
+    #[clap(
+        short = 't',
+        long = "type_tag",
+        name = "type-tag",
+        help = "can specify multi type_tag",
+        value_parser = parse_type_tag,
+    )]
+    type_tags: Option<Vec<TypeTag>>,
+
+    #[clap(long = "arg", name = "transaction-arg", value_parser = parse_transaction_argument_advance)]
+    /// transaction arguments
+    args: Option<Vec<TransactionArgument>>,
+
+    #[clap(
+        name = "expiration_time",
+        long = "timeout",
+        default_value = "3000",
+        help = "how long(in seconds) the txn stay alive"
+    )]
+    expiration_time: u64,
+
+    #[clap(
+        short = 'g',
+        long = "max-gas",
+        name = "max-gas-amount",
+        default_value = "10000000",
+        help = "max gas used to execute the script"
+    )]
+    max_gas_amount: u64,
+    #[clap(
+        long = "gas-price",
+        name = "price of gas",
+        default_value = "1",
+        help = "gas price used to execute the script"
+    )]
+    gas_price: u64,
+
+    #[clap(name = "output-dir", long = "output-dir")]
+    /// dir used to save txn data file. Default to current dir.
+    output_dir: Option<PathBuf>,
+}
+
+pub struct SignMultiAgentTxnCommand;
+
+impl CommandAction for SignMultiAgentTxnCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SignMultiAgentTxnOpt;
+    type ReturnItem = PathBuf;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let opt = ctx.opt();
+        let rpc_client = ctx.state().client();
+        let account_client = ctx.state().vm2()?.account_client();
+
+        // gen multi-agent txn or read from file sent by other participants.
+        let mut multi_agent_txn = if let Some(file_input) = opt.multi_agent_txn_file.as_ref() {
+            bcs_ext::from_bytes::<MultiAgentTransaction>(&std::fs::read(file_input)?)?
+        } else {
+            let sender = opt
+                .sender
+                .ok_or_else(|| format_err!("sender address should be provided"))?;
+            let function_id = opt
+                .script_function
+                .clone()
+                .ok_or_else(|| format_err!("script function should be provided"))?
+                .0;
+            let script_function = EntryFunction::new(
+                function_id.module,
+                function_id.function,
+                opt.type_tags.clone().unwrap_or_default(),
+                convert_txn_args(&opt.args.clone().unwrap_or_default()),
+            );
+            let node_info = rpc_client.node_info()?;
+            let chain_state_reader = rpc_client.state_reader2(StateRootOption::Latest)?;
+            let account_resource = chain_state_reader.get_account_resource(sender)?;
+            let raw_txn = RawUserTransaction::new(
+                sender,
+                account_resource.sequence_number(),
+                TransactionPayload::EntryFunction(script_function),
+                opt.max_gas_amount,
+                opt.gas_price,
+                opt.expiration_time + node_info.now_seconds,
+                ChainId::new(ctx.state().vm2()?.net().chain_id().id()),
+                STC_TOKEN_CODE_STR.to_string(),
+            );
+            MultiAgentTransaction::new(raw_txn, opt.secondary_signers.clone().unwrap_or_default())
+        };
+        let raw_txn = multi_agent_txn.raw_txn.clone();
+        let secondary_signer_addresses = multi_agent_txn.secondary_signer_addresses.clone();
+
+        let mut raw_txn_view: RawUserTransactionView = raw_txn.clone().try_into()?;
+        raw_txn_view.decoded_payload = Some(TransactionPayloadView::from(
+            ctx.state().vm2()?.decode_txn_payload(raw_txn.payload())?,
+        ));
+        // Use `eprintln` instead of `println`, for keep the cli stdout's format(such as json) is not broken by print.
+        eprintln!(
+            "Prepare to sign the transaction: \n {}",
+            serde_json::to_string_pretty(&raw_txn_view)?
+        );
+
+        // pre-run the txn when first generation, the public keys of the secondary signers are
+        // only known if they are all in the local wallet.
+        if opt.multi_agent_txn_file.is_none() {
+            let sender = ctx.state().vm2()?.get_account(raw_txn.sender())?;
+            let secondary_signers = secondary_signer_addresses
+                .iter()
+                .map(|address| {
+                    account_client
+                        .get_account(*address)
+                        .map(|account| account.map(|account| (*address, account.public_key)))
+                })
+                .collect::<Result<Option<Vec<_>>>>()?;
+            match secondary_signers {
+                Some(secondary_signers) => {
+                    let output = ctx.state().vm2()?.dry_run_transaction(DryRunTransaction {
+                        raw_txn: raw_txn.clone(),
+                        public_key: sender.public_key,
+                        fee_payer: None,
+                        secondary_signers,
+                    })?;
+                    eprintln!(
+                        "Transaction dry run execute output: \n {}",
+                        serde_json::to_string_pretty(&output)?
+                    );
+                    match &output.txn_output.status {
+                        TransactionStatusView2::Discard {
+                            status_code,
+                            status_code_name,
+                        } => {
+                            bail!(
+                                "TransactionStatus is discard: {:?}, {}",
+                                status_code,
+                                status_code_name
+                            )
+                        }
+                        TransactionStatusView2::Executed => {}
+                        s => {
+                            bail!("pre-run failed, status: {:?}", s);
+                        }
+                    }
+                }
+                None => eprintln!(
+                    "skip the dry run, some secondary signers are not in the local wallet"
+                ),
+            }
+        }
+
+        let signer = match opt.signer {
+            Some(signer) => signer,
+            None if opt.multi_agent_txn_file.is_none() => raw_txn.sender(),
+            None => bail!("signer address should be provided"),
+        };
+        let signature = account_client.sign_multi_agent_txn(
+            raw_txn.clone(),
+            secondary_signer_addresses,
+            signer,
+        )?;
+        multi_agent_txn.collect_signature(signer, signature)?;
+
+        let mut output_file = opt.output_dir.clone().unwrap_or(current_dir()?);
+        if !output_file.exists() {
+            std::fs::create_dir_all(output_file.as_path())?;
+        }
+        // use raw txn's hash as output file name
+        output_file.push(raw_txn.crypto_hash().to_hex());
+        let missing_signers = multi_agent_txn.missing_signers();
+        let mut file = if missing_signers.is_empty() {
+            eprintln!("all signatures collected for the multi-agent txn, txn can be submitted now");
+            output_file.set_extension("signed-txn");
+            let mut file = File::create(output_file.as_path())?;
+            bcs_ext::serialize_into(&mut file, &multi_agent_txn.into_signed_txn()?)?;
+            file
+        } else {
+            eprintln!(
+                "still require the signatures of {}",
+                missing_signers
+                    .iter()
+                    .map(|address| address.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            output_file.set_extension("multi-agent-txn");
+            let mut file = File::create(output_file.as_path())?;
+            bcs_ext::serialize_into(&mut file, &multi_agent_txn)?;
+            file
+        };
+        file.sync_all()?;
+        Ok(output_file)
+    }
+}
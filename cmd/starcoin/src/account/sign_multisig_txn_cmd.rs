@@ -166,7 +166,9 @@ impl CommandAction for GenerateMultisigTxnCommand {
             .get_account(sender)?
             .ok_or_else(|| anyhow::anyhow!("cannot find multisig address {}", sender))?;
         let account_public_key = match &account.public_key {
-            AccountPublicKey::Single(_) => {
+            AccountPublicKey::Single(_)
+            | AccountPublicKey::Secp256k1(_)
+            | AccountPublicKey::Secp256r1(_) => {
                 bail!("sender {} is not a multisig address", sender);
             }
             AccountPublicKey::Multi(m) => m.clone(),
@@ -177,6 +179,8 @@ impl CommandAction for GenerateMultisigTxnCommand {
             let output = ctx.state().vm2()?.dry_run_transaction(DryRunTransaction {
                 public_key: AccountPublicKey::Multi(account_public_key.clone()),
                 raw_txn: raw_txn.clone(),
+                fee_payer: None,
+                secondary_signers: vec![],
             })?;
 
             eprintln!(
@@ -221,6 +221,9 @@ impl CliState {
         txn_opts: TransactionOptions,
         payload: TransactionPayload,
     ) -> Result<ExecuteResultView> {
+        if txn_opts.fee_payer.is_some() {
+            bail!("fee payer is only supported by vm2 transaction");
+        }
         let (raw_txn, future_transaction) = self.build_transaction(
             txn_opts.sender,
             txn_opts.sequence_number,
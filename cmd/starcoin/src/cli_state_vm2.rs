@@ -222,7 +222,15 @@ impl CliStateVM2 {
             bail!("there is transaction from sender({}) in the txpool, please wait it to been executed or use sequence_number({}) to replace it.",
                 raw_txn.sender(), raw_txn.sequence_number() - 1);
         }
-        self.execute_transaction(raw_txn, txn_opts.dry_run, txn_opts.blocking)
+        match txn_opts.fee_payer {
+            Some(fee_payer) => self.execute_fee_payer_transaction(
+                raw_txn,
+                AccountAddress::new(fee_payer.into_bytes()),
+                txn_opts.dry_run,
+                txn_opts.blocking,
+            ),
+            None => self.execute_transaction(raw_txn, txn_opts.dry_run, txn_opts.blocking),
+        }
     }
 
     fn build_transaction(
@@ -290,6 +298,8 @@ impl CliStateVM2 {
         let dry_output = self.dry_run_transaction(DryRunTransaction {
             public_key: public_key.clone(),
             raw_txn: raw_txn.clone(),
+            fee_payer: None,
+            secondary_signers: vec![],
         })?;
         let mut raw_txn_view: RawUserTransactionView = raw_txn.clone().try_into()?;
         raw_txn_view.decoded_payload = Some(TransactionPayloadView::from(
@@ -312,7 +322,9 @@ impl CliStateVM2 {
 
         let signed_txn = self.account_client.sign_txn(raw_txn, sender.address)?;
         let multisig_public_key = match &public_key {
-            AccountPublicKey::Single(_) => {
+            AccountPublicKey::Single(_)
+            | AccountPublicKey::Secp256k1(_)
+            | AccountPublicKey::Secp256r1(_) => {
                 let signed_txn_hex = hex::encode(signed_txn.encode()?);
                 let txn_hash = self.client.submit_hex_transaction2(signed_txn_hex)?;
                 eprintln!("txn {} submitted.", txn_hash);
@@ -356,6 +368,67 @@ impl CliStateVM2 {
         Ok(execute_result)
     }
 
+    /// Execute a txn whose gas is paid by `fee_payer`, both the sender and the fee payer sign it
+    /// with the local wallet.
+    pub fn execute_fee_payer_transaction(
+        &self,
+        raw_txn: RawUserTransaction,
+        fee_payer: AccountAddress,
+        only_dry_run: bool,
+        blocking: bool,
+    ) -> Result<ExecuteResultView> {
+        let sender = self.get_account(raw_txn.sender())?;
+        let fee_payer = self.get_account(fee_payer)?;
+        if matches!(sender.public_key, AccountPublicKey::Multi(_))
+            || matches!(fee_payer.public_key, AccountPublicKey::Multi(_))
+        {
+            bail!("multisig account is not supported by fee payer txn");
+        }
+        let dry_output = self.dry_run_transaction(DryRunTransaction {
+            public_key: sender.public_key.clone(),
+            raw_txn: raw_txn.clone(),
+            fee_payer: Some((fee_payer.address, fee_payer.public_key.clone())),
+            secondary_signers: vec![],
+        })?;
+        let mut raw_txn_view: RawUserTransactionView = raw_txn.clone().try_into()?;
+        raw_txn_view.decoded_payload = Some(TransactionPayloadView::from(
+            self.decode_txn_payload(raw_txn.payload())?,
+        ));
+
+        let mut execute_result = ExecuteResultView::new(raw_txn_view, raw_txn.to_hex(), dry_output);
+        if only_dry_run
+            || !matches!(
+                execute_result.dry_run_output.txn_output.status,
+                TransactionStatusView::Executed
+            )
+        {
+            eprintln!(
+                "txn dry run result: {:?}",
+                execute_result.dry_run_output.txn_output
+            );
+            return Ok(execute_result);
+        }
+
+        let sender_signer = self.account_client.sign_fee_payer_txn(
+            raw_txn.clone(),
+            fee_payer.address,
+            sender.address,
+        )?;
+        let fee_payer_signer = self.account_client.sign_fee_payer_txn(
+            raw_txn.clone(),
+            fee_payer.address,
+            fee_payer.address,
+        )?;
+        let signed_txn = SignedUserTransaction::fee_payer(
+            raw_txn,
+            sender_signer,
+            fee_payer.address,
+            fee_payer_signer,
+        );
+        execute_result.execute_output = Some(self.submit_txn(signed_txn, blocking)?);
+        Ok(execute_result)
+    }
+
     pub fn decode_txn_payload(
         &self,
         payload: &TransactionPayload,
@@ -121,6 +121,28 @@ impl CommandAction for SubscribeNewMintBlockCommand {
     }
 }
 
+#[derive(Debug, Parser)]
+#[clap(name = "reorgs")]
+pub struct SubscribeReorgsOpt {}
+pub struct SubscribeReorgsCommand;
+impl CommandAction for SubscribeReorgsCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = SubscribeReorgsOpt;
+    type ReturnItem = ();
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let event_stream = ctx.state().client().subscribe_reorgs()?;
+        println!("Subscribe successful, Press `q` and Enter to quit");
+        blocking_display_notification(event_stream, |evt| {
+            serde_json::to_string(&evt).expect("should never fail")
+        });
+        Ok(())
+    }
+}
+
 #[derive(Debug, Parser)]
 #[clap(name = "new_pending_txn")]
 pub struct SubscribeNewTxnOpt {}
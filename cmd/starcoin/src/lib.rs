@@ -40,8 +40,11 @@ pub fn add_command(
                 .subcommand(account::ImportCommand)
                 .subcommand(account::ImportReadonlyCommand)
                 .subcommand(account::ExecuteScriptFunctionCmd)
+                .subcommand(account::ExecuteBatchCmd)
                 .subcommand(account::ExecuteScriptCommand)
                 .subcommand(account::GenerateMultisigTxnCommand)
+                .subcommand(account::SignMultiAgentTxnCommand)
+                .subcommand(account::SignFeePayerTxnCommand)
                 .subcommand(account::SubmitSignedTxnCommand)
                 .subcommand(account::SignMessageCmd)
                 .subcommand(account::VerifySignMessageCmd)
@@ -84,6 +87,7 @@ pub fn add_command(
                 .subcommand(
                     CustomCommand::with_name("network")
                         .subcommand(node::network::StateCommand)
+                        .subcommand(node::network::BandwidthCommand)
                         .subcommand(node::network::KnownPeersCommand)
                         .subcommand(node::network::GetAddressCommand)
                         .subcommand(node::network::AddPeerCommand)
@@ -127,7 +131,8 @@ pub fn add_command(
                         .subcommand(dev::SubscribeNewMintBlockCommand)
                         .subcommand(dev::SubscribeBlockCommand)
                         .subcommand(dev::SubscribeEventCommand)
-                        .subcommand(dev::SubscribeNewTxnCommand),
+                        .subcommand(dev::SubscribeNewTxnCommand)
+                        .subcommand(dev::SubscribeReorgsCommand),
                 )
                 .subcommand(
                     CustomCommand::with_name("log")
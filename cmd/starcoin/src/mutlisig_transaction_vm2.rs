@@ -101,7 +101,11 @@ pub fn read_multisig_existing_signatures(file_input: &Path) -> Result<RawTxnMult
     let txn: SignedUserTransaction = bcs_ext::from_bytes(&std::fs::read(file_input)?)?;
 
     let existing_signatures = match txn.authenticator() {
-        TransactionAuthenticator::Ed25519 { .. } => {
+        TransactionAuthenticator::Ed25519 { .. }
+        | TransactionAuthenticator::FeePayer { .. }
+        | TransactionAuthenticator::MultiAgent { .. }
+        | TransactionAuthenticator::Secp256k1Ecdsa { .. }
+        | TransactionAuthenticator::Secp256r1WebAuthn { .. } => {
             bail!("expect a multisig txn in file {}", file_input.display());
         }
         TransactionAuthenticator::MultiEd25519 {
@@ -0,0 +1,31 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_state::CliState;
+use crate::StarcoinOpt;
+use anyhow::Result;
+use clap::Parser;
+use network_p2p_types::bandwidth::BandwidthStats;
+use scmd::{CommandAction, ExecContext};
+
+/// Show the bandwidth consumed by the node, split by protocol and by peer.
+#[derive(Debug, Parser, Default)]
+#[clap(name = "bandwidth")]
+pub struct BandwidthOpt {}
+
+pub struct BandwidthCommand;
+
+impl CommandAction for BandwidthCommand {
+    type State = CliState;
+    type GlobalOpt = StarcoinOpt;
+    type Opt = BandwidthOpt;
+    type ReturnItem = BandwidthStats;
+
+    fn run(
+        &self,
+        ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
+    ) -> Result<Self::ReturnItem> {
+        let client = ctx.state().client();
+        client.network_bandwidth()
+    }
+}
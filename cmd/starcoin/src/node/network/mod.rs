@@ -3,6 +3,7 @@
 
 mod add_peer_cmd;
 mod ban_peer_cmd;
+mod bandwidth_cmd;
 mod call_peer_cmd;
 mod get_address_cmd;
 mod known_peers_cmd;
@@ -10,6 +11,7 @@ mod set_peer_reputation;
 mod state_cmd;
 pub use add_peer_cmd::*;
 pub use ban_peer_cmd::*;
+pub use bandwidth_cmd::*;
 pub use call_peer_cmd::*;
 pub use get_address_cmd::*;
 pub use known_peers_cmd::*;
@@ -5,12 +5,25 @@ use crate::cli_state::CliState;
 use crate::StarcoinOpt;
 use anyhow::Result;
 use clap::Parser;
+use network_api::peer_score::PeerQualityView;
 use scmd::{CommandAction, ExecContext};
+use serde::Serialize;
 use starcoin_rpc_api::types::PeerInfoView;
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum PeersView {
+    Peers(Vec<PeerInfoView>),
+    Scores(Vec<PeerQualityView>),
+}
+
 #[derive(Debug, Parser)]
 #[clap(name = "peers")]
-pub struct PeersOpt {}
+pub struct PeersOpt {
+    #[clap(long = "scores")]
+    /// Show the rpc quality scoreboard of peers instead of the peer infos.
+    scores: bool,
+}
 
 pub struct PeersCommand;
 
@@ -18,14 +31,17 @@ impl CommandAction for PeersCommand {
     type State = CliState;
     type GlobalOpt = StarcoinOpt;
     type Opt = PeersOpt;
-    type ReturnItem = Vec<PeerInfoView>;
+    type ReturnItem = PeersView;
 
     fn run(
         &self,
         ctx: &ExecContext<Self::State, Self::GlobalOpt, Self::Opt>,
     ) -> Result<Self::ReturnItem> {
         let client = ctx.state().client();
-        let peers = client.node_peers()?;
-        Ok(peers)
+        if ctx.opt().scores {
+            Ok(PeersView::Scores(client.sync_peer_quality()?))
+        } else {
+            Ok(PeersView::Peers(client.node_peers()?))
+        }
     }
 }
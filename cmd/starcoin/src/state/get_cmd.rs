@@ -7,7 +7,7 @@ use anyhow::{format_err, Result};
 use clap::Parser;
 use scmd::{CommandAction, ExecContext};
 use serde::{Serialize, Serializer};
-use starcoin_vm2_rpc_api::state_api::PrimaryFungibleStoreOption;
+use starcoin_vm2_rpc_api::state_api::{ExpandOption, PrimaryFungibleStoreOption};
 use starcoin_vm2_types::view::{CodeView, ResourceView, StrView};
 use starcoin_vm2_vm_types::account_address::AccountAddress;
 use starcoin_vm2_vm_types::language_storage::{ModuleId, StructTag};
@@ -20,6 +20,7 @@ use starcoin_vm2_vm_types::{
 ///  ``` shell
 ///  state get code 0x1::Account
 ///  state get resource 0x1 0x1::Account::Account
+///  state get resource 0x1 0x1::fungible_asset::FungibleStore --primary-store --expand 2
 ///  ```
 #[derive(Debug, Parser)]
 #[clap(name = "get")]
@@ -49,6 +50,9 @@ pub enum GetOpt {
             help = "Token code for primary store, default STC"
         )]
         token_code: Option<String>,
+        #[clap(long = "expand")]
+        /// Expand the objects, fungible stores and tables referenced by the resource to the depth.
+        expand: Option<u8>,
     },
 }
 
@@ -107,6 +111,7 @@ impl CommandAction for GetCommand {
                 block_number,
                 primary_store,
                 token_code,
+                expand,
             } => {
                 let state_root = match block_number {
                     Some(block_number) => ctx
@@ -135,6 +140,10 @@ impl CommandAction for GetCommand {
                     true,
                     state_root,
                     primary_option,
+                    expand.map(|depth| ExpandOption {
+                        depth,
+                        ..Default::default()
+                    }),
                 )?)
             }
         };
@@ -150,6 +150,7 @@ impl CommandAction for ListCmd {
                         max_size.unwrap_or_else(|| usize::MAX),
                         resource_type.as_ref().map(|a| vec![a.clone()]),
                         primary_option,
+                        None,
                     )?,
                 ))
             }
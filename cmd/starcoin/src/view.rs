@@ -63,6 +63,13 @@ pub struct TransactionOptions {
     #[clap(name = "gas-token", long = "gas-token", name = "token code of gas")]
     /// "token code of gas to pay, for example: 0x1::STC::STC, default is STC."
     pub gas_token: Option<String>,
+
+    #[clap(long = "fee-payer")]
+    /// the account paying the gas of a vm2 transaction instead of the sender, it signs the txn too.
+    /// a sender without account on chain can send its first txn with `--sequence-number 0`.
+    /// the sender and the fee payer must be both in the local wallet, use
+    /// `account sign-fee-payer-txn` to sign the txn by different wallets.
+    pub fee_payer: Option<AccountAddress>,
 }
 
 #[derive(Debug, Clone)]
@@ -5,7 +5,8 @@ use serde::{Deserialize, Serialize};
 use starcoin_crypto::HashValue;
 use starcoin_vm2_account_api::AccountInfo;
 use starcoin_vm2_types::view::{
-    DryRunOutputView, RawUserTransactionView, TransactionEventResponse, TransactionInfoView,
+    DryRunOutputView, PortfolioView, RawUserTransactionView, TransactionEventResponse,
+    TransactionInfoView,
 };
 use starcoin_vm2_vm_types::account_config::token_code::TokenCode;
 use std::collections::HashMap;
@@ -74,4 +75,6 @@ pub struct AccountWithStateView {
     pub auth_key: String,
     pub sequence_number: Option<u64>,
     pub balances: HashMap<TokenCode, u128>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub portfolio: Option<PortfolioView>,
 }
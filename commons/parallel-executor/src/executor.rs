@@ -8,6 +8,7 @@ use crate::{
     },
     errors::*,
     scheduler::{Scheduler, SchedulerTask, TaskGuard, TxnIndex, Version},
+    stats::{self, ExecutionStats},
     task::{ExecutionStatus, ExecutorTask, Transaction, TransactionOutput},
     txn_last_input_output::{ReadDescriptor, TxnLastInputOutput},
 };
@@ -27,7 +28,7 @@ use std::{
     hash::Hash,
     marker::PhantomData,
     sync::{
-        atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
     thread::spawn,
@@ -245,6 +246,9 @@ pub struct ParallelTransactionExecutor<T: Transaction, E: ExecutorTask> {
     delayed_fields_enabled: bool,
     delayed_field_id_start: u32,
     delayed_field_id_counter: AtomicU32,
+    executions: AtomicU64,
+    aborts: AtomicU64,
+    last_stats: Mutex<ExecutionStats>,
 }
 
 impl<T, E> ParallelTransactionExecutor<T, E>
@@ -269,6 +273,9 @@ where
             delayed_fields_enabled: false,
             delayed_field_id_start,
             delayed_field_id_counter: AtomicU32::new(delayed_field_id_start),
+            executions: AtomicU64::new(0),
+            aborts: AtomicU64::new(0),
+            last_stats: Mutex::new(ExecutionStats::default()),
         }
     }
 
@@ -304,6 +311,7 @@ where
         if txn.is_block_epilogue() {
             return scheduler.finish_execution(idx_to_execute, incarnation, false, guard);
         }
+        self.executions.fetch_add(1, Ordering::Relaxed);
 
         let state_view = MVHashMapView {
             versioned_map: versioned_data_cache,
@@ -474,6 +482,7 @@ where
                 // Speculative delayed-field read failure requires re-execution.
                 let aborted = scheduler.try_abort(idx_to_validate, incarnation);
                 if aborted {
+                    self.aborts.fetch_add(1, Ordering::Relaxed);
                     for k in &last_input_output.write_set(idx_to_validate) {
                         versioned_data_cache.mark_estimate(k, idx_to_validate);
                     }
@@ -511,6 +520,7 @@ where
         let aborted = !valid && scheduler.try_abort(idx_to_validate, incarnation);
 
         if aborted {
+            self.aborts.fetch_add(1, Ordering::Relaxed);
             info!(
                 target: "starcoin_parallel_executor",
                 "abort txn_idx={} incarnation={}",
@@ -772,6 +782,8 @@ where
         }
 
         let num_txns = signature_verified_block.len();
+        self.executions.store(0, Ordering::Relaxed);
+        self.aborts.store(0, Ordering::Relaxed);
         let versioned_data_cache = MVHashMap::new();
         let last_input_output = TxnLastInputOutput::new(num_txns);
         let scheduler = Scheduler::new(num_txns, self.gas_limit);
@@ -986,11 +998,24 @@ where
             drop(scheduler);
         });
 
+        let stats = ExecutionStats {
+            txns: num_txns as u64,
+            executions: self.executions.load(Ordering::Relaxed),
+            aborts: self.aborts.load(Ordering::Relaxed),
+        };
+        stats::record(&stats);
+        *self.last_stats.lock() = stats;
+
         match maybe_err {
             Some(err) => Err(err),
             None => Ok((final_results, delayed_fields)),
         }
     }
+
+    /// The stats of the last block executed by this executor.
+    pub fn last_execution_stats(&self) -> ExecutionStats {
+        *self.last_stats.lock()
+    }
 }
 
 #[cfg(test)]
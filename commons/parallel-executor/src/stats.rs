@@ -0,0 +1,54 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// How much work the parallel execution of blocks took, an execution is one incarnation of a txn,
+/// so every re-execution follows an abort of the txn.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ExecutionStats {
+    pub txns: u64,
+    pub executions: u64,
+    pub aborts: u64,
+}
+
+impl ExecutionStats {
+    pub fn re_executions(&self) -> u64 {
+        self.executions.saturating_sub(self.txns)
+    }
+
+    /// Aborts per txn.
+    pub fn abort_rate(&self) -> f64 {
+        if self.txns == 0 {
+            return 0.0;
+        }
+        self.aborts as f64 / self.txns as f64
+    }
+
+    /// Re-executions per txn.
+    pub fn re_execution_rate(&self) -> f64 {
+        if self.txns == 0 {
+            return 0.0;
+        }
+        self.re_executions() as f64 / self.txns as f64
+    }
+}
+
+static TOTAL_TXNS: AtomicU64 = AtomicU64::new(0);
+static TOTAL_EXECUTIONS: AtomicU64 = AtomicU64::new(0);
+static TOTAL_ABORTS: AtomicU64 = AtomicU64::new(0);
+
+pub(crate) fn record(stats: &ExecutionStats) {
+    TOTAL_TXNS.fetch_add(stats.txns, Ordering::Relaxed);
+    TOTAL_EXECUTIONS.fetch_add(stats.executions, Ordering::Relaxed);
+    TOTAL_ABORTS.fetch_add(stats.aborts, Ordering::Relaxed);
+}
+
+/// The stats of all the blocks executed in parallel by this process.
+pub fn total_execution_stats() -> ExecutionStats {
+    ExecutionStats {
+        txns: TOTAL_TXNS.load(Ordering::Relaxed),
+        executions: TOTAL_EXECUTIONS.load(Ordering::Relaxed),
+        aborts: TOTAL_ABORTS.load(Ordering::Relaxed),
+    }
+}
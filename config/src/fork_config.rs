@@ -0,0 +1,105 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BaseConfig, ConfigModule, StarcoinOpt};
+use anyhow::{ensure, Result};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use starcoin_vm2_vm_types::account_address::AccountAddress;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// The chain a dev node forks from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ForkFrom {
+    /// Rpc url of a remote node, ws or http.
+    Rpc(String),
+    /// Data dir of a local node, opened readonly.
+    DataDir(PathBuf),
+}
+
+impl ForkFrom {
+    fn parse(value: &str) -> Self {
+        let is_url = ["ws://", "wss://", "http://", "https://"]
+            .iter()
+            .any(|scheme| value.starts_with(scheme));
+        if is_url {
+            Self::Rpc(value.to_string())
+        } else {
+            Self::DataDir(PathBuf::from(value))
+        }
+    }
+}
+
+#[derive(Clone, Default, Debug, Deserialize, PartialEq, Eq, Serialize, Parser)]
+#[serde(deny_unknown_fields)]
+pub struct ForkConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "fork-from",
+        long,
+        help = "fork the dev chain from a node, by its rpc url (ws or http) or its data dir, the state is fetched lazily from it. The forked chain uses the chain id of the dev network, transactions should be signed with it"
+    )]
+    fork_from: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "fork-block",
+        long,
+        help = "number of the block to fork from, default the head block of the forked node"
+    )]
+    fork_block: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "fork-impersonate",
+        long,
+        help = "address of an account to impersonate in the forked chain, its authentication key is replaced by the genesis key of the dev network when the chain is forked",
+        number_of_values = 1
+    )]
+    impersonate: Option<Vec<AccountAddress>>,
+}
+
+impl ForkConfig {
+    pub fn fork_from(&self) -> Option<ForkFrom> {
+        self.fork_from.as_deref().map(ForkFrom::parse)
+    }
+
+    pub fn fork_block(&self) -> Option<u64> {
+        self.fork_block
+    }
+
+    pub fn impersonate(&self) -> &[AccountAddress] {
+        self.impersonate.as_deref().unwrap_or(&[])
+    }
+}
+
+impl ConfigModule for ForkConfig {
+    fn merge_with_opt(&mut self, opt: &StarcoinOpt, base: Arc<BaseConfig>) -> Result<()> {
+        if opt.fork.fork_from.is_some() {
+            self.fork_from.clone_from(&opt.fork.fork_from);
+        }
+
+        if opt.fork.fork_block.is_some() {
+            self.fork_block = opt.fork.fork_block;
+        }
+
+        if opt.fork.impersonate.is_some() {
+            self.impersonate.clone_from(&opt.fork.impersonate);
+        }
+
+        if self.fork_from.is_some() {
+            ensure!(
+                base.net().is_dev(),
+                "Only the dev network can fork from another node, current network is {}",
+                base.net()
+            );
+        } else {
+            ensure!(
+                self.fork_block.is_none() && self.impersonate.is_none(),
+                "fork-block and fork-impersonate require fork-from"
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,260 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Genesis account allocations, let custom and dev networks start with funded accounts and
+//! published packages instead of sending transfer transactions after genesis.
+
+use anyhow::{ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, ValidCryptoMaterialStringExt};
+use starcoin_vm_types::account_address::AccountAddress;
+use starcoin_vm_types::transaction::authenticator::AuthenticationKey;
+use starcoin_vm_types::transaction::Package;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// An account entry of the genesis allocation file, the fields are parsed by the vm of the
+/// allocation list the entry is in.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AllocationEntry {
+    /// Account address, hex with `0x` prefix.
+    pub address: String,
+    /// Authentication key of the account, hex with `0x` prefix.
+    #[serde(default)]
+    pub auth_key: Option<String>,
+    /// STC amount in nanoSTC, transferred from the association account.
+    #[serde(default)]
+    pub amount: u128,
+    /// Path of a package blob (`mpm release` output) published under the account, relative paths
+    /// are relative to the allocation file.
+    #[serde(default)]
+    pub package: Option<PathBuf>,
+    /// Ed25519 private key of the account, hex encoded, required to sign the package publishing.
+    #[serde(default)]
+    pub private_key: Option<String>,
+}
+
+impl AllocationEntry {
+    fn read_package(&self, base_dir: &Path) -> Result<Option<Vec<u8>>> {
+        self.package
+            .as_ref()
+            .map(|path| {
+                let path = if path.is_absolute() {
+                    path.clone()
+                } else {
+                    base_dir.join(path)
+                };
+                fs::read(&path)
+                    .map_err(|e| format_err!("Read package blob {:?} failed: {}", path, e))
+            })
+            .transpose()
+    }
+}
+
+/// Suffix of the file next to a genesis config which keeps the private keys of its allocations,
+/// the keys are not serialized into the genesis config.
+const ALLOCATION_KEYS_SUFFIX: &str = ".keys";
+
+fn allocation_keys_path(config_path: &Path) -> PathBuf {
+    let mut path = config_path.as_os_str().to_owned();
+    path.push(ALLOCATION_KEYS_SUFFIX);
+    PathBuf::from(path)
+}
+
+/// Write the encoded private keys of the allocations, keyed by address, to the key file of the
+/// genesis config. Only the owner can read the file.
+pub(crate) fn save_allocation_keys(
+    config_path: &Path,
+    keys: BTreeMap<String, String>,
+) -> Result<()> {
+    if keys.is_empty() {
+        return Ok(());
+    }
+    let path = allocation_keys_path(config_path);
+    if path.exists() {
+        fs::remove_file(&path)?;
+    }
+    let mut file = crate::helper::open_key_file(&path)?;
+    file.write_all(serde_json::to_vec_pretty(&keys)?.as_slice())?;
+    Ok(())
+}
+
+/// Read the encoded private keys of the allocations from the key file of the genesis config.
+pub(crate) fn load_allocation_keys(config_path: &Path) -> Result<BTreeMap<String, String>> {
+    let path = allocation_keys_path(config_path);
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    Ok(serde_json::from_slice(fs::read(&path)?.as_slice())?)
+}
+
+/// Implement the parsing and the checks of the `GenesisAllocation` of a vm with the address, key
+/// and package types in scope of the invocation. With `$auth_key_derives_address` the auth key
+/// must derive the address of the account.
+macro_rules! impl_genesis_allocation {
+    ($auth_key_derives_address:literal) => {
+        impl GenesisAllocation {
+            pub(crate) fn encoded_private_key(&self) -> Result<Option<(String, String)>> {
+                self.private_key
+                    .as_ref()
+                    .map(|key| Ok((self.address.to_string(), key.to_encoded_string()?)))
+                    .transpose()
+            }
+
+            pub(crate) fn restore_private_key(
+                &mut self,
+                keys: &BTreeMap<String, String>,
+            ) -> Result<()> {
+                if let Some(key) = keys.get(&self.address.to_string()) {
+                    self.private_key = Some(Arc::new(Ed25519PrivateKey::from_encoded_string(key)?));
+                }
+                Ok(())
+            }
+
+            pub(crate) fn from_entry(
+                entry: &AllocationEntry,
+                package: Option<Vec<u8>>,
+            ) -> Result<Self> {
+                let address = AccountAddress::from_str(entry.address.as_str())?;
+                let auth_key = entry
+                    .auth_key
+                    .as_deref()
+                    .map(AuthenticationKey::from_str)
+                    .transpose()?;
+                let package = package
+                    .map(|blob| bcs_ext::from_bytes::<Package>(&blob))
+                    .transpose()?;
+                let private_key = entry
+                    .private_key
+                    .as_deref()
+                    .map(Ed25519PrivateKey::from_encoded_string)
+                    .transpose()?
+                    .map(Arc::new);
+                let allocation = Self {
+                    address,
+                    auth_key,
+                    amount: entry.amount,
+                    package,
+                    private_key,
+                };
+                allocation.validate()?;
+                Ok(allocation)
+            }
+
+            pub fn validate(&self) -> Result<()> {
+                if $auth_key_derives_address {
+                    if let Some(auth_key) = self.auth_key.as_ref() {
+                        ensure!(
+                            auth_key.derived_address() == self.address,
+                            "auth key of genesis allocation {} does not derive its address",
+                            self.address
+                        );
+                    }
+                }
+                if let Some(private_key) = self.private_key.as_ref() {
+                    let derived_auth_key = AuthenticationKey::ed25519(&private_key.public_key());
+                    if let Some(auth_key) = self.auth_key.as_ref() {
+                        ensure!(
+                            auth_key == &derived_auth_key,
+                            "auth key of genesis allocation {} does not match its private key",
+                            self.address
+                        );
+                    }
+                    ensure!(
+                        derived_auth_key.derived_address() == self.address,
+                        "address of genesis allocation {} does not match its private key",
+                        self.address
+                    );
+                }
+                if let Some(package) = self.package.as_ref() {
+                    ensure!(
+                        self.private_key.is_some(),
+                        "genesis allocation {} publishes a package, but has no private key",
+                        self.address
+                    );
+                    ensure!(
+                        package.package_address() == self.address,
+                        "package of genesis allocation {} is for address {}",
+                        self.address,
+                        package.package_address()
+                    );
+                }
+                Ok(())
+            }
+        }
+    };
+}
+pub(crate) use impl_genesis_allocation;
+
+/// Genesis allocation file, in json or toml (by the `.toml` extension) format.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisAllocations {
+    #[serde(default)]
+    pub vm1: Vec<AllocationEntry>,
+    #[serde(default)]
+    pub vm2: Vec<AllocationEntry>,
+    #[serde(skip)]
+    base_dir: PathBuf,
+}
+
+impl GenesisAllocations {
+    pub fn load<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+        let mut allocations: Self = if path.extension().and_then(|ext| ext.to_str()) == Some("toml")
+        {
+            toml::from_str(&contents)?
+        } else {
+            serde_json::from_str(&contents)?
+        };
+        allocations.base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        Ok(allocations)
+    }
+
+    pub fn vm1_allocations(&self) -> Result<Vec<GenesisAllocation>> {
+        self.vm1
+            .iter()
+            .map(|entry| {
+                GenesisAllocation::from_entry(entry, entry.read_package(self.base_dir.as_path())?)
+            })
+            .collect()
+    }
+
+    pub fn vm2_allocations(&self) -> Result<Vec<super::vm2::GenesisAllocation>> {
+        self.vm2
+            .iter()
+            .map(|entry| {
+                super::vm2::GenesisAllocation::from_entry(
+                    entry,
+                    entry.read_package(self.base_dir.as_path())?,
+                )
+            })
+            .collect()
+    }
+}
+
+/// A vm1 account allocated in genesis, executed by the association after the genesis txn.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct GenesisAllocation {
+    pub address: AccountAddress,
+    /// Authentication key of the account, the account is created with it if present.
+    pub auth_key: Option<AuthenticationKey>,
+    /// STC amount in nanoSTC.
+    pub amount: u128,
+    /// Package published under the account.
+    pub package: Option<Package>,
+    /// Private key to sign the package publishing, kept in the key file of the genesis config.
+    #[serde(skip)]
+    pub private_key: Option<Arc<Ed25519PrivateKey>>,
+}
+
+impl_genesis_allocation!(false);
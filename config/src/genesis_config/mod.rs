@@ -1,8 +1,12 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+mod allocation;
 pub mod vm2;
 
+use allocation::{load_allocation_keys, save_allocation_keys};
+pub use allocation::{AllocationEntry, GenesisAllocation, GenesisAllocations};
+
 use anyhow::{bail, ensure, format_err, Result};
 use network_p2p_types::MultiaddrWithPeerId;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
@@ -664,6 +668,9 @@ pub struct GenesisConfig {
     pub time_service_type: TimeServiceType,
     /// transaction timeout
     pub transaction_timeout: u64,
+    /// Accounts funded, and packages published, by the association in genesis.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allocations: Vec<GenesisAllocation>,
 }
 
 impl GenesisConfig {
@@ -712,7 +719,12 @@ impl GenesisConfig {
         let mut file = File::open(&path)?;
         let mut contents = String::new();
         file.read_to_string(&mut contents)?;
-        Ok(serde_json::from_str(&contents)?)
+        let mut config: Self = serde_json::from_str(&contents)?;
+        let keys = load_allocation_keys(path.as_ref())?;
+        for allocation in config.allocations.iter_mut() {
+            allocation.restore_private_key(&keys)?;
+        }
+        Ok(config)
     }
 
     pub fn save<P>(&self, path: P) -> Result<()>
@@ -722,6 +734,12 @@ impl GenesisConfig {
         let mut file = File::create(&path)?;
         let buf = serde_json::to_vec_pretty(self)?;
         file.write_all(buf.as_slice())?;
+        let keys = self
+            .allocations
+            .iter()
+            .filter_map(|allocation| allocation.encoded_private_key().transpose())
+            .collect::<Result<_>>()?;
+        save_allocation_keys(path.as_ref(), keys)?;
         Ok(())
     }
 
@@ -808,6 +826,7 @@ pub static G_TEST_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 1000, // 1h
         },
         transaction_timeout: ONE_DAY,
+        allocations: vec![],
     }
 });
 
@@ -861,6 +880,7 @@ pub static G_DEV_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 1000, // 1h
         },
         transaction_timeout: ONE_DAY,
+        allocations: vec![],
     }
 });
 
@@ -919,6 +939,7 @@ pub static G_HALLEY_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 1000, // 1h
         },
         transaction_timeout: ONE_DAY,
+        allocations: vec![],
     }
 });
 
@@ -987,6 +1008,7 @@ pub static G_PROXIMA_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 1000, // 1 minute
         },
         transaction_timeout: ONE_DAY,
+        allocations: vec![],
     }
 });
 
@@ -1044,6 +1066,7 @@ pub static G_BARNARD_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 24 * 1000, // 1d
         },
         transaction_timeout: ONE_DAY,
+        allocations: vec![],
     }
 });
 
@@ -1108,6 +1131,7 @@ pub static G_MAIN_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 24 * 1000, // 1d
         },
         transaction_timeout: ONE_DAY,
+        allocations: vec![],
     }
 });
 
@@ -1,6 +1,9 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::genesis_config::allocation::{
+    impl_genesis_allocation, load_allocation_keys, save_allocation_keys, AllocationEntry,
+};
 use crate::BuiltinNetworkID;
 use anyhow::{bail, ensure, Result};
 use once_cell::sync::Lazy;
@@ -15,10 +18,11 @@ use starcoin_vm2_crypto::{
     ed25519::*,
     multi_ed25519::multi_shard::MultiEd25519KeyShard,
     multi_ed25519::{genesis_multi_key_pair, MultiEd25519PublicKey},
-    HashValue, ValidCryptoMaterialStringExt,
+    HashValue, PrivateKey, ValidCryptoMaterialStringExt,
 };
 //use network_p2p_types::MultiaddrWithPeerId;
 use starcoin_vm2_vm_types::{
+    account_address::AccountAddress,
     gas_schedule::{G_GAS_CONSTANTS_V1, G_GAS_CONSTANTS_V2, G_LATEST_GAS_CONSTANTS},
     on_chain_config::{
         instruction_table_v1, native_table_v1, native_table_v2, ConsensusConfig, DaoConfig,
@@ -27,13 +31,17 @@ use starcoin_vm2_vm_types::{
     },
     token::stc::STCUnit,
     token::token_value::TokenValue,
-    transaction::{RawUserTransaction, SignedUserTransaction},
+    transaction::{
+        authenticator::AuthenticationKey, Package, RawUserTransaction, SignedUserTransaction,
+    },
 };
+use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::fs::File;
 use std::io::{Read, Write};
 use std::path::Path;
+use std::str::FromStr;
 use std::sync::Arc;
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
@@ -91,6 +99,9 @@ pub struct GenesisConfig {
     pub time_service_type: TimeServiceType,
     /// transaction timeout
     pub transaction_timeout: u64,
+    /// Accounts funded, and packages published, by the association in genesis.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allocations: Vec<GenesisAllocation>,
 }
 
 impl GenesisConfig {
@@ -139,7 +150,12 @@ impl GenesisConfig {
         let mut file = File::open(&path)?;
         let mut contents = String::new();
         file.read_to_string(&mut contents)?;
-        Ok(serde_json::from_str(&contents)?)
+        let mut config: Self = serde_json::from_str(&contents)?;
+        let keys = load_allocation_keys(path.as_ref())?;
+        for allocation in config.allocations.iter_mut() {
+            allocation.restore_private_key(&keys)?;
+        }
+        Ok(config)
     }
 
     pub fn save<P>(&self, path: P) -> Result<()>
@@ -149,6 +165,12 @@ impl GenesisConfig {
         let mut file = File::create(&path)?;
         let buf = serde_json::to_vec_pretty(self)?;
         file.write_all(buf.as_slice())?;
+        let keys = self
+            .allocations
+            .iter()
+            .filter_map(|allocation| allocation.encoded_private_key().transpose())
+            .collect::<Result<_>>()?;
+        save_allocation_keys(path.as_ref(), keys)?;
         Ok(())
     }
 
@@ -158,6 +180,24 @@ impl GenesisConfig {
     }
 }
 
+/// A vm2 account allocated in genesis, executed by the association after the genesis txn.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct GenesisAllocation {
+    pub address: AccountAddress,
+    /// Authentication key of the account, vm2 accounts are created with the key derived from
+    /// their address, so it is only checked to derive the address.
+    pub auth_key: Option<AuthenticationKey>,
+    /// STC amount in nanoSTC.
+    pub amount: u128,
+    /// Package published under the account.
+    pub package: Option<Package>,
+    /// Private key to sign the package publishing, kept in the key file of the genesis config.
+    #[serde(skip)]
+    pub private_key: Option<Arc<Ed25519PrivateKey>>,
+}
+
+impl_genesis_allocation!(true);
+
 static G_UNCLE_RATE_TARGET: u64 = 1;
 static G_DEFAULT_BASE_BLOCK_TIME_TARGET: u64 = 1000;
 static G_DEFAULT_BASE_BLOCK_DIFF_WINDOW: u64 = 24;
@@ -244,6 +284,7 @@ pub static G_TEST_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 1000, // 1h
         },
         transaction_timeout: ONE_DAY,
+        allocations: vec![],
     }
 });
 
@@ -301,6 +342,7 @@ pub static G_DEV_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 1000, // 1h
         },
         transaction_timeout: ONE_DAY,
+        allocations: vec![],
     }
 });
 
@@ -363,6 +405,7 @@ pub static G_HALLEY_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 1000, // 1h
         },
         transaction_timeout: ONE_DAY,
+        allocations: vec![],
     }
 });
 
@@ -424,6 +467,7 @@ pub static G_PROXIMA_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 1000, // 1 minute
         },
         transaction_timeout: ONE_DAY,
+        allocations: vec![],
     }
 });
 
@@ -484,6 +528,7 @@ pub static G_BARNARD_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 24 * 1000, // 1d
         },
         transaction_timeout: ONE_DAY,
+        allocations: vec![],
     }
 });
 
@@ -558,6 +603,7 @@ pub static G_MAIN_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 24 * 1000, // 1d
         },
         transaction_timeout: ONE_DAY,
+        allocations: vec![],
     }
 });
 
@@ -611,6 +657,7 @@ pub static G_VEGA_CONFIG: Lazy<GenesisConfig> = Lazy::new(|| {
             min_action_delay: 60 * 60 * 24 * 1000, // 1d
         },
         transaction_timeout: ONE_DAY,
+        allocations: vec![],
     }
 });
 
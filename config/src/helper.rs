@@ -70,7 +70,7 @@ pub(crate) fn gen_keypair() -> (Ed25519PrivateKey, Ed25519PublicKey) {
 
 /// Opens a file containing a secret key in write mode.
 #[cfg(unix)]
-fn open_key_file<P>(path: P) -> io::Result<fs::File>
+pub(crate) fn open_key_file<P>(path: P) -> io::Result<fs::File>
 where
     P: AsRef<Path>,
 {
@@ -84,7 +84,7 @@ where
 
 /// Opens a file containing a secret key in write mode.
 #[cfg(not(unix))]
-fn open_key_file<P>(path: P) -> Result<fs::File, io::Error>
+pub(crate) fn open_key_file<P>(path: P) -> Result<fs::File, io::Error>
 where
     P: AsRef<Path>,
 {
@@ -29,6 +29,7 @@ mod account_vault_config;
 mod api_config;
 mod api_quota;
 mod available_port;
+mod fork_config;
 pub mod genesis_config;
 mod helper;
 mod logger_config;
@@ -54,6 +55,7 @@ pub use api_quota::{ApiQuotaConfig, QuotaDuration};
 pub use available_port::{
     get_available_port_from, get_random_available_port, get_random_available_ports,
 };
+pub use fork_config::{ForkConfig, ForkFrom};
 pub use genesis_config::{
     BuiltinNetworkID, ChainNetwork, ChainNetworkID, FutureBlockParameter,
     FutureBlockParameterResolver, GenesisBlockParameter, GenesisBlockParameterConfig,
@@ -229,6 +231,9 @@ pub struct StarcoinOpt {
     pub stratum: StratumConfig,
     #[clap(flatten)]
     pub account_provider: AccountProviderConfig,
+    #[serde(default)]
+    #[clap(flatten)]
+    pub fork: ForkConfig,
 }
 
 impl std::fmt::Display for StarcoinOpt {
@@ -340,9 +345,16 @@ impl BaseConfig {
         let genesis_config = match (configs_in_file, id) {
             (Some((config_in_file, config2_in_file)), ChainNetworkID::Builtin(net)) => {
                 // only check the genesis config is resolved.
+                // the dev network can start with the allocations of a file.
+                let ignore_allocations = net.is_dev();
                 if config_in_file.is_ready() && net.genesis_config().is_ready() {
                     ensure!(
-                        &config_in_file == net.genesis_config(),
+                        (ignore_allocations
+                            && GenesisConfig {
+                                allocations: vec![],
+                                ..config_in_file.clone()
+                            } == *net.genesis_config())
+                            || &config_in_file == net.genesis_config(),
                         "GenesisConfig in file:{:?} is not same with builtin config: {:?}\n{:?}\n*****\n{:?}",
                         config_path.as_path(),
                         net,
@@ -352,7 +364,12 @@ impl BaseConfig {
                 }
                 if config2_in_file.is_ready() && net.genesis_config2().is_ready() {
                     ensure!(
-                        &config2_in_file == net.genesis_config2(),
+                        (ignore_allocations
+                            && GenesisConfig2 {
+                                allocations: vec![],
+                                ..config2_in_file.clone()
+                            } == *net.genesis_config2())
+                            || &config2_in_file == net.genesis_config2(),
                         "GenesisConfig in file:{:?} is not same with builtin config: {:?}\n{:?}\n*****\n{:?}",
                         config_path.as_path(),
                         net,
@@ -485,6 +502,8 @@ pub struct NodeConfig {
     pub stratum: StratumConfig,
     #[serde(default)]
     pub account_provider: AccountProviderConfig,
+    #[serde(default)]
+    pub fork: ForkConfig,
 }
 
 impl std::fmt::Display for NodeConfig {
@@ -577,7 +596,8 @@ impl NodeConfig {
         self.metrics.merge_with_opt(opt, base.clone())?;
         self.logger.merge_with_opt(opt, base.clone())?;
         self.stratum.merge_with_opt(opt, base.clone())?;
-        self.account_provider.merge_with_opt(opt, base)?;
+        self.account_provider.merge_with_opt(opt, base.clone())?;
+        self.fork.merge_with_opt(opt, base)?;
         Ok(())
     }
 }
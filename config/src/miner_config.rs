@@ -11,6 +11,7 @@ use std::sync::Arc;
 pub static G_MAX_PARENTS_COUNT: usize = 10;
 pub static G_DAG_BLOCK_RECEIVE_TIME_WINDOW: u64 = 2; // in second, 2s for default
 pub static G_MERGE_DEPTH: u64 = 3600; // the merge depth should be smaller than the pruning finality
+pub static G_SPECULATIVE_EXECUTION_INTERVAL: u64 = 0; // in millisecond, 0 disables it
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize, Parser)]
 #[serde(deny_unknown_fields)]
@@ -51,6 +52,18 @@ pub struct MinerConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[clap(long = "dag-merge-depth")]
     pub dag_merge_depth: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "miner-speculative-execution-interval")]
+    /// Interval in milliseconds the block builder pre-executes the pending vm2 txns on the head
+    /// state while mining, so templates reuse the outputs. 0 disables it, default is 0.
+    pub speculative_execution_interval: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long = "miner-access-list-scheduling")]
+    /// Predict the access lists of the pending vm2 txns by dry runs, and order them into batches
+    /// of non-conflicting txns for parallel execution. Default is false.
+    pub access_list_scheduling: Option<bool>,
 }
 
 impl MinerConfig {
@@ -90,6 +103,15 @@ impl MinerConfig {
     pub fn dag_merge_depth(&self) -> u64 {
         self.dag_merge_depth.unwrap_or(G_MERGE_DEPTH)
     }
+
+    pub fn speculative_execution_interval(&self) -> u64 {
+        self.speculative_execution_interval
+            .unwrap_or(G_SPECULATIVE_EXECUTION_INTERVAL)
+    }
+
+    pub fn access_list_scheduling(&self) -> bool {
+        self.access_list_scheduling.unwrap_or(false)
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
@@ -145,6 +167,14 @@ impl ConfigModule for MinerConfig {
             self.dag_merge_depth = opt.miner.dag_merge_depth;
         }
 
+        if opt.miner.speculative_execution_interval.is_some() {
+            self.speculative_execution_interval = opt.miner.speculative_execution_interval;
+        }
+
+        if opt.miner.access_list_scheduling.is_some() {
+            self.access_list_scheduling = opt.miner.access_list_scheduling;
+        }
+
         // Validate DAG parameters: K must be >= max_parents_count
         let max_parents = self.maximum_parents_count();
         let k = base
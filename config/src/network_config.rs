@@ -118,6 +118,86 @@ impl NetworkRpcQuotaConfiguration {
         Ok(())
     }
 }
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize, Parser)]
+pub struct NetworkBandwidthConfiguration {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "p2p-max-upload-rate",
+        long,
+        help = "max bytes per second uploaded to all peers, unlimited by default"
+    )]
+    pub max_upload_rate: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "p2p-protocol-upload-rate",
+        long,
+        help = "max bytes per second uploaded by a protocol, eg: /starcoin/rpc/get_blocks=1048576",
+        value_parser = parse_key_val::<String, u64>,
+        number_of_values = 1
+    )]
+    pub protocol_upload_rate: Option<Vec<(String, u64)>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "p2p-protocol-download-rate",
+        long,
+        help = "max bytes per second downloaded by a protocol, eg: /starcoin/txn/1=1048576",
+        value_parser = parse_key_val::<String, u64>,
+        number_of_values = 1
+    )]
+    pub protocol_download_rate: Option<Vec<(String, u64)>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "p2p-peer-upload-rate",
+        long,
+        help = "max bytes per second uploaded to a single peer, unlimited by default"
+    )]
+    pub peer_upload_rate: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "p2p-peer-download-rate",
+        long,
+        help = "max bytes per second downloaded from a single peer, unlimited by default"
+    )]
+    pub peer_download_rate: Option<u64>,
+}
+
+impl NetworkBandwidthConfiguration {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    pub fn protocol_upload_rate(&self) -> Vec<(String, u64)> {
+        self.protocol_upload_rate.clone().unwrap_or_default()
+    }
+
+    pub fn protocol_download_rate(&self) -> Vec<(String, u64)> {
+        self.protocol_download_rate.clone().unwrap_or_default()
+    }
+
+    pub fn merge(&mut self, o: &Self) -> Result<()> {
+        if o.max_upload_rate.is_some() {
+            self.max_upload_rate = o.max_upload_rate;
+        }
+        if o.protocol_upload_rate.is_some() {
+            self.protocol_upload_rate = o.protocol_upload_rate.clone();
+        }
+        if o.protocol_download_rate.is_some() {
+            self.protocol_download_rate = o.protocol_download_rate.clone();
+        }
+        if o.peer_upload_rate.is_some() {
+            self.peer_upload_rate = o.peer_upload_rate;
+        }
+        if o.peer_download_rate.is_some() {
+            self.peer_download_rate = o.peer_download_rate;
+        }
+        Ok(())
+    }
+}
+
 //for avoid conflict between seed vec and subcommand, so define a custom type to parse seeds.
 //https://github.com/TeXitoi/clap/issues/367
 #[derive(Default, Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
@@ -202,6 +282,13 @@ pub struct NetworkConfig {
     #[clap(flatten)]
     pub network_rpc_quotas: NetworkRpcQuotaConfiguration,
 
+    #[serde(
+        default,
+        skip_serializing_if = "NetworkBandwidthConfiguration::is_default"
+    )]
+    #[clap(flatten)]
+    pub network_bandwidth: NetworkBandwidthConfiguration,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     #[clap(long)]
     /// min peers to propagate new block and new transactions. Default 8.
@@ -212,6 +299,23 @@ pub struct NetworkConfig {
     ///max peers to propagate new block and new transactions. Default 128.
     max_peers_to_propagate: Option<u32>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long)]
+    /// peers to relay new compact blocks in high-bandwidth mode, before the block is executed.
+    /// They are the peers which most recently delivered a new block first. Default 3, 0 disable it.
+    high_bandwidth_relay_peers: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long)]
+    /// peers to send new transactions in full, the other peers only get the transaction hashes
+    /// announced and pull the unknown ones. Default 0, announce to all peers.
+    txn_full_broadcast_peers: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long)]
+    /// max transaction hashes in one announcement message. Default 256.
+    max_txn_announcement_size: Option<u32>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     #[clap(long)]
     ///max count for incoming peers. Default 25.
@@ -315,6 +419,18 @@ impl NetworkConfig {
         self.min_peers_to_propagate.unwrap_or(8)
     }
 
+    pub fn high_bandwidth_relay_peers(&self) -> u32 {
+        self.high_bandwidth_relay_peers.unwrap_or(3)
+    }
+
+    pub fn txn_full_broadcast_peers(&self) -> u32 {
+        self.txn_full_broadcast_peers.unwrap_or(0)
+    }
+
+    pub fn max_txn_announcement_size(&self) -> u32 {
+        self.max_txn_announcement_size.unwrap_or(256).max(1)
+    }
+
     pub fn max_incoming_peers(&self) -> u32 {
         self.max_incoming_peers.unwrap_or(25)
     }
@@ -410,6 +526,8 @@ impl ConfigModule for NetworkConfig {
 
         self.network_rpc_quotas
             .merge(&opt.network.network_rpc_quotas)?;
+        self.network_bandwidth
+            .merge(&opt.network.network_bandwidth)?;
 
         if opt.network.node_name.is_some() {
             self.node_name = opt.network.node_name.clone();
@@ -432,6 +550,15 @@ impl ConfigModule for NetworkConfig {
         if let Some(m) = opt.network.min_peers_to_propagate {
             self.min_peers_to_propagate = Some(m);
         }
+        if let Some(m) = opt.network.high_bandwidth_relay_peers {
+            self.high_bandwidth_relay_peers = Some(m);
+        }
+        if let Some(m) = opt.network.txn_full_broadcast_peers {
+            self.txn_full_broadcast_peers = Some(m);
+        }
+        if let Some(m) = opt.network.max_txn_announcement_size {
+            self.max_txn_announcement_size = Some(m);
+        }
         if opt.network.discover_local.is_some() {
             self.discover_local = opt.network.discover_local;
         }
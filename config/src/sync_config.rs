@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{BaseConfig, ConfigModule, StarcoinOpt};
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use clap::Parser;
+use network_api::peer_score::DEFAULT_EVICTION_THRESHOLD;
 use network_api::PeerStrategy;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -82,6 +83,16 @@ pub struct SyncConfig {
         help = "enable sync profiling info logs, default false."
     )]
     profiling_info: Option<bool>,
+
+    /// peer quality score at which a peer is evicted for bad rpc behaviour
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "sync-peer-eviction-threshold",
+        long,
+        allow_hyphen_values = true,
+        help = "peer quality score at which a peer is evicted, default -1000."
+    )]
+    peer_eviction_threshold: Option<i64>,
 }
 
 impl SyncConfig {
@@ -125,6 +136,11 @@ impl SyncConfig {
     pub fn profiling_info(&self) -> bool {
         self.profiling_info.unwrap_or(false)
     }
+
+    pub fn peer_eviction_threshold(&self) -> i64 {
+        self.peer_eviction_threshold
+            .unwrap_or(DEFAULT_EVICTION_THRESHOLD)
+    }
 }
 
 impl ConfigModule for SyncConfig {
@@ -161,6 +177,17 @@ impl ConfigModule for SyncConfig {
             self.profiling_info = opt.sync.profiling_info;
         }
 
+        if opt.sync.peer_eviction_threshold.is_some() {
+            self.peer_eviction_threshold = opt.sync.peer_eviction_threshold;
+        }
+        if let Some(threshold) = self.peer_eviction_threshold {
+            ensure!(
+                threshold < 0,
+                "peer-eviction-threshold must be negative, got {}",
+                threshold
+            );
+        }
+
         Ok(())
     }
 }
@@ -58,6 +58,37 @@ fn test_genesis_config_save_and_load() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_genesis_allocation_key_not_in_config() -> Result<()> {
+    use starcoin_crypto::ValidCryptoMaterialStringExt;
+    use starcoin_vm_types::transaction::authenticator::AuthenticationKey;
+
+    let (private_key, public_key) = crate::helper::gen_keypair();
+    let encoded_key = private_key.to_encoded_string()?;
+    let mut genesis_config = BuiltinNetworkID::Dev.genesis_config().clone();
+    genesis_config.allocations = vec![crate::genesis_config::GenesisAllocation {
+        address: AuthenticationKey::ed25519(&public_key).derived_address(),
+        auth_key: None,
+        amount: 1,
+        package: None,
+        private_key: Some(Arc::new(private_key)),
+    }];
+    let temp_path = temp_dir();
+    let file_path = temp_path.path().join(G_GENESIS_CONFIG_FILE_NAME);
+    genesis_config.save(file_path.as_path())?;
+    assert!(!fs::read_to_string(file_path.as_path())?.contains(encoded_key.as_str()));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let key_file = temp_path
+            .path()
+            .join(format!("{}.keys", G_GENESIS_CONFIG_FILE_NAME));
+        assert_eq!(fs::metadata(key_file)?.permissions().mode() & 0o777, 0o600);
+    }
+    assert_eq!(genesis_config, GenesisConfig::load(file_path.as_path())?);
+    Ok(())
+}
+
 #[test]
 fn test_api_quota_config() {
     let config = "1000/s".parse::<ApiQuotaConfig>().unwrap();
@@ -215,3 +246,24 @@ fn test_default_toml_omits_empty_tcp_table() -> Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn test_peer_eviction_threshold_must_be_negative() -> Result<()> {
+    let temp_path = temp_dir();
+    let base_data_dir = temp_path.path().to_str().unwrap();
+    let load = |threshold: &str| {
+        NodeConfig::load_with_opt(&StarcoinOpt::try_parse_from([
+            "starcoin",
+            "--net",
+            "test",
+            "--data-dir",
+            base_data_dir,
+            "--peer-eviction-threshold",
+            threshold,
+        ])?)
+    };
+    assert_eq!(load("-10")?.sync.peer_eviction_threshold(), -10);
+    assert!(load("0").is_err());
+    assert!(load("100").is_err());
+    Ok(())
+}
@@ -6,11 +6,13 @@ use anyhow::Result;
 use clap::Parser;
 use serde::{Deserialize, Serialize};
 use starcoin_system::get_free_mem_size;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 pub const DEFAULT_MEM_SIZE: u64 = 128 * 1024 * 1024; // 128M
+pub const LOCAL_TXN_JOURNAL_FILE: &str = "txpool/local_txns.journal";
 
-#[derive(Default, Clone, Debug, Eq, PartialEq, Deserialize, Serialize, Parser)]
+#[derive(Default, Clone, Debug, PartialEq, Deserialize, Serialize, Parser)]
 #[serde(deny_unknown_fields)]
 pub struct TxPoolConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -62,6 +64,20 @@ pub struct TxPoolConfig {
     #[clap(name = "txpool-verifier-pool-enabled", long)]
     /// Enable pooled VM validators for txpool verification. default to true.
     verifier_pool_enabled: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "txpool-local-txn-journal", long)]
+    /// Persist locally submitted transactions to a journal in the data dir, and re-import them after restart. default to true.
+    local_txn_journal: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(name = "txpool-local-txn-journal-interval", long)]
+    /// interval(s) of compacting the local txn journal, drop the txns no longer in the pool. default to 600.
+    local_txn_journal_interval: Option<u64>,
+
+    #[serde(skip)]
+    #[clap(skip)]
+    base: Option<Arc<BaseConfig>>,
 }
 
 impl TxPoolConfig {
@@ -111,10 +127,27 @@ impl TxPoolConfig {
     pub fn verifier_pool_enabled(&self) -> bool {
         self.verifier_pool_enabled.unwrap_or(true)
     }
+    pub fn local_txn_journal(&self) -> bool {
+        self.local_txn_journal.unwrap_or(true)
+    }
+    pub fn local_txn_journal_interval(&self) -> u64 {
+        self.local_txn_journal_interval.unwrap_or(600).max(1)
+    }
+    /// Path of the local txn journal, `None` if the journal is disabled or the config is not
+    /// bound to a data dir.
+    pub fn local_txn_journal_path(&self) -> Option<PathBuf> {
+        if !self.local_txn_journal() {
+            return None;
+        }
+        self.base
+            .as_ref()
+            .map(|base| base.data_dir().join(LOCAL_TXN_JOURNAL_FILE))
+    }
 }
 
 impl ConfigModule for TxPoolConfig {
-    fn merge_with_opt(&mut self, opt: &StarcoinOpt, _base: Arc<BaseConfig>) -> Result<()> {
+    fn merge_with_opt(&mut self, opt: &StarcoinOpt, base: Arc<BaseConfig>) -> Result<()> {
+        self.base = Some(base);
         let txpool_opt = &opt.txpool;
         if let Some(m) = txpool_opt.max_mem_usage.as_ref() {
             self.max_mem_usage = Some(*m);
@@ -146,6 +179,12 @@ impl ConfigModule for TxPoolConfig {
         if let Some(m) = txpool_opt.verifier_pool_enabled.as_ref() {
             self.verifier_pool_enabled = Some(*m);
         }
+        if let Some(m) = txpool_opt.local_txn_journal.as_ref() {
+            self.local_txn_journal = Some(*m);
+        }
+        if let Some(m) = txpool_opt.local_txn_journal_interval.as_ref() {
+            self.local_txn_journal_interval = Some(*m);
+        }
         Ok(())
     }
 }
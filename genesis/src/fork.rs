@@ -0,0 +1,290 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Start a dev chain from a block of another chain. Only the block and its accumulator infos are
+//! copied, the state trees are fetched lazily from the source by the storage.
+
+use crate::{Genesis, GenesisError};
+use anyhow::{format_err, Result};
+use starcoin_accumulator::node::AccumulatorStoreType;
+use starcoin_accumulator::{Accumulator, MerkleAccumulator};
+use starcoin_config::ChainNetwork;
+use starcoin_crypto::HashValue;
+use starcoin_dag::blockdag::BlockDAG;
+use starcoin_dag::consensusdb::consensus_pruning_info::{PruningPointInfo, PruningPointInfoWriter};
+use starcoin_dag::consensusdb::consensus_state::{DagState, DagStateStore};
+use starcoin_logger::prelude::*;
+use starcoin_state_api::{AccountStateReader, ChainStateWriter as _};
+use starcoin_state_store_api::{StateNode, StateNodeStore};
+use starcoin_statedb::ChainStateDB as ChainStateDB1;
+use starcoin_storage::block_info::BlockInfoStore;
+use starcoin_storage::state_node::StateNodeSource;
+use starcoin_storage::{BlockStore, IntoSuper, Storage, Storage2, Store};
+use starcoin_types::block::{Block, BlockInfo, BlockNumber};
+use starcoin_types::multi_state::MultiState;
+use starcoin_types::startup_info::{ChainInfo, StartupInfo};
+use starcoin_vm2_crypto::ed25519::genesis_key_pair;
+use starcoin_vm2_state_api::ChainStateWriter;
+use starcoin_vm2_statedb::ChainStateDB;
+use starcoin_vm2_vm_types::access_path::AccessPath;
+use starcoin_vm2_vm_types::account_address::AccountAddress;
+use starcoin_vm2_vm_types::account_config::{genesis_address, AccountResource};
+use starcoin_vm2_vm_types::move_resource::MoveStructType;
+use starcoin_vm2_vm_types::on_chain_resource::{ChainId, Epoch};
+use starcoin_vm2_vm_types::state_view::StateReaderExt;
+use starcoin_vm2_vm_types::transaction::authenticator::AuthenticationKey;
+use starcoin_vm_types::access_path::AccessPath as AccessPath1;
+use starcoin_vm_types::account_config::genesis_address as genesis_address1;
+use starcoin_vm_types::genesis_config::ChainId as ChainId1;
+use starcoin_vm_types::move_resource::MoveResource as _;
+use std::sync::Arc;
+
+/// The chain a dev chain forks from.
+pub trait ForkSource: StateNodeSource {
+    fn head_number(&self) -> Result<BlockNumber>;
+
+    fn get_block_by_number(&self, number: BlockNumber) -> Result<Option<Block>>;
+
+    fn get_block_info(&self, block_id: HashValue) -> Result<Option<BlockInfo>>;
+
+    fn get_vm_multi_state(&self, block_id: HashValue) -> Result<Option<MultiState>>;
+}
+
+/// Fork from the storage of another node.
+pub struct StorageForkSource {
+    storage: Arc<Storage>,
+}
+
+impl StorageForkSource {
+    pub fn new(storage: Arc<Storage>) -> Self {
+        Self { storage }
+    }
+}
+
+impl StateNodeSource for StorageForkSource {
+    fn get_state_node(&self, hash: &HashValue) -> Result<Option<StateNode>> {
+        StateNodeStore::get(self.storage.as_ref(), hash)
+    }
+
+    fn get_state_node2(&self, hash: &HashValue) -> Result<Option<StateNode>> {
+        Storage2(self.storage.clone()).get(hash)
+    }
+}
+
+impl ForkSource for StorageForkSource {
+    fn head_number(&self) -> Result<BlockNumber> {
+        let chain_info = self
+            .storage
+            .get_chain_info()?
+            .ok_or_else(|| format_err!("Can not find chain info in the fork source"))?;
+        Ok(chain_info.status().head().number())
+    }
+
+    fn get_block_by_number(&self, number: BlockNumber) -> Result<Option<Block>> {
+        let head_id = self
+            .storage
+            .get_startup_info()?
+            .ok_or_else(|| format_err!("Can not find startup info in the fork source"))?
+            .main;
+        let head_info = self
+            .storage
+            .get_block_info(head_id)?
+            .ok_or_else(|| format_err!("Can not find block info {} in the fork source", head_id))?;
+        let block_accumulator = MerkleAccumulator::new_with_info(
+            head_info.block_accumulator_info,
+            self.storage
+                .get_accumulator_store(AccumulatorStoreType::Block),
+        );
+        match block_accumulator.get_leaf(number)? {
+            Some(block_id) => self.storage.get_block_by_hash(block_id),
+            None => Ok(None),
+        }
+    }
+
+    fn get_block_info(&self, block_id: HashValue) -> Result<Option<BlockInfo>> {
+        self.storage.get_block_info(block_id)
+    }
+
+    fn get_vm_multi_state(&self, block_id: HashValue) -> Result<Option<MultiState>> {
+        self.storage.get_vm_multi_state(block_id).map(Some)
+    }
+}
+
+impl Genesis {
+    /// Init `storage` with a block of `source` as the genesis of a dev chain, or load the chain
+    /// forked before. The accounts in `impersonate` get the authentication key of the genesis key
+    /// pair, so transactions can be sent as them.
+    pub fn init_fork_storage(
+        net: &ChainNetwork,
+        storage: Arc<Storage>,
+        mut dag: BlockDAG,
+        source: &dyn ForkSource,
+        fork_block: Option<BlockNumber>,
+        impersonate: &[AccountAddress],
+    ) -> Result<(ChainInfo, Genesis)> {
+        if let Some(chain_info) = storage.get_chain_info()? {
+            let block = storage
+                .get_block_by_hash(chain_info.genesis_hash())?
+                .ok_or_else(|| GenesisError::GenesisNotExist("database".to_owned()))?;
+            if fork_block.is_some_and(|number| number != block.header().number()) {
+                warn!(
+                    "The chain is forked at block {}, ignore fork block {:?}, remove the data dir to fork again.",
+                    block.header().number(),
+                    fork_block
+                );
+            }
+            return Ok((chain_info, Genesis { block }));
+        }
+
+        let number = match fork_block {
+            Some(number) => number,
+            None => source.head_number()?,
+        };
+        let block = source
+            .get_block_by_number(number)?
+            .ok_or_else(|| format_err!("Can not find block {} in the fork source", number))?;
+        let block_id = block.id();
+        let block_info = source.get_block_info(block_id)?.ok_or_else(|| {
+            format_err!("Can not find block info {} in the fork source", block_id)
+        })?;
+        let multi_state = source
+            .get_vm_multi_state(block_id)?
+            .ok_or_else(|| format_err!("Can not find vm state of block {}", block_id))?;
+        info!(
+            "Fork {} from block {}:{}, state roots {:?}",
+            net, number, block_id, multi_state
+        );
+
+        let state_root1 =
+            Self::patch_fork_chain_id(net, storage.clone(), multi_state.state_root1())?;
+        let state_root2 =
+            Self::patch_fork_state(net, storage.clone(), multi_state.state_root2(), impersonate)?;
+        let vm_state_accumulator = MerkleAccumulator::new_empty(
+            storage.get_accumulator_store(AccumulatorStoreType::VMState),
+        );
+        vm_state_accumulator.append(&[state_root1, state_root2])?;
+        vm_state_accumulator.flush()?;
+
+        // The accumulators of the source can not be read locally, appending only needs their
+        // frozen subtree roots.
+        storage.save_block_info(BlockInfo::new(
+            block_id,
+            block_info.total_difficulty,
+            block_info.txn_accumulator_info,
+            block_info.block_accumulator_info,
+            vm_state_accumulator.get_info(),
+        ))?;
+        storage.commit_block(block.clone())?;
+        storage.save_genesis(block_id)?;
+        storage.save_startup_info(StartupInfo::new(block_id))?;
+
+        dag.init_with_genesis(block.header().clone())?;
+        dag.storage
+            .pruning_point_store
+            .write()
+            .insert(PruningPointInfo {
+                pruning_point: block_id,
+            })?;
+        let dag_state = DagState {
+            tips: vec![block_id],
+        };
+        dag.storage
+            .state_store
+            .write()
+            .insert(block_id, dag_state.clone())?;
+        let pruning_point = block.header().pruning_point();
+        if pruning_point != HashValue::zero() {
+            dag.storage
+                .state_store
+                .write()
+                .insert(pruning_point, dag_state)?;
+        }
+        net.time_service().adjust(block.header().timestamp());
+
+        let chain_info = storage
+            .get_chain_info()?
+            .ok_or_else(|| format_err!("ChainInfo should exist after the chain forked."))?;
+        Ok((chain_info, Genesis { block }))
+    }
+
+    /// Give the forked vm1 state the chain id of the dev network, return the new vm1 state root.
+    fn patch_fork_chain_id(
+        net: &ChainNetwork,
+        storage: Arc<Storage>,
+        state_root1: HashValue,
+    ) -> Result<HashValue> {
+        let statedb = ChainStateDB1::new(storage.into_super_arc(), Some(state_root1));
+        let chain_id = net.chain_id();
+        let source_chain_id = AccountStateReader::new(&statedb).get_chain_id()?;
+        if source_chain_id == chain_id {
+            return Ok(state_root1);
+        }
+        info!(
+            "Replace vm1 chain id {} of the forked chain by {}",
+            source_chain_id, chain_id
+        );
+        statedb.set(
+            &AccessPath1::resource_access_path(genesis_address1(), ChainId1::struct_tag()),
+            bcs_ext::to_bytes(&chain_id)?,
+        )?;
+        let state_root = statedb.commit()?;
+        statedb.flush()?;
+        Ok(state_root)
+    }
+
+    /// Let the dev network mine on the forked state with its own chain id, so the transactions
+    /// signed for the fork can not be replayed on the source chain, and impersonate accounts,
+    /// return the new vm2 state root.
+    fn patch_fork_state(
+        net: &ChainNetwork,
+        storage: Arc<Storage>,
+        state_root2: HashValue,
+        impersonate: &[AccountAddress],
+    ) -> Result<HashValue> {
+        let statedb = ChainStateDB::new(Arc::new(Storage2(storage)), Some(state_root2));
+
+        let strategy = net.genesis_config2().consensus_config.strategy;
+        let mut epoch = statedb.get_epoch()?;
+        if epoch.strategy() != strategy {
+            info!(
+                "Replace consensus strategy {} of the forked chain by {}",
+                epoch.strategy(),
+                strategy
+            );
+            epoch.set_strategy(strategy);
+            statedb.set(
+                &AccessPath::resource_access_path(genesis_address(), Epoch::struct_tag()),
+                bcs_ext::to_bytes(&epoch)?,
+            )?;
+        }
+
+        let chain_id = ChainId::new(net.chain_id().id());
+        let source_chain_id = statedb.get_chain_id()?;
+        if source_chain_id != chain_id {
+            info!(
+                "Replace vm2 chain id {} of the forked chain by {}",
+                source_chain_id, chain_id
+            );
+            statedb.set(
+                &AccessPath::resource_access_path(genesis_address(), ChainId::struct_tag()),
+                bcs_ext::to_bytes(&chain_id)?,
+            )?;
+        }
+
+        let (_, public_key) = genesis_key_pair();
+        let auth_key = AuthenticationKey::ed25519(&public_key);
+        for address in impersonate {
+            let mut account = statedb.get_account_resource(*address)?;
+            account.set_authentication_key(auth_key.to_vec());
+            statedb.set(
+                &AccessPath::resource_access_path(*address, AccountResource::struct_tag()),
+                bcs_ext::to_bytes(&account)?,
+            )?;
+            info!("Impersonate account {} by auth key {}", address, auth_key);
+        }
+
+        let state_root = statedb.commit()?;
+        statedb.flush()?;
+        Ok(state_root)
+    }
+}
@@ -13,21 +13,27 @@ use starcoin_config::{
     genesis_key_pair, BuiltinNetworkID, ChainNetwork, ChainNetworkID, GenesisBlockParameter,
     DEFAULT_CACHE_SIZE,
 };
-use starcoin_crypto::HashValue;
+use starcoin_crypto::{HashValue, PrivateKey};
 use starcoin_dag::blockdag::BlockDAG;
+use starcoin_executor::BlockExecutedData;
 use starcoin_logger::prelude::*;
-use starcoin_state_api::ChainStateWriter;
+use starcoin_state_api::{ChainStateReader, ChainStateWriter};
 use starcoin_statedb::ChainStateDB;
 use starcoin_storage::storage::{InnerStore, StorageInstance};
 use starcoin_storage::{BlockStore, Storage, Store};
 use starcoin_transaction_builder::build_stdlib_package_with_modules;
-use starcoin_transaction_builder::{build_stdlib_package, StdLibOptions};
+use starcoin_transaction_builder::{
+    build_stdlib_package, encode_create_account_script_function, encode_transfer_script_function,
+    StdLibOptions, DEFAULT_MAX_GAS_AMOUNT,
+};
+use starcoin_types::account_address::AccountAddress;
 use starcoin_types::blockhash::KType;
 use starcoin_types::startup_info::{ChainInfo, StartupInfo};
 use starcoin_types::transaction::Package;
 use starcoin_types::transaction::TransactionInfo;
 use starcoin_types::{block::Block, transaction::Transaction};
-use starcoin_vm_types::account_config::CORE_CODE_ADDRESS;
+use starcoin_vm_types::account_config::{association_address, CORE_CODE_ADDRESS};
+use starcoin_vm_types::token::stc::stc_type_tag;
 use starcoin_vm_types::transaction::{
     RawUserTransaction, SignedUserTransaction, TransactionPayload,
 };
@@ -40,9 +46,11 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 mod errors;
+mod fork;
 pub mod vm2;
 
 pub use errors::GenesisError;
+pub use fork::{ForkSource, StorageForkSource};
 use starcoin_vm_types::state_store::table::{TableHandle, TableInfo};
 use starcoin_vm_types::state_view::StateView;
 
@@ -132,16 +140,27 @@ impl Genesis {
                     .as_slice(),
             );
 
-            let (txn2, txn2_info) = vm2::build_and_execute_genesis_transaction(net);
-
-            let txn = Self::build_genesis_transaction(net)?;
+            let block_gas_limit = net.genesis_epoch().block_gas_limit();
+            let mut txns = vec![Self::build_genesis_transaction(net)?];
+            txns.extend(Self::build_allocation_transactions(net)?);
 
             let instance =
                 StorageInstance::new_cache_instance_with_capacity(DEFAULT_CACHE_SIZE * 1000);
             let storage = Arc::new(Storage::new(instance.clone())?);
             let chain_state_db = ChainStateDB::new(storage.clone(), None);
 
-            let (_, txn_info) = Self::execute_genesis_txn(&chain_state_db, txn.clone())?;
+            let executed_data =
+                Self::execute_genesis_txns(&chain_state_db, &txns, block_gas_limit)?;
+            let (txns2, executed_data2) = vm2::build_and_execute_genesis_transactions(
+                net,
+                block_gas_limit - executed_data.gas_used(),
+            )?;
+            let gas_used = executed_data.gas_used()
+                + executed_data2
+                    .txn_infos
+                    .iter()
+                    .map(|txn_info| txn_info.gas_used())
+                    .sum::<u64>();
 
             let accumulator = MerkleAccumulator::new_with_info(
                 AccumulatorInfo::default(),
@@ -156,19 +175,21 @@ impl Genesis {
                 let state_root1 = if starcoin_chain::should_do_migration(net.chain_id()) {
                     starcoin_chain::do_migration(&chain_state_db, net.chain_id())?
                 } else {
-                    txn_info
-                        .state_root_hash
-                        .expect("genesis state root hash cannot be none!")
+                    executed_data.state_root
                 };
 
-                let state_root2 = txn2_info
-                    .state_root_hash()
-                    .expect("genesis state root hash cannot be none for vm2!");
+                let state_root2 = executed_data2.state_root;
                 vm_state_accumulator.append(&[state_root1, state_root2])?;
-                (
-                    vm_state_accumulator.root_hash(),
-                    vec![txn2_info.id(), txn_info.id()],
-                )
+                // same order as the block execution: the first vm2 txn, the vm1 txns, then the
+                // rest of the vm2 txns.
+                let mut txn_info_hashes2 = executed_data2.txn_infos.iter().map(|info| info.id());
+                let txn_info_hash_vec = txn_info_hashes2
+                    .next()
+                    .into_iter()
+                    .chain(executed_data.txn_infos.iter().map(|info| info.id()))
+                    .chain(txn_info_hashes2)
+                    .collect::<Vec<_>>();
+                (vm_state_accumulator.root_hash(), txn_info_hash_vec)
             };
 
             let accumulator_root = accumulator.append(txn_info_hash_vec.as_slice())?;
@@ -182,14 +203,15 @@ impl Genesis {
                 }
             }
 
-            Ok(Block::genesis_block(
+            Ok(Block::genesis_block_with_transactions(
                 parent_hash,
                 *timestamp,
                 accumulator_root,
                 state_root,
                 *difficulty,
-                txn,
-                txn2,
+                gas_used,
+                txns,
+                txns2,
             ))
         } else {
             bail!("{}'s genesis config not ready to build genesis block", net);
@@ -208,6 +230,68 @@ impl Genesis {
         Self::build_genesis_transaction_with_package(net, package)
     }
 
+    /// Build the txns allocating the genesis accounts: the association funds, or creates with
+    /// the auth key, the accounts first, then the accounts publish their packages.
+    pub fn build_allocation_transactions(net: &ChainNetwork) -> Result<Vec<SignedUserTransaction>> {
+        let genesis_config = net.genesis_config();
+        let expiration_timestamp_secs =
+            net.genesis_block_parameter().timestamp / 1000 + genesis_config.transaction_timeout;
+        let mut sequence_numbers = BTreeMap::<AccountAddress, u64>::new();
+        let mut next_sequence_number = |address: AccountAddress| {
+            let sequence_number = sequence_numbers.entry(address).or_default();
+            *sequence_number += 1;
+            *sequence_number - 1
+        };
+
+        let mut txns = vec![];
+        for allocation in &genesis_config.allocations {
+            let script_function = match allocation.auth_key.as_ref() {
+                Some(auth_key) => encode_create_account_script_function(
+                    net.stdlib_version(),
+                    stc_type_tag(),
+                    &allocation.address,
+                    auth_key.clone(),
+                    allocation.amount,
+                ),
+                None => encode_transfer_script_function(allocation.address, allocation.amount),
+            };
+            let txn = RawUserTransaction::new_with_default_gas_token(
+                association_address(),
+                next_sequence_number(association_address()),
+                TransactionPayload::ScriptFunction(script_function),
+                DEFAULT_MAX_GAS_AMOUNT,
+                1,
+                expiration_timestamp_secs,
+                net.chain_id(),
+            );
+            txns.push(genesis_config.sign_with_association(txn)?);
+        }
+        for allocation in &genesis_config.allocations {
+            if let Some(package) = allocation.package.as_ref() {
+                let private_key = allocation.private_key.as_ref().ok_or_else(|| {
+                    format_err!(
+                        "genesis allocation {} has no private key",
+                        allocation.address
+                    )
+                })?;
+                let txn = RawUserTransaction::new_with_default_gas_token(
+                    allocation.address,
+                    next_sequence_number(allocation.address),
+                    TransactionPayload::Package(package.clone()),
+                    DEFAULT_MAX_GAS_AMOUNT,
+                    1,
+                    expiration_timestamp_secs,
+                    net.chain_id(),
+                );
+                txns.push(
+                    txn.sign(private_key, private_key.public_key())?
+                        .into_inner(),
+                );
+            }
+        }
+        Ok(txns)
+    }
+
     pub fn build_genesis_transaction_with_stdlib(
         net: &ChainNetwork,
         stdlib: Vec<Vec<u8>>,
@@ -269,6 +353,40 @@ impl Genesis {
         ))
     }
 
+    /// Execute the genesis txn and the allocation txns as the genesis block does, every txn must
+    /// be executed successfully.
+    fn execute_genesis_txns<S: ChainStateReader + ChainStateWriter>(
+        chain_state: &S,
+        txns: &[SignedUserTransaction],
+        block_gas_limit: u64,
+    ) -> Result<BlockExecutedData> {
+        let executed_data = starcoin_executor::block_execute(
+            chain_state,
+            txns.iter()
+                .cloned()
+                .map(Transaction::UserTransaction)
+                .collect(),
+            block_gas_limit,
+            None,
+        )?;
+        ensure!(
+            executed_data.txn_infos.len() == txns.len(),
+            "only {} of {} genesis txns are executed",
+            executed_data.txn_infos.len(),
+            txns.len()
+        );
+        for (txn, txn_info) in txns.iter().zip(executed_data.txn_infos.iter()) {
+            ensure!(
+                txn_info.status() == &KeptVMStatus::Executed,
+                "Genesis txn {} execute fail for: {:?}",
+                txn.id(),
+                txn_info.status()
+            );
+        }
+        chain_state.flush()?;
+        Ok(executed_data)
+    }
+
     pub fn block(&self) -> &Block {
         &self.block
     }
@@ -494,6 +612,8 @@ impl Genesis {
 mod tests {
     use super::*;
     use anyhow::Ok;
+    use starcoin_config::genesis_config::vm2::GenesisAllocation as GenesisAllocation2;
+    use starcoin_config::genesis_config::GenesisAllocation;
     use starcoin_crypto::HashValue;
     use starcoin_state_api::AccountStateReader;
     use starcoin_storage::block_info::BlockInfoStore;
@@ -556,6 +676,123 @@ mod tests {
         do_test_genesis(&net, temp_dir.path())
     }
 
+    #[stest::test]
+    pub fn test_custom_genesis_with_allocations() -> Result<()> {
+        let alice = starcoin_types::account::Account::new();
+        let bob = starcoin_vm2_types::account::Account::new();
+        let mut genesis_config = BuiltinNetworkID::Test.genesis_config().clone();
+        genesis_config.allocations = vec![GenesisAllocation {
+            address: *alice.address(),
+            auth_key: Some(alice.auth_key()),
+            amount: 1_000_000_000,
+            package: None,
+            private_key: None,
+        }];
+        let mut genesis_config2 = BuiltinNetworkID::Test.genesis_config2().clone();
+        genesis_config2.allocations = vec![GenesisAllocation2 {
+            address: *bob.address(),
+            auth_key: None,
+            amount: 2_000_000_000,
+            package: None,
+            private_key: None,
+        }];
+        let net = ChainNetwork::new_custom(
+            "testx".to_string(),
+            ChainId::new(123),
+            genesis_config,
+            genesis_config2,
+        )?;
+        let genesis = Genesis::build(&net)?;
+        assert_eq!(genesis.block().transactions().len(), 2);
+        assert_eq!(genesis.block().transactions2().len(), 2);
+        assert!(genesis.block().header().gas_used() > 0);
+
+        let temp_dir = starcoin_config::temp_dir();
+        do_test_genesis(&net, temp_dir.path())?;
+
+        let storage = Arc::new(Storage::new(StorageInstance::new_cache_instance())?);
+        let (chain_info, _) = Genesis::init_and_check_storage(
+            &net,
+            storage.clone(),
+            BlockDAG::create_for_testing()?,
+            temp_dir.path(),
+        )?;
+        let multi_state = storage.get_vm_multi_state(chain_info.status().head().id())?;
+        let state_db = ChainStateDB::new(
+            storage.clone().into_super_arc(),
+            Some(multi_state.state_root1()),
+        );
+        let balance = AccountStateReader::new(&state_db).get_balance(alice.address())?;
+        assert_eq!(balance, Some(1_000_000_000));
+        Ok(())
+    }
+
+    #[stest::test]
+    pub fn test_fork_storage() -> Result<()> {
+        use starcoin_vm2_vm_types::account_config::association_address as association_address2;
+        use starcoin_vm2_vm_types::state_view::StateReaderExt;
+        use starcoin_vm2_vm_types::transaction::authenticator::AuthenticationKey;
+
+        let net = ChainNetwork::new_builtin(BuiltinNetworkID::Dev);
+        // fork from another network, the forked chain gets the chain id of the dev network.
+        let source_net = ChainNetwork::new_builtin(BuiltinNetworkID::Test);
+        let (source_storage, _, source_chain_info, _, _) =
+            Genesis::init_storage_for_test(&source_net)?;
+        let source = Arc::new(StorageForkSource::new(source_storage));
+        let fork_block_id = source_chain_info.status().head().id();
+
+        let storage = Arc::new(
+            Storage::new(StorageInstance::new_cache_instance())?
+                .with_state_node_source(source.clone()),
+        );
+        let (chain_info, genesis) = Genesis::init_fork_storage(
+            &net,
+            storage.clone(),
+            BlockDAG::create_for_testing()?,
+            source.as_ref(),
+            None,
+            &[association_address2()],
+        )?;
+        assert_eq!(chain_info.genesis_hash(), fork_block_id);
+        assert_eq!(chain_info.status().head().id(), fork_block_id);
+        assert_eq!(genesis.block().id(), fork_block_id);
+
+        // the state nodes are fetched from the source.
+        let multi_state = storage.get_vm_multi_state(fork_block_id)?;
+        let state_db = ChainStateDB::new(
+            storage.clone().into_super_arc(),
+            Some(multi_state.state_root1()),
+        );
+        assert_eq!(
+            AccountStateReader::new(&state_db).get_chain_id()?,
+            net.chain_id()
+        );
+        let state_db2 = starcoin_vm2_statedb::ChainStateDB::new(
+            Arc::new(Storage2(storage.clone())),
+            Some(multi_state.state_root2()),
+        );
+        assert_eq!(state_db2.get_chain_id()?.id(), net.chain_id().id());
+        let (_, public_key) = starcoin_vm2_crypto::ed25519::genesis_key_pair();
+        assert_eq!(
+            state_db2
+                .get_account_resource(association_address2())?
+                .authentication_key(),
+            AuthenticationKey::ed25519(&public_key).to_vec().as_slice()
+        );
+
+        // the forked chain is reused.
+        let (chain_info2, _) = Genesis::init_fork_storage(
+            &net,
+            storage,
+            BlockDAG::create_for_testing()?,
+            source.as_ref(),
+            Some(1),
+            &[],
+        )?;
+        assert_eq!(chain_info, chain_info2);
+        Ok(())
+    }
+
     pub fn do_test_genesis(net: &ChainNetwork, data_dir: &Path) -> Result<()> {
         let storage1 = Arc::new(Storage::new(StorageInstance::new_cache_instance())?);
         let dag1 = BlockDAG::create_for_testing()?;
@@ -1,27 +1,33 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::{ensure, format_err};
 use starcoin_storage::{storage::StorageInstance, Storage};
-use starcoin_transaction_builder::vm2::build_stdlib_package;
-use starcoin_vm2_crypto::ed25519::genesis_key_pair;
+use starcoin_transaction_builder::vm2::{
+    build_stdlib_package, encode_transfer_script_function, DEFAULT_MAX_GAS_AMOUNT,
+};
+use starcoin_vm2_crypto::{ed25519::genesis_key_pair, PrivateKey};
 use starcoin_vm2_executor::{
-    block_executor::BlockExecutedData, executor::do_execute_block_transactions,
+    block_executor::{block_execute, BlockExecutedData},
+    executor::do_execute_block_transactions,
 };
 use starcoin_vm2_state_api::ChainStateWriter;
 use starcoin_vm2_statedb::ChainStateDB;
 use starcoin_vm2_types::{
-    account_config::CORE_CODE_ADDRESS,
+    account_config::{association_address, CORE_CODE_ADDRESS},
     error::{BlockExecutorError, ExecutorResult},
     vm_error::KeptVMStatus,
 };
 
 use starcoin_config::ChainNetwork;
+use starcoin_vm2_vm_types::account_address::AccountAddress;
 use starcoin_vm2_vm_types::{
     transaction::TransactionInfo,
     transaction::{Package, RawUserTransaction, SignedUserTransaction, TransactionPayload},
     transaction::{Transaction, TransactionStatus},
     StateView,
 };
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use stdlib::StdLibOptions;
 
@@ -53,6 +59,98 @@ pub fn build_genesis_transaction(net: &ChainNetwork) -> anyhow::Result<SignedUse
     build_genesis_transaction_with_package(net.chain_id().id(), package)
 }
 
+/// Build the txns allocating the vm2 genesis accounts: the association funds the accounts first,
+/// then the accounts publish their packages.
+pub fn build_allocation_transactions(
+    net: &ChainNetwork,
+) -> anyhow::Result<Vec<SignedUserTransaction>> {
+    let genesis_config = net.genesis_config2();
+    let expiration_timestamp_secs = genesis_config
+        .genesis_block_parameter()
+        .ok_or_else(|| format_err!("{}'s vm2 genesis config is not ready", net))?
+        .timestamp
+        / 1000
+        + genesis_config.transaction_timeout;
+    let mut sequence_numbers = BTreeMap::<AccountAddress, u64>::new();
+    let mut next_sequence_number = |address: AccountAddress| {
+        let sequence_number = sequence_numbers.entry(address).or_default();
+        *sequence_number += 1;
+        *sequence_number - 1
+    };
+
+    let mut txns = vec![];
+    for allocation in &genesis_config.allocations {
+        let txn = RawUserTransaction::new_with_default_gas_token(
+            association_address(),
+            next_sequence_number(association_address()),
+            encode_transfer_script_function(allocation.address, allocation.amount),
+            DEFAULT_MAX_GAS_AMOUNT,
+            1,
+            expiration_timestamp_secs,
+            net.chain_id().id().into(),
+        );
+        txns.push(genesis_config.sign_with_association(txn)?);
+    }
+    for allocation in &genesis_config.allocations {
+        if let Some(package) = allocation.package.as_ref() {
+            let private_key = allocation.private_key.as_ref().ok_or_else(|| {
+                format_err!(
+                    "genesis allocation {} has no private key",
+                    allocation.address
+                )
+            })?;
+            let txn = RawUserTransaction::new_with_default_gas_token(
+                allocation.address,
+                next_sequence_number(allocation.address),
+                TransactionPayload::Package(package.clone()),
+                DEFAULT_MAX_GAS_AMOUNT,
+                1,
+                expiration_timestamp_secs,
+                net.chain_id().id().into(),
+            );
+            txns.push(
+                txn.sign(private_key, private_key.public_key())?
+                    .into_inner(),
+            );
+        }
+    }
+    Ok(txns)
+}
+
+/// Build the genesis txn and the allocation txns, and execute them in the order of the genesis
+/// block. Every txn must be executed successfully.
+pub fn build_and_execute_genesis_transactions(
+    net: &ChainNetwork,
+    block_gas_limit: u64,
+) -> anyhow::Result<(Vec<SignedUserTransaction>, BlockExecutedData)> {
+    let mut user_txns = vec![build_genesis_transaction(net)?];
+    user_txns.extend(build_allocation_transactions(net)?);
+
+    let storage = Arc::new(Storage::new(StorageInstance::new_cache_instance())?);
+    let chain_state = ChainStateDB::new(storage, None);
+    let txns = user_txns
+        .iter()
+        .cloned()
+        .map(Transaction::UserTransaction)
+        .collect::<Vec<_>>();
+    let executed_data = block_execute(&chain_state, txns, block_gas_limit, None)?;
+    ensure!(
+        executed_data.txn_infos.len() == user_txns.len(),
+        "only {} of {} vm2 genesis txns are executed",
+        executed_data.txn_infos.len(),
+        user_txns.len()
+    );
+    for (txn, txn_info) in user_txns.iter().zip(executed_data.txn_infos.iter()) {
+        ensure!(
+            txn_info.status() == &KeptVMStatus::Executed,
+            "vm2 genesis txn {} execute fail for: {:?}",
+            txn.id(),
+            txn_info.status()
+        );
+    }
+    Ok((user_txns, executed_data))
+}
+
 pub fn build_and_execute_genesis_transaction(
     net: &ChainNetwork,
 ) -> (SignedUserTransaction, TransactionInfo) {
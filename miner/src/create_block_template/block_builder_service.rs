@@ -1,14 +1,16 @@
-use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::{cmp::min, sync::Arc};
 
 use anyhow::{format_err, Result};
 use futures::executor::block_on;
 use rand::seq::SliceRandom;
 use rand::RngExt;
+use rayon::prelude::*;
 use starcoin_chain::{
     get_merge_bound_hash, global_block_state_cache, is_node_shutting_down, BlockChain,
     CachedBlockState, ChainReader,
 };
+use starcoin_chain_api::ExcludedTxns;
 use starcoin_config::upgrade_config::vm1_offline_height;
 use starcoin_config::NodeConfig;
 use starcoin_consensus::Consensus;
@@ -17,7 +19,7 @@ use starcoin_dag::blockdag::{BlockDAG, MineNewDagBlockInfo};
 use starcoin_dag::consensusdb::schemadb::RelationsStoreReader;
 use starcoin_dag::reachability::reachability_service::ReachabilityService;
 use starcoin_executor::VMMetrics;
-use starcoin_logger::prelude::{error, info};
+use starcoin_logger::prelude::{debug, error, info, log, Level};
 use starcoin_open_block::OpenedBlock;
 use starcoin_pipeline_timing::global_collector;
 use starcoin_service_registry::{
@@ -39,14 +41,21 @@ use starcoin_types::{
 };
 use starcoin_vm2_account_api::{AccountAsyncService, AccountInfo, DefaultAccountChangeEvent};
 use starcoin_vm2_account_service::AccountService;
+use starcoin_vm2_executor::predict_access_list;
 use starcoin_vm2_state_api::ChainStateReader as ChainStateReader2;
 use starcoin_vm2_statedb::ChainStateDB as ChainStateDB2;
-use starcoin_vm2_vm_types::transaction::SignedUserTransaction as SignedUserTransaction2;
+use starcoin_vm2_types::account_address::AccountAddress;
+use starcoin_vm2_vm_types::state_store::state_key::StateKey;
+use starcoin_vm2_vm_types::transaction::{
+    AccessList, SignedUserTransaction as SignedUserTransaction2,
+};
 use std::sync::RwLock;
+use std::time::{Duration, Instant};
 
 use crate::{MinerService, NewHeaderChannel};
 
 use super::metrics::BlockBuilderMetrics;
+use super::speculative::{SpeculationGuard, SpeculationKey, SpeculativeCache, SpeculativeOutputs};
 use sp_utils::thread_pool::RAYON_EXEC_POOL;
 use starcoin_dag::types::ghostdata::GhostdagData;
 use starcoin_types::U256;
@@ -82,9 +91,13 @@ pub struct BlockTemplateResponse {
     pub event: GenerateBlockEvent,
 }
 
+/// The pending txns are only pre-executed while the miner asked for a template recently.
+const SPECULATION_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
 pub struct BlockBuilderService {
     inner: Inner<TxPoolService>,
     new_header_channel: NewHeaderChannel,
+    last_template_request: Option<Instant>,
 }
 
 enum ReceiveHeader {
@@ -169,6 +182,7 @@ impl ServiceFactory<Self> for BlockBuilderService {
         Ok(Self {
             inner,
             new_header_channel,
+            last_template_request: None,
         })
     }
 }
@@ -177,6 +191,11 @@ impl ActorService for BlockBuilderService {
     fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
         ctx.subscribe::<DefaultAccountChangeEvent>();
         ctx.subscribe::<BlockTemplateRequest>();
+        if let Some(speculative_cache) = &self.inner.speculative_cache {
+            ctx.run_interval(Duration::from_millis(speculative_cache.interval()), |ctx| {
+                ctx.notify(SpeculateEvent)
+            });
+        }
         Ok(())
     }
 
@@ -241,6 +260,7 @@ impl EventHandler<Self, BlockTemplateRequest> for BlockBuilderService {
     fn handle_event(&mut self, msg: BlockTemplateRequest, ctx: &mut ServiceContext<Self>) {
         // TODO: Get block_header_version from GenesisConfig according to dag-master's implementation
         let header_version = 1u8; // Default block header version for now
+        self.last_template_request = Some(Instant::now());
         let miner_service = ctx
             .service_ref::<MinerService>()
             .expect("MinerService should exist")
@@ -256,6 +276,25 @@ impl EventHandler<Self, BlockTemplateRequest> for BlockBuilderService {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct SpeculateEvent;
+
+impl EventHandler<Self, SpeculateEvent> for BlockBuilderService {
+    fn handle_event(&mut self, _msg: SpeculateEvent, _ctx: &mut ServiceContext<Self>) {
+        let mining = self
+            .last_template_request
+            .is_some_and(|requested| requested.elapsed() < SPECULATION_IDLE_TIMEOUT);
+        if !mining || self.inner.tx_provider.status().txn_count == 0 {
+            return;
+        }
+        let header_version = 1u8;
+        let _ = self.receive_header();
+        if let Err(e) = self.inner.speculate(header_version) {
+            error!("Failed to pre-execute txns: {}", e);
+        }
+    }
+}
+
 pub trait TemplateTxProvider {
     fn get_txns_with_header(
         &self,
@@ -367,6 +406,7 @@ pub struct Inner<P> {
     #[allow(unused)]
     metrics: Option<BlockBuilderMetrics>,
     vm_metrics: Option<VMMetrics>,
+    speculative_cache: Option<Arc<SpeculativeCache>>,
 }
 
 impl<P> Inner<P>
@@ -388,6 +428,9 @@ where
         let genesis_hash = storage
             .get_genesis()?
             .ok_or_else(|| format_err!("Can not find genesis hash"))?;
+        let speculative_execution_interval = config.miner.speculative_execution_interval();
+        let speculative_cache = (speculative_execution_interval > 0)
+            .then(|| Arc::new(SpeculativeCache::new(speculative_execution_interval)));
         Ok(Self {
             storage: storage.clone(),
             storage2,
@@ -400,6 +443,7 @@ where
             metrics,
             vm_metrics,
             genesis_hash,
+            speculative_cache,
         })
     }
 
@@ -501,13 +545,8 @@ where
         ))
     }
 
-    pub fn create_block_template(
-        &mut self,
-        version: Version,
-        mut block_template_call_back: Box<dyn BlockTemplateCallBack + Send + Sync>,
-        // miner_service: Option<ServiceRef<MinerService>>,
-        // event: Option<GenerateBlockEvent>,
-    ) -> Result<()> {
+    /// Resolve the parents of the next block, and collect what the block is opened with.
+    fn prepare_block(&mut self, version: Version, log_level: Level) -> Result<BlockBuildPlan> {
         let (
             MinerResponse {
                 previous_header,
@@ -539,7 +578,8 @@ where
             .address();
 
         if now_millis <= previous_header.timestamp() {
-            info!(
+            log!(
+                log_level,
                 "Adjust new block timestamp by parent timestamp, parent.timestamp: {}, now: {}, gap: {}",
                 previous_header.timestamp(), now_millis, previous_header.timestamp() - now_millis,
             );
@@ -563,7 +603,8 @@ where
             .map(|block| block.header().clone())
             .collect::<Vec<_>>();
 
-        info!(
+        log!(
+            log_level,
             "[CreateBlockTemplate] previous_header: {:?}, block_gas_limit: {}, max_txns: {}, uncles len: {}, timestamp: {}",
             previous_header,
             block_gas_limit,
@@ -573,19 +614,66 @@ where
         );
 
         let (blue_txns, blue_txns2, seen_hashes) = collect_blue_transactions(&blue_blocks);
-        info!(
+        log!(
+            log_level,
             "[BlockProcess] Blue VM1 txns len: {}, Blue VM2 txns len: {}",
             blue_txns.len(),
             blue_txns2.len()
         );
 
+        let vm1_offline = previous_header.number().saturating_add(1)
+            >= vm1_offline_height(previous_header.chain_id().id().into());
+
+        Ok(BlockBuildPlan {
+            previous_header,
+            selected_parents,
+            strategy,
+            difficulty,
+            pruning_point,
+            red_blocks: ghostdata.mergeset_reds.len() as u64,
+            block_gas_limit,
+            max_txns,
+            author,
+            now_millis,
+            uncles,
+            blue_txns,
+            blue_txns2,
+            seen_hashes,
+            vm1_offline,
+            version,
+            state_dbs: main.into_state_dbs(),
+            access_list_scheduling: self.config.miner.access_list_scheduling(),
+            log_level,
+        })
+    }
+
+    pub fn create_block_template(
+        &mut self,
+        version: Version,
+        mut block_template_call_back: Box<dyn BlockTemplateCallBack + Send + Sync>,
+        // miner_service: Option<ServiceRef<MinerService>>,
+        // event: Option<GenerateBlockEvent>,
+    ) -> Result<()> {
+        let mut plan = self.prepare_block(version, Level::Info)?;
+        if let Some(speculative_cache) = &self.speculative_cache {
+            // The cached outputs are only valid at the timestamp they were pre-executed at.
+            if let Some(cached) = speculative_cache.latest(&plan.speculation_key(), plan.now_millis)
+            {
+                info!(
+                    "[CreateBlockTemplate] Open the block at the pre-execution timestamp: {}, now: {}",
+                    cached.timestamp, plan.now_millis
+                );
+                plan.now_millis = cached.timestamp;
+            }
+        }
+        let previous_header = plan.previous_header.clone();
+        let vm1_offline = plan.vm1_offline;
+
         let storage = self.storage.clone();
         let storage2 = self.storage2.clone();
         let vm_metrics = self.vm_metrics.clone();
         let tx_provider = self.tx_provider.clone();
-
-        let vm1_offline = previous_header.number().saturating_add(1)
-            >= vm1_offline_height(previous_header.chain_id().id().into());
+        let speculative_cache = self.speculative_cache.clone();
 
         RAYON_EXEC_POOL.spawn(move || {
             let build_start = if global_collector().is_enabled() {
@@ -597,148 +685,15 @@ where
                 return;
             }
 
-            let mut opened_block = match OpenedBlock::new(
-                storage.clone(),
-                storage2.clone(),
-                previous_header.clone(),
-                block_gas_limit,
-                author,
-                now_millis,
-                uncles.clone(),
-                difficulty,
-                strategy,
-                vm_metrics.clone(),
-                selected_parents,
-                version,
-                pruning_point,
-                ghostdata.mergeset_reds.len() as u64,
-                main.into_state_dbs(),
-            ) {
-                Ok(opened_block) => opened_block,
-                Err(e) => {
-                    error!("[BlockProcess] open block error: {}", e);
-                    return;
-                }
-            };
-
-            if is_node_shutting_down() {
-                return;
-            }
-
-            let mut seen_hashes = seen_hashes;
-            let mut included_blue = 0usize;
-
-            // Process blue VM1 transactions (includes VM1 block metadata)
-            if !vm1_offline {
-                let blue_vm1_len = blue_txns.len();
-                let excluded_txns = match opened_block.process_vm1_transactions(blue_txns) {
-                    Ok(excluded_txns) => excluded_txns,
+            let (mut opened_block, pending_transactions, pending_transactions2) =
+                match plan.open(storage, storage2, vm_metrics, &tx_provider, true) {
+                    Ok(Some(opened)) => opened,
+                    Ok(None) => return,
                     Err(e) => {
-                        error!("[BlockProcess] process vm1 transactions error: {}", e);
-                        return;
-                    }
-                };
-                for invalid_txn in &excluded_txns.discarded_txns {
-                    tx_provider.remove_invalid_txn(invalid_txn.id());
-                }
-                let included_vm1 = blue_vm1_len
-                    .saturating_sub(excluded_txns.discarded_txns.len())
-                    .saturating_sub(excluded_txns.untouched_txns.len());
-                included_blue += included_vm1;
-                info!(
-                    "[BlockProcess] Blue VM1 included: {}, discarded: {}, untouched: {}",
-                    included_vm1,
-                    excluded_txns.discarded_txns.len(),
-                    excluded_txns.untouched_txns.len(),
-                );
-            }
-
-            if is_node_shutting_down() {
-                return;
-            }
-
-            // Process blue VM2 transactions
-            let blue_vm2_len = blue_txns2.len();
-            if blue_vm2_len > 0 {
-                let excluded_txns2 = match opened_block.push_txns2(blue_txns2) {
-                    Ok(excluded_txns) => excluded_txns,
-                    Err(e) => {
-                        error!("[BlockProcess] push txns2 error: {}", e);
+                        error!("[BlockProcess] {}", e);
                         return;
                     }
                 };
-                for invalid_txn in &excluded_txns2.discarded_txns {
-                    tx_provider.remove_invalid_txn(invalid_txn.id());
-                }
-                let included_vm2 = blue_vm2_len
-                    .saturating_sub(excluded_txns2.discarded_txns.len())
-                    .saturating_sub(excluded_txns2.untouched_txns.len());
-                included_blue += included_vm2;
-                info!(
-                    "[BlockProcess] Blue VM2 included: {}, discarded: {}, untouched: {}",
-                    included_vm2,
-                    excluded_txns2.discarded_txns.len(),
-                    excluded_txns2.untouched_txns.len()
-                );
-            }
-
-            if is_node_shutting_down() {
-                return;
-            }
-
-            let remaining_max = max_txns.saturating_sub(included_blue as u64);
-            let mut pending_transactions = Vec::new();
-            let mut pending_transactions2 = Vec::new();
-            if remaining_max > 0 && opened_block.gas_left() > 0 {
-                let statedb = opened_block.state_db();
-                let statedb2 = opened_block.state_db2();
-                let state_root1 = statedb.state_root();
-                let state_root2 = statedb2.state_root();
-                let current_timestamp_secs = now_millis / 1000;
-                let pending_multi_transactions = tx_provider.get_pending_with_state_dbs(
-                    remaining_max,
-                    current_timestamp_secs,
-                    state_root1,
-                    state_root2,
-                    statedb,
-                    statedb2,
-                );
-                for txn in pending_multi_transactions {
-                    match txn {
-                        MultiSignedUserTransaction::VM1(txn) => {
-                            if vm1_offline {
-                                continue;
-                            }
-                            if seen_hashes.insert(txn.id()) {
-                                pending_transactions.push(txn);
-                            }
-                        }
-                        MultiSignedUserTransaction::VM2(txn) => {
-                            let hash = HashValue::new(txn.id().to_inner());
-                            if seen_hashes.insert(hash) {
-                                pending_transactions2.push(txn);
-                            }
-                        }
-                    }
-                }
-            }
-
-            // Post-process ordering for txpool only.
-            let pending_transactions = round_robin_by_sender(
-                pending_transactions,
-                |txn: &SignedUserTransaction| txn.sender(),
-                |txn: &SignedUserTransaction| txn.sequence_number(),
-            );
-            let pending_transactions2 = round_robin_by_sender(
-                pending_transactions2,
-                |txn: &SignedUserTransaction2| txn.sender(),
-                |txn: &SignedUserTransaction2| txn.sequence_number(),
-            );
-            info!(
-                "[BlockProcess] TxPool VM1 txns len: {}, TxPool VM2 txns len: {}",
-                pending_transactions.len(),
-                pending_transactions2.len()
-            );
             let pending_vm1_len = pending_transactions.len();
             let pending_vm2_len = pending_transactions2.len();
 
@@ -767,7 +722,11 @@ where
 
             // Process txpool VM2 transactions
             if !pending_transactions2.is_empty() {
-                let excluded_txns2 = match opened_block.push_txns2(pending_transactions2) {
+                let excluded_txns2 = match push_pending_txns2(
+                    &mut opened_block,
+                    pending_transactions2,
+                    speculative_cache.as_deref(),
+                ) {
                     Ok(excluded_txns) => excluded_txns,
                     Err(e) => {
                         error!("[BlockProcess] push txns2 error: {}", e);
@@ -863,6 +822,45 @@ where
         Ok(())
     }
 
+    /// Pre-execute the pending vm2 txns on the state a template of the current tips executes
+    /// them on, the outputs are cached for the templates.
+    pub fn speculate(&mut self, version: Version) -> Result<()> {
+        if let Some(task) = self.speculation_task(version)? {
+            RAYON_EXEC_POOL.spawn(move || task.run());
+        }
+        Ok(())
+    }
+
+    /// Prepare a pre-execution of the pending vm2 txns, return None if the speculative execution
+    /// is disabled or a pre-execution is running.
+    pub fn speculation_task(&mut self, version: Version) -> Result<Option<SpeculationTask<P>>> {
+        let speculative_cache = match &self.speculative_cache {
+            Some(speculative_cache) => speculative_cache.clone(),
+            None => return Ok(None),
+        };
+        let guard = match speculative_cache.try_start() {
+            Some(guard) => guard,
+            None => return Ok(None),
+        };
+        let plan = self.prepare_block(version, Level::Debug)?;
+        Ok(Some(SpeculationTask {
+            plan,
+            storage: self.storage.clone(),
+            storage2: self.storage2.clone(),
+            vm_metrics: self.vm_metrics.clone(),
+            tx_provider: self.tx_provider.clone(),
+            speculative_cache,
+            _guard: guard,
+        }))
+    }
+
+    /// The number of templates which reused the pre-executed outputs.
+    pub fn speculative_hits(&self) -> u64 {
+        self.speculative_cache
+            .as_ref()
+            .map_or(0, |speculative_cache| speculative_cache.hits())
+    }
+
     pub fn set_current_block_header(&mut self, header: BlockHeader) -> Result<()> {
         if self.main.id() == header.id() {
             return Ok(());
@@ -1018,6 +1016,334 @@ where
     }
 }
 
+/// Everything a block on the current tips is opened with.
+struct BlockBuildPlan {
+    previous_header: BlockHeader,
+    selected_parents: Vec<HashValue>,
+    strategy: ConsensusStrategy,
+    difficulty: U256,
+    pruning_point: HashValue,
+    red_blocks: u64,
+    block_gas_limit: u64,
+    max_txns: u64,
+    author: AccountAddress,
+    now_millis: u64,
+    uncles: Vec<BlockHeader>,
+    blue_txns: Vec<SignedUserTransaction>,
+    blue_txns2: Vec<SignedUserTransaction2>,
+    seen_hashes: HashSet<HashValue>,
+    vm1_offline: bool,
+    version: Version,
+    state_dbs: (Arc<ChainStateDB>, Arc<ChainStateDB2>),
+    access_list_scheduling: bool,
+    log_level: Level,
+}
+
+impl BlockBuildPlan {
+    fn speculation_key(&self) -> SpeculationKey {
+        SpeculationKey {
+            parent_id: self.previous_header.id(),
+            uncles: self.uncles.iter().map(|uncle| uncle.id()).collect(),
+            author: self.author,
+        }
+    }
+
+    /// Open the block and push the blue txns, then pick the pending txns of the txpool. The
+    /// discarded blue txns are removed from the txpool if `remove_invalid_txns`.
+    /// Return None if the node is shutting down.
+    #[allow(clippy::type_complexity)]
+    fn open<P: TemplateTxProvider>(
+        self,
+        storage: Arc<dyn Store>,
+        storage2: Arc<dyn Store2>,
+        vm_metrics: Option<VMMetrics>,
+        tx_provider: &P,
+        remove_invalid_txns: bool,
+    ) -> Result<
+        Option<(
+            OpenedBlock,
+            Vec<SignedUserTransaction>,
+            Vec<SignedUserTransaction2>,
+        )>,
+    > {
+        let log_level = self.log_level;
+        let access_list_scheduling = self.access_list_scheduling;
+        let vm1_offline = self.vm1_offline;
+        let now_millis = self.now_millis;
+        let mut opened_block = OpenedBlock::new(
+            storage,
+            storage2,
+            self.previous_header,
+            self.block_gas_limit,
+            self.author,
+            now_millis,
+            self.uncles,
+            self.difficulty,
+            self.strategy,
+            vm_metrics,
+            self.selected_parents,
+            self.version,
+            self.pruning_point,
+            self.red_blocks,
+            self.state_dbs,
+        )
+        .map_err(|e| format_err!("open block error: {}", e))?;
+
+        if is_node_shutting_down() {
+            return Ok(None);
+        }
+
+        let mut seen_hashes = self.seen_hashes;
+        let mut included_blue = 0usize;
+
+        // Process blue VM1 transactions (includes VM1 block metadata)
+        if !vm1_offline {
+            let blue_vm1_len = self.blue_txns.len();
+            let excluded_txns = opened_block
+                .process_vm1_transactions(self.blue_txns)
+                .map_err(|e| format_err!("process vm1 transactions error: {}", e))?;
+            if remove_invalid_txns {
+                for invalid_txn in &excluded_txns.discarded_txns {
+                    tx_provider.remove_invalid_txn(invalid_txn.id());
+                }
+            }
+            let included_vm1 = blue_vm1_len
+                .saturating_sub(excluded_txns.discarded_txns.len())
+                .saturating_sub(excluded_txns.untouched_txns.len());
+            included_blue += included_vm1;
+            log!(
+                log_level,
+                "[BlockProcess] Blue VM1 included: {}, discarded: {}, untouched: {}",
+                included_vm1,
+                excluded_txns.discarded_txns.len(),
+                excluded_txns.untouched_txns.len(),
+            );
+        }
+
+        if is_node_shutting_down() {
+            return Ok(None);
+        }
+
+        // Process blue VM2 transactions
+        let blue_vm2_len = self.blue_txns2.len();
+        if blue_vm2_len > 0 {
+            let excluded_txns2 = opened_block
+                .push_txns2(self.blue_txns2)
+                .map_err(|e| format_err!("push txns2 error: {}", e))?;
+            if remove_invalid_txns {
+                for invalid_txn in &excluded_txns2.discarded_txns {
+                    tx_provider.remove_invalid_txn(invalid_txn.id());
+                }
+            }
+            let included_vm2 = blue_vm2_len
+                .saturating_sub(excluded_txns2.discarded_txns.len())
+                .saturating_sub(excluded_txns2.untouched_txns.len());
+            included_blue += included_vm2;
+            log!(
+                log_level,
+                "[BlockProcess] Blue VM2 included: {}, discarded: {}, untouched: {}",
+                included_vm2,
+                excluded_txns2.discarded_txns.len(),
+                excluded_txns2.untouched_txns.len()
+            );
+        }
+
+        if is_node_shutting_down() {
+            return Ok(None);
+        }
+
+        let remaining_max = self.max_txns.saturating_sub(included_blue as u64);
+        let mut pending_transactions = Vec::new();
+        let mut pending_transactions2 = Vec::new();
+        if remaining_max > 0 && opened_block.gas_left() > 0 {
+            let statedb = opened_block.state_db();
+            let statedb2 = opened_block.state_db2();
+            let state_root1 = statedb.state_root();
+            let state_root2 = statedb2.state_root();
+            let current_timestamp_secs = now_millis / 1000;
+            let pending_multi_transactions = tx_provider.get_pending_with_state_dbs(
+                remaining_max,
+                current_timestamp_secs,
+                state_root1,
+                state_root2,
+                statedb,
+                statedb2,
+            );
+            for txn in pending_multi_transactions {
+                match txn {
+                    MultiSignedUserTransaction::VM1(txn) => {
+                        if vm1_offline {
+                            continue;
+                        }
+                        if seen_hashes.insert(txn.id()) {
+                            pending_transactions.push(txn);
+                        }
+                    }
+                    MultiSignedUserTransaction::VM2(txn) => {
+                        let hash = HashValue::new(txn.id().to_inner());
+                        if seen_hashes.insert(hash) {
+                            pending_transactions2.push(txn);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Post-process ordering for txpool only.
+        let pending_transactions = round_robin_by_sender(
+            pending_transactions,
+            |txn: &SignedUserTransaction| txn.sender(),
+            |txn: &SignedUserTransaction| txn.sequence_number(),
+        );
+        let pending_transactions2 = round_robin_by_sender(
+            pending_transactions2,
+            |txn: &SignedUserTransaction2| txn.sender(),
+            |txn: &SignedUserTransaction2| txn.sequence_number(),
+        );
+        let pending_transactions2 = if access_list_scheduling && pending_transactions2.len() > 1 {
+            let statedb2 = opened_block.state_db2();
+            let access_lists = pending_transactions2
+                .par_iter()
+                .map(|txn| {
+                    predict_access_list(statedb2.as_ref(), txn.clone(), None).unwrap_or_else(|e| {
+                        debug!("Failed to predict access list of txn {}: {}", txn.id(), e);
+                        AccessList::default()
+                    })
+                })
+                .collect::<Vec<_>>();
+            schedule_by_access_lists(pending_transactions2, &access_lists, |txn| txn.sender())
+        } else {
+            pending_transactions2
+        };
+        log!(
+            log_level,
+            "[BlockProcess] TxPool VM1 txns len: {}, TxPool VM2 txns len: {}",
+            pending_transactions.len(),
+            pending_transactions2.len()
+        );
+        Ok(Some((
+            opened_block,
+            pending_transactions,
+            pending_transactions2,
+        )))
+    }
+}
+
+/// A pre-execution of the pending vm2 txns prepared by `Inner::speculation_task`. It only caches
+/// the outputs, the txpool is left untouched.
+pub struct SpeculationTask<P> {
+    plan: BlockBuildPlan,
+    storage: Arc<dyn Store>,
+    storage2: Arc<dyn Store2>,
+    vm_metrics: Option<VMMetrics>,
+    tx_provider: P,
+    speculative_cache: Arc<SpeculativeCache>,
+    _guard: SpeculationGuard,
+}
+
+impl<P: TemplateTxProvider> SpeculationTask<P> {
+    pub fn run(self) {
+        if is_node_shutting_down() {
+            return;
+        }
+        let speculative_cache = self.speculative_cache;
+        let key = self.plan.speculation_key();
+        let timestamp = self.plan.now_millis;
+        let (opened_block, _, pending_transactions2) = match self.plan.open(
+            self.storage,
+            self.storage2,
+            self.vm_metrics,
+            &self.tx_provider,
+            false,
+        ) {
+            Ok(Some(opened)) => opened,
+            Ok(None) => return,
+            Err(e) => {
+                error!("[Speculate] {}", e);
+                return;
+            }
+        };
+        if pending_transactions2.is_empty() {
+            return;
+        }
+        if let Some(cached) = speculative_cache.latest(&key, timestamp) {
+            if cached
+                .txns
+                .iter()
+                .map(|txn| txn.id())
+                .eq(pending_transactions2.iter().map(|txn| txn.id()))
+            {
+                return;
+            }
+        }
+        let result = opened_block.state_root2().and_then(|state_root| {
+            let gas_left = opened_block.gas_left();
+            let outputs = opened_block.execute_txns2(&pending_transactions2)?;
+            debug!(
+                "[Speculate] Pre-executed {} VM2 txns on state {}",
+                outputs.len(),
+                state_root
+            );
+            speculative_cache.insert(SpeculativeOutputs {
+                key,
+                timestamp,
+                state_root,
+                gas_left,
+                txns: pending_transactions2,
+                outputs,
+            });
+            Ok(())
+        });
+        if let Err(e) = result {
+            error!("[Speculate] pre-execute txns2 error: {}", e);
+        }
+    }
+}
+
+/// Push the pending vm2 txns into the block, the outputs pre-executed on the same state are
+/// reused for the leading txns. The state root after the block metadata guards the reuse, it only
+/// matches if the block is opened at the timestamp of the pre-execution.
+fn push_pending_txns2(
+    opened_block: &mut OpenedBlock,
+    mut txns: Vec<SignedUserTransaction2>,
+    speculative_cache: Option<&SpeculativeCache>,
+) -> Result<ExcludedTxns> {
+    let cached = match speculative_cache {
+        Some(speculative_cache) => {
+            speculative_cache.get(opened_block.state_root2()?, opened_block.gas_left())
+        }
+        None => None,
+    };
+    let matched = cached
+        .as_ref()
+        .map(|cached| cached.matched_prefix(&txns))
+        .unwrap_or_default();
+    let cached = match cached {
+        Some(cached) if matched > 0 => cached,
+        _ => return opened_block.push_txns2(txns),
+    };
+    info!(
+        "[BlockProcess] Reuse the pre-executed outputs of {} VM2 txns",
+        matched
+    );
+    if let Some(speculative_cache) = speculative_cache {
+        speculative_cache.record_hit();
+    }
+    let rest = txns.split_off(matched);
+    let mut excluded_txns =
+        opened_block.push_executed_txns2(txns, cached.outputs[..matched].to_vec())?;
+    if !rest.is_empty() {
+        let rest_excluded_txns = opened_block.push_txns2(rest)?;
+        excluded_txns
+            .discarded_txns
+            .extend(rest_excluded_txns.discarded_txns);
+        excluded_txns
+            .untouched_txns
+            .extend(rest_excluded_txns.untouched_txns);
+    }
+    Ok(excluded_txns)
+}
+
 fn collect_blue_transactions(
     blue_blocks: &[Block],
 ) -> (
@@ -1046,6 +1372,58 @@ fn collect_blue_transactions(
     (pending_transactions, pending_transactions2, seen_hashes)
 }
 
+/// Order `txns` into batches of txns which do not conflict with each other by `access_lists`,
+/// a txn goes to the batch after the last one it conflicts with. Access lists only record writes,
+/// so only write-write conflicts are scheduled around, read-write conflicts are left to the
+/// executor. The txns of a sender always conflict, so they keep their order. Keys written by more
+/// than half of the txns are ignored, the txns conflict on them in any order.
+fn schedule_by_access_lists<T, K, FSender>(
+    txns: Vec<T>,
+    access_lists: &[AccessList],
+    sender: FSender,
+) -> Vec<T>
+where
+    K: Eq + std::hash::Hash,
+    FSender: Fn(&T) -> K,
+{
+    debug_assert_eq!(txns.len(), access_lists.len());
+    let mut writers: HashMap<&StateKey, usize> = HashMap::new();
+    for state_key in access_lists
+        .iter()
+        .flat_map(|access_list| access_list.writes())
+    {
+        *writers.entry(state_key).or_default() += 1;
+    }
+    let hot_writers = (txns.len() / 2).max(1);
+
+    let mut key_batches: HashMap<&StateKey, usize> = HashMap::new();
+    let mut sender_batches: HashMap<K, usize> = HashMap::new();
+    let mut batches: Vec<Vec<T>> = Vec::new();
+    for (txn, access_list) in txns.into_iter().zip(access_lists) {
+        let sender = sender(&txn);
+        let keys = access_list
+            .writes()
+            .filter(|state_key| writers.get(state_key).copied().unwrap_or_default() <= hot_writers)
+            .collect::<Vec<_>>();
+        let batch = keys
+            .iter()
+            .filter_map(|state_key| key_batches.get(state_key))
+            .chain(sender_batches.get(&sender))
+            .map(|batch| batch + 1)
+            .max()
+            .unwrap_or_default();
+        for state_key in keys {
+            key_batches.insert(state_key, batch);
+        }
+        sender_batches.insert(sender, batch);
+        if batch == batches.len() {
+            batches.push(Vec::new());
+        }
+        batches[batch].push(txn);
+    }
+    batches.into_iter().flatten().collect()
+}
+
 fn round_robin_by_sender<T, K, FSender, FSeq>(txns: Vec<T>, sender: FSender, seq: FSeq) -> Vec<T>
 where
     K: Ord,
@@ -1084,3 +1462,57 @@ where
 
     ordered
 }
+
+#[cfg(test)]
+mod tests {
+    use super::schedule_by_access_lists;
+    use starcoin_vm2_types::account_address::AccountAddress;
+    use starcoin_vm2_vm_types::state_store::state_key::StateKey;
+    use starcoin_vm2_vm_types::state_store::table::TableHandle;
+    use starcoin_vm2_vm_types::transaction::AccessList;
+
+    fn access_list(handle: &TableHandle, keys: &[&str]) -> AccessList {
+        AccessList::new(
+            keys.iter()
+                .map(|key| StateKey::table_item(handle, key.as_bytes())),
+        )
+    }
+
+    #[test]
+    fn test_schedule_by_access_lists() {
+        let handle = TableHandle(AccountAddress::random());
+        // (id, sender)
+        let txns = vec![(0, 0), (1, 1), (2, 2), (3, 0), (4, 2)];
+        let access_lists = vec![
+            access_list(&handle, &["a"]),
+            access_list(&handle, &["a"]),
+            access_list(&handle, &["b"]),
+            access_list(&handle, &["c"]),
+            access_list(&handle, &["d"]),
+        ];
+        // txn 1 writes `a` after txn 0, txns 3 and 4 follow an earlier txn of their sender.
+        let ordered = schedule_by_access_lists(txns, &access_lists, |(_, sender)| *sender);
+        assert_eq!(
+            ordered.into_iter().map(|(id, _)| id).collect::<Vec<_>>(),
+            vec![0, 2, 1, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_schedule_by_access_lists_ignores_hot_keys() {
+        let handle = TableHandle(AccountAddress::random());
+        let txns = vec![(0, 0), (1, 1), (2, 2), (3, 3)];
+        let access_lists = vec![
+            access_list(&handle, &["hot"]),
+            access_list(&handle, &["hot"]),
+            access_list(&handle, &["hot", "a"]),
+            access_list(&handle, &["b"]),
+        ];
+        // `hot` is written by more than half of the txns, so it does not split them into batches.
+        let ordered = schedule_by_access_lists(txns, &access_lists, |(_, sender)| *sender);
+        assert_eq!(
+            ordered.into_iter().map(|(id, _)| id).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3]
+        );
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod metrics;
+mod speculative;
 pub mod new_header_service;
 //#[cfg(test)]
 //mod test_create_block_template;
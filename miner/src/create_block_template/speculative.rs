@@ -0,0 +1,134 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The block builder pre-executes the pending vm2 txns on the state a template of the head would
+//! execute them on, and caches the outputs by the parent block, the uncles and the author, which
+//! don't depend on the time. The txns read the block timestamp, so a template built on the same
+//! parent reopens the block at the timestamp of a recent pre-execution, and pushes the cached
+//! outputs of the txns it picks instead of executing them again.
+//! Only vm2 txns are pre-executed. A template without a recent pre-execution is opened at the
+//! current time and executes the txns as usual.
+
+use parking_lot::Mutex;
+use starcoin_crypto::HashValue;
+use starcoin_vm2_types::account_address::AccountAddress;
+use starcoin_vm2_vm_types::transaction::{
+    SignedUserTransaction as SignedUserTransaction2, TransactionOutput as TransactionOutput2,
+};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+const MAX_CACHED_STATES: usize = 8;
+
+/// The parts of a template which decide the state its pending txns are executed on, except the
+/// block timestamp.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpeculationKey {
+    pub parent_id: HashValue,
+    pub uncles: Vec<HashValue>,
+    pub author: AccountAddress,
+}
+
+/// The outputs of `txns` executed in order on `state_root` with `gas_left`, the outputs may be
+/// fewer than the txns if the gas runs out. `state_root` is the vm2 state after the block metadata
+/// of `timestamp` is applied.
+pub struct SpeculativeOutputs {
+    pub key: SpeculationKey,
+    pub timestamp: u64,
+    pub state_root: HashValue,
+    pub gas_left: u64,
+    pub txns: Vec<SignedUserTransaction2>,
+    pub outputs: Vec<TransactionOutput2>,
+}
+
+impl SpeculativeOutputs {
+    /// The number of leading `txns` which have cached outputs. A txn only reads the writes of
+    /// the txns before it, so the outputs of a common prefix can be reused.
+    pub fn matched_prefix(&self, txns: &[SignedUserTransaction2]) -> usize {
+        self.txns
+            .iter()
+            .zip(txns)
+            .take(self.outputs.len())
+            .take_while(|(cached, txn)| cached.id() == txn.id())
+            .count()
+    }
+}
+
+pub struct SpeculativeCache {
+    interval: u64,
+    outputs: Mutex<VecDeque<Arc<SpeculativeOutputs>>>,
+    running: AtomicBool,
+    /// The number of templates which reused the cached outputs.
+    hits: AtomicU64,
+}
+
+impl SpeculativeCache {
+    pub fn new(interval: u64) -> Self {
+        Self {
+            interval,
+            outputs: Mutex::new(VecDeque::new()),
+            running: AtomicBool::new(false),
+            hits: AtomicU64::new(0),
+        }
+    }
+
+    pub fn interval(&self) -> u64 {
+        self.interval
+    }
+
+    /// The latest outputs pre-executed for `key` no longer than two intervals before `now_millis`.
+    pub fn latest(&self, key: &SpeculationKey, now_millis: u64) -> Option<Arc<SpeculativeOutputs>> {
+        self.outputs
+            .lock()
+            .iter()
+            .find(|outputs| {
+                outputs.key == *key
+                    && now_millis.saturating_sub(outputs.timestamp)
+                        <= self.interval.saturating_mul(2)
+            })
+            .cloned()
+    }
+
+    pub fn get(&self, state_root: HashValue, gas_left: u64) -> Option<Arc<SpeculativeOutputs>> {
+        self.outputs
+            .lock()
+            .iter()
+            .find(|outputs| outputs.state_root == state_root && outputs.gas_left == gas_left)
+            .cloned()
+    }
+
+    pub fn record_hit(&self) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    pub fn insert(&self, outputs: SpeculativeOutputs) {
+        let mut cached = self.outputs.lock();
+        cached.retain(|cached| cached.key != outputs.key);
+        if cached.len() >= MAX_CACHED_STATES {
+            cached.pop_front();
+        }
+        cached.push_back(Arc::new(outputs));
+    }
+
+    /// Mark a pre-execution running, return None if one is running already.
+    pub fn try_start(self: &Arc<Self>) -> Option<SpeculationGuard> {
+        self.running
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .ok()
+            .map(|_| SpeculationGuard(self.clone()))
+    }
+}
+
+/// Mark the pre-execution finished when dropped.
+pub struct SpeculationGuard(Arc<SpeculativeCache>);
+
+impl Drop for SpeculationGuard {
+    fn drop(&mut self) {
+        self.0.running.store(false, Ordering::Release);
+    }
+}
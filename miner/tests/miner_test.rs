@@ -643,3 +643,142 @@ pub fn test_block_template_filters_txpool_after_blue() -> Result<()> {
 
     Ok(())
 }
+
+struct TestTemplateSender(Sender<BlockTemplate>);
+
+impl BlockTemplateCallBack for TestTemplateSender {
+    fn block_template_callback(
+        &mut self,
+        _parent: BlockHeader,
+        block_template: BlockTemplate,
+    ) -> Result<()> {
+        self.0.send(block_template)?;
+        Ok(())
+    }
+}
+
+fn association_transfers(
+    config: &NodeConfig,
+    chain: &BlockChain,
+    count: u64,
+) -> Result<Vec<MultiSignedUserTransaction>> {
+    let association_sequence_num = chain
+        .chain_state_reader2()
+        .get_sequence_number(account_config::association_address())?;
+    (0..count)
+        .map(|i| -> Result<MultiSignedUserTransaction> {
+            let (_receive_prikey, receive_public_key) = KeyGen::from_os_rng().generate_keypair();
+            let txn: SignedUserTransaction = build_transfer_from_association(
+                account_address::from_public_key(&receive_public_key),
+                association_sequence_num + i,
+                50_000_000,
+                config.net().time_service().now_secs() + DEFAULT_EXPIRATION_TIME,
+                config.net().chain_id().id().into(),
+                config.net().genesis_config2(),
+            )
+            .try_into()?;
+            Ok(txn.into())
+        })
+        .collect()
+}
+
+#[stest::test]
+pub fn test_speculative_template_same_as_built() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    config.miner.speculative_execution_interval = Some(500);
+    let config = Arc::new(config);
+    let chain = test_helper::gen_blockchain_for_test(config.net())?;
+
+    let txpool = MockTxPoolService::new();
+    txpool.add_txns_multi_signed(association_transfers(&config, &chain, 3)?, false, None)?;
+
+    let miner_account_info = AccountInfo::random();
+    let new_inner = |config: Arc<NodeConfig>| {
+        Inner::new(
+            chain.current_header(),
+            chain.get_storage(),
+            chain.get_storage2(),
+            txpool.clone(),
+            config.miner.block_gas_limit,
+            miner_account_info.clone(),
+            chain.dag(),
+            config,
+            None,
+            None,
+        )
+    };
+    let (sender, receiver) = std::sync::mpsc::channel::<BlockTemplate>();
+
+    // the template reuses the outputs pre-executed on the same state.
+    let mut speculative_service = new_inner(config.clone())?;
+    speculative_service
+        .speculation_task(1)?
+        .expect("the speculative execution is enabled")
+        .run();
+    speculative_service.create_block_template(1, Box::new(TestTemplateSender(sender.clone())))?;
+    let speculative_template = receiver.recv_timeout(Duration::from_secs(10))?;
+    assert_eq!(speculative_service.speculative_hits(), 1);
+    assert_eq!(speculative_template.body.transactions2.len(), 3);
+
+    // the template built from scratch is the same.
+    let mut scratch_config = config.as_ref().clone();
+    scratch_config.miner.speculative_execution_interval = Some(0);
+    let mut scratch_service = new_inner(Arc::new(scratch_config))?;
+    assert!(scratch_service.speculation_task(1)?.is_none());
+    scratch_service.create_block_template(1, Box::new(TestTemplateSender(sender)))?;
+    let scratch_template = receiver.recv_timeout(Duration::from_secs(10))?;
+    assert_eq!(scratch_service.speculative_hits(), 0);
+    assert_eq!(speculative_template, scratch_template);
+    Ok(())
+}
+
+#[stest::test]
+pub fn test_speculative_template_after_clock_advanced() -> Result<()> {
+    let mut config = NodeConfig::random_for_test();
+    config.miner.speculative_execution_interval = Some(500);
+    let config = Arc::new(config);
+    let chain = test_helper::gen_blockchain_for_test(config.net())?;
+    let time_service = config.net().time_service();
+
+    let txpool = MockTxPoolService::new();
+    txpool.add_txns_multi_signed(association_transfers(&config, &chain, 3)?, false, None)?;
+    let mut service = Inner::new(
+        chain.current_header(),
+        chain.get_storage(),
+        chain.get_storage2(),
+        txpool,
+        config.miner.block_gas_limit,
+        AccountInfo::random(),
+        chain.dag(),
+        config.clone(),
+        None,
+        None,
+    )?;
+    let (sender, receiver) = std::sync::mpsc::channel::<BlockTemplate>();
+
+    let speculated_at = time_service
+        .now_millis()
+        .max(chain.current_header().timestamp() + 1);
+    service
+        .speculation_task(1)?
+        .expect("the speculative execution is enabled")
+        .run();
+
+    // the template is opened at the pre-execution timestamp and reuses the outputs.
+    time_service.sleep(300);
+    service.create_block_template(1, Box::new(TestTemplateSender(sender.clone())))?;
+    let template = receiver.recv_timeout(Duration::from_secs(10))?;
+    assert_eq!(service.speculative_hits(), 1);
+    assert_eq!(template.timestamp, speculated_at);
+    assert_eq!(template.body.transactions2.len(), 3);
+
+    // the outputs pre-executed too long ago are not reused.
+    time_service.sleep(1000);
+    let now = time_service.now_millis();
+    service.create_block_template(1, Box::new(TestTemplateSender(sender)))?;
+    let template = receiver.recv_timeout(Duration::from_secs(10))?;
+    assert_eq!(service.speculative_hits(), 1);
+    assert_eq!(template.timestamp, now);
+    assert_eq!(template.body.transactions2.len(), 3);
+    Ok(())
+}
@@ -0,0 +1,341 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-protocol and per-peer bandwidth accounting.
+//!
+//! Every payload sent or received through a request-response or notifications protocol is
+//! accounted here. Optional byte-per-second limits are enforced with token buckets holding one
+//! second of burst; a message that does not fit in its buckets is dropped and accounted as such.
+
+use libp2p::PeerId;
+use log::debug;
+use network_p2p_types::bandwidth::{BandwidthStats, ByteCounter};
+use parking_lot::Mutex;
+use starcoin_metrics::UIntCounterVec;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Byte-per-second limits. `None` or a missing entry means unlimited.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BandwidthLimits {
+    /// Upload limit across all peers and protocols.
+    pub max_upload_rate: Option<u64>,
+    /// Upload limit of individual protocols, by protocol name.
+    pub protocol_upload_rates: HashMap<String, u64>,
+    /// Download limit of individual protocols, by protocol name.
+    pub protocol_download_rates: HashMap<String, u64>,
+    /// Upload limit towards each single peer.
+    pub peer_upload_rate: Option<u64>,
+    /// Download limit from each single peer.
+    pub peer_download_rate: Option<u64>,
+}
+
+impl BandwidthLimits {
+    fn protocol_rate(&self, protocol: &str, direction: Direction) -> Option<u64> {
+        match direction {
+            Direction::Inbound => self.protocol_download_rates.get(protocol).copied(),
+            Direction::Outbound => self.protocol_upload_rates.get(protocol).copied(),
+        }
+    }
+
+    fn peer_rate(&self, direction: Direction) -> Option<u64> {
+        match direction {
+            Direction::Inbound => self.peer_download_rate,
+            Direction::Outbound => self.peer_upload_rate,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Inbound,
+    Outbound,
+}
+
+impl Direction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Direction::Inbound => "in",
+            Direction::Outbound => "out",
+        }
+    }
+}
+
+fn add(counter: &mut ByteCounter, direction: Direction, len: u64) {
+    match direction {
+        Direction::Inbound => counter.inbound = counter.inbound.saturating_add(len),
+        Direction::Outbound => counter.outbound = counter.outbound.saturating_add(len),
+    }
+}
+
+/// Token bucket refilled at `rate` bytes per second, holding at most one second of burst.
+#[derive(Debug)]
+struct TokenBucket {
+    rate: u64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u64, now: Instant) -> Self {
+        Self {
+            rate,
+            tokens: rate as f64,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate as f64).min(self.rate as f64);
+        self.last_refill = now;
+    }
+
+    /// A full bucket always admits one message, so payloads larger than the rate are
+    /// throttled instead of being rejected forever.
+    fn has_room(&self, len: u64) -> bool {
+        self.tokens >= len as f64 || self.tokens >= self.rate as f64
+    }
+
+    fn consume(&mut self, len: u64) {
+        self.tokens = (self.tokens - len as f64).max(0.0);
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct BandwidthMetrics {
+    pub bytes_total: UIntCounterVec,
+    pub limited_total: UIntCounterVec,
+}
+
+#[derive(Default)]
+struct Inner {
+    protocols: HashMap<String, ByteCounter>,
+    peers: HashMap<PeerId, ByteCounter>,
+    dropped_messages: ByteCounter,
+    upload_bucket: Option<TokenBucket>,
+    protocol_buckets: HashMap<(String, Direction), TokenBucket>,
+    peer_buckets: HashMap<(PeerId, Direction), TokenBucket>,
+}
+
+/// Shared handle accounting the bandwidth of the node and enforcing [`BandwidthLimits`].
+#[derive(Clone, Default)]
+pub struct BandwidthLimiter {
+    limits: Arc<BandwidthLimits>,
+    inner: Arc<Mutex<Inner>>,
+    metrics: Option<BandwidthMetrics>,
+}
+
+impl BandwidthLimiter {
+    pub fn new(limits: BandwidthLimits) -> Self {
+        Self {
+            limits: Arc::new(limits),
+            inner: Arc::new(Mutex::new(Inner::default())),
+            metrics: None,
+        }
+    }
+
+    pub(crate) fn with_metrics(mut self, metrics: Option<BandwidthMetrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    pub fn limits(&self) -> &BandwidthLimits {
+        &self.limits
+    }
+
+    /// Accounts a message without applying any limit.
+    pub fn record(&self, peer: &PeerId, protocol: &str, direction: Direction, len: usize) {
+        let len = len as u64;
+        {
+            let mut inner = self.inner.lock();
+            add(
+                inner.protocols.entry(protocol.to_string()).or_default(),
+                direction,
+                len,
+            );
+            add(inner.peers.entry(*peer).or_default(), direction, len);
+        }
+        if let Some(metrics) = self.metrics.as_ref() {
+            metrics
+                .bytes_total
+                .with_label_values(&[direction.as_str(), protocol])
+                .inc_by(len);
+        }
+    }
+
+    /// Checks the message against the configured limits. Returns `true` and accounts the message
+    /// if it may go through, `false` if it must be dropped.
+    pub fn admit(&self, peer: &PeerId, protocol: &str, direction: Direction, len: usize) -> bool {
+        let admitted = self.try_consume(peer, protocol, direction, len as u64, Instant::now());
+        if admitted {
+            self.record(peer, protocol, direction, len);
+        } else {
+            add(&mut self.inner.lock().dropped_messages, direction, 1);
+            if let Some(metrics) = self.metrics.as_ref() {
+                metrics
+                    .limited_total
+                    .with_label_values(&[direction.as_str(), protocol])
+                    .inc();
+            }
+            debug!(
+                target: "sub-libp2p",
+                "Drop {} bytes {} message of {} with peer {}: bandwidth limit exceeded",
+                len, direction.as_str(), protocol, peer,
+            );
+        }
+        admitted
+    }
+
+    fn try_consume(
+        &self,
+        peer: &PeerId,
+        protocol: &str,
+        direction: Direction,
+        len: u64,
+        now: Instant,
+    ) -> bool {
+        let limits = &self.limits;
+        let global_rate = match direction {
+            Direction::Inbound => None,
+            Direction::Outbound => limits.max_upload_rate,
+        };
+        let protocol_rate = limits.protocol_rate(protocol, direction);
+        let peer_rate = limits.peer_rate(direction);
+        if global_rate.is_none() && protocol_rate.is_none() && peer_rate.is_none() {
+            return true;
+        }
+
+        let mut guard = self.inner.lock();
+        let inner = &mut *guard;
+        let mut buckets = Vec::with_capacity(3);
+        if let Some(rate) = global_rate {
+            buckets.push(
+                inner
+                    .upload_bucket
+                    .get_or_insert_with(|| TokenBucket::new(rate, now)),
+            );
+        }
+        if let Some(rate) = protocol_rate {
+            buckets.push(
+                inner
+                    .protocol_buckets
+                    .entry((protocol.to_string(), direction))
+                    .or_insert_with(|| TokenBucket::new(rate, now)),
+            );
+        }
+        if let Some(rate) = peer_rate {
+            buckets.push(
+                inner
+                    .peer_buckets
+                    .entry((*peer, direction))
+                    .or_insert_with(|| TokenBucket::new(rate, now)),
+            );
+        }
+
+        buckets.iter_mut().for_each(|bucket| bucket.refill(now));
+        if !buckets.iter().all(|bucket| bucket.has_room(len)) {
+            return false;
+        }
+        buckets.iter_mut().for_each(|bucket| bucket.consume(len));
+        true
+    }
+
+    /// Forgets the per-peer state of a peer that is no longer connected.
+    pub fn remove_peer(&self, peer: &PeerId) {
+        let mut inner = self.inner.lock();
+        inner.peers.remove(peer);
+        inner.peer_buckets.retain(|(p, _), _| p != peer);
+    }
+
+    /// Returns a snapshot of the accounted bandwidth. The transport totals are left to the caller.
+    pub fn stats(&self) -> BandwidthStats {
+        let inner = self.inner.lock();
+        BandwidthStats {
+            total: ByteCounter::default(),
+            protocols: inner
+                .protocols
+                .iter()
+                .map(|(protocol, counter)| (protocol.clone(), *counter))
+                .collect(),
+            peers: inner
+                .peers
+                .iter()
+                .map(|(peer, counter)| (peer.to_base58(), *counter))
+                .collect(),
+            dropped_messages: inner.dropped_messages,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_unlimited_only_accounts() {
+        let limiter = BandwidthLimiter::new(BandwidthLimits::default());
+        let peer = PeerId::random();
+        assert!(limiter.admit(&peer, "/starcoin/rpc/1", Direction::Outbound, 1024));
+        assert!(limiter.admit(&peer, "/starcoin/rpc/1", Direction::Inbound, 512));
+        limiter.record(&peer, "/starcoin/txn/1", Direction::Outbound, 10);
+
+        let stats = limiter.stats();
+        let rpc = stats.protocols["/starcoin/rpc/1"];
+        assert_eq!(rpc.outbound, 1024);
+        assert_eq!(rpc.inbound, 512);
+        assert_eq!(stats.peers[&peer.to_base58()].outbound, 1034);
+        assert_eq!(stats.dropped_messages, ByteCounter::default());
+
+        limiter.remove_peer(&peer);
+        assert!(limiter.stats().peers.is_empty());
+    }
+
+    #[test]
+    fn test_protocol_and_peer_limits() {
+        let limits = BandwidthLimits {
+            protocol_upload_rates: vec![("/starcoin/rpc/1".to_string(), 1000)]
+                .into_iter()
+                .collect(),
+            peer_download_rate: Some(100),
+            ..Default::default()
+        };
+        let limiter = BandwidthLimiter::new(limits);
+        let peer = PeerId::random();
+        let now = Instant::now();
+
+        assert!(limiter.try_consume(&peer, "/starcoin/rpc/1", Direction::Outbound, 600, now));
+        assert!(!limiter.try_consume(&peer, "/starcoin/rpc/1", Direction::Outbound, 600, now));
+        // other protocols are not limited on upload
+        assert!(limiter.try_consume(&peer, "/starcoin/txn/1", Direction::Outbound, 5000, now));
+        // half a second refills half of the bucket
+        let later = now + Duration::from_millis(500);
+        assert!(limiter.try_consume(&peer, "/starcoin/rpc/1", Direction::Outbound, 600, later));
+
+        // a full bucket admits one oversized message, then throttles
+        assert!(limiter.try_consume(&peer, "/starcoin/txn/1", Direction::Inbound, 500, now));
+        assert!(!limiter.try_consume(&peer, "/starcoin/txn/1", Direction::Inbound, 1, now));
+        let other = PeerId::random();
+        assert!(limiter.try_consume(&other, "/starcoin/txn/1", Direction::Inbound, 50, now));
+
+        assert!(!limiter.admit(&peer, "/starcoin/txn/1", Direction::Inbound, 10));
+        assert_eq!(limiter.stats().dropped_messages.inbound, 1);
+    }
+
+    #[test]
+    fn test_global_upload_limit() {
+        let limiter = BandwidthLimiter::new(BandwidthLimits {
+            max_upload_rate: Some(100),
+            ..Default::default()
+        });
+        let now = Instant::now();
+        let (a, b) = (PeerId::random(), PeerId::random());
+        assert!(limiter.try_consume(&a, "/a", Direction::Outbound, 60, now));
+        assert!(!limiter.try_consume(&b, "/b", Direction::Outbound, 60, now));
+        assert!(limiter.try_consume(&b, "/b", Direction::Inbound, 60, now));
+    }
+}
@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
 
+use crate::bandwidth::BandwidthLimiter;
 use crate::business_layer_handle::BusinessLayerHandle;
 use crate::discovery::DiscoveryConfig;
 use crate::protocol::generic_proto::NotificationsSink;
@@ -155,6 +156,7 @@ impl<T: BusinessLayerHandle + Send> Behaviour<T> {
         local_public_key: PublicKey,
         disco_config: DiscoveryConfig,
         request_response_protocols: Vec<request_responses::ProtocolConfig>,
+        bandwidth: BandwidthLimiter,
     ) -> Result<Self, request_responses::RegisterError> {
         Ok(Behaviour {
             protocol,
@@ -163,7 +165,8 @@ impl<T: BusinessLayerHandle + Send> Behaviour<T> {
             discovery: disco_config.finish(),
             request_responses: request_responses::RequestResponsesBehaviour::new(
                 request_response_protocols.into_iter(),
-            )?,
+            )?
+            .with_bandwidth_limiter(bandwidth),
         })
     }
 
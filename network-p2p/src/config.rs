@@ -22,6 +22,7 @@ use std::{
 };
 use zeroize::Zeroize;
 
+pub use crate::bandwidth::BandwidthLimits;
 pub use crate::request_responses::{IncomingRequest, ProtocolConfig as RequestResponseConfig};
 pub use libp2p::{build_multiaddr, identity, identity::PublicKey};
 pub use network_p2p_types::{parse_addr, parse_str_addr, MultiaddrWithPeerId};
@@ -110,6 +111,8 @@ pub struct NetworkConfiguration {
     /// Require iterative Kademlia DHT queries to use disjoint paths for increased resiliency in the
     /// presence of potentially adversarial nodes.
     pub kademlia_disjoint_query_paths: bool,
+    /// Byte-per-second limits applied to request-response and notifications protocols.
+    pub bandwidth_limits: BandwidthLimits,
 }
 
 /// Configuration for the transport layer.
@@ -153,6 +156,7 @@ impl Default for NetworkConfiguration {
             request_response_protocols: vec![],
             allow_non_globals_in_dht: false,
             kademlia_disjoint_query_paths: false,
+            bandwidth_limits: BandwidthLimits::default(),
         }
     }
 }
@@ -183,6 +187,7 @@ impl NetworkConfiguration {
             request_response_protocols: vec![],
             allow_non_globals_in_dht: false,
             kademlia_disjoint_query_paths: false,
+            bandwidth_limits: BandwidthLimits::default(),
         }
     }
 
@@ -16,6 +16,7 @@ pub use libp2p::{
 };
 
 //TODO change to private
+pub mod bandwidth;
 pub mod behaviour;
 pub mod business_layer_handle;
 pub mod config;
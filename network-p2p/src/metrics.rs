@@ -8,6 +8,7 @@ use starcoin_metrics::{
 #[derive(Clone)]
 pub struct Metrics {
     // This list is ordered alphabetically
+    pub bandwidth_limited_total: UIntCounterVec,
     pub connections_closed_total: UIntCounterVec,
     pub connections_opened_total: UIntCounterVec,
     pub distinct_peers_connections_closed_total: IntCounter,
@@ -28,6 +29,7 @@ pub struct Metrics {
     pub peerset_num_requested: UIntGauge,
     pub pending_connections: UIntGauge,
     pub pending_connections_errors_total: UIntCounterVec,
+    pub protocol_bytes_total: UIntCounterVec,
     pub requests_in_failure_total: UIntCounterVec,
     pub requests_in_success_total: HistogramVec,
     pub requests_out_failure_total: UIntCounterVec,
@@ -40,6 +42,16 @@ impl Metrics {
     pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
         Ok(Self {
             // This list is ordered alphabetically
+            bandwidth_limited_total: register(
+                UIntCounterVec::new(
+                    Opts::new(
+                        "networkp2p_bandwidth_limited_total",
+                        "Total number of messages dropped by a bandwidth limit, by direction and protocol",
+                    ),
+                    &["direction", "protocol"],
+                )?,
+                registry,
+            )?,
             connections_closed_total: register(
                 UIntCounterVec::new(
                     Opts::new(
@@ -219,6 +231,16 @@ impl Metrics {
                 )?,
                 registry,
             )?,
+            protocol_bytes_total: register(
+                UIntCounterVec::new(
+                    Opts::new(
+                        "networkp2p_protocol_bytes_total",
+                        "Total payload bytes of request-response and notifications protocols, by direction and protocol",
+                    ),
+                    &["direction", "protocol"],
+                )?,
+                registry,
+            )?,
             requests_in_failure_total: register(
                 UIntCounterVec::new(
                     Opts::new(
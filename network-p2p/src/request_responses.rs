@@ -33,6 +33,7 @@
 //!   is used to handle incoming requests.
 //!
 
+use crate::bandwidth::{BandwidthLimiter, Direction};
 use futures::{
     channel::{mpsc, oneshot},
     prelude::*,
@@ -218,6 +219,9 @@ pub struct RequestResponsesBehaviour {
 
     /// Whenever an incoming request arrives, the arrival [`Instant`] is recorded here.
     pending_responses_arrival_time: HashMap<ProtocolRequestId, Instant>,
+
+    /// Accounts the payload bytes of every protocol and enforces the bandwidth limits.
+    bandwidth: BandwidthLimiter,
 }
 
 /// Generated by the response builder and waiting to be processed.
@@ -266,9 +270,16 @@ impl RequestResponsesBehaviour {
             pending_requests: Default::default(),
             pending_responses: Default::default(),
             pending_responses_arrival_time: Default::default(),
+            bandwidth: BandwidthLimiter::default(),
         })
     }
 
+    /// Sets the limiter used to account and limit the bandwidth of the protocols.
+    pub fn with_bandwidth_limiter(mut self, bandwidth: BandwidthLimiter) -> Self {
+        self.bandwidth = bandwidth;
+        self
+    }
+
     /// Initiates sending a request.
     ///
     /// If there is no established connection to the target peer, the behavior is determined by the choice of `connect`.
@@ -285,6 +296,8 @@ impl RequestResponsesBehaviour {
         if let Some((protocol, _)) = self.protocols.get_mut(protocol_name) {
             if protocol.is_connected(target) || connect.should_connect() {
                 let len = request.len();
+                self.bandwidth
+                    .record(target, protocol_name, Direction::Outbound, len);
                 let request_id = protocol.send_request(target, request);
                 let prev_req_id = self.pending_requests.insert(
                     (
@@ -446,7 +459,20 @@ impl NetworkBehaviour for RequestResponsesBehaviour {
                 };
 
                 if let Ok(payload) = result {
-                    if let Some((protocol, _)) = self.protocols.get_mut(&*protocol_name) {
+                    if !self.bandwidth.admit(
+                        &peer,
+                        &protocol_name,
+                        Direction::Outbound,
+                        payload.len(),
+                    ) {
+                        // Dropping `inner_channel` is reported to the remote as a failure, and
+                        // locally through `InboundFailure::ResponseOmission`.
+                        log::debug!(
+                            target: "sub-libp2p",
+                            "Upload limit exceeded, dropping response for {:?} on protocol {:?}",
+                            request_id, protocol_name,
+                        );
+                    } else if let Some((protocol, _)) = self.protocols.get_mut(&*protocol_name) {
                         if protocol.send_response(inner_channel, Ok(payload)).is_err() {
                             // Note: Failure is handled further below when receiving
                             // `InboundFailure` event from `RequestResponse` behaviour.
@@ -517,7 +543,16 @@ impl NetworkBehaviour for RequestResponsesBehaviour {
 
                             // Submit the request to the "response builder" passed by the user at
                             // initialization.
-                            if let Some(resp_builder) = resp_builder {
+                            if !self.bandwidth.admit(
+                                &peer,
+                                protocol,
+                                Direction::Inbound,
+                                request.len(),
+                            ) {
+                                // Over the download limit of the peer or protocol, drop `tx` as
+                                // if the response builder was too busy.
+                                drop(tx);
+                            } else if let Some(resp_builder) = resp_builder {
                                 // If the response builder is too busy, silently drop `tx`. This
                                 // will be reported by the corresponding `RequestResponse` through
                                 // an `InboundFailure::Omission` event.
@@ -570,6 +605,12 @@ impl NetworkBehaviour for RequestResponsesBehaviour {
                                 Some((started, pending_response)) => {
                                     let response_len =
                                         response.as_ref().map(|resp| resp.len()).unwrap_or(0);
+                                    self.bandwidth.record(
+                                        &peer,
+                                        protocol,
+                                        Direction::Inbound,
+                                        response_len,
+                                    );
                                     let delivered = pending_response
                                         .send(response.map_err(|()| RequestFailure::Refused))
                                         .map_err(|_| RequestFailure::Obsolete);
@@ -33,6 +33,7 @@ use std::sync::{
 use std::task::Poll;
 use std::{borrow::Cow, collections::HashSet, io, iter};
 
+use crate::bandwidth::{BandwidthLimiter, BandwidthMetrics, Direction};
 use crate::business_layer_handle::BusinessLayerHandle;
 use crate::config::{Params, TransportConfig};
 use crate::discovery::DiscoveryConfig;
@@ -64,6 +65,7 @@ use libp2p::{
     PeerId,
 };
 use log::{debug, error, info, trace, warn};
+use network_p2p_types::bandwidth::BandwidthStats;
 use network_p2p_types::IfDisconnected;
 use parking_lot::Mutex;
 use sc_peerset::{peersstate, PeersetHandle, ReputationChange};
@@ -103,6 +105,9 @@ pub struct NetworkService {
     local_peer_id: PeerId,
     /// Bandwidth logging system. Can be queried to know the average bandwidth consumed.
     bandwidth: Arc<transport::BandwidthSinks>,
+    /// Per-protocol and per-peer bandwidth accounting, shared with the request-response
+    /// behaviour. Also enforces the configured bandwidth limits.
+    bandwidth_limiter: BandwidthLimiter,
     /// Peerset manager (PSM); manages the reputation of nodes and indicates the network which
     /// nodes it should be connected to or not.
     peerset: PeersetHandle,
@@ -223,6 +228,18 @@ impl<T: BusinessLayerHandle + Send> NetworkWorker<T> {
                 .collect(),
         )?;
 
+        let metrics = params
+            .metrics_registry
+            .as_ref()
+            .and_then(|registry| Metrics::register(registry).ok());
+        let bandwidth_limiter = BandwidthLimiter::new(
+            params.network_config.bandwidth_limits.clone(),
+        )
+        .with_metrics(metrics.as_ref().map(|metrics| BandwidthMetrics {
+            bytes_total: metrics.protocol_bytes_total.clone(),
+            limited_total: metrics.bandwidth_limited_total.clone(),
+        }));
+
         // Build the swarm.
         let (mut swarm, bandwidth): (Swarm<Behaviour<T>>, _) = {
             let user_agent = format!(
@@ -261,6 +278,7 @@ impl<T: BusinessLayerHandle + Send> NetworkWorker<T> {
                 local_public,
                 discovery_config,
                 params.network_config.request_response_protocols,
+                bandwidth_limiter.clone(),
             ) {
                 Ok(behaviour) => behaviour,
                 Err(crate::request_responses::RegisterError::DuplicateProtocol(proto)) => {
@@ -299,12 +317,9 @@ impl<T: BusinessLayerHandle + Send> NetworkWorker<T> {
         let external_addresses = Arc::new(Mutex::new(Vec::new()));
         let peers_notifications_sinks = Arc::new(Mutex::new(HashMap::new()));
 
-        let metrics = params
-            .metrics_registry
-            .as_ref()
-            .and_then(|registry| Metrics::register(registry).ok());
         let service = Arc::new(NetworkService {
             bandwidth,
+            bandwidth_limiter,
             external_addresses,
             num_connected,
             is_major_syncing,
@@ -338,6 +353,11 @@ impl<T: BusinessLayerHandle + Send> NetworkWorker<T> {
         self.service.bandwidth.total_outbound()
     }
 
+    /// Returns the bandwidth consumed so far, split by protocol and by peer.
+    pub fn bandwidth_stats(&self) -> BandwidthStats {
+        self.service.bandwidth_stats()
+    }
+
     /// Returns the number of peers we're connected to.
     pub fn num_connected_peers(&self) -> usize {
         self.network_service
@@ -523,6 +543,14 @@ impl NetworkService {
         // Used later for the metrics report.
         let message_len = message.len();
 
+        if !self
+            .bandwidth_limiter
+            .admit(&target, &protocol_name, Direction::Outbound, message_len)
+        {
+            // Notification silently discarded, as documented.
+            return;
+        }
+
         sink.send_sync_notification(message);
 
         if let Some(notifications_sizes_metric) = self.notifications_sizes_metric.as_ref() {
@@ -618,6 +646,7 @@ impl NetworkService {
         Ok(NotificationSender {
             sink,
             protocol_name: protocol_name.clone(),
+            bandwidth_limiter: self.bandwidth_limiter.clone(),
             notification_size_metric: self
                 .notifications_sizes_metric
                 .as_ref()
@@ -645,6 +674,14 @@ impl NetworkService {
         debug!("finish send broadcast message");
     }
 
+    /// Returns the bandwidth consumed so far, split by protocol and by peer.
+    pub fn bandwidth_stats(&self) -> BandwidthStats {
+        let mut stats = self.bandwidth_limiter.stats();
+        stats.total.inbound = self.bandwidth.total_inbound();
+        stats.total.outbound = self.bandwidth.total_outbound();
+        stats
+    }
+
     pub async fn is_connected(&self, address: PeerId) -> bool {
         let (tx, rx) = oneshot::channel();
         let _ = self
@@ -886,6 +923,9 @@ pub struct NotificationSender {
     /// Name of the protocol on the wire.
     protocol_name: Cow<'static, str>,
 
+    /// Accounts the notifications and enforces the upload limits.
+    bandwidth_limiter: BandwidthLimiter,
+
     /// Field extracted from the [`Metrics`] struct and necessary to report the
     /// notifications-related metrics.
     notification_size_metric: Option<Histogram>,
@@ -901,6 +941,7 @@ impl NotificationSender {
             },
             peer_id: self.sink.peer_id(),
             protocol_name: &self.protocol_name,
+            bandwidth_limiter: &self.bandwidth_limiter,
             notification_size_metric: self.notification_size_metric.clone(),
         })
     }
@@ -917,6 +958,9 @@ pub struct NotificationSenderReady<'a> {
     /// Name of the protocol on the wire.
     protocol_name: &'a Cow<'static, str>,
 
+    /// Accounts the notifications and enforces the upload limits.
+    bandwidth_limiter: &'a BandwidthLimiter,
+
     /// Field extracted from the [`Metrics`] struct and necessary to report the
     /// notifications-related metrics.
     notification_size_metric: Option<Histogram>,
@@ -927,6 +971,17 @@ impl NotificationSenderReady<'_> {
     pub fn send(self, notification: impl Into<Vec<u8>>) -> Result<(), NotificationSenderError> {
         let notification = notification.into();
 
+        if !self.bandwidth_limiter.admit(
+            self.peer_id,
+            self.protocol_name,
+            Direction::Outbound,
+            notification.len(),
+        ) {
+            // As for `write_notification`, delivery is not guaranteed: over the upload limit
+            // the notification is silently discarded.
+            return Ok(());
+        }
+
         if let Some(notification_size_metric) = &self.notification_size_metric {
             notification_size_metric.observe(notification.len() as f64);
         }
@@ -1293,6 +1348,20 @@ impl<T: BusinessLayerHandle + Send> Future for NetworkWorker<T> {
                     remote,
                     messages,
                 })) => {
+                    let messages: Vec<_> = messages
+                        .into_iter()
+                        .filter(|(protocol, message)| {
+                            this.service.bandwidth_limiter.admit(
+                                &remote,
+                                protocol,
+                                Direction::Inbound,
+                                message.len(),
+                            )
+                        })
+                        .collect();
+                    if messages.is_empty() {
+                        continue;
+                    }
                     if let Some(metrics) = this.metrics.as_ref() {
                         for (protocol, message) in &messages {
                             info!(
@@ -1372,6 +1441,9 @@ impl<T: BusinessLayerHandle + Send> Future for NetworkWorker<T> {
                             metrics.distinct_peers_connections_closed_total.inc();
                         }
                     }
+                    if num_established == 0 {
+                        this.service.bandwidth_limiter.remove_peer(&peer_id);
+                    }
                 }
                 Poll::Ready(SwarmEvent::NewListenAddr { address, .. }) => {
                     trace!(target: "sub-libp2p", "Libp2p => NewListenAddr({})", address)
@@ -0,0 +1,30 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bandwidth accounting snapshot, for diagnostic purposes.
+
+use schemars::{self, JsonSchema};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Number of bytes (or messages) moved in each direction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ByteCounter {
+    pub inbound: u64,
+    pub outbound: u64,
+}
+
+/// Returns the bandwidth consumed by the node, split by protocol and by peer.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BandwidthStats {
+    /// Total bytes received and sent on the transport, including protocol overhead.
+    pub total: ByteCounter,
+    /// Payload bytes of request-response and notification protocols, by protocol name.
+    pub protocols: BTreeMap<String, ByteCounter>,
+    /// Payload bytes exchanged with each currently connected peer.
+    pub peers: BTreeMap<String, ByteCounter>,
+    /// Number of messages dropped because a bandwidth limit was exceeded.
+    pub dropped_messages: ByteCounter,
+}
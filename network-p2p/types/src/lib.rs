@@ -5,6 +5,7 @@ use libp2p::futures::channel::oneshot;
 use std::borrow::Cow;
 use std::fmt;
 
+pub mod bandwidth;
 pub mod multi_address_with_peer_id;
 pub mod network_state;
 pub mod peer_id;
@@ -149,6 +149,27 @@ impl RpcRequest for GetTxnsWithHash {
     }
 }
 
+/// Get the transactions of a block by their index in the block body, the VM1 transactions come
+/// before the VM2 ones, in the same order as the compact block short ids.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetBlockTxns {
+    pub block_id: HashValue,
+    pub indexes: Vec<u64>,
+}
+
+impl RpcRequest for GetBlockTxns {
+    fn verify(&self) -> Result<()> {
+        if self.indexes.len() as u64 > MAX_TXN_REQUEST_SIZE {
+            return Err(NetRpcError::new(
+                RpcErrorCode::BadRequest,
+                format!("max_size is too big > {}", MAX_TXN_REQUEST_SIZE),
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GetStateWithProof {
     pub state_root: HashValue,
@@ -220,6 +241,13 @@ pub trait NetworkRpc: Sized + Send + Sync + 'static {
         block_id: HashValue,
     ) -> BoxFuture<Result<Option<Vec<StcTransactionInfo>>>>;
 
+    ///Get txns of a block by index, return None if the block is unknown
+    fn get_block_txns(
+        &self,
+        peer_id: PeerId,
+        req: GetBlockTxns,
+    ) -> BoxFuture<Result<Option<Vec<MultiSignedUserTransaction>>>>;
+
     fn get_headers_by_number(
         &self,
         peer_id: PeerId,
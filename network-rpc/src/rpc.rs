@@ -12,9 +12,9 @@ use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::error;
 use starcoin_network_rpc_api::{
     gen_server, BlockBody, GetAbsentBlockRequest, GetAbsentBlockResponse,
-    GetAccumulatorNodeByNodeHash, GetBlockHeadersByNumber, GetBlockIds, GetRangeInLocationRequest,
-    GetRangeInLocationResponse, GetTxnsWithHash, GetTxnsWithSize, Ping, RpcRequest,
-    MAX_BLOCK_HEADER_REQUEST_SIZE, MAX_BLOCK_INFO_REQUEST_SIZE, MAX_BLOCK_REQUEST_SIZE,
+    GetAccumulatorNodeByNodeHash, GetBlockHeadersByNumber, GetBlockIds, GetBlockTxns,
+    GetRangeInLocationRequest, GetRangeInLocationResponse, GetTxnsWithHash, GetTxnsWithSize, Ping,
+    RpcRequest, MAX_BLOCK_HEADER_REQUEST_SIZE, MAX_BLOCK_INFO_REQUEST_SIZE, MAX_BLOCK_REQUEST_SIZE,
     MAX_TXN_REQUEST_SIZE,
 };
 use starcoin_service_registry::ServiceRef;
@@ -123,6 +123,50 @@ impl gen_server::NetworkRpc for NetworkRpcImpl {
         Box::pin(fut)
     }
 
+    fn get_block_txns(
+        &self,
+        _peer_id: PeerId,
+        req: GetBlockTxns,
+    ) -> BoxFuture<'_, Result<Option<Vec<MultiSignedUserTransaction>>>> {
+        let chain_service = self.chain_service.clone();
+        let fut = async move {
+            req.verify()?;
+            let block = match chain_service
+                .get_blocks(vec![req.block_id])
+                .await?
+                .pop()
+                .flatten()
+            {
+                Some(block) => block,
+                None => return Ok(None),
+            };
+            let txns: Vec<MultiSignedUserTransaction> = block
+                .body
+                .transactions
+                .into_iter()
+                .map(Into::into)
+                .chain(block.body.transactions2.into_iter().map(Into::into))
+                .collect();
+            let mut result = Vec::with_capacity(req.indexes.len());
+            for index in req.indexes {
+                match txns.get(index as usize) {
+                    Some(txn) => result.push(txn.clone()),
+                    None => {
+                        return Err(NetRpcError::client_err(format!(
+                            "txn index {} out of block {} txn len {}",
+                            index,
+                            req.block_id,
+                            txns.len()
+                        ))
+                        .into())
+                    }
+                }
+            }
+            Ok(Some(result))
+        };
+        Box::pin(fut)
+    }
+
     fn get_headers_by_number(
         &self,
         _peer_id: PeerId,
@@ -10,8 +10,8 @@ use network_types::peer_info::PeerInfo;
 use serde::{Deserialize, Serialize};
 use starcoin_crypto::HashValue;
 use starcoin_service_registry::ServiceRequest;
-use starcoin_types::block::BlockInfo;
-use starcoin_types::compact_block::CompactBlock;
+use starcoin_types::block::{BlockHeader, BlockInfo};
+use starcoin_types::compact_block::{CompactBlock, CompactBlockV2};
 use starcoin_types::multi_transaction::MultiSignedUserTransaction;
 use starcoin_types::startup_info::ChainInfo;
 use std::borrow::Cow;
@@ -21,6 +21,7 @@ use std::result::Result::Ok;
 pub const TXN_PROTOCOL_NAME: &str = "/starcoin/txn/1";
 pub const BLOCK_PROTOCOL_NAME: &str = "/starcoin/block/1";
 pub const ANNOUNCEMENT_PROTOCOL_NAME: &str = "/starcoin/announcement/1";
+pub const COMPACT_BLOCK_PROTOCOL_NAME: &str = "/starcoin/cmpctblock/1";
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct TransactionsMessage {
@@ -65,6 +66,38 @@ impl Sample for CompactBlockMessage {
     }
 }
 
+/// Message of sending or receive block notification with salted short transaction ids.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct CompactBlockV2Message {
+    pub compact_block: CompactBlockV2,
+    pub block_info: BlockInfo,
+}
+
+impl CompactBlockV2Message {
+    pub fn new(compact_block: CompactBlockV2, block_info: BlockInfo) -> Self {
+        Self {
+            compact_block,
+            block_info,
+        }
+    }
+}
+
+impl From<&CompactBlockMessage> for CompactBlockV2Message {
+    /// Salt the short ids with a random nonce, so every node relays different short ids.
+    fn from(msg: &CompactBlockMessage) -> Self {
+        Self::new(
+            CompactBlockV2::from_compact_block(&msg.compact_block, rand::random()),
+            msg.block_info.clone(),
+        )
+    }
+}
+
+impl Sample for CompactBlockV2Message {
+    fn sample() -> Self {
+        Self::new(CompactBlockV2::sample(), BlockInfo::sample())
+    }
+}
+
 pub enum AnnouncementType {
     Txn,
 }
@@ -123,6 +156,7 @@ pub enum NotificationMessage {
     Transactions(TransactionsMessage),
     CompactBlock(Box<CompactBlockMessage>),
     Announcement(Announcement),
+    CompactBlockV2(Box<CompactBlockV2Message>),
 }
 
 impl NotificationMessage {
@@ -137,6 +171,9 @@ impl NotificationMessage {
             ANNOUNCEMENT_PROTOCOL_NAME => {
                 NotificationMessage::Announcement(Announcement::decode(bytes)?)
             }
+            COMPACT_BLOCK_PROTOCOL_NAME => NotificationMessage::CompactBlockV2(Box::new(
+                CompactBlockV2Message::decode(bytes)?,
+            )),
             unknown_protocol => bail!(
                 "Unknown protocol {}'s message: {}",
                 unknown_protocol,
@@ -152,6 +189,9 @@ impl NotificationMessage {
             NotificationMessage::Announcement(msg) => {
                 (ANNOUNCEMENT_PROTOCOL_NAME.into(), msg.encode()?)
             }
+            NotificationMessage::CompactBlockV2(msg) => {
+                (COMPACT_BLOCK_PROTOCOL_NAME.into(), msg.encode()?)
+            }
         })
     }
 
@@ -160,6 +200,7 @@ impl NotificationMessage {
             Self::Transactions(_) => TXN_PROTOCOL_NAME.into(),
             Self::CompactBlock(_) => BLOCK_PROTOCOL_NAME.into(),
             Self::Announcement(_) => ANNOUNCEMENT_PROTOCOL_NAME.into(),
+            Self::CompactBlockV2(_) => COMPACT_BLOCK_PROTOCOL_NAME.into(),
         }
     }
 
@@ -169,6 +210,7 @@ impl NotificationMessage {
             BLOCK_PROTOCOL_NAME.into(),
             TXN_PROTOCOL_NAME.into(),
             ANNOUNCEMENT_PROTOCOL_NAME.into(),
+            COMPACT_BLOCK_PROTOCOL_NAME.into(),
         ]
     }
 
@@ -192,6 +234,26 @@ impl NotificationMessage {
             _ => None,
         }
     }
+
+    pub fn into_compact_block_v2(self) -> Option<CompactBlockV2Message> {
+        match self {
+            NotificationMessage::CompactBlockV2(message) => Some(*message),
+            _ => None,
+        }
+    }
+
+    /// Header and block info carried by a compact block notification.
+    pub fn compact_block_status(&self) -> Option<(&BlockHeader, &BlockInfo)> {
+        match self {
+            NotificationMessage::CompactBlock(message) => {
+                Some((&message.compact_block.header, &message.block_info))
+            }
+            NotificationMessage::CompactBlockV2(message) => {
+                Some((&message.compact_block.header, &message.block_info))
+            }
+            _ => None,
+        }
+    }
 }
 
 /// Message for send or receive from peer
@@ -219,6 +281,13 @@ impl PeerMessage {
         )
     }
 
+    pub fn new_compact_block_v2(peer_id: PeerId, compact_block: CompactBlockV2Message) -> Self {
+        Self::new(
+            peer_id,
+            NotificationMessage::CompactBlockV2(Box::new(compact_block)),
+        )
+    }
+
     pub fn new_announcement(peer_id: PeerId, announcement: Announcement) -> Self {
         Self::new(peer_id, NotificationMessage::Announcement(announcement))
     }
@@ -237,6 +306,13 @@ impl PeerMessage {
             .map(|message| PeerCompactBlockMessage { peer_id, message })
     }
 
+    pub fn into_compact_block_v2(self) -> Option<PeerCompactBlockV2Message> {
+        let peer_id = self.peer_id;
+        self.notification
+            .into_compact_block_v2()
+            .map(|message| PeerCompactBlockV2Message { peer_id, message })
+    }
+
     pub fn into_announcement(self) -> Option<PeerAnnouncementMessage> {
         let peer_id = self.peer_id;
         self.notification
@@ -289,6 +365,26 @@ impl Into<PeerMessage> for PeerCompactBlockMessage {
     }
 }
 
+/// Message for combine PeerId and CompactBlockV2Message
+#[derive(Clone, Debug)]
+pub struct PeerCompactBlockV2Message {
+    pub peer_id: PeerId,
+    pub message: CompactBlockV2Message,
+}
+
+impl PeerCompactBlockV2Message {
+    pub fn new(peer_id: PeerId, message: CompactBlockV2Message) -> Self {
+        Self { peer_id, message }
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl Into<PeerMessage> for PeerCompactBlockV2Message {
+    fn into(self) -> PeerMessage {
+        PeerMessage::new_compact_block_v2(self.peer_id, self.message)
+    }
+}
+
 /// Message for combine PeerId and TransactionsMessage
 #[derive(Clone, Debug)]
 pub struct PeerAnnouncementMessage {
@@ -1,9 +1,20 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::peer_provider::PeerProvider;
 use anyhow::Result;
-use starcoin_metrics::{register, Opts, Registry, UIntGauge, UIntGaugeVec};
+use network_p2p_types::{peer_id::PeerId, ReputationChange};
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use starcoin_logger::prelude::*;
+use starcoin_metrics::{
+    register, Opts, Registry, UIntCounter, UIntCounterVec, UIntGauge, UIntGaugeVec,
+};
+use std::collections::{BTreeMap, HashMap};
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 pub struct ScoreCounter {
     score: AtomicU64,
@@ -131,6 +142,8 @@ impl Score<BlockBroadcastEntry> for LinearScore {
 pub struct PeerScoreMetrics {
     pub peer_score: UIntGaugeVec,
     pub total_score: UIntGauge,
+    pub peer_rpc_outcome: UIntCounterVec,
+    pub peer_evicted_total: UIntCounter,
 }
 
 impl PeerScoreMetrics {
@@ -141,11 +154,244 @@ impl PeerScoreMetrics {
         )?;
         let total_score =
             UIntGauge::with_opts(Opts::new("total_score", "total peer score".to_string()))?;
+        let peer_rpc_outcome = UIntCounterVec::new(
+            Opts::new(
+                "peer_rpc_outcome",
+                "outcome of rpc calls to peers, by method".to_string(),
+            ),
+            &["method", "outcome"],
+        )?;
+        let peer_evicted_total = UIntCounter::with_opts(Opts::new(
+            "peer_evicted_total",
+            "total peers evicted for bad rpc behaviour".to_string(),
+        ))?;
         let peer_score = register(peer_score, registry)?;
         let total_score = register(total_score, registry)?;
+        let peer_rpc_outcome = register(peer_rpc_outcome, registry)?;
+        let peer_evicted_total = register(peer_evicted_total, registry)?;
         Ok(Self {
             peer_score,
             total_score,
+            peer_rpc_outcome,
+            peer_evicted_total,
         })
     }
 }
+
+/// Rpc response slower than this is still a success, but earns less reputation.
+const SLOW_RPC_LATENCY: Duration = Duration::from_secs(3);
+/// The quality score is clamped in `[-MAX_QUALITY_SCORE, MAX_QUALITY_SCORE]`,
+/// so a long good history can not shield a peer which turns bad.
+const MAX_QUALITY_SCORE: i64 = 2_000;
+pub const DEFAULT_EVICTION_THRESHOLD: i64 = -1_000;
+
+/// The result of a single rpc call to a peer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RpcOutcome {
+    Success(Duration),
+    Timeout,
+    /// The request failed, such as not connected or the remote returned an error.
+    Failure,
+    /// The peer returned a response which does not pass verification.
+    InvalidData,
+}
+
+impl RpcOutcome {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Success(_) => "success",
+            Self::Timeout => "timeout",
+            Self::Failure => "failure",
+            Self::InvalidData => "invalid",
+        }
+    }
+
+    /// The reputation change reported to the peerset for this outcome.
+    pub fn reputation(&self) -> ReputationChange {
+        match self {
+            Self::Success(latency) if *latency <= SLOW_RPC_LATENCY => {
+                ReputationChange::new(10, "rpc: success")
+            }
+            Self::Success(_) => ReputationChange::new(2, "rpc: slow success"),
+            Self::Timeout => ReputationChange::new(-100, "rpc: timeout"),
+            Self::Failure => ReputationChange::new(-50, "rpc: failure"),
+            Self::InvalidData => ReputationChange::new(-500, "rpc: invalid data"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
+pub struct RpcMethodQuality {
+    pub success: u64,
+    pub timeout: u64,
+    pub failure: u64,
+    pub invalid: u64,
+    /// Sum of the latency of success calls, in milliseconds.
+    pub total_latency_ms: u64,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+}
+
+impl RpcMethodQuality {
+    pub fn calls(&self) -> u64 {
+        self.success
+            .saturating_add(self.timeout)
+            .saturating_add(self.failure)
+            .saturating_add(self.invalid)
+    }
+
+    pub fn avg_latency_ms(&self) -> u64 {
+        self.total_latency_ms
+            .checked_div(self.success)
+            .unwrap_or_default()
+    }
+
+    fn record(&mut self, outcome: RpcOutcome) {
+        match outcome {
+            RpcOutcome::Success(latency) => {
+                self.success = self.success.saturating_add(1);
+                self.total_latency_ms = self
+                    .total_latency_ms
+                    .saturating_add(latency.as_millis() as u64);
+            }
+            RpcOutcome::Timeout => self.timeout = self.timeout.saturating_add(1),
+            RpcOutcome::Failure => self.failure = self.failure.saturating_add(1),
+            RpcOutcome::InvalidData => self.invalid = self.invalid.saturating_add(1),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct PeerQualityView {
+    pub peer_id: PeerId,
+    pub score: i64,
+    /// How many times this peer has been evicted.
+    pub evictions: u64,
+    pub methods: BTreeMap<String, RpcMethodQuality>,
+}
+
+#[derive(Default)]
+struct PeerQualityEntry {
+    score: i64,
+    evictions: u64,
+    methods: BTreeMap<String, RpcMethodQuality>,
+}
+
+/// Tracks the rpc behaviour of every peer, shared by all the rpc clients of a node.
+/// Every outcome is fed into the peerset as a `ReputationChange`, and a peer whose
+/// quality score drops to the eviction threshold is disconnected.
+#[derive(Clone)]
+pub struct PeerQuality {
+    peers: Arc<Mutex<HashMap<PeerId, PeerQualityEntry>>>,
+    eviction_threshold: i64,
+    reporter: Option<Arc<dyn PeerProvider>>,
+    metrics: Option<PeerScoreMetrics>,
+}
+
+impl PeerQuality {
+    pub fn new(eviction_threshold: i64, metrics: Option<PeerScoreMetrics>) -> Self {
+        Self {
+            peers: Arc::new(Mutex::new(HashMap::new())),
+            eviction_threshold,
+            reporter: None,
+            metrics,
+        }
+    }
+
+    pub fn with_reporter(mut self, reporter: Arc<dyn PeerProvider>) -> Self {
+        self.reporter = Some(reporter);
+        self
+    }
+
+    /// Record the outcome of a rpc call, return true if the peer is evicted.
+    pub fn record(&self, peer_id: &PeerId, method: &str, outcome: RpcOutcome) -> bool {
+        let reputation = outcome.reputation();
+        let evicted = {
+            let mut peers = self.peers.lock();
+            let entry = peers.entry(peer_id.clone()).or_default();
+            entry
+                .methods
+                .entry(method.to_string())
+                .or_default()
+                .record(outcome);
+            entry.score = entry
+                .score
+                .saturating_add(reputation.value as i64)
+                .clamp(-MAX_QUALITY_SCORE, MAX_QUALITY_SCORE);
+            if entry.score <= self.eviction_threshold {
+                entry.score = 0;
+                entry.evictions = entry.evictions.saturating_add(1);
+                true
+            } else {
+                false
+            }
+        };
+        if let Some(metrics) = self.metrics.as_ref() {
+            metrics
+                .peer_rpc_outcome
+                .with_label_values(&[method, outcome.name()])
+                .inc();
+        }
+        if let Some(reporter) = self.reporter.as_ref() {
+            reporter.report_peer(peer_id.clone(), reputation);
+            if evicted {
+                warn!(
+                    "[peer-quality] Evict peer {} for persistently bad rpc behaviour, last method: {}, outcome: {}",
+                    peer_id,
+                    method,
+                    outcome.name()
+                );
+                reporter.report_peer(
+                    peer_id.clone(),
+                    ReputationChange::new_fatal("rpc: persistently bad peer"),
+                );
+            }
+        }
+        if evicted {
+            if let Some(metrics) = self.metrics.as_ref() {
+                metrics.peer_evicted_total.inc();
+            }
+        }
+        evicted
+    }
+
+    /// Record the bytes exchanged with a peer by a rpc call.
+    pub fn record_bandwidth(&self, peer_id: &PeerId, method: &str, sent: u64, received: u64) {
+        let mut peers = self.peers.lock();
+        let quality = peers
+            .entry(peer_id.clone())
+            .or_default()
+            .methods
+            .entry(method.to_string())
+            .or_default();
+        quality.bytes_sent = quality.bytes_sent.saturating_add(sent);
+        quality.bytes_received = quality.bytes_received.saturating_add(received);
+    }
+
+    pub fn score(&self, peer_id: &PeerId) -> Option<i64> {
+        self.peers.lock().get(peer_id).map(|entry| entry.score)
+    }
+
+    /// The scoreboard of all known peers, sorted by score (highest first).
+    pub fn scoreboard(&self) -> Vec<PeerQualityView> {
+        let mut views: Vec<PeerQualityView> = self
+            .peers
+            .lock()
+            .iter()
+            .map(|(peer_id, entry)| PeerQualityView {
+                peer_id: peer_id.clone(),
+                score: entry.score,
+                evictions: entry.evictions,
+                methods: entry.methods.clone(),
+            })
+            .collect();
+        views.sort_by(|a, b| b.score.cmp(&a.score));
+        views
+    }
+}
+
+impl Default for PeerQuality {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVICTION_THRESHOLD, None)
+    }
+}
@@ -2,13 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::peer_provider::{PeerSelector, PeerStrategy};
-use crate::peer_score::{InverseScore, Score};
+use crate::peer_score::{InverseScore, PeerQuality, RpcOutcome, Score};
 use network_p2p_types::peer_id::PeerId;
 use network_types::peer_info::PeerInfo;
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_types::startup_info::{ChainInfo, ChainStatus};
 use starcoin_types::U256;
+use std::time::Duration;
 
 #[test]
 fn test_inverse_score() {
@@ -96,3 +97,35 @@ fn test_better_peer() {
             })
     }
 }
+
+#[test]
+fn test_peer_quality_eviction() {
+    let quality = PeerQuality::new(-300, None);
+    let good_peer = PeerId::random();
+    let bad_peer = PeerId::random();
+
+    assert!(!quality.record(
+        &good_peer,
+        "get_blocks",
+        RpcOutcome::Success(Duration::from_millis(100))
+    ));
+    quality.record_bandwidth(&good_peer, "get_blocks", 10, 1000);
+    assert!(!quality.record(&bad_peer, "get_blocks", RpcOutcome::Timeout));
+    assert!(!quality.record(&bad_peer, "get_blocks", RpcOutcome::Timeout));
+    assert!(quality.record(&bad_peer, "get_block_ids", RpcOutcome::Timeout));
+    // the score is reset after eviction
+    assert_eq!(quality.score(&bad_peer), Some(0));
+
+    let scoreboard = quality.scoreboard();
+    assert_eq!(scoreboard.len(), 2);
+    let good = &scoreboard[0];
+    assert_eq!(good.peer_id, good_peer);
+    assert_eq!(good.evictions, 0);
+    let get_blocks = good.methods.get("get_blocks").unwrap();
+    assert_eq!(get_blocks.success, 1);
+    assert_eq!(get_blocks.bytes_received, 1000);
+    let bad = &scoreboard[1];
+    assert_eq!(bad.evictions, 1);
+    assert_eq!(bad.methods.get("get_blocks").unwrap().timeout, 2);
+    assert_eq!(bad.methods.get("get_block_ids").unwrap().timeout, 1);
+}
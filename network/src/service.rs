@@ -13,8 +13,9 @@ use futures::{
     FutureExt,
 };
 use network_api::messages::{
-    AnnouncementType, BanPeer, GetPeerById, GetPeerSet, GetSelfPeer, NotificationMessage,
-    PeerEvent, PeerMessage, PeerReputations, ReportReputation, TransactionsMessage,
+    AnnouncementType, BanPeer, CompactBlockV2Message, GetPeerById, GetPeerSet, GetSelfPeer,
+    NotificationMessage, PeerEvent, PeerMessage, PeerReputations, ReportReputation,
+    TransactionsMessage, ANNOUNCEMENT_PROTOCOL_NAME, COMPACT_BLOCK_PROTOCOL_NAME,
 };
 use network_api::{
     BroadcastProtocolFilter, NetworkActor, PeerId, PeerInfo, PeerMessageHandler, RpcInfo,
@@ -555,9 +556,12 @@ impl Inner {
                         }
                     }
                 }
-                NotificationMessage::CompactBlock(compact_block_message) => {
-                    let block_header = compact_block_message.compact_block.header.clone();
-                    let total_difficulty = compact_block_message.block_info.total_difficulty;
+                NotificationMessage::CompactBlock(_) | NotificationMessage::CompactBlockV2(_) => {
+                    let (block_header, block_info) = notification
+                        .compact_block_status()
+                        .expect("notification is a compact block");
+                    let block_header = block_header.clone();
+                    let total_difficulty = block_info.total_difficulty;
                     let block_id = block_header.id();
                     debug!(
                         "Receive new compact block from {:?} with hash {:?}",
@@ -568,10 +572,9 @@ impl Inner {
                         total_difficulty, peer_info
                     );
                     peer_info.known_blocks.insert(block_id, ());
-                    peer_info.peer_info.update_chain_status(ChainStatus::new(
-                        block_header,
-                        compact_block_message.block_info.clone(),
-                    ));
+                    peer_info
+                        .peer_info
+                        .update_chain_status(ChainStatus::new(block_header, block_info.clone()));
 
                     if self.self_peer.known_blocks.contains_key(&block_id) {
                         None
@@ -592,9 +595,9 @@ impl Inner {
                             let mut fresh_ids = Vec::new();
                             for txn_id in announcement.clone().ids() {
                                 peer_info.known_transactions.insert(txn_id, ());
-
+                                // The txn is known once its body arrives, until then the
+                                // announcements of other peers are kept to pull it again.
                                 if !self.self_peer.known_transactions.contains_key(&txn_id) {
-                                    self.self_peer.known_transactions.insert(txn_id, ());
                                     fresh_ids.push(txn_id);
                                 };
                             }
@@ -686,6 +689,14 @@ impl Inner {
         peer_id: PeerId,
         notification: NotificationMessage,
     ) -> Option<(Cow<'static, str>, Vec<u8>)> {
+        let notification = match notification {
+            NotificationMessage::CompactBlock(msg)
+                if self.is_supported(&peer_id, COMPACT_BLOCK_PROTOCOL_NAME.into()) =>
+            {
+                NotificationMessage::CompactBlockV2(Box::new(msg.as_ref().into()))
+            }
+            notification => notification,
+        };
         let (protocol_name, data) = notification
             .encode_notification()
             .expect("Encode notification message should ok");
@@ -702,10 +713,15 @@ impl Inner {
                     self.self_peer.known_transactions.insert(txn.id(), ());
                 })
             }
-            NotificationMessage::CompactBlock(block) => {
-                self.self_peer
-                    .known_blocks
-                    .insert(block.compact_block.header.id(), ());
+            NotificationMessage::CompactBlock(_) | NotificationMessage::CompactBlockV2(_) => {
+                let (header, _) = notification
+                    .compact_block_status()
+                    .expect("notification is a compact block");
+                let block_id = header.id();
+                self.self_peer.known_blocks.insert(block_id, ());
+                if let Some(peer) = self.peers.get_mut(&peer_id) {
+                    peer.known_blocks.insert(block_id, ());
+                }
             }
             NotificationMessage::Announcement(announcement) => {
                 if announcement.is_txn() {
@@ -779,14 +795,26 @@ impl Inner {
                     filtered_peer_ids.iter(),
                 );
                 let peers_send_message = selected_peers.len();
+                // Peers supporting short id compact blocks get the salted message instead.
+                let mut message_v2 = None;
                 for peer_id in &selected_peers {
+                    let (protocol_name, message) =
+                        if self.is_supported(peer_id, COMPACT_BLOCK_PROTOCOL_NAME.into()) {
+                            message_v2
+                                .get_or_insert_with(|| {
+                                    NotificationMessage::CompactBlockV2(Box::new(
+                                        CompactBlockV2Message::from(msg.as_ref()),
+                                    ))
+                                    .encode_notification()
+                                    .expect("Encode notification message should ok")
+                                })
+                                .clone()
+                        } else {
+                            (protocol_name.clone(), message.clone())
+                        };
                     let peer = self.peers.get_mut(peer_id).expect("peer should exists");
                     peer.known_blocks.insert(id, ());
-                    prepare_to_broadcast.push((
-                        protocol_name.clone(),
-                        peer_id.clone(),
-                        message.clone(),
-                    ));
+                    prepare_to_broadcast.push((protocol_name, peer_id.clone(), message));
                 }
                 debug!(
                     "[network] broadcast new compact block message {:?} to {} peers, total_peers: {}, peers_after_known_hash_filter: {}, peers_after_protocol_filter: {}",
@@ -801,20 +829,37 @@ impl Inner {
                     self.self_peer.known_transactions.insert(txn.id(), ());
                 });
                 let origin_txn_len = msg.txns.len();
+                let max_announcement_size =
+                    self.config.network.max_txn_announcement_size() as usize;
                 let mut send_peer_count: usize = 0;
-                let selected_peers = select_random_peers(
-                    self.config.network.min_peers_to_propagate()
-                        ..=self.config.network.max_peers_to_propagate(),
-                    self.peers
-                        .keys()
-                        .filter(|id| self.is_supported(id, protocol_name.clone()))
-                        .cloned()
-                        .collect::<Vec<_>>()
-                        .iter(),
-                );
+                // Only a few peers get the full transactions, the others get the hashes announced
+                // and pull the transactions they do not know yet.
+                let full_broadcast_peers = self.config.network.txn_full_broadcast_peers();
+                let selected_peers = if full_broadcast_peers == 0 {
+                    vec![]
+                } else {
+                    select_random_peers(
+                        full_broadcast_peers..=full_broadcast_peers,
+                        self.peers
+                            .keys()
+                            .filter(|id| self.is_supported(id, protocol_name.clone()))
+                            .cloned()
+                            .collect::<Vec<_>>()
+                            .iter(),
+                    )
+                };
                 let peers = self.peers.keys().cloned().collect::<Vec<_>>();
                 for peer_id in peers {
-                    let is_not_announcement = selected_peers.contains(&peer_id);
+                    // Peers not supporting announcement fallback to the full transactions.
+                    let is_full_broadcast = selected_peers.contains(&peer_id)
+                        || !self.is_supported(&peer_id, ANNOUNCEMENT_PROTOCOL_NAME.into());
+                    if is_full_broadcast && !self.is_supported(&peer_id, protocol_name.clone()) {
+                        debug!(
+                            "[network]remote peer: {:?} not support broadcast protocol :{:?}",
+                            peer_id, protocol_name
+                        );
+                        continue;
+                    }
                     let peer = self.peers.get_mut(&peer_id).expect("peer should exists");
                     let txns_unhandled = msg
                         .txns
@@ -839,50 +884,52 @@ impl Inner {
                     }
                     let txn_unhandled_ids: Vec<HashValue> =
                         txns_unhandled.iter().map(|txn| txn.id()).collect();
-                    // if txn after known_transactions filter is same length with origin, just send origin message for avoid encode data again.
-                    let (real_protocol_name, data) =
-                        if txns_unhandled.len() == origin_txn_len && is_not_announcement {
-                            (protocol_name.clone(), origin_message.clone())
-                        } else if is_not_announcement {
+                    info!(
+                        "[network] prepared to broadcast_transaction peer: {} full: {} idx: {:?}",
+                        peer_id, is_full_broadcast, txn_unhandled_ids,
+                    );
+                    send_peer_count = send_peer_count.saturating_add(1);
+                    if is_full_broadcast {
+                        // if txn after known_transactions filter is same length with origin, just send origin message for avoid encode data again.
+                        let data = if txns_unhandled.len() == origin_txn_len {
+                            origin_message.clone()
+                        } else {
                             NotificationMessage::Transactions(TransactionsMessage::new(
                                 txns_unhandled.into_iter().cloned().collect(),
                             ))
                             .encode_notification()
                             .expect("Encode notification Transactions message should ok")
-                        } else {
-                            NotificationMessage::Announcement(Announcement::new(
-                                AnnouncementType::Txn,
-                                txn_unhandled_ids.clone(),
-                            ))
-                            .encode_notification()
-                            .expect("Encode notification Announcement message should ok")
+                            .1
                         };
-
-                    if !is_not_announcement
-                        && !self.is_supported(&peer_id, real_protocol_name.clone())
-                    {
-                        debug!(
-                            "[network]remote peer: {:?} not support broadcast protocol :{:?}",
-                            peer_id, real_protocol_name
-                        );
-                        continue;
+                        prepare_to_broadcast.push((protocol_name.clone(), peer_id, data));
+                    } else {
+                        for ids in txn_unhandled_ids.chunks(max_announcement_size) {
+                            let (announcement_protocol, data) = NotificationMessage::Announcement(
+                                Announcement::new(AnnouncementType::Txn, ids.to_vec()),
+                            )
+                            .encode_notification()
+                            .expect("Encode notification Announcement message should ok");
+                            prepare_to_broadcast.push((
+                                announcement_protocol,
+                                peer_id.clone(),
+                                data,
+                            ));
+                        }
                     }
-                    info!("[network] prepared to broadcast_transaction with protocol:{} peer: {} idx: {:?}",
-                        real_protocol_name, peer_id, txn_unhandled_ids,
-                    );
-
-                    send_peer_count = send_peer_count.saturating_add(1);
-                    prepare_to_broadcast.push((real_protocol_name, peer_id, data));
                 }
                 debug!(
-                    "[network] broadcast new {} transactions to {} peers",
+                    "[network] broadcast new {} transactions to {} peers, {} of them in full",
                     msg.txns.len(),
-                    send_peer_count
+                    send_peer_count,
+                    selected_peers.len()
                 );
             }
             NotificationMessage::Announcement(_msg) => {
                 error!("[network] can not broadcast announcement message directly.");
             }
+            NotificationMessage::CompactBlockV2(_msg) => {
+                error!("[network] can not broadcast compact block v2 message directly, broadcast the compact block instead.");
+            }
         }
         prepare_to_broadcast
     }
@@ -14,6 +14,7 @@ use network_api::{
     NetworkService, PeerId, PeerInfo, PeerProvider, ReputationChange, SupportedRpcProtocol,
 };
 use network_p2p_core::{NetRpcError, RawRpcClient};
+use network_p2p_types::bandwidth::BandwidthStats;
 use network_p2p_types::network_state::NetworkState;
 use network_p2p_types::{IfDisconnected, Multiaddr, RequestFailure};
 use starcoin_service_registry::ServiceRef;
@@ -139,6 +140,10 @@ impl NetworkServiceRef {
             .map_err(|_| format_err!("request cancel."))
     }
 
+    pub fn bandwidth_stats(&self) -> BandwidthStats {
+        self.network_service.bandwidth_stats()
+    }
+
     pub async fn known_peers(&self) -> Vec<PeerId> {
         self.network_service
             .known_peers()
@@ -6,7 +6,7 @@ use futures::channel::mpsc::channel;
 use futures::prelude::*;
 use log::{debug, error, info};
 use network_api::{PeerInfo, RpcInfo};
-use network_p2p::config::{RequestResponseConfig, TransportConfig};
+use network_p2p::config::{BandwidthLimits, RequestResponseConfig, TransportConfig};
 use network_p2p::{
     identity, NetworkConfiguration, NetworkWorker, NodeKeyConfig, Params, ProtocolId, Secret,
 };
@@ -104,6 +104,16 @@ pub fn build_network_worker(
         node_name,
         client_version: starcoin_config::G_APP_NAME_WITH_VERSION.clone(),
         allow_non_globals_in_dht,
+        bandwidth_limits: {
+            let bandwidth = &network_config.network_bandwidth;
+            BandwidthLimits {
+                max_upload_rate: bandwidth.max_upload_rate,
+                protocol_upload_rates: bandwidth.protocol_upload_rate().into_iter().collect(),
+                protocol_download_rates: bandwidth.protocol_download_rate().into_iter().collect(),
+                peer_upload_rate: bandwidth.peer_upload_rate,
+                peer_download_rate: bandwidth.peer_download_rate,
+            }
+        },
         ..NetworkConfiguration::default()
     };
     // protocol id is chain/{chain_id}, `RegisteredProtocol` will append `/starcoin` prefix
@@ -162,7 +162,7 @@ async fn test_event_notify_receive() {
     let mut receiver = network2.message_handler.channel();
     network1.service_ref.send_peer_message(msg_send.clone());
     let msg_receive = receiver.next().await.unwrap();
-    assert_eq!(msg_send.notification, msg_receive.notification);
+    assert_compact_block_relayed(&msg_send.notification, msg_receive.notification);
 }
 
 #[stest::test]
@@ -185,7 +185,7 @@ async fn test_event_notify_receive_repeat_block() {
     network1.service_ref.send_peer_message(msg_send1.clone());
     network1.service_ref.send_peer_message(msg_send2.clone());
     let msg_receive1 = receiver.next().await.unwrap();
-    assert_eq!(msg_send1.notification, msg_receive1.notification);
+    assert_compact_block_relayed(&msg_send1.notification, msg_receive1.notification);
 
     //repeat message is filter, so expect timeout error.
     let msg_receive2 = tokio::time::timeout(Duration::from_secs(2), receiver.next()).await;
@@ -239,6 +239,19 @@ async fn test_event_notify_receive_repeat_transaction() {
     assert!(msg_receive3.is_err());
 }
 
+/// Peers supporting short id compact blocks receive the salted version of the sent block.
+fn assert_compact_block_relayed(sent: &NotificationMessage, received: NotificationMessage) {
+    assert_eq!(sent.compact_block_status(), received.compact_block_status());
+    let received = received
+        .into_compact_block_v2()
+        .expect("expect a compact block v2 message");
+    let sent = sent.clone().into_compact_block().unwrap();
+    assert_eq!(
+        received.compact_block.txn_len(),
+        sent.compact_block.txn_len()
+    );
+}
+
 fn mock_block_info(total_difficulty: U256) -> BlockInfo {
     BlockInfo::new(
         HashValue::random(),
@@ -269,10 +282,10 @@ async fn test_event_broadcast() {
     node1.service_ref.broadcast(notification.clone());
 
     let msg_receive2 = receiver2.next().await.unwrap();
-    assert_eq!(notification, msg_receive2.notification);
+    assert_compact_block_relayed(&notification, msg_receive2.notification);
 
     let msg_receive3 = receiver3.next().await.unwrap();
-    assert_eq!(notification, msg_receive3.notification);
+    assert_compact_block_relayed(&notification, msg_receive3.notification);
 
     //repeat broadcast
     node2.service_ref.broadcast(notification.clone());
@@ -357,11 +370,16 @@ async fn test_filter_protocol() {
     let mut receiver3 = service3.message_handler.channel();
 
     let txns = vec![MultiSignedUserTransaction::mock()];
+    let ids = txns.iter().map(|txn| txn.id()).collect::<Vec<_>>();
     let notification = NotificationMessage::Transactions(TransactionsMessage::new(txns));
-    service1.service_ref.broadcast(notification.clone());
+    service1.service_ref.broadcast(notification);
 
+    // transactions are announced by default, peers pull the unknown ones.
     let msg_2 = receiver2.next().await.unwrap();
-    assert_eq!(notification, msg_2.notification);
+    assert_eq!(
+        NotificationMessage::Announcement(Announcement::new(AnnouncementType::Txn, ids)),
+        msg_2.notification
+    );
 
     let msg_3 = receiver3.next().await.unwrap();
     assert_eq!(
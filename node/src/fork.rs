@@ -0,0 +1,141 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sources a dev node forks from, see `--fork-from`.
+
+use anyhow::{format_err, Result};
+use starcoin_config::ForkFrom;
+use starcoin_crypto::HashValue;
+use starcoin_genesis::{ForkSource, StorageForkSource};
+use starcoin_rpc_client::RpcClient;
+use starcoin_state_store_api::StateNode;
+use starcoin_storage::db_storage::DBStorage;
+use starcoin_storage::state_node::StateNodeSource;
+use starcoin_storage::storage::StorageInstance;
+use starcoin_storage::{Storage, StorageVersion};
+use starcoin_types::block::{Block, BlockInfo, BlockNumber};
+use starcoin_types::multi_state::MultiState;
+use std::path::Path;
+use std::sync::{mpsc, Arc};
+
+type RpcCall = Box<dyn FnOnce(&RpcClient) + Send>;
+
+/// Fork from a remote node by its rpc.
+///
+/// The blocking `RpcClient` runs its own runtime, which can not be entered from the runtime of the
+/// node, so the client lives on a worker thread and the calls are sent to it.
+pub struct RpcForkSource {
+    calls: mpsc::Sender<RpcCall>,
+}
+
+impl RpcForkSource {
+    pub fn connect(url: &str) -> Result<Self> {
+        let url = url.to_string();
+        let (calls, call_receiver) = mpsc::channel::<RpcCall>();
+        let (connected, connect_result) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("fork-source-rpc".to_string())
+            .spawn(move || {
+                let client = if url.starts_with("http") {
+                    RpcClient::connect_http(url.as_str())
+                } else {
+                    RpcClient::connect_websocket(url.as_str())
+                };
+                let client = match client {
+                    Ok(client) => {
+                        let _ = connected.send(Ok(()));
+                        client
+                    }
+                    Err(e) => {
+                        let _ = connected.send(Err(e));
+                        return;
+                    }
+                };
+                // the worker exits when the source is dropped.
+                for call in call_receiver {
+                    call(&client);
+                }
+            })?;
+        connect_result
+            .recv()
+            .map_err(|_| format_err!("Fork source rpc thread exited"))??;
+        Ok(Self { calls })
+    }
+
+    fn call<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&RpcClient) -> Result<T> + Send + 'static,
+    {
+        let (result_sender, result) = mpsc::channel();
+        self.calls
+            .send(Box::new(move |client| {
+                let _ = result_sender.send(f(client));
+            }))
+            .map_err(|_| format_err!("Fork source rpc thread exited"))?;
+        result
+            .recv()
+            .map_err(|_| format_err!("Fork source rpc thread panicked"))?
+    }
+}
+
+impl StateNodeSource for RpcForkSource {
+    fn get_state_node(&self, hash: &HashValue) -> Result<Option<StateNode>> {
+        let hash = *hash;
+        self.call(move |client| client.get_state_node_by_node_hash(hash))
+            .map(|node| node.map(StateNode))
+    }
+
+    fn get_state_node2(&self, hash: &HashValue) -> Result<Option<StateNode>> {
+        let hash = *hash;
+        self.call(move |client| client.get_state_node_by_node_hash2(hash))
+            .map(|node| node.map(StateNode))
+    }
+}
+
+impl ForkSource for RpcForkSource {
+    fn head_number(&self) -> Result<BlockNumber> {
+        self.call(|client| client.chain_info())
+            .map(|chain_info| chain_info.head.number.0)
+    }
+
+    fn get_block_by_number(&self, number: BlockNumber) -> Result<Option<Block>> {
+        self.call(move |client| client.chain_get_block_by_number(number, None))?
+            .map(Block::try_from)
+            .transpose()
+    }
+
+    fn get_block_info(&self, block_id: HashValue) -> Result<Option<BlockInfo>> {
+        self.call(move |client| client.chain_get_block_info_by_hash(block_id))
+            .map(|block_info| block_info.map(|view| view.into_info()))
+    }
+
+    fn get_vm_multi_state(&self, block_id: HashValue) -> Result<Option<MultiState>> {
+        self.call(move |client| client.chain_get_vm_multi_state(block_id))
+            .map(|multi_state| {
+                multi_state.map(|view| MultiState::new(view.state_root1, view.state_root2))
+            })
+    }
+}
+
+/// Open the data dir of a local node readonly, the node may keep running.
+fn open_data_dir(data_dir: &Path) -> Result<StorageForkSource> {
+    let db = DBStorage::open_with_cfs(
+        data_dir.join("starcoindb/db/starcoindb"),
+        StorageVersion::current_version()
+            .get_column_family_names()
+            .to_vec(),
+        true,
+        Default::default(),
+        None,
+    )?;
+    let storage = Storage::new(StorageInstance::new_db_instance(db))?;
+    Ok(StorageForkSource::new(Arc::new(storage)))
+}
+
+pub fn open_fork_source(fork_from: &ForkFrom) -> Result<Arc<dyn ForkSource>> {
+    Ok(match fork_from {
+        ForkFrom::Rpc(url) => Arc::new(RpcForkSource::connect(url)?),
+        ForkFrom::DataDir(data_dir) => Arc::new(open_data_dir(data_dir)?),
+    })
+}
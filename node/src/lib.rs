@@ -28,6 +28,7 @@ use tokio::runtime::Runtime;
 use tokio::task;
 
 pub mod crash_handler;
+pub mod fork;
 mod genesis_parameter_resolve;
 mod metrics;
 pub mod network_service_factory;
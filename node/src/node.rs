@@ -1,6 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::fork::open_fork_source;
 use crate::metrics::{MetricsPushActorService, MetricsServerActorService};
 use crate::network_service_factory::NetworkServiceFactory;
 use crate::peer_message_handler::NodePeerMessageHandler;
@@ -53,10 +54,12 @@ use starcoin_sync::sync::SyncService;
 use starcoin_sync::txn_sync::TxnSyncService;
 use starcoin_txpool::{TxPoolActorService, TxPoolService};
 use starcoin_types::system_events::{SystemShutdown, SystemStarted};
+use starcoin_vm2_account_api::AccountAsyncService;
 use starcoin_vm2_account_service::{
     AccountEventService as AccountEventService2, AccountService as AccountService2,
     AccountStorage as AccountStorage2,
 };
+use starcoin_vm2_crypto::ed25519::genesis_key_pair;
 use starcoin_vm2_state_service::ChainStateService as ChainStateService2;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
@@ -211,7 +214,7 @@ impl NodeService {
             logger_handle.enable_stderr();
         }
 
-        // StarcoinVM::set_concurrency_level_once(num_cpus::get());
+        // StarcoinVM::set_concurrency_level(num_cpus::get());
         let (start_sender, start_receiver) = oneshot::channel();
         let join_handle = timeout_join_handler::spawn(move || {
             let system = System::with_tokio_rt(|| {
@@ -286,7 +289,17 @@ impl NodeService {
         storage_instance.check_upgrade(DEFAULT_UPGRADE_BATCH_SIZE)?;
 
         let upgrade_time = SystemTime::now().duration_since(start_time)?;
-        let storage = Arc::new(Storage::new(storage_instance)?);
+        let mut storage = Storage::new(storage_instance)?;
+        let fork_source = match config.fork.fork_from() {
+            Some(fork_from) => {
+                info!("Fork the dev chain from {:?}", fork_from);
+                let source = open_fork_source(&fork_from)?;
+                storage = storage.with_state_node_source(source.clone());
+                Some(source)
+            }
+            None => None,
+        };
+        let storage = Arc::new(storage);
         let storage2 = Arc::new(Storage2(storage.clone()));
         registry.put_shared(storage.clone()).await?;
         registry.put_shared(storage2.clone()).await?;
@@ -310,12 +323,22 @@ impl NodeService {
         );
         registry.put_shared(dag.clone()).await?;
 
-        let (chain_info, genesis) = Genesis::init_and_check_storage(
-            config.net(),
-            storage.clone(),
-            dag.clone(),
-            config.data_dir(),
-        )?;
+        let (chain_info, genesis) = match fork_source.as_ref() {
+            Some(source) => Genesis::init_fork_storage(
+                config.net(),
+                storage.clone(),
+                dag.clone(),
+                source.as_ref(),
+                config.fork.fork_block(),
+                config.fork.impersonate(),
+            )?,
+            None => Genesis::init_and_check_storage(
+                config.net(),
+                storage.clone(),
+                dag.clone(),
+                config.data_dir(),
+            )?,
+        };
 
         info!(
             "Start node with chain info: {}, number {}, dragon fork disabled, upgrade_time cost {} secs, ",
@@ -353,9 +376,24 @@ impl NodeService {
         registry.register::<AccountService>().await?;
         registry.register::<AccountEventService>().await?;
 
-        registry.register::<AccountService2>().await?;
+        let account_service2 = registry.register::<AccountService2>().await?;
         registry.register::<AccountEventService2>().await?;
 
+        // Impersonated accounts are signed by the genesis key of the dev network.
+        let (genesis_private_key, _) = genesis_key_pair();
+        for address in config.fork.impersonate() {
+            if let Err(e) = account_service2
+                .import_account(
+                    *address,
+                    genesis_private_key.to_bytes().to_vec(),
+                    String::new(),
+                )
+                .await
+            {
+                warn!("Import impersonated account {} failed: {}", address, e);
+            }
+        }
+
         let txpool_service = registry.register::<TxPoolActorService>().await?;
 
         //wait TxPoolService put shared..
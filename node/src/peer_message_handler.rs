@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use network_api::messages::{
-    NotificationMessage, PeerCompactBlockMessage, PeerMessage, PeerTransactionsMessage,
+    NotificationMessage, PeerCompactBlockMessage, PeerCompactBlockV2Message, PeerMessage,
+    PeerTransactionsMessage,
 };
 use network_api::PeerMessageHandler;
 use starcoin_block_relayer::BlockRelayer;
@@ -66,6 +67,21 @@ impl PeerMessageHandler for NodePeerMessageHandler {
                     }
                 }
             }
+            NotificationMessage::CompactBlockV2(message) => {
+                if let Err(e) = self.block_relayer.notify(PeerCompactBlockV2Message::new(
+                    peer_message.peer_id,
+                    *message,
+                )) {
+                    match e {
+                        TrySendError::Full(_) => {
+                            warn!("Handle PeerCmpctBlockV2 error, BlockRelayer is too busy.");
+                        }
+                        TrySendError::Disconnected(_) => {
+                            error!("Handle PeerCmpctBlockV2 error, BlockRelayer is shutdown.");
+                        }
+                    }
+                }
+            }
             NotificationMessage::Announcement(message) => {
                 if let Err(e) = self
                     .announcement_service
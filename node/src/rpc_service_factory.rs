@@ -13,7 +13,7 @@ use starcoin_logger::LoggerHandle;
 use starcoin_miner::MinerService;
 use starcoin_network::NetworkServiceRef;
 use starcoin_rpc_server::module::{
-    AccountRpcImpl, ChainRpcImpl, ContractRpcImpl, DebugRpcImpl, MinerRpcImpl,
+    AccountRpcImpl, ChainRpcImpl, ContractRpcImpl, DebugRpcImpl, GasPriceOracle, MinerRpcImpl,
     NetworkManagerRpcImpl, NodeManagerRpcImpl, NodeRpcImpl, PubSubImpl, PubSubService,
     StateRpcImpl, SyncManagerRpcImpl, TxPoolRpcImpl,
 };
@@ -64,14 +64,22 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
                 )
             });
         let txpool_service = ctx.get_shared::<TxPoolService>()?;
-        let txpool_api = Some(TxPoolRpcImpl::new(txpool_service.clone()));
+        let txpool_api = Some(
+            TxPoolRpcImpl::new(txpool_service.clone()).with_gas_price_oracle(GasPriceOracle::new(
+                storage.clone(),
+                config.tx_pool.min_gas_price(),
+            )),
+        );
 
         let state_api = ctx
             .service_ref_opt::<ChainStateService>()?
             .map(|service_ref| StateRpcImpl::new(service_ref.clone(), storage.clone()));
         let state_api2 = ctx
             .service_ref_opt::<ChainStateService2>()?
-            .map(|service_ref| StateRpcImpl2::new(service_ref.clone(), storage2.clone()));
+            .map(|service_ref| {
+                StateRpcImpl2::new(service_ref.clone(), storage2.clone())
+                    .with_table_info_store(storage.clone())
+            });
         let chain_state_service = ctx.service_ref::<ChainStateService>()?.clone();
         let chain_state_service2 = ctx.service_ref::<ChainStateService2>()?.clone();
         let account_service = ctx.service_ref_opt::<AccountService>()?.cloned();
@@ -91,6 +99,7 @@ impl ServiceFactory<RpcService> for RpcServiceFactory {
                 txpool_service.clone(),
                 chain_state_service2.clone(),
             )
+            .with_state_store(storage2.clone())
         });
         let pubsub_api = ctx
             .service_ref_opt::<PubSubService>()?
@@ -4,7 +4,7 @@
 use crate::types::pubsub::EventFilter;
 use crate::types::{
     BlockColorView, BlockHeaderView, BlockInfoView, BlockView, ChainId, ChainInfoView,
-    MultiStateView, StrView, TransactionEventResponse, TransactionInfoView,
+    FeeHistoryView, MultiStateView, StrView, TransactionEventResponse, TransactionInfoView,
     TransactionInfoViewEnum, TransactionInfoWithProofView, TransactionView,
 };
 use jsonrpsee::{
@@ -18,6 +18,7 @@ use starcoin_crypto::HashValue;
 use starcoin_dag::types::ghostdata::GhostdagData;
 use starcoin_types::block::BlockNumber;
 use starcoin_types::multi_access_path::MultiAccessPath;
+use starcoin_types::system_events::ChainReorgEvent;
 use starcoin_vm2_rpc_api::{block_info_view2::BlockInfoView2, transaction_view2::TransactionView2};
 use starcoin_vm2_types::view::{
     StrView as StrView2, TransactionEventResponse as TransactionEventResponse2,
@@ -62,6 +63,15 @@ pub trait ChainApi {
         option: Option<GetBlocksOption>,
     ) -> RpcResult<Vec<BlockView>>;
 
+    /// Get gas usage and gas price percentiles of the latest `block_count` blocks, oldest first.
+    /// `percentiles` must be ascending and in [0, 100].
+    #[method(name = "fee_history")]
+    async fn fee_history(
+        &self,
+        block_count: u64,
+        percentiles: Vec<f64>,
+    ) -> RpcResult<FeeHistoryView>;
+
     #[method(name = "get_block_info_by_number")]
     async fn get_block_info_by_number(
         &self,
@@ -238,6 +248,11 @@ pub trait ChainApi {
         &self,
         block_hash: HashValue,
     ) -> RpcResult<Option<BlockColorView>>;
+
+    /// Get the logged reorgs of the selected chain with sequence numbers not less than `since`,
+    /// oldest first. The node keeps the latest reorgs only.
+    #[method(name = "get_reorgs")]
+    async fn get_reorgs(&self, since: u64) -> RpcResult<Vec<ChainReorgEvent>>;
 }
 
 pub use ChainApiClient as ChainApiRpcClient;
@@ -34,7 +34,7 @@ pub trait DebugApi {
     #[method(name = "txfactory.status")]
     fn txfactory_status(&self, action: FactoryAction) -> RpcResult<bool>;
 
-    /// Update vm concurrency level, level = min(level, num_cpus::get)
+    /// Update vm concurrency level of vm1 and vm2, level = min(level, num_cpus::get)
     #[method(name = "debug.set_concurrency_level")]
     fn set_concurrency_level(&self, level: usize) -> RpcResult<()>;
 
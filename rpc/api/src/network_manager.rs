@@ -7,6 +7,7 @@ use jsonrpsee::{
     proc_macros::rpc,
     Methods,
 };
+use network_p2p_types::bandwidth::BandwidthStats;
 use network_p2p_types::network_state::NetworkState;
 use network_p2p_types::peer_id::PeerId;
 use network_types::peer_info::Multiaddr;
@@ -23,6 +24,10 @@ pub trait NetworkManagerApi {
     #[method(name = "state")]
     async fn state(&self) -> RpcResult<NetworkState>;
 
+    /// Bandwidth consumed by the node, split by protocol and by peer.
+    #[method(name = "bandwidth")]
+    async fn bandwidth(&self) -> RpcResult<BandwidthStats>;
+
     #[method(name = "known_peers")]
     async fn known_peers(&self) -> RpcResult<Vec<PeerId>>;
 
@@ -7,10 +7,12 @@ use anyhow::Result;
 use jsonrpsee::core::SubscriptionResult;
 use jsonrpsee::proc_macros::rpc;
 use starcoin_crypto::HashValue;
-use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::system_events::{ChainReorgEvent, MintBlockEvent};
 use starcoin_vm2_types::view::TransactionEventView as TransactionEventView2;
 
-use crate::types::pubsub::{self, EventFilter, EventFilterV2};
+use crate::types::pubsub::{
+    self, ConfirmationParams, ConfirmationView, EventFilter, EventFilterV2,
+};
 use crate::types::{BlockView, TransactionEventView};
 
 #[rpc(client, server, namespace = "starcoin", namespace_separator = "_")]
@@ -49,6 +51,25 @@ pub trait StarcoinPubSubApi {
         item = MintBlockEvent
     )]
     async fn subscribe_new_mint_block(&self) -> SubscriptionResult;
+
+    /// Subscribe the reorgs of the selected chain, with the common ancestor and the removed and
+    /// added blocks.
+    #[subscription(
+        name = "subscribeReorgs",
+        unsubscribe = "unsubscribeReorgs",
+        item = ChainReorgEvent
+    )]
+    async fn subscribe_reorgs(&self) -> SubscriptionResult;
+
+    /// Subscribe the confirmation progress of a transaction, or of the transactions emitting the
+    /// matching events, until they reach the threshold. A transaction whose block leaves the
+    /// selected chain is reported removed.
+    #[subscription(
+        name = "subscribeConfirmations",
+        unsubscribe = "unsubscribeConfirmations",
+        item = ConfirmationView
+    )]
+    async fn subscribe_confirmations(&self, params: ConfirmationParams) -> SubscriptionResult;
 }
 
 /// Starcoin PUB-SUB rpc interface.
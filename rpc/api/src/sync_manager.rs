@@ -7,6 +7,7 @@ use jsonrpsee::{
     proc_macros::rpc,
     Methods,
 };
+use network_api::peer_score::PeerQualityView;
 use network_api::PeerStrategy;
 use network_p2p_types::peer_id::PeerId;
 use starcoin_rpc_schema_derive::rpc_schema;
@@ -37,6 +38,10 @@ pub trait SyncManagerApi {
 
     #[method(name = "score")]
     async fn peer_score(&self) -> RpcResult<PeerScoreResponse>;
+
+    /// Get the rpc quality scoreboard of peers, include latency, timeouts, invalid data and bandwidth per rpc method.
+    #[method(name = "peer_quality")]
+    async fn peer_quality(&self) -> RpcResult<Vec<PeerQualityView>>;
 }
 
 pub use SyncManagerApiClient as SyncManagerApiRpcClient;
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2
 
 use crate::multi_types::MultiSignedUserTransactionView;
-use crate::types::{SignedUserTransactionView, StrView};
+use crate::types::{GasPriceSuggestionView, SignedUserTransactionView, StrView};
 use jsonrpsee::{
     core::{RegisterMethodError, RpcResult},
     proc_macros::rpc,
@@ -43,6 +43,10 @@ pub trait TxPoolApi {
     #[method(name = "gas_price")]
     async fn gas_price(&self) -> RpcResult<StrView<u64>>;
 
+    /// return slow/normal/fast gas price suggestions, sampled from recent blocks and the txpool.
+    #[method(name = "gas_price_suggestion")]
+    async fn gas_price_suggestion(&self) -> RpcResult<GasPriceSuggestionView>;
+
     /// get all pending txns in txpool of given sender.
     /// no matter the state of txn is ready or in future.
     #[method(name = "pending_txns_of_sender")]
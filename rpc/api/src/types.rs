@@ -2330,6 +2330,29 @@ impl From<starcoin_types::multi_state::MultiState> for MultiStateView {
         }
     }
 }
+/// Gas unit price suggestions, percentiles of the prices paid in recent blocks and the txpool.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct GasPriceSuggestionView {
+    pub slow: StrView<u64>,
+    pub normal: StrView<u64>,
+    pub fast: StrView<u64>,
+    /// Count of the gas prices sampled.
+    pub sample_size: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct FeeHistoryView {
+    /// Number of the oldest block of the history.
+    pub oldest_block: StrView<BlockNumber>,
+    /// Gas used of each block, from the oldest block.
+    pub gas_used: Vec<StrView<u64>>,
+    /// User transaction count of each block, vm1 and vm2 transactions.
+    pub txn_count: Vec<u64>,
+    /// Requested percentiles of the gas unit prices of the user transactions of each block,
+    /// empty for the blocks without user transaction.
+    pub gas_price_percentiles: Vec<Vec<StrView<u64>>>,
+}
+
 #[cfg(test)]
 mod tests {
     use crate::types::{ByteCodeOrScriptFunction, FunctionId, StrView};
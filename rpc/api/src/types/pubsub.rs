@@ -11,7 +11,7 @@ use serde_json::{from_value, Value};
 use starcoin_crypto::HashValue;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::filter::{Filter, FilterType};
-use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::system_events::{ChainReorgEvent, MintBlockEvent};
 use starcoin_types::U256;
 use starcoin_vm2_types::view::{
     TransactionEventResponse as TransactionEventResponse2, TypeTagView,
@@ -34,6 +34,10 @@ pub enum Kind {
     NewPendingTransactions,
     /// New block for minting
     NewMintBlock,
+    /// Chain reorganization subscription.
+    Reorgs,
+    /// Transaction confirmations subscription.
+    Confirmations,
 }
 
 /// Subscription result.
@@ -46,6 +50,8 @@ pub enum Result {
     Event(Box<TransactionEventResponse>),
     EventV2(Box<TransactionEventResponse2>),
     MintBlock(Box<MintBlockEvent>),
+    Reorg(Box<ChainReorgEvent>),
+    Confirmation(Box<ConfirmationView>),
 }
 
 impl Serialize for Result {
@@ -59,6 +65,8 @@ impl Serialize for Result {
             Result::EventV2(ref evt) => evt.serialize(serializer),
             Result::TransactionHash(ref hash) => hash.serialize(serializer),
             Result::MintBlock(ref block) => block.serialize(serializer), // Result::SyncState(ref sync) => sync.serialize(serializer),
+            Result::Reorg(ref reorg) => reorg.serialize(serializer),
+            Result::Confirmation(ref confirmation) => confirmation.serialize(serializer),
         }
     }
 }
@@ -72,6 +80,7 @@ pub enum Params {
     /// Log parameters.
     Events(EventParams),
     EventsV2(EventParamsV2),
+    Confirmations(ConfirmationParams),
 }
 
 impl<'a> Deserialize<'a> for Params {
@@ -83,6 +92,8 @@ impl<'a> Deserialize<'a> for Params {
 
         if v.is_null() {
             Ok(Params::None)
+        } else if let Ok(params) = from_value::<ConfirmationParams>(v.clone()) {
+            Ok(Params::Confirmations(params))
         } else if let Ok(params) = from_value::<EventParamsV2>(v.clone()) {
             Ok(Params::EventsV2(params))
         } else {
@@ -260,3 +271,59 @@ impl TryInto<Filter> for EventFilterV2 {
         })
     }
 }
+
+/// How the confirmations of a block are counted.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfirmationDepth {
+    /// The blue score of the head over the block.
+    #[default]
+    BlueScore,
+    /// The number of selected chain blocks on top of the block, or on top of the selected chain
+    /// block merging it.
+    SelectedChain,
+}
+
+/// Watch the confirmations of a transaction, or of the transactions emitting the vm2 events
+/// matching `event_filter`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq, Hash, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ConfirmationParams {
+    #[serde(default)]
+    pub txn_hash: Option<HashValue>,
+    #[serde(default)]
+    pub event_filter: Option<EventFilterV2>,
+    /// The confirmations a transaction is confirmed at.
+    pub threshold: u64,
+    #[serde(default)]
+    pub depth: ConfirmationDepth,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, Eq, Hash, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfirmationStatus {
+    /// The block is in the selected chain with fewer confirmations than the threshold.
+    Pending,
+    /// The block reached the threshold, no more updates follow for the transaction.
+    Confirmed,
+    /// The block left the selected chain or is merged as a red block, no more updates follow for
+    /// the transaction.
+    Removed,
+    /// The transaction was not included in the selected chain in time, no more updates follow
+    /// for the transaction.
+    Expired,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq, Hash, JsonSchema)]
+pub struct ConfirmationView {
+    pub txn_hash: HashValue,
+    /// The block including the transaction, None if the transaction expired before it was
+    /// included.
+    pub block_hash: Option<HashValue>,
+    /// The head of the selected chain the confirmations are counted at.
+    pub head: HashValue,
+    pub depth: ConfirmationDepth,
+    pub confirmations: u64,
+    pub threshold: u64,
+    pub status: ConfirmationStatus,
+}
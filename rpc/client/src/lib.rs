@@ -13,7 +13,9 @@ use bcs_ext::BCSCodec;
 use futures::channel::oneshot;
 use futures::{TryStream, TryStreamExt};
 use jsonrpsee::core::client::Error as JsonRpseeClientError;
+use network_api::peer_score::PeerQualityView;
 use network_api::PeerStrategy;
+use network_p2p_types::bandwidth::BandwidthStats;
 use network_p2p_types::network_state::NetworkState;
 use network_p2p_types::peer_id::PeerId;
 use network_types::peer_info::Multiaddr;
@@ -38,14 +40,17 @@ use starcoin_rpc_api::service::RpcAsyncService;
 use starcoin_rpc_api::state::{
     GetCodeOption, GetResourceOption, ListCodeOption, ListResourceOption,
 };
-use starcoin_rpc_api::types::pubsub::{EventFilter, EventFilterV2};
+use starcoin_rpc_api::types::pubsub::{
+    ConfirmationParams, ConfirmationView, EventFilter, EventFilterV2,
+};
 use starcoin_rpc_api::types::{
     AccountStateSetView, AnnotatedMoveStructView, BlockHeaderView, BlockInfoView, BlockView,
     ChainId, ChainInfoView, CodeView, ContractCall, DecodedMoveValue, DryRunOutputView,
-    DryRunTransactionRequest, FactoryAction, FunctionIdView, ListCodeView, ListResourceView,
-    MintedBlockView, ModuleIdView, MultiStateView, PeerInfoView, ResourceView, SignedMessageView,
-    StateWithProofView, StateWithTableItemProofView, StrView, StructTagView, SyncStatusView,
-    TransactionEventResponse, TransactionEventView, TransactionInfoView, TransactionInfoViewEnum,
+    DryRunTransactionRequest, FactoryAction, FeeHistoryView, FunctionIdView,
+    GasPriceSuggestionView, ListCodeView, ListResourceView, MintedBlockView, ModuleIdView,
+    MultiStateView, PeerInfoView, ResourceView, SignedMessageView, StateWithProofView,
+    StateWithTableItemProofView, StrView, StructTagView, SyncStatusView, TransactionEventResponse,
+    TransactionEventView, TransactionInfoView, TransactionInfoViewEnum,
     TransactionInfoWithProofView, TransactionRequest, TransactionView,
 };
 use starcoin_service_registry::{ServiceInfo, ServiceStatus};
@@ -56,7 +61,7 @@ use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_state::AccountState;
 use starcoin_types::block::BlockNumber;
 use starcoin_types::sign_message::SigningMessage;
-use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::system_events::{ChainReorgEvent, MintBlockEvent};
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
 use starcoin_vm2_rpc_api::block_info_view2::BlockInfoView2;
 use starcoin_vm2_rpc_api::transaction_view2::TransactionView2;
@@ -916,6 +921,20 @@ impl RpcClient {
         .map_err(map_err)
     }
 
+    pub fn chain_fee_history(
+        &self,
+        block_count: u64,
+        percentiles: Vec<f64>,
+    ) -> anyhow::Result<FeeHistoryView> {
+        self.call_rpc_blocking(|inner| inner.chain_client.fee_history(block_count, percentiles))
+            .map_err(map_err)
+    }
+
+    pub fn chain_get_reorgs(&self, since: u64) -> anyhow::Result<Vec<ChainReorgEvent>> {
+        self.call_rpc_blocking(|inner| inner.chain_client.get_reorgs(since))
+            .map_err(map_err)
+    }
+
     pub fn chain_get_transaction(
         &self,
         txn_id: HashValue,
@@ -1078,6 +1097,11 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn txpool_gas_price_suggestion(&self) -> anyhow::Result<GasPriceSuggestionView> {
+        self.call_rpc_blocking(|inner| inner.txpool_client.gas_price_suggestion())
+            .map_err(map_err)
+    }
+
     pub fn subscribe_events(
         &self,
         filter: EventFilter,
@@ -1132,6 +1156,27 @@ impl RpcClient {
         .map_err(map_err)
     }
 
+    pub fn subscribe_confirmations(
+        &self,
+        params: ConfirmationParams,
+    ) -> anyhow::Result<impl TryStream<Ok = ConfirmationView, Error = anyhow::Error>> {
+        self.call_rpc_blocking(|inner| async move {
+            let res = inner.pubsub_client.subscribe_confirmations(params).await;
+            res.map(|s| s.map_err(map_err))
+        })
+        .map_err(map_err)
+    }
+
+    pub fn subscribe_reorgs(
+        &self,
+    ) -> anyhow::Result<impl TryStream<Ok = ChainReorgEvent, Error = anyhow::Error>> {
+        self.call_rpc_blocking(|inner| async move {
+            let res = inner.pubsub_client.subscribe_reorgs().await;
+            res.map(|s| s.map_err(map_err))
+        })
+        .map_err(map_err)
+    }
+
     fn call_rpc_blocking<F, T>(
         &self,
         f: impl FnOnce(RpcClientInner) -> F + Send,
@@ -1198,6 +1243,11 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn sync_peer_quality(&self) -> anyhow::Result<Vec<PeerQualityView>> {
+        self.call_rpc_blocking(|inner| inner.sync_client.peer_quality())
+            .map_err(map_err)
+    }
+
     pub fn sync_start(
         &self,
         force: bool,
@@ -1228,6 +1278,11 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn network_bandwidth(&self) -> anyhow::Result<BandwidthStats> {
+        self.call_rpc_blocking(|inner| inner.network_client.bandwidth())
+            .map_err(map_err)
+    }
+
     pub fn network_get_address(&self, peer_id: String) -> anyhow::Result<Vec<Multiaddr>> {
         self.call_rpc_blocking(|inner| inner.network_client.get_address(peer_id))
             .map_err(map_err)
@@ -13,7 +13,9 @@ use jsonrpsee::{
 use jsonrpsee_http_client::{HttpClient, HttpClientBuilder};
 use jsonrpsee_ws_client::WsClientBuilder;
 use log::debug;
+use network_api::peer_score::PeerQualityView;
 use network_api::PeerStrategy;
+use network_p2p_types::bandwidth::BandwidthStats;
 use network_p2p_types::network_state::NetworkState;
 use network_p2p_types::peer_id::PeerId;
 use network_types::peer_info::Multiaddr;
@@ -42,14 +44,17 @@ use starcoin_rpc_api::state::{
 };
 use starcoin_rpc_api::sync_manager::SyncManagerApiRpcClient;
 use starcoin_rpc_api::txpool::TxPoolApiRpcClient;
-use starcoin_rpc_api::types::pubsub::{EventFilter, EventFilterV2};
+use starcoin_rpc_api::types::pubsub::{
+    ConfirmationParams, ConfirmationView, EventFilter, EventFilterV2,
+};
 use starcoin_rpc_api::types::{
     AccountStateSetView, AnnotatedMoveStructView, BlockHeaderView, BlockInfoView, BlockView,
     ChainId, ChainInfoView, CodeView, ContractCall, DecodedMoveValue, DryRunOutputView,
-    DryRunTransactionRequest, FactoryAction, FunctionIdView, ListCodeView, ListResourceView,
-    MintedBlockView, ModuleIdView, MultiStateView, PeerInfoView, ResourceView, SignedMessageView,
-    StateWithProofView, StateWithTableItemProofView, StrView, StructTagView, SyncStatusView,
-    TransactionEventResponse, TransactionEventView, TransactionInfoView, TransactionInfoViewEnum,
+    DryRunTransactionRequest, FactoryAction, FeeHistoryView, FunctionIdView,
+    GasPriceSuggestionView, ListCodeView, ListResourceView, MintedBlockView, ModuleIdView,
+    MultiStateView, PeerInfoView, ResourceView, SignedMessageView, StateWithProofView,
+    StateWithTableItemProofView, StrView, StructTagView, SyncStatusView, TransactionEventResponse,
+    TransactionEventView, TransactionInfoView, TransactionInfoViewEnum,
     TransactionInfoWithProofView, TransactionRequest, TransactionView,
 };
 use starcoin_service_registry::{ServiceInfo, ServiceStatus};
@@ -61,7 +66,7 @@ use starcoin_types::account_state::AccountState;
 use starcoin_types::block::BlockNumber;
 use starcoin_types::multi_access_path::MultiAccessPath;
 use starcoin_types::sign_message::SigningMessage;
-use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::system_events::{ChainReorgEvent, MintBlockEvent};
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
 use starcoin_vm2_account_api::AccountInfo as AccountInfo2;
 use starcoin_vm2_rpc_api::account_api::AccountApiRpcClient as AccountApiRpcClient2;
@@ -605,6 +610,12 @@ impl TxPoolClient {
                 .map_err(Into::into),
         }
     }
+    pub async fn gas_price_suggestion(self) -> Result<GasPriceSuggestionView, RpcError> {
+        match self.inner {
+            RpcChannel::Async(client) => client.gas_price_suggestion().await.map_err(Into::into),
+            RpcChannel::Http(client) => client.gas_price_suggestion().await.map_err(Into::into),
+        }
+    }
     pub async fn submit_transaction2(
         self,
         txn: SignedUserTransaction2,
@@ -1083,6 +1094,20 @@ impl AccountClient2 {
                 .map_err(Into::into),
         }
     }
+    pub async fn portfolio(
+        self,
+        address: AccountAddress2,
+        option: Option<starcoin_vm2_rpc_api::account_api::PortfolioOption>,
+    ) -> Result<starcoin_vm2_types::view::PortfolioView, RpcError> {
+        match self.inner {
+            RpcChannel::Async(client) => AccountApiRpcClient2::portfolio(&*client, address, option)
+                .await
+                .map_err(Into::into),
+            RpcChannel::Http(client) => AccountApiRpcClient2::portfolio(&*client, address, option)
+                .await
+                .map_err(Into::into),
+        }
+    }
     pub async fn remove(
         self,
         address: AccountAddress2,
@@ -1111,12 +1136,62 @@ impl AccountClient2 {
                 .map_err(Into::into),
         }
     }
-    pub async fn create(self, password: String) -> Result<AccountInfo2, RpcError> {
+    pub async fn sign_fee_payer_txn(
+        self,
+        raw_txn: starcoin_vm2_vm_types::transaction::RawUserTransaction,
+        fee_payer: AccountAddress2,
+        signer: AccountAddress2,
+    ) -> Result<starcoin_vm2_vm_types::transaction::authenticator::AccountAuthenticator, RpcError>
+    {
+        match self.inner {
+            RpcChannel::Async(client) => {
+                AccountApiRpcClient2::sign_fee_payer_txn(&*client, raw_txn, fee_payer, signer)
+                    .await
+                    .map_err(Into::into)
+            }
+            RpcChannel::Http(client) => {
+                AccountApiRpcClient2::sign_fee_payer_txn(&*client, raw_txn, fee_payer, signer)
+                    .await
+                    .map_err(Into::into)
+            }
+        }
+    }
+    pub async fn sign_multi_agent_txn(
+        self,
+        raw_txn: starcoin_vm2_vm_types::transaction::RawUserTransaction,
+        secondary_signers: Vec<AccountAddress2>,
+        signer: AccountAddress2,
+    ) -> Result<starcoin_vm2_vm_types::transaction::authenticator::AccountAuthenticator, RpcError>
+    {
+        match self.inner {
+            RpcChannel::Async(client) => AccountApiRpcClient2::sign_multi_agent_txn(
+                &*client,
+                raw_txn,
+                secondary_signers,
+                signer,
+            )
+            .await
+            .map_err(Into::into),
+            RpcChannel::Http(client) => AccountApiRpcClient2::sign_multi_agent_txn(
+                &*client,
+                raw_txn,
+                secondary_signers,
+                signer,
+            )
+            .await
+            .map_err(Into::into),
+        }
+    }
+    pub async fn create(
+        self,
+        password: String,
+        key_type: Option<starcoin_vm2_vm_types::transaction::authenticator::Scheme>,
+    ) -> Result<AccountInfo2, RpcError> {
         match self.inner {
-            RpcChannel::Async(client) => AccountApiRpcClient2::create(&*client, password)
+            RpcChannel::Async(client) => AccountApiRpcClient2::create(&*client, password, key_type)
                 .await
                 .map_err(Into::into),
-            RpcChannel::Http(client) => AccountApiRpcClient2::create(&*client, password)
+            RpcChannel::Http(client) => AccountApiRpcClient2::create(&*client, password, key_type)
                 .await
                 .map_err(Into::into),
         }
@@ -1990,6 +2065,28 @@ impl ChainClient {
                 .map_err(Into::into),
         }
     }
+    pub async fn fee_history(
+        self,
+        block_count: u64,
+        percentiles: Vec<f64>,
+    ) -> Result<FeeHistoryView, RpcError> {
+        match self.inner {
+            RpcChannel::Async(client) => client
+                .fee_history(block_count, percentiles)
+                .await
+                .map_err(Into::into),
+            RpcChannel::Http(client) => client
+                .fee_history(block_count, percentiles)
+                .await
+                .map_err(Into::into),
+        }
+    }
+    pub async fn get_reorgs(self, since: u64) -> Result<Vec<ChainReorgEvent>, RpcError> {
+        match self.inner {
+            RpcChannel::Async(client) => client.get_reorgs(since).await.map_err(Into::into),
+            RpcChannel::Http(client) => client.get_reorgs(since).await.map_err(Into::into),
+        }
+    }
     pub async fn get_transaction(
         self,
         txn_id: HashValue,
@@ -2369,6 +2466,12 @@ impl SyncManagerClient {
             RpcChannel::Http(client) => client.peer_score().await.map_err(Into::into),
         }
     }
+    pub async fn peer_quality(self) -> Result<Vec<PeerQualityView>, RpcError> {
+        match self.inner {
+            RpcChannel::Async(client) => client.peer_quality().await.map_err(Into::into),
+            RpcChannel::Http(client) => client.peer_quality().await.map_err(Into::into),
+        }
+    }
     pub async fn start(
         self,
         force: bool,
@@ -2402,6 +2505,12 @@ impl NetworkManagerClient {
             RpcChannel::Http(client) => client.known_peers().await.map_err(Into::into),
         }
     }
+    pub async fn bandwidth(self) -> Result<BandwidthStats, RpcError> {
+        match self.inner {
+            RpcChannel::Async(client) => client.bandwidth().await.map_err(Into::into),
+            RpcChannel::Http(client) => client.bandwidth().await.map_err(Into::into),
+        }
+    }
     pub async fn state(self) -> Result<NetworkState, RpcError> {
         match self.inner {
             RpcChannel::Async(client) => NetworkManagerApiRpcClient::state(&*client)
@@ -2534,4 +2643,31 @@ impl PubSubClient {
             )),
         }
     }
+
+    pub async fn subscribe_confirmations(
+        self,
+        params: ConfirmationParams,
+    ) -> Result<Subscription<ConfirmationView>, RpcError> {
+        match self.inner {
+            RpcChannel::Async(client) => {
+                StarcoinPubSubApiClient::subscribe_confirmations(&*client, params)
+                    .await
+                    .map_err(Into::into)
+            }
+            RpcChannel::Http(_) => Err(anyhow::anyhow!(
+                "http/https transport does not support pubsub"
+            )),
+        }
+    }
+
+    pub async fn subscribe_reorgs(self) -> Result<Subscription<ChainReorgEvent>, RpcError> {
+        match self.inner {
+            RpcChannel::Async(client) => StarcoinPubSubApiClient::subscribe_reorgs(&*client)
+                .await
+                .map_err(Into::into),
+            RpcChannel::Http(_) => Err(anyhow::anyhow!(
+                "http/https transport does not support pubsub"
+            )),
+        }
+    }
 }
@@ -9,8 +9,9 @@ use starcoin_vm2_abi_types::{FunctionABI, ModuleABI, StructInstantiation};
 use starcoin_vm2_account_api::AccountInfo;
 use starcoin_vm2_crypto::HashValue;
 use starcoin_vm2_rpc_api::{
+    account_api::PortfolioOption,
     state_api::{
-        GetCodeOption, GetResourceOption, ListCodeOption, ListResourceOption,
+        ExpandOption, GetCodeOption, GetResourceOption, ListCodeOption, ListResourceOption,
         PrimaryFungibleStoreOption,
     },
     DecodedMoveValue,
@@ -22,8 +23,9 @@ use starcoin_vm2_types::{
     view::{
         AccountStateSetView, AnnotatedMoveStructView, CodeView, ContractCall, DryRunOutputView,
         DryRunTransactionRequest, FunctionIdView, ListCodeView, ListResourceView, ModuleIdView,
-        ResourceView, SignedMessageView, StateWithProofView, StateWithTableItemProofView, StrView,
-        StructTagView, TransactionEventResponse as TransactionEventResponse2, TransactionInfoView,
+        PortfolioView, ResourceView, SignedMessageView, StateWithProofView,
+        StateWithTableItemProofView, StrView, StructTagView,
+        TransactionEventResponse as TransactionEventResponse2, TransactionInfoView,
         TransactionRequest,
     },
 };
@@ -31,7 +33,10 @@ use starcoin_vm2_vm_types::{
     account_config::token_code::TokenCode,
     sign_message::SigningMessage,
     state_store::{state_key::StateKey, table::TableHandle},
-    transaction::{DryRunTransaction, RawUserTransaction, SignedUserTransaction},
+    transaction::{
+        authenticator::{AccountAuthenticator, Scheme},
+        DryRunTransaction, RawUserTransaction, SignedUserTransaction,
+    },
 };
 
 impl RpcClient {
@@ -45,8 +50,12 @@ impl RpcClient {
             .map_err(map_err)
     }
 
-    pub fn account_create2(&self, password: String) -> anyhow::Result<AccountInfo> {
-        self.call_rpc_blocking(|inner| inner.account_client2.create(password))
+    pub fn account_create2(
+        &self,
+        password: String,
+        key_type: Scheme,
+    ) -> anyhow::Result<AccountInfo> {
+        self.call_rpc_blocking(|inner| inner.account_client2.create(password, Some(key_type)))
             .map_err(map_err)
     }
 
@@ -92,6 +101,34 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn account_sign_fee_payer_txn2(
+        &self,
+        raw_txn: RawUserTransaction,
+        fee_payer: AccountAddress,
+        signer: AccountAddress,
+    ) -> anyhow::Result<AccountAuthenticator> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .account_client2
+                .sign_fee_payer_txn(raw_txn, fee_payer, signer)
+        })
+        .map_err(map_err)
+    }
+
+    pub fn account_sign_multi_agent_txn2(
+        &self,
+        raw_txn: RawUserTransaction,
+        secondary_signers: Vec<AccountAddress>,
+        signer: AccountAddress,
+    ) -> anyhow::Result<AccountAuthenticator> {
+        self.call_rpc_blocking(|inner| {
+            inner
+                .account_client2
+                .sign_multi_agent_txn(raw_txn, secondary_signers, signer)
+        })
+        .map_err(map_err)
+    }
+
     pub fn account_sign_message2(
         &self,
         signer: AccountAddress,
@@ -174,6 +211,15 @@ impl RpcClient {
             .map_err(map_err)
     }
 
+    pub fn account_portfolio2(
+        &self,
+        address: AccountAddress,
+        option: Option<PortfolioOption>,
+    ) -> anyhow::Result<PortfolioView> {
+        self.call_rpc_blocking(|inner| inner.account_client2.portfolio(address, option))
+            .map_err(map_err)
+    }
+
     pub fn account_remove2(
         &self,
         address: AccountAddress,
@@ -266,6 +312,7 @@ impl RpcClient {
         decode: bool,
         state_root: Option<HashValue>,
         primary_fungible_store: Option<PrimaryFungibleStoreOption>,
+        expand: Option<ExpandOption>,
     ) -> anyhow::Result<Option<ResourceView>> {
         self.call_rpc_blocking(|inner| {
             inner.state_client2.get_resource(
@@ -275,6 +322,7 @@ impl RpcClient {
                     decode,
                     state_root,
                     primary_fungible_store,
+                    expand,
                 }),
             )
         })
@@ -290,6 +338,7 @@ impl RpcClient {
         max_size: usize,
         resource_types: Option<Vec<StructTagView>>,
         primary_fungible_store: Option<PrimaryFungibleStoreOption>,
+        expand: Option<ExpandOption>,
     ) -> anyhow::Result<ListResourceView> {
         self.call_rpc_blocking(|inner| {
             inner.state_client2.list_resource(
@@ -301,6 +350,7 @@ impl RpcClient {
                     max_size,
                     resource_types,
                     primary_fungible_store,
+                    expand,
                 }),
             )
         })
@@ -421,7 +471,17 @@ impl RpcClient {
         let DryRunTransaction {
             raw_txn,
             public_key,
+            fee_payer,
+            secondary_signers,
         } = txn;
+        anyhow::ensure!(
+            fee_payer.is_none(),
+            "dry run a txn with a fee payer is not supported by rpc"
+        );
+        anyhow::ensure!(
+            secondary_signers.is_empty(),
+            "dry run a multi-agent txn is not supported by rpc"
+        );
         let raw_txn = hex::encode(raw_txn.encode()?);
         self.call_rpc_blocking(|inner| {
             inner
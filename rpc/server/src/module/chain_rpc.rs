@@ -1,6 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::module::gas_oracle::{block_gas_prices, percentile};
 use crate::module::map_err;
 use anyhow::Result;
 use futures::future::TryFutureExt;
@@ -19,8 +20,8 @@ use starcoin_rpc_api::multi_types::MultiSignedUserTransactionView;
 use starcoin_rpc_api::types::pubsub::EventFilter;
 use starcoin_rpc_api::types::{
     BlockColorView, BlockHeaderView, BlockInfoView, BlockTransactionsView, BlockView, ChainId,
-    ChainInfoView, MultiStateView, StrView, TransactionEventResponse, TransactionInfoView,
-    TransactionInfoWithProofView, TransactionView,
+    ChainInfoView, FeeHistoryView, MultiStateView, StrView, TransactionEventResponse,
+    TransactionInfoView, TransactionInfoWithProofView, TransactionView,
 };
 use starcoin_state_api::StateView;
 use starcoin_statedb::ChainStateDB;
@@ -32,6 +33,7 @@ use starcoin_types::contract_event::{ContractEventInfo, StcContractEventInfo};
 use starcoin_types::filter::Filter;
 use starcoin_types::multi_access_path::MultiAccessPath;
 use starcoin_types::startup_info::ChainInfo;
+use starcoin_types::system_events::ChainReorgEvent;
 use starcoin_vm2_abi_decoder::decode_txn_payload as decode_txn_payload_v2;
 use starcoin_vm2_resource_viewer::MoveValueAnnotator as MoveValueAnnotator2;
 use starcoin_vm2_rpc_api::block_info_view2::BlockInfoView2;
@@ -196,6 +198,56 @@ where
         fut.await.map_err(crate::module::map_jsonrpc_err)
     }
 
+    async fn fee_history(
+        &self,
+        block_count: u64,
+        percentiles: Vec<f64>,
+    ) -> RpcResult<FeeHistoryView> {
+        let service = self.service.clone();
+        let config = self.config.clone();
+        let fut = async move {
+            if percentiles.iter().any(|p| !(0.0..=100.0).contains(p))
+                || percentiles.windows(2).any(|w| w[0] > w[1])
+            {
+                return Err(crate::module::to_invalid_param_err(anyhow::anyhow!(
+                    "percentiles must be ascending and in [0, 100]"
+                )));
+            }
+            let count = block_count.min(config.rpc.block_query_max_range());
+            let mut blocks = service.main_blocks_by_number(None, true, count).await?;
+            blocks.sort_by_key(|block| block.header().number());
+
+            let oldest_block = blocks
+                .first()
+                .map(|block| block.header().number())
+                .unwrap_or_default();
+            let mut gas_used: Vec<StrView<u64>> = Vec::with_capacity(blocks.len());
+            let mut txn_count = Vec::with_capacity(blocks.len());
+            let mut gas_price_percentiles: Vec<Vec<StrView<u64>>> =
+                Vec::with_capacity(blocks.len());
+            for block in blocks {
+                let prices = block_gas_prices(&block);
+                gas_used.push(block.header().gas_used().into());
+                txn_count.push(prices.len() as u64);
+                gas_price_percentiles.push(
+                    percentiles
+                        .iter()
+                        .map(|p| percentile(&prices, *p).unwrap_or_default().into())
+                        .collect(),
+                );
+            }
+            Ok(FeeHistoryView {
+                oldest_block: oldest_block.into(),
+                gas_used,
+                txn_count,
+                gas_price_percentiles,
+            })
+        }
+        .map_err(map_err);
+
+        fut.await.map_err(crate::module::map_jsonrpc_err)
+    }
+
     async fn get_block_info_by_number(&self, number: u64) -> RpcResult<Option<BlockInfoView>> {
         let service = self.service.clone();
 
@@ -834,6 +886,12 @@ where
         .map_err(map_err);
         fut.await.map_err(crate::module::map_jsonrpc_err)
     }
+
+    async fn get_reorgs(&self, since: u64) -> RpcResult<Vec<ChainReorgEvent>> {
+        let storage = self.storage.clone();
+        let fut = async move { storage.get_reorgs(since) }.map_err(map_err);
+        fut.await.map_err(crate::module::map_jsonrpc_err)
+    }
 }
 
 fn try_decode_block_txns(
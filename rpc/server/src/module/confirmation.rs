@@ -0,0 +1,313 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Confirmation tracking of the confirmation subscriptions. A transaction is confirmed by the
+//! head of the selected chain through its containing block, by the blue score of the head over
+//! the block or by the selected chain depth of the head over the block. The block must be in the
+//! selected chain, or be merged into it as a blue block.
+//!
+//! The tracker runs in the stream of its subscription, the pubsub service only forwards the new
+//! heads and events to it, so the DAG queries do not block the service.
+
+use anyhow::{format_err, Result};
+use starcoin_chain_notify::message::Event2;
+use starcoin_crypto::HashValue;
+use starcoin_dag::blockdag::{BlockDAG, DagBlockColor};
+use starcoin_logger::prelude::*;
+use starcoin_rpc_api::types::pubsub::{ConfirmationDepth, ConfirmationStatus, ConfirmationView};
+use starcoin_storage::Store;
+use starcoin_types::contract_event::StcContractEvent;
+use starcoin_types::filter::Filter;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How long a transaction is watched before it is included in a block of the selected chain.
+pub(crate) const CONFIRMATION_WATCH_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+pub(crate) enum ConfirmationTarget {
+    Txn(HashValue),
+    Events(Filter),
+}
+
+/// The notifications the pubsub service forwards to the confirmation subscriptions.
+#[derive(Clone, Debug)]
+pub(crate) enum ConfirmationNotification {
+    NewHead(HashValue),
+    Events(Arc<[Event2]>),
+}
+
+struct WatchedTxn {
+    since: Instant,
+    /// The block the transaction was last reported in.
+    reported_block: Option<HashValue>,
+}
+
+impl WatchedTxn {
+    fn new() -> Self {
+        Self {
+            since: Instant::now(),
+            reported_block: None,
+        }
+    }
+}
+
+/// The block of a transaction at a head.
+enum Inclusion {
+    /// The block is in the selected chain or merged as a blue block, with its confirmations.
+    Included(HashValue, u64),
+    /// The transaction is only in red blocks merged by the head.
+    Red(HashValue),
+    /// The transaction is not in any block merged by the head.
+    NotIncluded,
+}
+
+pub(crate) struct ConfirmationTracker {
+    target: ConfirmationTarget,
+    threshold: u64,
+    depth: ConfirmationDepth,
+    timeout: Duration,
+    storage: Arc<dyn Store>,
+    dag: BlockDAG,
+    watched: HashMap<HashValue, WatchedTxn>,
+}
+
+impl ConfirmationTracker {
+    pub fn new(
+        target: ConfirmationTarget,
+        threshold: u64,
+        depth: ConfirmationDepth,
+        timeout: Duration,
+        storage: Arc<dyn Store>,
+        dag: BlockDAG,
+    ) -> Self {
+        let watched = match &target {
+            ConfirmationTarget::Txn(txn_hash) => HashMap::from([(*txn_hash, WatchedTxn::new())]),
+            ConfirmationTarget::Events(_) => HashMap::new(),
+        };
+        Self {
+            target,
+            threshold,
+            depth,
+            timeout,
+            storage,
+            dag,
+            watched,
+        }
+    }
+
+    pub fn handle(&mut self, msg: ConfirmationNotification) -> Vec<ConfirmationView> {
+        match msg {
+            ConfirmationNotification::NewHead(head) => match self.on_new_head(head) {
+                Ok(views) => views,
+                Err(e) => {
+                    error!("[pubsub] Failed to update confirmations at {}: {}", head, e);
+                    vec![]
+                }
+            },
+            ConfirmationNotification::Events(events) => {
+                self.on_events(&events);
+                vec![]
+            }
+        }
+    }
+
+    /// Watch the transactions of the events matching the filter of this tracker.
+    fn on_events(&mut self, events: &[Event2]) {
+        let ConfirmationTarget::Events(filter) = &self.target else {
+            return;
+        };
+        for event in events {
+            if filter.matching(
+                event.block_number,
+                &StcContractEvent::V2(event.contract_event.clone()),
+            ) {
+                self.watched
+                    .entry(event.transaction_hash)
+                    .or_insert_with(WatchedTxn::new);
+            }
+        }
+    }
+
+    /// The confirmation updates of the watched transactions at the new `head`. The transactions
+    /// which are confirmed, removed from the selected chain, only in red blocks or not included
+    /// within the timeout are not watched any more.
+    fn on_new_head(&mut self, head: HashValue) -> Result<Vec<ConfirmationView>> {
+        let mut views = vec![];
+        let mut evicted = vec![];
+        for (txn_hash, watched) in self.watched.iter_mut() {
+            let (block_hash, confirmations, status) =
+                match find_inclusion(*txn_hash, head, self.depth, &*self.storage, &self.dag)? {
+                    Inclusion::Included(block_hash, confirmations) => {
+                        watched.reported_block = Some(block_hash);
+                        if confirmations >= self.threshold {
+                            (
+                                Some(block_hash),
+                                confirmations,
+                                ConfirmationStatus::Confirmed,
+                            )
+                        } else {
+                            (Some(block_hash), confirmations, ConfirmationStatus::Pending)
+                        }
+                    }
+                    Inclusion::Red(block_hash) => {
+                        (Some(block_hash), 0, ConfirmationStatus::Removed)
+                    }
+                    Inclusion::NotIncluded => match watched.reported_block {
+                        Some(block_hash) => (Some(block_hash), 0, ConfirmationStatus::Removed),
+                        None if watched.since.elapsed() >= self.timeout => {
+                            (None, 0, ConfirmationStatus::Expired)
+                        }
+                        None => continue,
+                    },
+                };
+            if status != ConfirmationStatus::Pending {
+                evicted.push(*txn_hash);
+            }
+            views.push(ConfirmationView {
+                txn_hash: *txn_hash,
+                block_hash,
+                head,
+                depth: self.depth,
+                confirmations,
+                threshold: self.threshold,
+                status,
+            });
+        }
+        for txn_hash in evicted {
+            self.watched.remove(&txn_hash);
+        }
+        Ok(views)
+    }
+}
+
+/// The block of `txn_hash` merged by `head`, preferring a block confirmed by `head`.
+fn find_inclusion(
+    txn_hash: HashValue,
+    head: HashValue,
+    depth: ConfirmationDepth,
+    storage: &dyn Store,
+    dag: &BlockDAG,
+) -> Result<Inclusion> {
+    let mut red_block = None;
+    for txn_info in storage.get_transaction_info_by_txn_hash(txn_hash)? {
+        let block_hash = txn_info.block_id;
+        if dag.ghostdata_by_hash(block_hash)?.is_none()
+            || !dag.check_ancestor_of(block_hash, head)?
+        {
+            continue;
+        }
+        // `block_hash` if it is in the selected chain of `head`, or the selected chain block
+        // merging it as a blue block.
+        let chain_block = if dag.check_ancestor_of_chain(block_hash, head)? {
+            block_hash
+        } else {
+            let color = dag.get_block_color(block_hash, head)?;
+            match color.color {
+                DagBlockColor::Blue => color.confirmed_block,
+                DagBlockColor::Red => {
+                    red_block = Some(block_hash);
+                    continue;
+                }
+            }
+        };
+        let confirmations = match depth {
+            ConfirmationDepth::BlueScore => {
+                blue_score(head, dag)?.saturating_sub(blue_score(block_hash, dag)?)
+            }
+            ConfirmationDepth::SelectedChain => {
+                block_number(head, storage)?.saturating_sub(block_number(chain_block, storage)?)
+            }
+        };
+        return Ok(Inclusion::Included(block_hash, confirmations));
+    }
+    Ok(red_block.map_or(Inclusion::NotIncluded, Inclusion::Red))
+}
+
+fn blue_score(block_hash: HashValue, dag: &BlockDAG) -> Result<u64> {
+    dag.ghostdata_by_hash(block_hash)?
+        .map(|ghostdata| ghostdata.blue_score)
+        .ok_or_else(|| format_err!("Can not find the ghostdag data of block {}", block_hash))
+}
+
+fn block_number(block_hash: HashValue, storage: &dyn Store) -> Result<u64> {
+    storage
+        .get_block_header_by_hash(block_hash)?
+        .map(|header| header.number())
+        .ok_or_else(|| format_err!("Can not find block header {}", block_hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConfirmationNotification, ConfirmationTarget, ConfirmationTracker};
+    use starcoin_chain::ChainReader;
+    use starcoin_chain_mock::MockChain;
+    use starcoin_config::ChainNetwork;
+    use starcoin_crypto::HashValue;
+    use starcoin_rpc_api::types::pubsub::{ConfirmationDepth, ConfirmationStatus};
+    use std::time::Duration;
+
+    #[test]
+    fn test_confirmation_tracker() -> anyhow::Result<()> {
+        let mut mock_chain = MockChain::new(ChainNetwork::new_test())?;
+        let block = mock_chain.produce_and_apply()?;
+        let storage = mock_chain.get_storage();
+        // The block metadata txn of the block.
+        let txn_hash = storage
+            .get_transaction_info_by_block_and_index(block.id(), 0)?
+            .expect("block metadata txn info should exist")
+            .transaction_hash();
+        let mut tracker = ConfirmationTracker::new(
+            ConfirmationTarget::Txn(txn_hash),
+            2,
+            ConfirmationDepth::BlueScore,
+            Duration::from_secs(60),
+            storage,
+            mock_chain.head().dag(),
+        );
+
+        let views = tracker.handle(ConfirmationNotification::NewHead(block.id()));
+        assert_eq!(views.len(), 1);
+        assert_eq!(views[0].block_hash, Some(block.id()));
+        assert_eq!(views[0].confirmations, 0);
+        assert_eq!(views[0].status, ConfirmationStatus::Pending);
+
+        mock_chain.produce_and_apply_times(2)?;
+        let head = mock_chain.head().current_header().id();
+        let views = tracker.handle(ConfirmationNotification::NewHead(head));
+        assert_eq!(views.len(), 1);
+        assert_eq!(views[0].confirmations, 2);
+        assert_eq!(views[0].status, ConfirmationStatus::Confirmed);
+
+        // The confirmed txn is not watched any more.
+        assert!(tracker
+            .handle(ConfirmationNotification::NewHead(head))
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_confirmation_tracker_expires_unknown_txn() -> anyhow::Result<()> {
+        let mut mock_chain = MockChain::new(ChainNetwork::new_test())?;
+        let head = mock_chain.produce_and_apply()?.id();
+        let txn_hash = HashValue::random();
+        let mut tracker = ConfirmationTracker::new(
+            ConfirmationTarget::Txn(txn_hash),
+            1,
+            ConfirmationDepth::SelectedChain,
+            Duration::ZERO,
+            mock_chain.get_storage(),
+            mock_chain.head().dag(),
+        );
+
+        let views = tracker.handle(ConfirmationNotification::NewHead(head));
+        assert_eq!(views.len(), 1);
+        assert_eq!(views[0].txn_hash, txn_hash);
+        assert_eq!(views[0].block_hash, None);
+        assert_eq!(views[0].status, ConfirmationStatus::Expired);
+        assert!(tracker
+            .handle(ConfirmationNotification::NewHead(head))
+            .is_empty());
+        Ok(())
+    }
+}
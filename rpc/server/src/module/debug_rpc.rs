@@ -14,6 +14,7 @@ use starcoin_service_registry::bus::{Bus, BusService};
 use starcoin_service_registry::ServiceRef;
 use starcoin_types::system_events::GenerateBlockEvent;
 use starcoin_vm2_vm_runtime::starcoin_vm::StarcoinVM as StarcoinVM2;
+use starcoin_vm_runtime::starcoin_vm::StarcoinVM;
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -91,6 +92,7 @@ impl DebugApiServer for DebugRpcImpl {
     }
 
     fn set_concurrency_level(&self, level: usize) -> RpcResult<()> {
+        StarcoinVM::set_concurrency_level(level);
         StarcoinVM2::set_concurrency_level(level);
         Ok(())
     }
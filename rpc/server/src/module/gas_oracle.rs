@@ -0,0 +1,161 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Gas price oracle, suggests gas unit prices from the user transactions included in the recent
+//! main chain blocks and the ones pending in the txpool, for both vm1 and vm2 transactions.
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use starcoin_crypto::HashValue;
+use starcoin_rpc_api::types::{GasPriceSuggestionView, StrView};
+use starcoin_storage::Store;
+use starcoin_types::block::Block;
+use starcoin_types::multi_transaction::MultiSignedUserTransaction;
+use std::sync::Arc;
+
+/// How many recent blocks are sampled by default.
+pub const DEFAULT_GAS_ORACLE_BLOCKS: u64 = 20;
+/// Max pending transactions sampled from the txpool.
+pub const MAX_GAS_ORACLE_PENDING_TXNS: u64 = 1000;
+
+const SLOW_PERCENTILE: f64 = 25.0;
+const NORMAL_PERCENTILE: f64 = 50.0;
+const FAST_PERCENTILE: f64 = 90.0;
+
+/// Gas unit prices of the user transactions of the block, sorted ascending.
+pub(crate) fn block_gas_prices(block: &Block) -> Vec<u64> {
+    let mut prices = block
+        .transactions()
+        .iter()
+        .map(|txn| txn.gas_unit_price())
+        .chain(block.transactions2().iter().map(|txn| txn.gas_unit_price()))
+        .collect::<Vec<_>>();
+    prices.sort_unstable();
+    prices
+}
+
+/// Nearest-rank percentile of the sorted values, `percentile` is in [0, 100].
+pub(crate) fn percentile(sorted: &[u64], percentile: f64) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (percentile.clamp(0.0, 100.0) / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted.get(rank.saturating_sub(1)).copied()
+}
+
+pub struct GasPriceOracle {
+    storage: Arc<dyn Store>,
+    min_gas_price: u64,
+    sample_blocks: u64,
+    /// Sorted gas prices of the sampled blocks, by the head block they were sampled at.
+    cache: Mutex<Option<(HashValue, Arc<Vec<u64>>)>>,
+}
+
+impl GasPriceOracle {
+    pub fn new(storage: Arc<dyn Store>, min_gas_price: u64) -> Self {
+        Self {
+            storage,
+            min_gas_price,
+            sample_blocks: DEFAULT_GAS_ORACLE_BLOCKS,
+            cache: Mutex::new(None),
+        }
+    }
+
+    pub fn min_gas_price(&self) -> u64 {
+        self.min_gas_price
+    }
+
+    fn recent_block_prices(&self) -> Result<Arc<Vec<u64>>> {
+        let head_id = match self.storage.get_startup_info()? {
+            Some(startup_info) => startup_info.main,
+            None => return Ok(Arc::new(vec![])),
+        };
+        if let Some((cached_head, prices)) = self.cache.lock().as_ref() {
+            if *cached_head == head_id {
+                return Ok(prices.clone());
+            }
+        }
+
+        let mut prices = vec![];
+        let mut block_id = head_id;
+        for _ in 0..self.sample_blocks {
+            let block = match self.storage.get_block_by_hash(block_id)? {
+                Some(block) => block,
+                None => break,
+            };
+            prices.extend(block_gas_prices(&block));
+            if block.header().number() == 0 {
+                break;
+            }
+            block_id = block.header().parent_hash();
+        }
+        prices.sort_unstable();
+        let prices = Arc::new(prices);
+        *self.cache.lock() = Some((head_id, prices.clone()));
+        Ok(prices)
+    }
+
+    /// Suggest gas prices from the recent blocks and the given pending transactions. Every
+    /// suggestion is at least the txpool min gas price.
+    pub fn suggest(
+        &self,
+        pending: &[MultiSignedUserTransaction],
+    ) -> Result<GasPriceSuggestionView> {
+        let recent_prices = self.recent_block_prices()?;
+        let mut prices = recent_prices
+            .iter()
+            .copied()
+            .chain(pending.iter().map(|txn| txn.gas_unit_price()))
+            .collect::<Vec<_>>();
+        prices.sort_unstable();
+        let suggest = |p: f64| -> StrView<u64> {
+            percentile(&prices, p)
+                .unwrap_or(self.min_gas_price)
+                .max(self.min_gas_price)
+                .into()
+        };
+        Ok(GasPriceSuggestionView {
+            slow: suggest(SLOW_PERCENTILE),
+            normal: suggest(NORMAL_PERCENTILE),
+            fast: suggest(FAST_PERCENTILE),
+            sample_size: prices.len() as u64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starcoin_storage::storage::StorageInstance;
+    use starcoin_storage::Storage;
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(percentile(&[], 50.0), None);
+        let prices = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+        assert_eq!(percentile(&prices, 0.0), Some(1));
+        assert_eq!(percentile(&prices, 25.0), Some(3));
+        assert_eq!(percentile(&prices, 50.0), Some(5));
+        assert_eq!(percentile(&prices, 90.0), Some(9));
+        assert_eq!(percentile(&prices, 100.0), Some(10));
+        assert_eq!(percentile(&prices, 150.0), Some(10));
+    }
+
+    #[test]
+    fn test_suggest_without_samples() {
+        let storage = Arc::new(Storage::new(StorageInstance::new_cache_instance()).unwrap());
+        let oracle = GasPriceOracle::new(storage, 2);
+        let suggestion = oracle.suggest(&[]).unwrap();
+        assert_eq!(suggestion.sample_size, 0);
+        assert_eq!(suggestion.slow.0, 2);
+        assert_eq!(suggestion.fast.0, 2);
+
+        let pending = vec![MultiSignedUserTransaction::mock()];
+        let suggestion = oracle.suggest(&pending).unwrap();
+        assert_eq!(suggestion.sample_size, 1);
+        assert_eq!(
+            suggestion.normal.0,
+            pending[0].gas_unit_price().max(oracle.min_gas_price())
+        );
+    }
+}
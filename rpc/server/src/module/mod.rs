@@ -3,8 +3,10 @@
 
 mod account_rpc;
 mod chain_rpc;
+mod confirmation;
 mod contract_rpc;
 mod debug_rpc;
+mod gas_oracle;
 mod helpers;
 mod miner_rpc;
 mod network_manager_rpc;
@@ -43,6 +45,7 @@ pub use self::account_rpc::AccountRpcImpl;
 pub use self::chain_rpc::ChainRpcImpl;
 pub use self::contract_rpc::ContractRpcImpl;
 pub use self::debug_rpc::DebugRpcImpl;
+pub use self::gas_oracle::GasPriceOracle;
 pub use self::miner_rpc::MinerRpcImpl;
 pub use self::network_manager_rpc::NetworkManagerRpcImpl;
 pub use self::node_manager_rpc::NodeManagerRpcImpl;
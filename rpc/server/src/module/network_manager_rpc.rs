@@ -4,6 +4,7 @@
 use jsonrpsee::core::{async_trait, RpcResult};
 use network_api::{PeerProvider, ReputationChange};
 use network_p2p_core::RawRpcClient;
+use network_p2p_types::bandwidth::BandwidthStats;
 use network_p2p_types::network_state::NetworkState;
 use network_p2p_types::peer_id::PeerId;
 use network_types::peer_info::Multiaddr;
@@ -32,6 +33,10 @@ impl NetworkManagerApiServer for NetworkManagerRpcImpl {
             .map_err(crate::module::map_jsonrpc_err)
     }
 
+    async fn bandwidth(&self) -> RpcResult<BandwidthStats> {
+        Ok(self.service.bandwidth_stats())
+    }
+
     async fn known_peers(&self) -> RpcResult<Vec<PeerId>> {
         let service = self.service.clone();
         let result = service.known_peers().await;
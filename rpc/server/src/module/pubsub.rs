@@ -1,6 +1,9 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::module::confirmation::{
+    ConfirmationNotification, ConfirmationTarget, ConfirmationTracker, CONFIRMATION_WATCH_TIMEOUT,
+};
 use anyhow::Result;
 use futures::channel::mpsc;
 use futures::future::{self, AbortHandle, Either, FutureExt};
@@ -18,10 +21,11 @@ use starcoin_abi_decoder::decode_move_value;
 use starcoin_abi_resolver::ABIResolver;
 use starcoin_chain_notify::message::{ContractEventNotification, Notification, ThinBlock};
 use starcoin_crypto::HashValue;
+use starcoin_dag::blockdag::BlockDAG;
 use starcoin_logger::prelude::*;
 use starcoin_miner::{MinerService, UpdateSubscriberNumRequest};
 use starcoin_rpc_api::pubsub::StarcoinPubSubApiServer;
-use starcoin_rpc_api::types::pubsub::{EventParams, EventParamsV2, Params};
+use starcoin_rpc_api::types::pubsub::{ConfirmationParams, EventParams, EventParamsV2, Params};
 use starcoin_rpc_api::types::{BlockView, TransactionEventResponse, TransactionEventView};
 use starcoin_rpc_api::{errors, types::pubsub};
 use starcoin_service_registry::{
@@ -35,7 +39,7 @@ use starcoin_txpool::TxPoolService;
 use starcoin_txpool_api::TxPoolSyncService;
 use starcoin_types::contract_event::StcContractEvent;
 use starcoin_types::filter::Filter;
-use starcoin_types::system_events::MintBlockEvent;
+use starcoin_types::system_events::{ChainReorgEvent, MintBlockEvent};
 use starcoin_vm2_abi_decoder::decode_move_value as decode_move_value2;
 use starcoin_vm2_abi_resolver::ABIResolver as ABIResolver2;
 use starcoin_vm2_statedb::ChainStateDB as ChainStateDB2;
@@ -363,6 +367,53 @@ impl StarcoinPubSubApiServer for PubSubImpl {
         .await;
         Ok(())
     }
+
+    async fn subscribe_confirmations(
+        &self,
+        pending: PendingSubscriptionSink,
+        params: ConfirmationParams,
+    ) -> SubscriptionResult {
+        let local_sub = self
+            .subscribe(
+                pubsub::Kind::Confirmations,
+                Some(Params::Confirmations(params)),
+            )
+            .await
+            .map_err(map_anyhow_subscription_err)?;
+        forward_subscription_stream(self, pending, local_sub, |item| match item {
+            pubsub::Result::Confirmation(confirmation) => {
+                serialize_subscription_item(&*confirmation)
+            }
+            other => {
+                warn!(
+                    "unexpected pubsub payload for starcoin_subscribeConfirmations: {:?}",
+                    other
+                );
+                None
+            }
+        })
+        .await;
+        Ok(())
+    }
+
+    async fn subscribe_reorgs(&self, pending: PendingSubscriptionSink) -> SubscriptionResult {
+        let local_sub = self
+            .subscribe(pubsub::Kind::Reorgs, None)
+            .await
+            .map_err(map_anyhow_subscription_err)?;
+        forward_subscription_stream(self, pending, local_sub, |item| match item {
+            pubsub::Result::Reorg(reorg) => serialize_subscription_item(&*reorg),
+            other => {
+                warn!(
+                    "unexpected pubsub payload for starcoin_subscribeReorgs: {:?}",
+                    other
+                );
+                None
+            }
+        })
+        .await;
+        Ok(())
+    }
 }
 
 pub struct PubSubServiceFactory;
@@ -372,11 +423,13 @@ impl ServiceFactory<PubSubService> for PubSubServiceFactory {
         let miner_service = ctx.service_ref::<MinerService>()?.clone();
         let storage = ctx.get_shared::<Arc<Storage>>()?;
         let storage2 = ctx.get_shared::<Arc<Storage2>>()?;
+        let dag = ctx.get_shared::<BlockDAG>()?;
         Ok(PubSubService::new(
             ctx.get_shared::<TxPoolService>()?,
             miner_service,
             storage,
             storage2,
+            dag,
         ))
     }
 }
@@ -387,11 +440,15 @@ pub struct PubSubService {
     miner_service: ServiceRef<MinerService>,
     storage: Arc<Storage>,
     storage2: Arc<Storage2>,
+    dag: BlockDAG,
     new_header_subscribers:
         HashMap<LocalSubscriptionId, mpsc::UnboundedSender<NewHeadNotification>>,
     new_event_subscribers:
         HashMap<LocalSubscriptionId, mpsc::UnboundedSender<ContractEventNotification>>,
     mint_block_subscribers: HashMap<LocalSubscriptionId, mpsc::UnboundedSender<MintBlockEvent>>,
+    reorg_subscribers: HashMap<LocalSubscriptionId, mpsc::UnboundedSender<ChainReorgEvent>>,
+    confirmation_subscribers:
+        HashMap<LocalSubscriptionId, mpsc::UnboundedSender<ConfirmationNotification>>,
     new_pending_txn_tasks: Arc<RwLock<HashMap<LocalSubscriptionId, AbortHandle>>>,
 }
 
@@ -401,6 +458,7 @@ impl PubSubService {
         miner_service: ServiceRef<MinerService>,
         storage: Arc<Storage>,
         storage2: Arc<Storage2>,
+        dag: BlockDAG,
     ) -> Self {
         Self {
             subscriber_id: 0,
@@ -408,9 +466,12 @@ impl PubSubService {
             miner_service,
             storage,
             storage2,
+            dag,
             new_event_subscribers: Default::default(),
             new_header_subscribers: Default::default(),
             mint_block_subscribers: Default::default(),
+            reorg_subscribers: Default::default(),
+            confirmation_subscribers: Default::default(),
             new_pending_txn_tasks: Arc::new(RwLock::new(HashMap::default())),
         }
     }
@@ -432,6 +493,47 @@ impl PubSubService {
         LocalSubscription { id, stream }
     }
 
+    fn subscribe_reorgs(&mut self) -> LocalSubscription {
+        let (sender, receiver) = mpsc::unbounded();
+        let id = self.next_id();
+        self.reorg_subscribers.insert(id, sender);
+        let stream = receiver
+            .flat_map(move |msg| futures::stream::iter(ReorgHandler.handle(msg)))
+            .boxed();
+
+        LocalSubscription { id, stream }
+    }
+
+    fn subscribe_confirmations(
+        &mut self,
+        target: ConfirmationTarget,
+        params: &ConfirmationParams,
+    ) -> LocalSubscription {
+        let (sender, receiver) = mpsc::unbounded();
+        let id = self.next_id();
+        self.confirmation_subscribers.insert(id, sender);
+        let mut tracker = ConfirmationTracker::new(
+            target,
+            params.threshold,
+            params.depth,
+            CONFIRMATION_WATCH_TIMEOUT,
+            self.storage.clone(),
+            self.dag.clone(),
+        );
+        let stream = receiver
+            .flat_map(move |msg| {
+                futures::stream::iter(
+                    tracker
+                        .handle(msg)
+                        .into_iter()
+                        .map(|view| Ok(pubsub::Result::Confirmation(Box::new(view)))),
+                )
+            })
+            .boxed();
+
+        LocalSubscription { id, stream }
+    }
+
     fn subscribe_mint_block(&mut self, ctx: &mut ServiceContext<Self>) -> LocalSubscription {
         let (sender, receiver) = mpsc::unbounded();
         let id = self.next_id();
@@ -517,7 +619,8 @@ impl PubSubService {
 mod tests {
     use super::parse_subscribe_params_value;
     use serde_json::json;
-    use starcoin_rpc_api::types::pubsub::Kind;
+    use starcoin_crypto::HashValue;
+    use starcoin_rpc_api::types::pubsub::{ConfirmationDepth, ConfirmationParams, Kind, Params};
 
     #[test]
     fn parse_kind_only() {
@@ -542,6 +645,25 @@ mod tests {
         assert_eq!(parsed.0, Kind::NewHeads);
         assert!(parsed.1.is_none());
     }
+
+    #[test]
+    fn parse_confirmation_params() {
+        let parsed = parse_subscribe_params_value(json!([
+            { "type_name": "confirmations" },
+            { "txn_hash": HashValue::zero(), "threshold": 6, "depth": "selected_chain" }
+        ]))
+        .expect("parse confirmation params");
+        assert_eq!(parsed.0, Kind::Confirmations);
+        assert_eq!(
+            parsed.1,
+            Some(Params::Confirmations(ConfirmationParams {
+                txn_hash: Some(HashValue::zero()),
+                event_filter: None,
+                threshold: 6,
+                depth: ConfirmationDepth::SelectedChain,
+            }))
+        );
+    }
 }
 
 impl ActorService for PubSubService {
@@ -550,6 +672,7 @@ impl ActorService for PubSubService {
         ctx.subscribe::<NewHeadNotification>();
         ctx.subscribe::<ContractEventNotification>();
         ctx.subscribe::<MintBlockEvent>();
+        ctx.subscribe::<ChainReorgEvent>();
 
         Ok(())
     }
@@ -557,6 +680,10 @@ impl ActorService for PubSubService {
 
 impl ActorEventHandler<Self, NewHeadNotification> for PubSubService {
     fn handle_event(&mut self, msg: NewHeadNotification, _ctx: &mut ServiceContext<PubSubService>) {
+        send_to_all(
+            &mut self.confirmation_subscribers,
+            ConfirmationNotification::NewHead(msg.0.header.id()),
+        );
         send_to_all(&mut self.new_header_subscribers, msg);
     }
 }
@@ -567,6 +694,10 @@ impl ActorEventHandler<Self, ContractEventNotification> for PubSubService {
         msg: ContractEventNotification,
         _ctx: &mut ServiceContext<PubSubService>,
     ) {
+        send_to_all(
+            &mut self.confirmation_subscribers,
+            ConfirmationNotification::Events(msg.0 .3.clone()),
+        );
         send_to_all(&mut self.new_event_subscribers, msg);
     }
 }
@@ -577,6 +708,12 @@ impl ActorEventHandler<Self, MintBlockEvent> for PubSubService {
     }
 }
 
+impl ActorEventHandler<Self, ChainReorgEvent> for PubSubService {
+    fn handle_event(&mut self, msg: ChainReorgEvent, _ctx: &mut ServiceContext<PubSubService>) {
+        send_to_all(&mut self.reorg_subscribers, msg);
+    }
+}
+
 #[derive(Debug)]
 struct Subscribe {
     kind: pubsub::Kind,
@@ -610,7 +747,7 @@ impl ServiceHandler<Self, Subscribe> for PubSubService {
                 let (decode, filter) = match param {
                     Params::Events(e) => (e.decode, e.filter.try_into()),
                     Params::EventsV2(e) => (e.decode, e.filter.try_into()),
-                    Params::None => {
+                    Params::None | Params::Confirmations(_) => {
                         return Err(errors::invalid_params(
                             "events",
                             "Expected a filter object.",
@@ -628,6 +765,27 @@ impl ServiceHandler<Self, Subscribe> for PubSubService {
                 "Expected a filter object.",
             )),
             (pubsub::Kind::NewMintBlock, _) => Ok(self.subscribe_mint_block(ctx)),
+            (pubsub::Kind::Confirmations, Some(Params::Confirmations(params))) => {
+                let target = match (params.txn_hash, params.event_filter) {
+                    (Some(txn_hash), None) => ConfirmationTarget::Txn(txn_hash),
+                    (None, Some(filter)) => ConfirmationTarget::Events(filter.try_into()?),
+                    _ => {
+                        return Err(errors::invalid_params(
+                            "confirmations",
+                            "Expected either a txn_hash or an event_filter.",
+                        ));
+                    }
+                };
+                Ok(self.subscribe_confirmations(target, &params))
+            }
+            (pubsub::Kind::Confirmations, _) => Err(errors::invalid_params(
+                "confirmations",
+                "Expected confirmation parameters.",
+            )),
+            (pubsub::Kind::Reorgs, None) => Ok(self.subscribe_reorgs()),
+            (pubsub::Kind::Reorgs, _) => {
+                Err(errors::invalid_params("reorgs", "Expected no parameters."))
+            }
         }
     }
 }
@@ -644,6 +802,8 @@ impl ServiceHandler<Self, Unsubscribe> for PubSubService {
         let mut removed = false;
         removed = self.new_header_subscribers.remove(&msg.0).is_some() || removed;
         removed = self.new_event_subscribers.remove(&msg.0).is_some() || removed;
+        removed = self.reorg_subscribers.remove(&msg.0).is_some() || removed;
+        removed = self.confirmation_subscribers.remove(&msg.0).is_some() || removed;
 
         let mint_removed = self.mint_block_subscribers.remove(&msg.0).is_some();
         removed = mint_removed || removed;
@@ -723,6 +883,15 @@ impl EventHandler<MintBlockEvent> for NewMintBlockHandler {
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub struct ReorgHandler;
+
+impl EventHandler<ChainReorgEvent> for ReorgHandler {
+    fn handle(&self, msg: ChainReorgEvent) -> Vec<anyhow::Result<pubsub::Result>> {
+        vec![Ok(pubsub::Result::Reorg(Box::new(msg)))]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ContractEventHandler {
     filter: Filter,
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use jsonrpsee::core::{async_trait, RpcResult};
+use network_api::peer_score::PeerQualityView;
 use network_api::PeerStrategy;
 use network_p2p_types::peer_id::PeerId;
 use starcoin_rpc_api::{sync_manager::SyncManagerApiServer, types::SyncStatusView};
@@ -78,4 +79,13 @@ where
             .map_err(crate::module::map_jsonrpc_err)?;
         Ok(result)
     }
+
+    async fn peer_quality(&self) -> RpcResult<Vec<PeerQualityView>> {
+        let service = self.service.clone();
+        let result = service
+            .sync_peer_quality()
+            .await
+            .map_err(crate::module::map_jsonrpc_err)?;
+        Ok(result)
+    }
 }
@@ -1,7 +1,8 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::module::{convert_to_rpc_error, map_err};
+use crate::module::gas_oracle::MAX_GAS_ORACLE_PENDING_TXNS;
+use crate::module::{convert_to_rpc_error, map_err, GasPriceOracle};
 use bcs_ext::BCSCodec;
 use jsonrpsee::core::{async_trait, RpcResult};
 use starcoin_crypto::HashValue;
@@ -9,9 +10,9 @@ use starcoin_rpc_api::txpool::TxPoolApiServer;
 /// Re-export the API
 use starcoin_rpc_api::{
     multi_types::MultiSignedUserTransactionView,
-    types::{SignedUserTransactionView, StrView},
+    types::{GasPriceSuggestionView, SignedUserTransactionView, StrView},
 };
-use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService};
+use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService, LOCAL_RPC_PEER_ID};
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::multi_transaction::{
     MultiAccountAddress, MultiSignedUserTransaction, MultiTransactionError,
@@ -27,6 +28,7 @@ where
     S: TxPoolSyncService + 'static,
 {
     service: S,
+    gas_oracle: Option<GasPriceOracle>,
 }
 
 impl<S> TxPoolRpcImpl<S>
@@ -34,7 +36,30 @@ where
     S: TxPoolSyncService,
 {
     pub fn new(service: S) -> Self {
-        Self { service }
+        Self {
+            service,
+            gas_oracle: None,
+        }
+    }
+
+    pub fn with_gas_price_oracle(mut self, gas_oracle: GasPriceOracle) -> Self {
+        self.gas_oracle = Some(gas_oracle);
+        self
+    }
+
+    fn suggest_gas_price(&self) -> RpcResult<Option<GasPriceSuggestionView>> {
+        let gas_oracle = match self.gas_oracle.as_ref() {
+            Some(gas_oracle) => gas_oracle,
+            None => return Ok(None),
+        };
+        let pending = self
+            .service
+            .get_pending_txns(Some(MAX_GAS_ORACLE_PENDING_TXNS), None)
+            .map_err(crate::module::map_jsonrpc_err)?;
+        gas_oracle
+            .suggest(&pending)
+            .map(Some)
+            .map_err(crate::module::map_jsonrpc_err)
     }
 
     fn submit_multi_signed_transactions(
@@ -55,9 +80,12 @@ where
 
     fn submit_transaction_multi(&self, txn: MultiSignedUserTransaction) -> RpcResult<HashValue> {
         let bypass_vm1_limit = matches!(txn, MultiSignedUserTransaction::VM2(_));
-        let local_peer_id = (!bypass_vm1_limit).then(|| "local-rpc".to_string());
-        self.submit_multi_signed_transactions(vec![txn], bypass_vm1_limit, local_peer_id)
-            .map(|mut txn_hashes| txn_hashes.pop().expect("single txn must yield one hash"))
+        self.submit_multi_signed_transactions(
+            vec![txn],
+            bypass_vm1_limit,
+            Some(LOCAL_RPC_PEER_ID.to_string()),
+        )
+        .map(|mut txn_hashes| txn_hashes.pop().expect("single txn must yield one hash"))
     }
 
     fn ensure_submission_results(
@@ -101,7 +129,7 @@ where
         txns: Vec<SignedUserTransaction>,
     ) -> RpcResult<Vec<HashValue>> {
         let txns = txns.into_iter().map(Into::into).collect();
-        self.submit_multi_signed_transactions(txns, false, Some("local-rpc".to_string()))
+        self.submit_multi_signed_transactions(txns, false, Some(LOCAL_RPC_PEER_ID.to_string()))
     }
 
     async fn submit_hex_transaction(&self, tx: String) -> RpcResult<HashValue> {
@@ -123,10 +151,24 @@ where
     }
 
     async fn gas_price(&self) -> RpcResult<StrView<u64>> {
-        let gas_price = 1u64;
+        let gas_price = self
+            .suggest_gas_price()?
+            .map(|suggestion| suggestion.normal.0)
+            .unwrap_or(1u64);
         Ok(gas_price.into())
     }
 
+    async fn gas_price_suggestion(&self) -> RpcResult<GasPriceSuggestionView> {
+        Ok(self
+            .suggest_gas_price()?
+            .unwrap_or_else(|| GasPriceSuggestionView {
+                slow: 1u64.into(),
+                normal: 1u64.into(),
+                fast: 1u64.into(),
+                sample_size: 0,
+            }))
+    }
+
     async fn pending_txns(
         &self,
         addr: AccountAddress,
@@ -3,7 +3,9 @@
 SCRIPT_PATH="$( cd "$( dirname "$0" )" >/dev/null 2>&1 && pwd )"
 cd "$SCRIPT_PATH/.." || exit
 
-cargo build -p move-package-manager2
+# built in its own invocation, the vm tracing of the coverage is not enabled for the node.
+cargo build -p move-package-manager2 --features coverage
+cargo test -p move-package-manager2 --features coverage || exit
 MPM=./target/debug/mpm2
 
 # Unit test
@@ -13,6 +15,9 @@ $MPM package test -p ./vm2/framework/starcoin-token -t 8 -i 400000000 || exit
 $MPM package test -p ./vm2/framework/starcoin-token-objects -t 8 -i 400000000 || exit
 $MPM package test -p ./vm2/framework/starcoin-framework  -t 8 -i 400000000 || exit
 
+# Coverage
+$MPM coverage -p ./vm2/framework/move-stdlib -t 8 -i 400000000 --skip-integration-test || exit
+
 # Integration test
 export RUST_TEST_THREADS=32
 $MPM integration-test -p ./vm2/framework/starcoin-framework || exit
\ No newline at end of file
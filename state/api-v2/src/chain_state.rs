@@ -196,6 +196,17 @@ where
         self.reader.get_account_resource(*address)
     }
 
+    /// Get AccountResource by address, None if the account does not exist
+    pub fn get_account_resource_opt(
+        &self,
+        address: &AccountAddress,
+    ) -> Result<Option<AccountResource>> {
+        self.reader
+            .get_state_value_bytes(&StateKey::resource_typed::<AccountResource>(address)?)?
+            .map(|bytes| bcs_ext::from_bytes(&bytes))
+            .transpose()
+    }
+
     /// Get Resource by type
     pub fn get_resource<R>(&self, address: AccountAddress) -> Result<R>
     where
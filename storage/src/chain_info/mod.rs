@@ -1,11 +1,14 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::batch::WriteBatch;
 use crate::storage::{ColumnFamily, InnerStorage, KVStore};
 use crate::{StorageVersion, CHAIN_INFO_PREFIX_NAME};
 use anyhow::Result;
+use bcs_ext::BCSCodec;
 use starcoin_crypto::HashValue;
 use starcoin_types::startup_info::{BarnardHardFork, DragonHardFork, SnapshotRange, StartupInfo};
+use starcoin_types::system_events::ChainReorgEvent;
 use std::convert::{TryFrom, TryInto};
 
 #[derive(Clone)]
@@ -29,6 +32,10 @@ impl ChainInfoStorage {
     const SNAPSHOT_RANGE_KEY: &'static str = "snapshot_height";
     const BARNARD_HARD_FORK: &'static str = "barnard_hard_fork";
     const DRAGON_HARD_FORK: &'static str = "dragon_hard_fork";
+    const REORG_NEXT_SEQUENCE_KEY: &'static str = "reorg_next_sequence";
+    const REORG_KEY_PREFIX: &'static str = "reorg_";
+    /// The reorg log keeps the latest reorgs only.
+    const MAX_REORG_LOG_LEN: u64 = 1024;
 
     pub fn get_startup_info(&self) -> Result<Option<StartupInfo>> {
         self.get(Self::STARTUP_INFO_KEY.as_bytes())
@@ -128,4 +135,48 @@ impl ChainInfoStorage {
             dragon_hard_fork.try_into()?,
         )
     }
+
+    fn reorg_key(sequence: u64) -> Vec<u8> {
+        let mut key = Self::REORG_KEY_PREFIX.as_bytes().to_vec();
+        key.extend_from_slice(&sequence.to_be_bytes());
+        key
+    }
+
+    fn get_reorg_next_sequence(&self) -> Result<u64> {
+        self.get(Self::REORG_NEXT_SEQUENCE_KEY.as_bytes())
+            .and_then(|bytes| match bytes {
+                Some(bytes) => Ok(u64::from_be_bytes(bytes.as_slice().try_into()?)),
+                None => Ok(0),
+            })
+    }
+
+    /// Get the logged reorgs with sequence numbers not less than `since`, oldest first.
+    pub fn get_reorgs(&self, since: u64) -> Result<Vec<ChainReorgEvent>> {
+        let next_sequence = self.get_reorg_next_sequence()?;
+        let first = since.max(next_sequence.saturating_sub(Self::MAX_REORG_LOG_LEN));
+        let mut reorgs = vec![];
+        for sequence in first..next_sequence {
+            if let Some(bytes) = self.get(&Self::reorg_key(sequence))? {
+                reorgs.push(ChainReorgEvent::decode(bytes.as_slice())?);
+            }
+        }
+        Ok(reorgs)
+    }
+
+    /// Save `reorg` under the next sequence number and drop the reorg which falls out of the log.
+    pub fn append_reorg(&self, mut reorg: ChainReorgEvent) -> Result<ChainReorgEvent> {
+        let sequence = self.get_reorg_next_sequence()?;
+        reorg.sequence = sequence;
+        let mut batch = WriteBatch::new();
+        batch.put(Self::reorg_key(sequence), reorg.encode()?)?;
+        batch.put(
+            Self::REORG_NEXT_SEQUENCE_KEY.as_bytes().to_vec(),
+            (sequence + 1).to_be_bytes().to_vec(),
+        )?;
+        if sequence >= Self::MAX_REORG_LOG_LEN {
+            batch.delete(Self::reorg_key(sequence - Self::MAX_REORG_LOG_LEN))?;
+        }
+        self.write_batch_sync(batch)?;
+        Ok(reorg)
+    }
 }
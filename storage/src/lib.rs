@@ -9,7 +9,7 @@ use crate::block::BlockStorage;
 use crate::block_info::{BlockInfoStore, StcBlockInfoStorage};
 use crate::chain_info::ChainInfoStorage;
 use crate::contract_event::StcContractEventStorage;
-use crate::state_node::{StateStorage, StateStorageV2};
+use crate::state_node::{get_or_fetch, StateNodeSource, StateStorage, StateStorageV2};
 use crate::storage::{CodecKVStore, CodecWriteBatch, ColumnFamilyName, StorageInstance};
 use crate::table_info::{StcTableInfoStorage, TableInfoStore};
 use crate::transaction::StcTransactionStorage;
@@ -23,6 +23,7 @@ use starcoin_state_store_api::{StateNode, StateNodeStore};
 use starcoin_types::contract_event::StcContractEvent;
 use starcoin_types::multi_state::MultiState;
 use starcoin_types::startup_info::{ChainInfo, ChainStatus, SnapshotRange};
+use starcoin_types::system_events::ChainReorgEvent;
 use starcoin_types::table::{StcTableHandle, StcTableInfo};
 use starcoin_types::transaction::{StcRichTransactionInfo, StcTransaction};
 use starcoin_types::{
@@ -151,6 +152,11 @@ pub trait BlockStore {
     fn get_snapshot_range(&self) -> Result<Option<SnapshotRange>>;
     fn save_snapshot_range(&self, snapshot_height: SnapshotRange) -> Result<()>;
 
+    /// Save `reorg` to the reorg log, return it with its sequence number.
+    fn append_reorg(&self, reorg: ChainReorgEvent) -> Result<ChainReorgEvent>;
+    /// Get the logged reorgs with sequence numbers not less than `since`, oldest first.
+    fn get_reorgs(&self, since: u64) -> Result<Vec<ChainReorgEvent>>;
+
     // DAG sync block methods
     fn save_dag_sync_block(&self, block: crate::block::DagSyncBlock) -> Result<()>;
     fn get_dag_sync_block(&self, block_id: HashValue)
@@ -254,6 +260,7 @@ pub struct Storage {
     event_storage: StcContractEventStorage,
     chain_info_storage: ChainInfoStorage,
     table_info_storage: StcTableInfoStorage,
+    pub(crate) state_node_source: Option<Arc<dyn StateNodeSource>>,
     // instance: StorageInstance,
 }
 
@@ -278,11 +285,18 @@ impl Storage {
             event_storage: StcContractEventStorage::new(instance.clone()),
             chain_info_storage: ChainInfoStorage::new(instance.clone()),
             table_info_storage: StcTableInfoStorage::new(instance),
+            state_node_source: None,
             // instance,
         };
         Ok(storage)
     }
 
+    /// Fetch the state nodes missing in this storage from `source`, and cache them.
+    pub fn with_state_node_source(mut self, source: Arc<dyn StateNodeSource>) -> Self {
+        self.state_node_source = Some(source);
+        self
+    }
+
     pub fn get_block_accumulator_storage(&self) -> AccumulatorStorage<BlockAccumulatorStorage> {
         self.block_accumulator_storage.clone()
     }
@@ -296,7 +310,12 @@ impl Storage {
 
 impl StateNodeStore for Storage {
     fn get(&self, hash: &HashValue) -> Result<Option<StateNode>> {
-        self.state_node_storage.get(*hash)
+        get_or_fetch(
+            &self.state_node_storage,
+            self.state_node_source.as_ref(),
+            *hash,
+            |source, hash| source.get_state_node(hash),
+        )
     }
 
     fn put(&self, key: HashValue, node: StateNode) -> Result<()> {
@@ -440,6 +459,14 @@ impl BlockStore for Storage {
         self.chain_info_storage.save_snapshot_range(snapshot_range)
     }
 
+    fn append_reorg(&self, reorg: ChainReorgEvent) -> Result<ChainReorgEvent> {
+        self.chain_info_storage.append_reorg(reorg)
+    }
+
+    fn get_reorgs(&self, since: u64) -> Result<Vec<ChainReorgEvent>> {
+        self.chain_info_storage.get_reorgs(since)
+    }
+
     fn save_dag_sync_block(&self, block: crate::block::DagSyncBlock) -> Result<()> {
         self.block_storage.save_dag_sync_block(block)
     }
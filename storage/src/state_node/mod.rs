@@ -2,11 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    define_storage, storage::ValueCodec, STATE_NODE_PREFIX_NAME, STATE_NODE_PREFIX_NAME_V2,
+    define_storage,
+    storage::{CodecKVStore, ValueCodec},
+    STATE_NODE_PREFIX_NAME, STATE_NODE_PREFIX_NAME_V2,
 };
 use anyhow::Result;
 use starcoin_crypto::HashValue;
 use starcoin_state_store_api::StateNode;
+use std::sync::Arc;
 
 define_storage!(StateStorage, HashValue, StateNode, STATE_NODE_PREFIX_NAME);
 define_storage!(
@@ -24,3 +27,38 @@ impl ValueCodec for StateNode {
         Ok(StateNode(data.to_vec()))
     }
 }
+
+/// Where the state tree nodes missing in the local storage are fetched from, such as the chain a
+/// dev node forks from. The fetched nodes are cached in the local storage.
+pub trait StateNodeSource: Send + Sync {
+    /// Get a node of the vm1 state tree.
+    fn get_state_node(&self, hash: &HashValue) -> Result<Option<StateNode>>;
+
+    /// Get a node of the vm2 state tree.
+    fn get_state_node2(&self, hash: &HashValue) -> Result<Option<StateNode>>;
+}
+
+/// Get the node from `local`, fall back to `fetch` and cache the node on a miss.
+pub(crate) fn get_or_fetch<S, F>(
+    local: &S,
+    source: Option<&Arc<dyn StateNodeSource>>,
+    hash: HashValue,
+    fetch: F,
+) -> Result<Option<StateNode>>
+where
+    S: CodecKVStore<HashValue, StateNode>,
+    F: FnOnce(&dyn StateNodeSource, &HashValue) -> Result<Option<StateNode>>,
+{
+    if let Some(node) = local.get(hash)? {
+        return Ok(Some(node));
+    }
+    let source = match source {
+        Some(source) => source,
+        None => return Ok(None),
+    };
+    let node = fetch(source.as_ref(), &hash)?;
+    if let Some(node) = node.as_ref() {
+        local.put(hash, node.clone())?;
+    }
+    Ok(node)
+}
@@ -15,6 +15,7 @@ use crate::{
 use anyhow::Result;
 use starcoin_config::RocksdbConfig;
 use starcoin_crypto::HashValue;
+use starcoin_types::system_events::ChainReorgEvent;
 use starcoin_types::table::StcTableInfo;
 use starcoin_types::transaction::StcRichTransactionInfo;
 use starcoin_types::{
@@ -246,6 +247,38 @@ pub fn test_snapshot_range() -> Result<()> {
     Ok(())
 }
 
+#[test]
+pub fn test_reorg_log() -> Result<()> {
+    let tmpdir = starcoin_config::temp_dir();
+    let instance = StorageInstance::new_cache_and_db_instance(
+        CacheStorage::new(None),
+        DBStorage::new(tmpdir.path(), RocksdbConfig::default(), None)?,
+    );
+    let storage = Storage::new(instance)?;
+    assert!(storage.get_reorgs(0)?.is_empty());
+    for i in 0..3u64 {
+        let reorg = storage.append_reorg(ChainReorgEvent {
+            sequence: 0,
+            ancestor: HashValue::random(),
+            ancestor_number: i,
+            removed: vec![HashValue::random()],
+            added: vec![HashValue::random(), HashValue::random()],
+            timestamp: i,
+        })?;
+        assert_eq!(reorg.sequence, i);
+    }
+    let reorgs = storage.get_reorgs(1)?;
+    assert_eq!(
+        reorgs
+            .iter()
+            .map(|reorg| reorg.sequence)
+            .collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    assert_eq!(reorgs[0].ancestor_number, 1);
+    Ok(())
+}
+
 #[test]
 pub fn test_cache_evict_multi_get() -> Result<()> {
     let tmpdir = starcoin_config::temp_dir();
@@ -1,6 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::state_node::get_or_fetch;
 use crate::storage::{CodecKVStore, CodecWriteBatch};
 use crate::{IntoSuper, Storage};
 use starcoin_crypto::HashValue;
@@ -14,7 +15,12 @@ pub struct Storage2(pub Arc<Storage>);
 
 impl StateNodeStore for Storage2 {
     fn get(&self, hash: &HashValue) -> anyhow::Result<Option<StateNode>> {
-        self.deref().state_node_storage2.get(*hash)
+        get_or_fetch(
+            &self.deref().state_node_storage2,
+            self.deref().state_node_source.as_ref(),
+            *hash,
+            |source, hash| source.get_state_node2(hash),
+        )
     }
 
     fn put(&self, key: HashValue, node: StateNode) -> anyhow::Result<()> {
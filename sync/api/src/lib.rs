@@ -4,6 +4,7 @@
 use std::cmp::Ordering;
 
 use anyhow::Result;
+use network_api::peer_score::PeerQualityView;
 use network_api::PeerId;
 use network_api::PeerStrategy;
 use schemars::JsonSchema;
@@ -194,3 +195,10 @@ impl From<Option<Vec<(PeerId, u64)>>> for PeerScoreResponse {
         Self { peers }
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct PeerQualityRequest;
+
+impl ServiceRequest for PeerQualityRequest {
+    type Response = Vec<PeerQualityView>;
+}
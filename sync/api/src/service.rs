@@ -2,10 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    PeerScoreRequest, PeerScoreResponse, SyncCancelRequest, SyncProgressReport,
+    PeerQualityRequest, PeerScoreRequest, PeerScoreResponse, SyncCancelRequest, SyncProgressReport,
     SyncProgressRequest, SyncStartRequest, SyncStatusRequest,
 };
 use anyhow::Result;
+use network_api::peer_score::PeerQualityView;
 use network_api::PeerId;
 use network_api::PeerStrategy;
 use starcoin_service_registry::{ActorService, ServiceHandler, ServiceRef};
@@ -30,6 +31,11 @@ pub trait SyncAsyncService: Clone + std::marker::Unpin + Send + Sync {
     fn sync_peer_score(
         &self,
     ) -> impl std::future::Future<Output = Result<PeerScoreResponse>> + Send;
+
+    /// The rpc quality scoreboard of all peers ever requested by sync.
+    fn sync_peer_quality(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Vec<PeerQualityView>>> + Send;
 }
 
 pub trait SyncServiceHandler:
@@ -39,6 +45,7 @@ pub trait SyncServiceHandler:
     + ServiceHandler<Self, SyncCancelRequest>
     + ServiceHandler<Self, SyncStartRequest>
     + ServiceHandler<Self, PeerScoreRequest>
+    + ServiceHandler<Self, PeerQualityRequest>
 {
 }
 
@@ -77,4 +84,8 @@ where
     async fn sync_peer_score(&self) -> Result<PeerScoreResponse> {
         self.send(PeerScoreRequest {}).await
     }
+
+    async fn sync_peer_quality(&self) -> Result<Vec<PeerQualityView>> {
+        self.send(PeerQualityRequest).await
+    }
 }
@@ -1,12 +1,12 @@
 use crate::verified_rpc_client::VerifiedRpcClient;
 use anyhow::Result;
 use network_api::messages::PeerAnnouncementMessage;
-use network_api::{PeerProvider, PeerSelector, PeerStrategy, ReputationChange};
+use network_api::{PeerId, PeerProvider, PeerSelector, PeerStrategy, ReputationChange};
 use starcoin_config::NodeConfig;
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_network::NetworkServiceRef;
-use starcoin_network_rpc_api::GetTxnsWithHash;
+use starcoin_network_rpc_api::{GetTxnsWithHash, MAX_TXN_REQUEST_SIZE};
 use starcoin_service_registry::{ActorService, EventHandler, ServiceContext, ServiceFactory};
 use starcoin_storage::{BlockTransactionInfoStore, Storage};
 use starcoin_txpool::TxPoolService;
@@ -14,104 +14,250 @@ use starcoin_txpool_api::TxPoolSyncService;
 use starcoin_types::multi_transaction::MultiTransactionError;
 use starcoin_types::transaction::TransactionError;
 use starcoin_vm2_vm_types::transaction::TransactionError as TransactionError2;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// An announced txn is pulled from one peer at a time, the other announcers are remembered and
+/// the txn is pulled from the next one if it has not arrived when the pull times out.
+const TXN_PULL_TIMEOUT: Duration = Duration::from_secs(10);
+/// How often the timed out pulls are checked.
+const TXN_PULL_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+/// Max count of txns being pulled at the same time.
+const MAX_PULLING_TXNS: usize = 10240;
+/// Max count of other announcers remembered for a txn being pulled.
+const MAX_TXN_ANNOUNCERS: usize = 8;
+
+struct PullingTxn {
+    begin: Instant,
+    /// Peers which also announced the txn and have not been asked for it yet.
+    announcers: VecDeque<PeerId>,
+}
+
+/// Retry the timed out pulls from the other announcers.
+#[derive(Clone, Debug)]
+struct RetryTxnPullEvent;
 
 /// Service which handle Announcement message
 pub struct AnnouncementService {
     storage: Arc<Storage>,
     txpool: TxPoolService,
+    pulling_txns: HashMap<HashValue, PullingTxn>,
 }
 
 impl AnnouncementService {
     fn new(storage: Arc<Storage>, txpool: TxPoolService) -> Self {
-        Self { storage, txpool }
+        Self {
+            storage,
+            txpool,
+            pulling_txns: HashMap::new(),
+        }
     }
-}
 
-impl ActorService for AnnouncementService {}
+    fn is_unknown_txn(txpool: &TxPoolService, storage: &Storage, txn_id: &HashValue) -> bool {
+        txpool.find_txn(txn_id).is_none()
+            && matches!(
+                storage.get_transaction_info_by_rich_info_id(*txn_id),
+                Ok(None)
+            )
+    }
 
-impl ServiceFactory<Self> for AnnouncementService {
-    fn create(ctx: &mut ServiceContext<Self>) -> Result<Self> {
-        let storage = ctx.get_shared::<Arc<Storage>>()?;
-        let txpool_service = ctx.get_shared::<TxPoolService>()?;
+    /// Remember the peer as another announcer of the txns already being pulled, and mark the
+    /// others as being pulled from the peer.
+    fn start_pulling(&mut self, peer_id: &PeerId, ids: Vec<HashValue>) -> Vec<HashValue> {
+        let now = Instant::now();
+        let mut pulling = vec![];
+        for id in ids {
+            if let Some(txn) = self.pulling_txns.get_mut(&id) {
+                if txn.announcers.len() < MAX_TXN_ANNOUNCERS && !txn.announcers.contains(peer_id) {
+                    txn.announcers.push_back(peer_id.clone());
+                }
+                continue;
+            }
+            if self.pulling_txns.len() >= MAX_PULLING_TXNS {
+                continue;
+            }
+            self.pulling_txns.insert(
+                id,
+                PullingTxn {
+                    begin: now,
+                    announcers: VecDeque::new(),
+                },
+            );
+            pulling.push(id);
+        }
+        pulling
+    }
 
-        Ok(Self::new(storage, txpool_service))
+    /// Drop the timed out pulls whose txn has arrived or has no other announcer, and move the
+    /// others to their next announcer. Return the txns to pull again grouped by peer.
+    fn retry_timed_out_pulls(&mut self) -> HashMap<PeerId, Vec<HashValue>> {
+        let now = Instant::now();
+        let txpool = &self.txpool;
+        let storage = &self.storage;
+        let mut retries: HashMap<PeerId, Vec<HashValue>> = HashMap::new();
+        self.pulling_txns.retain(|id, txn| {
+            if now.saturating_duration_since(txn.begin) < TXN_PULL_TIMEOUT {
+                return true;
+            }
+            if !Self::is_unknown_txn(txpool, storage, id) {
+                return false;
+            }
+            match txn.announcers.pop_front() {
+                Some(peer_id) => {
+                    txn.begin = now;
+                    retries.entry(peer_id).or_default().push(*id);
+                    true
+                }
+                None => false,
+            }
+        });
+        retries
     }
-}
 
-impl EventHandler<Self, PeerAnnouncementMessage> for AnnouncementService {
-    fn handle_event(
-        &mut self,
-        announcement_msg: PeerAnnouncementMessage,
+    fn spawn_pull_txns(
+        &self,
+        peer_id: PeerId,
+        ids: Vec<HashValue>,
         ctx: &mut ServiceContext<Self>,
     ) {
-        let txpool = self.txpool.clone();
-        let storage = self.storage.clone();
         let network = ctx
             .get_shared::<NetworkServiceRef>()
             .expect("NetworkServiceRef not exist.");
-        let peer_id = announcement_msg.peer_id.clone();
-        debug!("[sync] receive announcement msg : {:?}", announcement_msg);
         let config = ctx
             .get_shared::<Arc<NodeConfig>>()
             .expect("NodeConfig not exist.");
         let max_retry_times = config.sync.max_retry_times();
+        let peer_selector = PeerSelector::new(Vec::new(), PeerStrategy::default(), None);
+        let rpc_client = VerifiedRpcClient::new(peer_selector, network.clone(), max_retry_times);
+        ctx.spawn(Self::pull_txns(
+            self.txpool.clone(),
+            network,
+            rpc_client,
+            peer_id,
+            ids,
+        ));
+    }
 
-        ctx.spawn(async move {
-            if announcement_msg.message.is_txn() {
-                let fresh_ids = announcement_msg.message.ids().into_iter().filter(|txn_id| {
-                    if txpool.find_txn(txn_id).is_none() {
-                        if let Ok(None) = storage.get_transaction_info_by_rich_info_id(*txn_id) {
-                            return true
-                        }
-                    }
-                    false
-                }).collect::<Vec<HashValue>>();
-
-                if !fresh_ids.is_empty() {
-                    let peer_selector =
-                        PeerSelector::new(Vec::new(), PeerStrategy::default(), None);
-                    let rpc_client = VerifiedRpcClient::new(
-                        peer_selector,
-                        network.clone(),
-                        max_retry_times,
+    async fn pull_txns(
+        txpool: TxPoolService,
+        network: NetworkServiceRef,
+        rpc_client: VerifiedRpcClient,
+        peer_id: PeerId,
+        ids: Vec<HashValue>,
+    ) {
+        for ids in ids.chunks(MAX_TXN_REQUEST_SIZE as usize) {
+            let txns = match rpc_client
+                .get_txns_with_hash_from_pool(
+                    Some(peer_id.clone()),
+                    GetTxnsWithHash { ids: ids.to_vec() },
+                )
+                .await
+            {
+                Ok((_, txns)) => txns,
+                Err(err) => {
+                    error!(
+                        "[sync] handle announcement msg result error: {:?}, peer_id:{:?} ",
+                        err, peer_id
                     );
-                    match rpc_client.get_txns_with_hash_from_pool(Some(peer_id.clone()), GetTxnsWithHash { ids:fresh_ids }).await {
-                        Err(err) => error!(
-                            "[sync] handle announcement msg result error: {:?}, peer_id:{:?} ",
+                    return;
+                }
+            };
+            let mut fresh_txns = Vec::new();
+            for txn in txns {
+                match txpool.verify_transaction(txn.clone()) {
+                    Ok(_) => fresh_txns.push(txn),
+                    Err(err) => {
+                        error!(
+                            "[sync] handle announcement msg error: {:?}, peer_id:{:?} ",
                             err, peer_id
-                        ),
-                        Ok((_, txns)) => {
-                            let mut fresh_txns = Vec::new();
-                            txns.into_iter().for_each(|txn| {
-                                match txpool.verify_transaction(txn.clone()) {
-                                    Ok(_) => fresh_txns.push(txn),
-                                    Err(err) => {
-                                        error!(
-                                            "[sync] handle announcement msg error: {:?}, peer_id:{:?} ",
-                                            err, peer_id
-                                        );
-                                        if let MultiTransactionError::VM1(TransactionError::InvalidSignature(_)) = err {
-                                            network.report_peer(peer_id.clone(), ReputationChange::new(i32::MIN / 2, "VM1 InvalidSignature"))
-                                        }
-
-                                        if let MultiTransactionError::VM2(TransactionError2::InvalidSignature(_)) = err {
-                                            network.report_peer(peer_id.clone(), ReputationChange::new(i32::MIN / 2, "VM2 InvalidSignature"))
-                                        }
-                                    }
-                                }
-                            });
-
-                            if !fresh_txns.is_empty() {
-                                if let Err(e) = txpool.add_txns_multi_signed(fresh_txns, true, None) {
-                                    error!("[sync] handle announcement msg error: {:?}, peer_id:{:?} ", e, peer_id);
-                                }
-                            }
+                        );
+                        if let MultiTransactionError::VM1(TransactionError::InvalidSignature(_)) =
+                            err
+                        {
+                            network.report_peer(
+                                peer_id.clone(),
+                                ReputationChange::new(i32::MIN / 2, "VM1 InvalidSignature"),
+                            )
+                        }
+
+                        if let MultiTransactionError::VM2(TransactionError2::InvalidSignature(_)) =
+                            err
+                        {
+                            network.report_peer(
+                                peer_id.clone(),
+                                ReputationChange::new(i32::MIN / 2, "VM2 InvalidSignature"),
+                            )
                         }
                     }
                 }
             }
+
+            if !fresh_txns.is_empty() {
+                if let Err(e) = txpool.add_txns_multi_signed(fresh_txns, true, None) {
+                    error!(
+                        "[sync] handle announcement msg error: {:?}, peer_id:{:?} ",
+                        e, peer_id
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl ActorService for AnnouncementService {
+    fn started(&mut self, ctx: &mut ServiceContext<Self>) -> Result<()> {
+        ctx.run_interval(TXN_PULL_CHECK_INTERVAL, |ctx| {
+            ctx.notify(RetryTxnPullEvent);
         });
+        Ok(())
+    }
+}
+
+impl ServiceFactory<Self> for AnnouncementService {
+    fn create(ctx: &mut ServiceContext<Self>) -> Result<Self> {
+        let storage = ctx.get_shared::<Arc<Storage>>()?;
+        let txpool_service = ctx.get_shared::<TxPoolService>()?;
+
+        Ok(Self::new(storage, txpool_service))
+    }
+}
+
+impl EventHandler<Self, PeerAnnouncementMessage> for AnnouncementService {
+    fn handle_event(
+        &mut self,
+        announcement_msg: PeerAnnouncementMessage,
+        ctx: &mut ServiceContext<Self>,
+    ) {
+        debug!("[sync] receive announcement msg : {:?}", announcement_msg);
+        if !announcement_msg.message.is_txn() {
+            return;
+        }
+        let peer_id = announcement_msg.peer_id;
+        let fresh_ids = announcement_msg
+            .message
+            .ids()
+            .into_iter()
+            .filter(|txn_id| Self::is_unknown_txn(&self.txpool, &self.storage, txn_id))
+            .collect::<Vec<HashValue>>();
+        let fresh_ids = self.start_pulling(&peer_id, fresh_ids);
+        if fresh_ids.is_empty() {
+            return;
+        }
+        self.spawn_pull_txns(peer_id, fresh_ids, ctx);
+    }
+}
+
+impl EventHandler<Self, RetryTxnPullEvent> for AnnouncementService {
+    fn handle_event(&mut self, _: RetryTxnPullEvent, ctx: &mut ServiceContext<Self>) {
+        for (peer_id, ids) in self.retry_timed_out_pulls() {
+            debug!(
+                "[sync] pull {} timed out txns again from peer {:?}",
+                ids.len(),
+                peer_id
+            );
+            self.spawn_pull_txns(peer_id, ids, ctx);
+        }
     }
 }
 
@@ -10,6 +10,7 @@ use starcoin_chain_service::WriteableChainService;
 use starcoin_config::{ChainNetwork, NodeConfig};
 use starcoin_consensus::Consensus;
 use starcoin_crypto::HashValue;
+use starcoin_storage::BlockStore;
 use starcoin_txpool_mock_service::MockTxPoolService;
 use starcoin_types::block::Block;
 use std::sync::Arc;
@@ -263,6 +264,84 @@ async fn test_block_chain_reset() -> anyhow::Result<()> {
         .get_main()
         .get_block_by_number(2)?
         .is_some());
+
+    let reorgs = writeable_block_chain_service
+        .get_main()
+        .get_storage()
+        .get_reorgs(0)?;
+    assert_eq!(reorgs.len(), 1);
+    assert_eq!(reorgs[0].ancestor, block.id());
+    assert_eq!(reorgs[0].removed.len() as u64, times - 3);
+    assert_eq!(reorgs[0].removed.last(), Some(&last_block));
+    assert!(reorgs[0].added.is_empty());
+    Ok(())
+}
+
+#[stest::test(timeout = 120)]
+async fn test_switch_new_main_emits_reorg() -> anyhow::Result<()> {
+    let times = 6;
+    let (mut writeable_block_chain_service, node_config, _, _) =
+        create_writeable_dag_block_chain().await;
+    let net = node_config.net();
+    let main_tip = gen_dag_blocks(times, &mut writeable_block_chain_service, net)?;
+    let genesis = writeable_block_chain_service
+        .get_main()
+        .get_header_by_number(0)?
+        .ok_or_else(|| format_err!("Genesis header is none"))?;
+
+    // Execute a heavier fork from the genesis without connecting it, as the sync task does.
+    let miner_account = AccountInfo::random();
+    let mut fork_blocks = vec![];
+    let mut parent_id = genesis.id();
+    for _ in 0..2 * times {
+        let block_chain = BlockChain::new(
+            net.time_service(),
+            parent_id,
+            writeable_block_chain_service.get_main().get_storage(),
+            writeable_block_chain_service.get_storage2(),
+            None,
+            writeable_block_chain_service.get_dag(),
+        )?;
+        let (block_template, _) = block_chain.create_block_template(
+            *miner_account.address(),
+            None,
+            Vec::new(),
+            None,
+            None,
+            Some(vec![parent_id]),
+            HashValue::zero(),
+        )?;
+        let block = block_chain
+            .consensus()
+            .create_block(block_template, net.time_service().as_ref())?;
+        parent_id = writeable_block_chain_service.execute(block)?.block().id();
+        fork_blocks.push(parent_id);
+    }
+    assert_eq!(
+        writeable_block_chain_service
+            .get_main()
+            .current_header()
+            .id(),
+        main_tip
+    );
+
+    assert!(writeable_block_chain_service.switch_main_if_heavier(parent_id)?);
+    assert_eq!(
+        writeable_block_chain_service
+            .get_main()
+            .current_header()
+            .id(),
+        parent_id
+    );
+    let reorgs = writeable_block_chain_service
+        .get_main()
+        .get_storage()
+        .get_reorgs(0)?;
+    assert_eq!(reorgs.len(), 1);
+    assert_eq!(reorgs[0].ancestor, genesis.id());
+    assert_eq!(reorgs[0].removed.len() as u64, times);
+    assert_eq!(reorgs[0].removed.last(), Some(&main_tip));
+    assert_eq!(reorgs[0].added, fork_blocks);
     Ok(())
 }
 
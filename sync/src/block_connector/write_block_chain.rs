@@ -23,9 +23,9 @@ use starcoin_types::multi_state::MultiState;
 #[cfg(test)]
 use starcoin_types::multi_transaction::MultiSignedUserTransaction;
 use starcoin_types::{
-    block::{Block, BlockHeader, ExecutedBlock},
+    block::{Block, BlockHeader, BlockIdAndNumber, ExecutedBlock},
     startup_info::StartupInfo,
-    system_events::{NewBranch, NewHeadBlock},
+    system_events::{ChainReorgEvent, NewBranch, NewHeadBlock},
 };
 #[cfg(test)]
 use starcoin_vm_types::account_address::AccountAddress;
@@ -292,6 +292,17 @@ where
     where
         TransactionPoolServiceT: TxPoolSyncService,
     {
+        if self.switch_main_if_heavier(new_head_block)? {
+            ctx.broadcast(NewHeadBlock {
+                executed_block: Arc::new(self.main.head_block()),
+            });
+        }
+        Ok(())
+    }
+
+    /// Switch the main chain to `new_head_block` if its total difficulty is larger, return
+    /// whether the main chain is switched.
+    pub(crate) fn switch_main_if_heavier(&mut self, new_head_block: HashValue) -> Result<bool> {
         let new_branch = BlockChain::new(
             self.config.net().time_service(),
             new_head_block,
@@ -304,14 +315,15 @@ where
         let main_total_difficulty = self.main.get_total_difficulty()?;
         let branch_total_difficulty = new_branch.get_total_difficulty()?;
         if branch_total_difficulty > main_total_difficulty {
+            let reorg = self.find_reorg(&new_branch)?;
             self.main = new_branch;
+            if let Some(reorg) = reorg {
+                self.broadcast_reorg(reorg);
+            }
             self.update_startup_info(self.main.head_block().header())?;
-            ctx.broadcast(NewHeadBlock {
-                executed_block: Arc::new(self.main.head_block()),
-            });
-            Ok(())
+            Ok(true)
         } else {
-            Ok(())
+            Ok(false)
         }
     }
 
@@ -328,7 +340,15 @@ where
                 } else {
                     (1, vec![executed_block.block().clone()], 0, vec![])
                 };
+            let reorg = if !parent_is_main_head {
+                self.find_reorg(&new_branch)?
+            } else {
+                None
+            };
             self.main = new_branch;
+            if let Some(reorg) = reorg {
+                self.broadcast_reorg(reorg);
+            }
 
             self.do_new_head(
                 executed_block,
@@ -427,7 +447,11 @@ where
 
         let executed_block = new_branch.head_block();
 
+        let reorg = self.find_reorg(&new_branch)?;
         self.main = new_branch;
+        if let Some(reorg) = reorg {
+            self.broadcast_reorg(reorg);
+        }
 
         let (enacted_count, enacted_blocks, retracted_count, retracted_blocks) =
             (1, vec![executed_block.block().clone()], 0, vec![]);
@@ -511,6 +535,61 @@ where
         Ok((enacted_count, enacted, retracted_count, retracted))
     }
 
+    /// The reorg from the main chain to `new_branch`, None if the main head is an ancestor of
+    /// the new branch.
+    fn find_reorg(&self, new_branch: &BlockChain) -> Result<Option<ChainReorgEvent>> {
+        let old_head = self.main.current_header();
+        let new_head = new_branch.current_header();
+        if old_head.number() <= new_head.number()
+            && new_branch.get_hash_by_number(old_head.number())? == Some(old_head.id())
+        {
+            return Ok(None);
+        }
+        // The new head is on the main chain when the chain is reset to an earlier block.
+        let ancestor = if new_head.number() <= old_head.number()
+            && self.main.get_hash_by_number(new_head.number())? == Some(new_head.id())
+        {
+            BlockIdAndNumber::new(new_head.id(), new_head.number())
+        } else {
+            self.main.find_ancestor(new_branch)?.ok_or_else(|| {
+                format_err!(
+                    "Can not find ancestors between main chain: {:?} and branch: {:?}",
+                    self.main.status(),
+                    new_branch.status()
+                )
+            })?
+        };
+        let removed = self.find_selected_chain_until(old_head.id(), ancestor.number)?;
+        let added = self.find_selected_chain_until(new_head.id(), ancestor.number)?;
+        Ok(Some(ChainReorgEvent {
+            sequence: 0,
+            ancestor: ancestor.id,
+            ancestor_number: ancestor.number,
+            removed,
+            added,
+            timestamp: self.config.net().time_service().now_millis(),
+        }))
+    }
+
+    /// The ids of the selected chain from the block after `number` to `from`.
+    fn find_selected_chain_until(&self, from: HashValue, number: u64) -> Result<Vec<HashValue>> {
+        let mut ids = vec![];
+        let mut block_id = from;
+        loop {
+            let header = self
+                .storage
+                .get_block_header_by_hash(block_id)?
+                .ok_or_else(|| format_err!("Can not find block header {:?}.", block_id))?;
+            if header.number() <= number {
+                break;
+            }
+            ids.push(block_id);
+            block_id = header.parent_hash();
+        }
+        ids.reverse();
+        Ok(ids)
+    }
+
     fn find_red_blocks(
         &self,
         selected_header: HashValue,
@@ -584,6 +663,23 @@ where
         }
     }
 
+    fn broadcast_reorg(&self, reorg: ChainReorgEvent) {
+        let reorg = match self.storage.append_reorg(reorg.clone()) {
+            Ok(reorg) => reorg,
+            Err(e) => {
+                error!("Save reorg {:?} error: {:?}", reorg, e);
+                reorg
+            }
+        };
+        info!(
+            "[chain] Reorg at ancestor {}, removed blocks: {:?}, added blocks: {:?}",
+            reorg.ancestor, reorg.removed, reorg.added
+        );
+        if let Err(e) = self.bus.broadcast(reorg) {
+            error!("Broadcast ChainReorgEvent error: {:?}", e);
+        }
+    }
+
     fn broadcast_new_branch(&self, block: ExecutedBlock) {
         if let Some(metrics) = self.metrics.as_ref() {
             metrics
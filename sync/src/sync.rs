@@ -12,7 +12,7 @@ use crate::verified_rpc_client::{RpcVerifyError, VerifiedRpcClient};
 use anyhow::{format_err, Result};
 use futures::FutureExt;
 use futures_timer::Delay;
-use network_api::peer_score::PeerScoreMetrics;
+use network_api::peer_score::{PeerQuality, PeerQualityView, PeerScoreMetrics};
 use network_api::{PeerId, PeerProvider, PeerSelector, PeerStrategy, ReputationChange};
 use starcoin_chain::verifier::FullVerifier;
 use starcoin_chain::{BlockChain, ChainWriter};
@@ -31,8 +31,8 @@ use starcoin_storage::block_info::BlockInfoStore;
 use starcoin_storage::Storage2;
 use starcoin_storage::{BlockStore, Storage, Store};
 use starcoin_sync_api::{
-    ParallelSyncStat, PeerScoreRequest, PeerScoreResponse, SyncAsyncService, SyncBlockSort,
-    SyncCancelRequest, SyncProgressReport, SyncProgressRequest, SyncServiceHandler,
+    ParallelSyncStat, PeerQualityRequest, PeerScoreRequest, PeerScoreResponse, SyncAsyncService,
+    SyncBlockSort, SyncCancelRequest, SyncProgressReport, SyncProgressRequest, SyncServiceHandler,
     SyncSpecificTargretRequest, SyncStartRequest, SyncStatusRequest, SyncTarget,
 };
 use starcoin_txpool::TxPoolService;
@@ -180,6 +180,7 @@ pub struct SyncService {
     dag: BlockDAG,
     metrics: Option<SyncMetrics>,
     peer_score_metrics: Option<PeerScoreMetrics>,
+    peer_quality: PeerQuality,
     vm_metrics: Option<VMMetrics>,
     pending_peer_sync_checks: Arc<Mutex<HashSet<PeerId>>>,
 }
@@ -222,6 +223,10 @@ impl SyncService {
             .metrics
             .registry()
             .and_then(|registry| PeerScoreMetrics::register(registry).ok());
+        let peer_quality = PeerQuality::new(
+            config.sync.peer_eviction_threshold(),
+            peer_score_metrics.clone(),
+        );
         Ok(Self {
             sync_status: SyncStatus::new(ChainStatus::new(head_block.header, head_block_info)),
             stage: SyncStage::NotStart,
@@ -232,6 +237,7 @@ impl SyncService {
             dag,
             metrics,
             peer_score_metrics,
+            peer_quality,
             vm_metrics,
             pending_peer_sync_checks: Arc::new(Mutex::new(HashSet::new())),
         })
@@ -243,6 +249,7 @@ impl SyncService {
         peer_strategy: Option<PeerStrategy>,
         peers: Vec<PeerId>,
         peer_score_metrics: Option<PeerScoreMetrics>,
+        peer_quality: PeerQuality,
     ) -> Result<Arc<VerifiedRpcClient>> {
         let peer_select_strategy =
             peer_strategy.unwrap_or_else(|| config.sync.peer_select_strategy());
@@ -298,10 +305,11 @@ impl SyncService {
             return Err(format_err!("[sync] No peers to sync."));
         }
 
-        Ok(Arc::new(VerifiedRpcClient::new(
+        Ok(Arc::new(VerifiedRpcClient::new_with_quality(
             peer_selector,
             network.clone(),
             config.sync.max_retry_times(),
+            peer_quality.with_reporter(Arc::new(network)),
         )))
     }
 
@@ -339,6 +347,7 @@ impl SyncService {
         let storage = self.storage.clone();
         let storage2 = self.storage2.clone();
         let dag = ctx.get_shared::<BlockDAG>()?;
+        let peer_quality = self.peer_quality.clone();
 
         let fut = async move {
             let verified_rpc_client = Self::create_verified_client(
@@ -348,6 +357,7 @@ impl SyncService {
                 msg.peer_id
                     .map_or_else(std::vec::Vec::new, |peer_id| vec![peer_id]),
                 None,
+                peer_quality,
             )
             .await?;
             let startup_info = storage
@@ -581,6 +591,7 @@ impl SyncService {
         let parallel_info_service = ctx.service_ref_opt::<ParallelInfoService>()?.cloned();
         let config = self.config.clone();
         let peer_score_metrics = self.peer_score_metrics.clone();
+        let peer_quality = self.peer_quality.clone();
         let sync_metrics = self.metrics.clone();
         let vm_metrics = self.vm_metrics.clone();
         let dag = ctx.get_shared::<BlockDAG>()?;
@@ -604,6 +615,7 @@ impl SyncService {
                 peer_strategy,
                 peers,
                 peer_score_metrics,
+                peer_quality,
             )
             .await?;
             if let Some(target) =
@@ -1163,6 +1175,16 @@ impl ServiceHandler<Self, PeerScoreRequest> for SyncService {
     }
 }
 
+impl ServiceHandler<Self, PeerQualityRequest> for SyncService {
+    fn handle(
+        &mut self,
+        _msg: PeerQualityRequest,
+        _ctx: &mut ServiceContext<Self>,
+    ) -> Vec<PeerQualityView> {
+        self.peer_quality.scoreboard()
+    }
+}
+
 impl ServiceHandler<Self, SyncProgressRequest> for SyncService {
     fn handle(
         &mut self,
@@ -1235,6 +1257,7 @@ impl SyncServiceHandler for SyncService {}
 mod tests {
     use super::{run_watchdog_once, SyncWatchdogSnapshot};
     use anyhow::{format_err, Result};
+    use network_api::peer_score::PeerQualityView;
     use network_api::{PeerId, PeerStrategy};
     use starcoin_crypto::HashValue;
     use starcoin_sync_api::{
@@ -1341,6 +1364,10 @@ mod tests {
         async fn sync_peer_score(&self) -> Result<PeerScoreResponse> {
             Ok(None.into())
         }
+
+        async fn sync_peer_quality(&self) -> Result<Vec<PeerQualityView>> {
+            Ok(vec![])
+        }
     }
 
     fn mock_progress_report(task_name: &str, processed_items: u64, ok: u64) -> SyncProgressReport {
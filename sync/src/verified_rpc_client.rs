@@ -3,7 +3,9 @@
 
 use crate::sync_profiling_info_enabled;
 use anyhow::{format_err, Result};
-use network_api::peer_score::{InverseScore, Score};
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use network_api::peer_score::{InverseScore, PeerQuality, RpcOutcome, Score};
 use network_api::PeerId;
 use network_api::PeerInfo;
 use network_api::PeerSelector;
@@ -14,7 +16,7 @@ use starcoin_crypto::hash::HashValue;
 use starcoin_logger::prelude::*;
 use starcoin_network_rpc_api::{
     gen_client::NetworkRpcClient, BlockBody, GetAccumulatorNodeByNodeHash, GetBlockHeadersByNumber,
-    GetBlockIds, GetTxnsWithHash, RawRpcClient,
+    GetBlockIds, GetBlockTxns, GetTxnsWithHash, RawRpcClient,
 };
 use starcoin_network_rpc_api::{GetAbsentBlockRequest, GetRangeInLocationRequest, RangeInLocation};
 // use starcoin_state_tree::StateNode;  // Unused
@@ -24,6 +26,7 @@ use starcoin_types::{
     block::{BlockHeader, BlockInfo, BlockNumber},
     transaction::{StcTransaction, StcTransactionInfo},
 };
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -210,12 +213,55 @@ fn is_timeout_error(err: &anyhow::Error) -> bool {
     // Fallback: match the formatted message from request-response layer.
     err.to_string().contains("Timeout")
 }
+
+fn error_outcome(err: &anyhow::Error) -> RpcOutcome {
+    if is_timeout_error(err) {
+        RpcOutcome::Timeout
+    } else {
+        RpcOutcome::Failure
+    }
+}
+
+/// Wrap a RawRpcClient to account the bytes exchanged with every peer.
+struct QualityRawRpcClient<C> {
+    inner: C,
+    quality: PeerQuality,
+}
+
+impl<C> RawRpcClient for QualityRawRpcClient<C>
+where
+    C: RawRpcClient + Send + Sync,
+{
+    fn send_raw_request(
+        &self,
+        peer_id: PeerId,
+        rpc_path: Cow<'static, str>,
+        message: Vec<u8>,
+    ) -> BoxFuture<'_, Result<Vec<u8>>> {
+        let sent = message.len() as u64;
+        async move {
+            let result = self
+                .inner
+                .send_raw_request(peer_id.clone(), rpc_path.clone(), message)
+                .await;
+            let received = result
+                .as_ref()
+                .map(|resp| resp.len() as u64)
+                .unwrap_or_default();
+            self.quality
+                .record_bandwidth(&peer_id, rpc_path.as_ref(), sent, received);
+            result
+        }
+        .boxed()
+    }
+}
 /// Enhancement RpcClient, for verify rpc response by request and auto select peer.
 #[derive(Clone)]
 pub struct VerifiedRpcClient {
     peer_selector: PeerSelector,
     client: NetworkRpcClient,
     score_handler: InverseScore,
+    quality: PeerQuality,
     max_retry_times: u64,
     rpc_timeout: Duration,
     rpc_retry_count: i32,
@@ -227,13 +273,33 @@ impl VerifiedRpcClient {
     where
         C: RawRpcClient + Send + Sync + 'static,
     {
-        Self::new_with_client(
+        Self::new_with_quality(
             peer_selector,
-            NetworkRpcClient::new(raw_rpc_client),
+            raw_rpc_client,
             max_retry_times,
+            PeerQuality::default(),
         )
     }
 
+    /// Create a client which reports the rpc behaviour of peers to the shared `quality`.
+    pub fn new_with_quality<C>(
+        peer_selector: PeerSelector,
+        raw_rpc_client: C,
+        max_retry_times: u64,
+        quality: PeerQuality,
+    ) -> Self
+    where
+        C: RawRpcClient + Send + Sync + 'static,
+    {
+        let client = NetworkRpcClient::new(QualityRawRpcClient {
+            inner: raw_rpc_client,
+            quality: quality.clone(),
+        });
+        let mut rpc_client = Self::new_with_client(peer_selector, client, max_retry_times);
+        rpc_client.quality = quality;
+        rpc_client
+    }
+
     pub fn new_with_client(
         peer_selector: PeerSelector,
         client: NetworkRpcClient,
@@ -253,6 +319,7 @@ impl VerifiedRpcClient {
             peer_selector,
             client,
             score_handler: InverseScore::new(100, 60),
+            quality: PeerQuality::default(),
             max_retry_times,
             rpc_timeout,
             rpc_retry_count: DEFAULT_RPC_RETRY_COUNT,
@@ -282,6 +349,33 @@ impl VerifiedRpcClient {
         self.score_handler.execute(time)
     }
 
+    pub fn quality(&self) -> &PeerQuality {
+        &self.quality
+    }
+
+    /// Feed the outcome of a rpc call to the peer quality, and stop selecting the peer if it is evicted.
+    fn record_outcome(&self, peer_id: &PeerId, method: &str, outcome: RpcOutcome) {
+        if self.quality.record(peer_id, method, outcome) {
+            self.peer_selector.remove_peer(peer_id);
+        }
+    }
+
+    fn record_verified<T>(
+        &self,
+        peer_id: &PeerId,
+        method: &str,
+        begin: Instant,
+        result: Result<T, RpcVerifyError>,
+    ) -> Result<T, RpcVerifyError> {
+        let outcome = if result.is_ok() {
+            RpcOutcome::Success(begin.elapsed())
+        } else {
+            RpcOutcome::InvalidData
+        };
+        self.record_outcome(peer_id, method, outcome);
+        result
+    }
+
     pub fn best_peer(&self) -> Option<PeerInfo> {
         self.peer_selector.best()
     }
@@ -299,13 +393,26 @@ impl VerifiedRpcClient {
     ) -> Result<Vec<Option<MultiSignedUserTransaction>>> {
         let mut count = 0;
         while count < self.rpc_retry_count {
+            let begin = Instant::now();
             match self
                 .client
                 .get_txns_with_hash_from_pool(peer_id.clone(), req.clone())
                 .await
             {
-                Ok(result) => return Ok(result),
+                Ok(result) => {
+                    self.record_outcome(
+                        &peer_id,
+                        "get_txns_with_hash_from_pool",
+                        RpcOutcome::Success(begin.elapsed()),
+                    );
+                    return Ok(result);
+                }
                 Err(e) => {
+                    self.record_outcome(
+                        &peer_id,
+                        "get_txns_with_hash_from_pool",
+                        error_outcome(&e),
+                    );
                     count = count.saturating_add(1);
                     if count == self.rpc_retry_count {
                         return Err(RpcVerifyError::new(
@@ -351,6 +458,11 @@ impl VerifiedRpcClient {
                 match data {
                     Some(txn) => {
                         if id != txn.id() {
+                            self.record_outcome(
+                                &peer_id,
+                                "get_txns_with_hash_from_pool",
+                                RpcOutcome::InvalidData,
+                            );
                             return Err(RpcVerifyError::new(
                                 peer_id.clone(),
                                 format!(
@@ -367,6 +479,11 @@ impl VerifiedRpcClient {
             }
             Ok((none_txn_vec, verified_txns))
         } else {
+            self.record_outcome(
+                &peer_id,
+                "get_txns_with_hash_from_pool",
+                RpcOutcome::InvalidData,
+            );
             Err(RpcVerifyError::new(
                 peer_id.clone(),
                 format!(
@@ -387,9 +504,14 @@ impl VerifiedRpcClient {
     ) -> Result<Vec<Option<StcTransaction>>> {
         let mut count = 0;
         while count < self.rpc_retry_count {
+            let begin = Instant::now();
             match self.client.get_txns(peer_id.clone(), req.clone()).await {
-                Ok(result) => return Ok(result),
+                Ok(result) => {
+                    self.record_outcome(&peer_id, "get_txns", RpcOutcome::Success(begin.elapsed()));
+                    return Ok(result);
+                }
                 Err(e) => {
+                    self.record_outcome(&peer_id, "get_txns", error_outcome(&e));
                     count = count.saturating_add(1);
                     if count == self.rpc_retry_count {
                         return Err(RpcVerifyError::new(
@@ -426,6 +548,7 @@ impl VerifiedRpcClient {
                 match data {
                     Some(txn) => {
                         if id != txn.id() {
+                            self.record_outcome(&peer_id, "get_txns", RpcOutcome::InvalidData);
                             return Err(RpcVerifyError::new(
                                 peer_id.clone(),
                                 format!(
@@ -442,6 +565,7 @@ impl VerifiedRpcClient {
             }
             Ok((none_txn_vec, verified_txns))
         } else {
+            self.record_outcome(&peer_id, "get_txns", RpcOutcome::InvalidData);
             Err(RpcVerifyError::new(
                 peer_id.clone(),
                 format!(
@@ -462,9 +586,18 @@ impl VerifiedRpcClient {
         let peer_id = self.select_a_peer()?;
         let mut count = 0;
         while count < self.rpc_retry_count {
+            let begin = Instant::now();
             match self.client.get_txn_infos(peer_id.clone(), block_id).await {
-                Ok(result) => return Ok((peer_id, result)),
+                Ok(result) => {
+                    self.record_outcome(
+                        &peer_id,
+                        "get_txn_infos",
+                        RpcOutcome::Success(begin.elapsed()),
+                    );
+                    return Ok((peer_id, result));
+                }
                 Err(e) => {
+                    self.record_outcome(&peer_id, "get_txn_infos", error_outcome(&e));
                     count = count.saturating_add(1);
                     if count == self.rpc_retry_count {
                         return Err(RpcVerifyError::new(
@@ -487,6 +620,66 @@ impl VerifiedRpcClient {
         .into())
     }
 
+    /// Get the txns of a block by index from the given peer, the result keeps the request order.
+    pub async fn get_block_txns(
+        &self,
+        peer_id: PeerId,
+        req: GetBlockTxns,
+    ) -> Result<Vec<MultiSignedUserTransaction>> {
+        let mut count = 0;
+        while count < self.rpc_retry_count {
+            let begin = Instant::now();
+            match self
+                .client
+                .get_block_txns(peer_id.clone(), req.clone())
+                .await
+            {
+                Ok(Some(txns)) if txns.len() == req.indexes.len() => {
+                    self.record_outcome(
+                        &peer_id,
+                        "get_block_txns",
+                        RpcOutcome::Success(begin.elapsed()),
+                    );
+                    return Ok(txns);
+                }
+                Ok(result) => {
+                    self.record_outcome(&peer_id, "get_block_txns", RpcOutcome::InvalidData);
+                    return Err(RpcVerifyError::new(
+                        peer_id.clone(),
+                        format!(
+                            "Block {} txn len mismatch {:?} : {} from peer : {:?}.",
+                            req.block_id,
+                            result.map(|txns| txns.len()),
+                            req.indexes.len(),
+                            peer_id
+                        ),
+                    )
+                    .into());
+                }
+                Err(e) => {
+                    self.record_outcome(&peer_id, "get_block_txns", error_outcome(&e));
+                    count = count.saturating_add(1);
+                    if count == self.rpc_retry_count {
+                        return Err(RpcVerifyError::new(
+                            peer_id.clone(),
+                            format!(
+                                "failed to get block txns from peer : {:?}. error: {:?}",
+                                peer_id, e
+                            ),
+                        )
+                        .into());
+                    }
+                    continue;
+                }
+            }
+        }
+        Err(RpcVerifyError::new(
+            peer_id.clone(),
+            format!("failed to get block txns from peer : {:?}.", peer_id,),
+        )
+        .into())
+    }
+
     pub async fn get_headers_by_number(
         &self,
         req: GetBlockHeadersByNumber,
@@ -494,15 +687,23 @@ impl VerifiedRpcClient {
         let peer_id = self.select_a_peer()?;
         let mut count = 0;
         while count < self.rpc_retry_count {
+            let begin = Instant::now();
             match self
                 .client
                 .get_headers_by_number(peer_id.clone(), req.clone())
                 .await
             {
                 Ok(result) => {
-                    return Ok(G_BLOCK_NUMBER_VERIFIER.verify(peer_id, req, result)?);
+                    let result = G_BLOCK_NUMBER_VERIFIER.verify(peer_id.clone(), req, result);
+                    return Ok(self.record_verified(
+                        &peer_id,
+                        "get_headers_by_number",
+                        begin,
+                        result,
+                    )?);
                 }
                 Err(e) => {
+                    self.record_outcome(&peer_id, "get_headers_by_number", error_outcome(&e));
                     count = count.saturating_add(1);
                     if count == self.rpc_retry_count {
                         return Err(RpcVerifyError::new(
@@ -532,15 +733,23 @@ impl VerifiedRpcClient {
         let peer_id = self.select_a_peer()?;
         let mut count = 0;
         while count < self.rpc_retry_count {
+            let begin = Instant::now();
             match self
                 .client
                 .get_headers_by_hash(peer_id.clone(), req.clone())
                 .await
             {
                 Ok(result) => {
-                    return Ok(G_BLOCK_ID_VERIFIER.verify(peer_id, req, result)?);
+                    let result = G_BLOCK_ID_VERIFIER.verify(peer_id.clone(), req, result);
+                    return Ok(self.record_verified(
+                        &peer_id,
+                        "get_headers_by_hash",
+                        begin,
+                        result,
+                    )?);
                 }
                 Err(e) => {
+                    self.record_outcome(&peer_id, "get_headers_by_hash", error_outcome(&e));
                     count = count.saturating_add(1);
                     if count == self.rpc_retry_count {
                         return Err(RpcVerifyError::new(
@@ -571,18 +780,21 @@ impl VerifiedRpcClient {
         debug!("rpc select peer {}", &peer_id);
         let mut count = 0;
         while count < self.rpc_retry_count {
+            let begin = Instant::now();
             match self
                 .client
                 .get_bodies_by_hash(peer_id.clone(), req.clone())
                 .await
             {
                 Ok(result) => {
+                    let result = G_BLOCK_BODY_VERIFIER.verify(peer_id.clone(), req, result);
                     return Ok((
-                        G_BLOCK_BODY_VERIFIER.verify(peer_id.clone(), req, result)?,
+                        self.record_verified(&peer_id, "get_bodies_by_hash", begin, result)?,
                         peer_id,
                     ));
                 }
                 Err(e) => {
+                    self.record_outcome(&peer_id, "get_bodies_by_hash", error_outcome(&e));
                     count = count.saturating_add(1);
                     if count == self.rpc_retry_count {
                         return Err(RpcVerifyError::new(
@@ -621,15 +833,18 @@ impl VerifiedRpcClient {
 
         let mut count = 0;
         while count < self.rpc_retry_count {
+            let begin = Instant::now();
             match self
                 .client
                 .get_block_infos(peer_id.clone(), req.clone())
                 .await
             {
                 Ok(result) => {
-                    return Ok(G_BLOCK_INFO_VERIFIER.verify(peer_id, req, result)?);
+                    let result = G_BLOCK_INFO_VERIFIER.verify(peer_id.clone(), req, result);
+                    return Ok(self.record_verified(&peer_id, "get_block_infos", begin, result)?);
                 }
                 Err(e) => {
+                    self.record_outcome(&peer_id, "get_block_infos", error_outcome(&e));
                     count = count.saturating_add(1);
                     if count == self.rpc_retry_count {
                         return Err(RpcVerifyError::new(
@@ -699,13 +914,26 @@ impl VerifiedRpcClient {
     ) -> Result<Option<AccumulatorNode>> {
         let mut count = 0;
         while count < self.rpc_retry_count {
+            let begin = Instant::now();
             match self
                 .client
                 .get_accumulator_node_by_node_hash(peer_id.clone(), req.clone())
                 .await
             {
-                Ok(result) => return Ok(result),
+                Ok(result) => {
+                    self.record_outcome(
+                        &peer_id,
+                        "get_accumulator_node_by_node_hash",
+                        RpcOutcome::Success(begin.elapsed()),
+                    );
+                    return Ok(result);
+                }
                 Err(e) => {
+                    self.record_outcome(
+                        &peer_id,
+                        "get_accumulator_node_by_node_hash",
+                        error_outcome(&e),
+                    );
                     count = count.saturating_add(1);
                     if count == self.rpc_retry_count {
                         return Err(RpcVerifyError::new(
@@ -748,6 +976,11 @@ impl VerifiedRpcClient {
             if node_key == accumulator_node_id {
                 Ok((peer_id, accumulator_node))
             } else {
+                self.record_outcome(
+                    &peer_id,
+                    "get_accumulator_node_by_node_hash",
+                    RpcOutcome::InvalidData,
+                );
                 Err(format_err!(
                     "Accumulator node hash {:?} and node key {:?} mismatch.",
                     accumulator_node_id,
@@ -789,6 +1022,11 @@ impl VerifiedRpcClient {
                 .await
             {
                 Ok(result) => {
+                    self.record_outcome(
+                        &peer_id,
+                        "get_block_ids",
+                        RpcOutcome::Success(rpc_begin.elapsed()),
+                    );
                     self.block_ids_sizer.on_success(result.len() as u64);
                     if profiling_info {
                         info!(
@@ -807,6 +1045,7 @@ impl VerifiedRpcClient {
                     return Ok(result);
                 }
                 Err(e) => {
+                    self.record_outcome(&peer_id, "get_block_ids", error_outcome(&e));
                     if is_timeout_error(&e) {
                         self.block_ids_sizer.on_timeout();
                         request.max_size = self.block_ids_sizer.clamp_request_size(requested_max);
@@ -854,6 +1093,7 @@ impl VerifiedRpcClient {
         let mut count = 0;
         let peer_id = self.select_a_peer()?;
         while count < self.rpc_retry_count {
+            let begin = Instant::now();
             match timeout(
                 self.rpc_timeout,
                 self.client
@@ -861,8 +1101,16 @@ impl VerifiedRpcClient {
             )
             .await
             {
-                Ok(Ok(result)) => return Ok(ids.into_iter().zip(result.into_iter()).collect()),
+                Ok(Ok(result)) => {
+                    self.record_outcome(
+                        &peer_id,
+                        "get_headers_by_hash",
+                        RpcOutcome::Success(begin.elapsed()),
+                    );
+                    return Ok(ids.into_iter().zip(result.into_iter()).collect());
+                }
                 Ok(Err(e)) => {
+                    self.record_outcome(&peer_id, "get_headers_by_hash", error_outcome(&e));
                     count = count.saturating_add(1);
                     if count == self.rpc_retry_count {
                         return Err(RpcVerifyError::new(
@@ -877,6 +1125,7 @@ impl VerifiedRpcClient {
                     continue;
                 }
                 Err(_) => {
+                    self.record_outcome(&peer_id, "get_headers_by_hash", RpcOutcome::Timeout);
                     count = count.saturating_add(1);
                     if count == self.rpc_retry_count {
                         return Err(RpcVerifyError::new(
@@ -906,14 +1155,23 @@ impl VerifiedRpcClient {
     ) -> Result<Vec<Option<Block>>> {
         let mut count = 0;
         while count < self.rpc_retry_count {
+            let begin = Instant::now();
             match timeout(
                 self.rpc_timeout,
                 self.client.get_blocks(peer_id.clone(), ids.clone()),
             )
             .await
             {
-                Ok(Ok(result)) => return Ok(result),
+                Ok(Ok(result)) => {
+                    self.record_outcome(
+                        &peer_id,
+                        "get_blocks",
+                        RpcOutcome::Success(begin.elapsed()),
+                    );
+                    return Ok(result);
+                }
                 Ok(Err(e)) => {
+                    self.record_outcome(&peer_id, "get_blocks", error_outcome(&e));
                     count = count.saturating_add(1);
                     if count == self.rpc_retry_count {
                         return Err(RpcVerifyError::new(
@@ -928,6 +1186,7 @@ impl VerifiedRpcClient {
                     continue;
                 }
                 Err(_) => {
+                    self.record_outcome(&peer_id, "get_blocks", RpcOutcome::Timeout);
                     count = count.saturating_add(1);
                     if count == self.rpc_retry_count {
                         return Err(RpcVerifyError::new(
@@ -1158,6 +1417,11 @@ impl VerifiedRpcClient {
             .await
             {
                 Ok(Ok(result)) => {
+                    self.record_outcome(
+                        &peer_id,
+                        "get_absent_blocks",
+                        RpcOutcome::Success(rpc_begin.elapsed()),
+                    );
                     if profiling_info {
                         info!(
                             "{} stage=rpc_get_absent_blocks status=ok peer_id={} request_ids={} exp={} returned_blocks={} attempt={} elapsed_ms={}",
@@ -1177,6 +1441,7 @@ impl VerifiedRpcClient {
                         .collect());
                 }
                 Ok(Err(e)) => {
+                    self.record_outcome(&peer_id, "get_absent_blocks", error_outcome(&e));
                     count = count.saturating_add(1);
                     if profiling_info {
                         warn!(
@@ -1203,6 +1468,7 @@ impl VerifiedRpcClient {
                     continue;
                 }
                 Err(_) => {
+                    self.record_outcome(&peer_id, "get_absent_blocks", RpcOutcome::Timeout);
                     count = count.saturating_add(1);
                     if profiling_info {
                         warn!(
@@ -1240,13 +1506,22 @@ impl VerifiedRpcClient {
         let mut count = 0;
         let peer_id = self.select_a_peer()?;
         while count < self.rpc_retry_count {
+            let begin = Instant::now();
             match self
                 .client
                 .get_dag_block_children(peer_id.clone(), req.clone())
                 .await
             {
-                Ok(result) => return Ok(result),
-                Err(_) => {
+                Ok(result) => {
+                    self.record_outcome(
+                        &peer_id,
+                        "get_dag_block_children",
+                        RpcOutcome::Success(begin.elapsed()),
+                    );
+                    return Ok(result);
+                }
+                Err(e) => {
+                    self.record_outcome(&peer_id, "get_dag_block_children", error_outcome(&e));
                     count = count.saturating_add(1);
                     continue;
                 }
@@ -1278,15 +1553,22 @@ impl VerifiedRpcClient {
 
         let mut count = 0;
         while count < self.rpc_retry_count {
+            let begin = Instant::now();
             match self
                 .client
                 .get_range_in_location(peer_id.clone(), req.clone())
                 .await
             {
                 Ok(result) => {
+                    self.record_outcome(
+                        &peer_id,
+                        "get_range_in_location",
+                        RpcOutcome::Success(begin.elapsed()),
+                    );
                     return Ok(result.range);
                 }
                 Err(e) => {
+                    self.record_outcome(&peer_id, "get_range_in_location", error_outcome(&e));
                     count = count.saturating_add(1);
                     if count == self.rpc_retry_count {
                         return Err(RpcVerifyError::new(
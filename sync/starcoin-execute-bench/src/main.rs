@@ -53,7 +53,9 @@ use starcoin_vm2_statedb::ChainStateDB;
 use starcoin_vm2_types::{
     account_config::{association_address, G_STC_TOKEN_CODE},
     genesis_config::ChainId as ChainId2,
-    transaction::{RawUserTransaction as RawUserTransaction2, SignedUserTransaction},
+    transaction::{
+        authenticator::Scheme, RawUserTransaction as RawUserTransaction2, SignedUserTransaction,
+    },
 };
 use starcoin_vm2_vm_runtime::starcoin_vm::StarcoinVM;
 use starcoin_vm2_vm_types::{account_address::AccountAddress, state_view::StateReaderExt};
@@ -206,6 +208,13 @@ struct Cli {
     )]
     pipeline_timing: bool,
 
+    #[arg(
+        long = "access-list-scheduling",
+        default_value = "false",
+        help = "Let the block builder order txns into non-conflicting batches by their access lists predicted by dry runs. Compare the abort and re-execution rates of runs with and without it."
+    )]
+    access_list_scheduling: bool,
+
     #[arg(
         long = "prepare-bench",
         value_hint = ValueHint::DirPath,
@@ -406,6 +415,9 @@ fn build_node_config(
     global_opt.txpool.set_max_count(txpool_max_count);
     global_opt.genesis_config = genesis_config;
     global_opt.network.disable_seed = true;
+    if cli.access_list_scheduling {
+        global_opt.miner.access_list_scheduling = Some(true);
+    }
 
     Ok(Arc::new(NodeConfig::load_with_opt(&global_opt)?))
 }
@@ -830,7 +842,10 @@ async fn create_account(
     let mut results = vec![];
     for _ in 0..account_number {
         let receiver = match account_service
-            .send(AccountRequest::CreateAccount("".to_string()))
+            .send(AccountRequest::CreateAccount {
+                password: "".to_string(),
+                key_type: Scheme::Ed25519,
+            })
             .await??
         {
             AccountResponse::AccountInfo(account) => *account,
@@ -6,6 +6,7 @@ use plotters::prelude::*;
 use serde::{Deserialize, Serialize};
 use starcoin_crypto::HashValue;
 use starcoin_logger::prelude::info;
+use starcoin_parallel_executor::stats::total_execution_stats;
 use starcoin_pipeline_timing::{global_collector, StageTiming};
 
 #[derive(Clone)]
@@ -152,6 +153,18 @@ pub struct BenchmarkStats {
     pub max_latency_ms: f64,
     pub avg_latency_ms: f64,
     pub median_latency_ms: f64,
+    /// Txns executed by the parallel executor, and how many of their executions were aborted
+    /// and re-executed on conflicts.
+    #[serde(default)]
+    pub parallel_txns: u64,
+    #[serde(default)]
+    pub parallel_aborts: u64,
+    #[serde(default)]
+    pub parallel_re_executions: u64,
+    #[serde(default)]
+    pub abort_rate: f64,
+    #[serde(default)]
+    pub re_execution_rate: f64,
 }
 
 impl std::fmt::Display for BenchmarkStats {
@@ -180,6 +193,15 @@ impl std::fmt::Display for BenchmarkStats {
             "Latency - Min: {:.2}ms | Max: {:.2}ms | Avg: {:.2}ms | Median: {:.2}ms",
             self.min_latency_ms, self.max_latency_ms, self.avg_latency_ms, self.median_latency_ms
         )?;
+        writeln!(
+            f,
+            "Parallel Txns: {} | Aborts: {} ({:.3}/txn) | Re-executions: {} ({:.3}/txn)",
+            self.parallel_txns,
+            self.parallel_aborts,
+            self.abort_rate,
+            self.parallel_re_executions,
+            self.re_execution_rate
+        )?;
         writeln!(f, "========================================")?;
         Ok(())
     }
@@ -276,6 +298,8 @@ impl<'a> ResultsDumper<'a> {
         let (mined_tps_min, mined_tps_max, mined_tps_avg, mined_tps_median) =
             self.calculate_per_block_mined_tps_stats();
 
+        let execution_stats = total_execution_stats();
+
         let duplicate_pct = if unique_txn_count > 0 {
             duplicate_exec_count as f64 / unique_txn_count as f64 * 100.0
         } else {
@@ -307,6 +331,11 @@ impl<'a> ResultsDumper<'a> {
             max_latency_ms: max_delay,
             avg_latency_ms: avg_delay,
             median_latency_ms: median_delay,
+            parallel_txns: execution_stats.txns,
+            parallel_aborts: execution_stats.aborts,
+            parallel_re_executions: execution_stats.re_executions(),
+            abort_rate: execution_stats.abort_rate(),
+            re_execution_rate: execution_stats.re_execution_rate(),
         }
     }
 
@@ -13,6 +13,7 @@ use starcoin_storage::db_storage::DBStorage;
 use starcoin_storage::storage::StorageInstance;
 use starcoin_storage::Storage;
 use starcoin_vm2_account_api::AccountInfo;
+use starcoin_vm2_types::transaction::authenticator::Scheme;
 use std::env;
 use std::sync::Arc;
 use std::time::Duration;
@@ -112,7 +113,7 @@ pub fn steps() -> Steps<MyWorld> {
             let password = "integration";
             let account = client
                 .clone()
-                .account_create2(password.to_string())
+                .account_create2(password.to_string(), Scheme::Ed25519)
                 .expect("should create account");
             client
                 .account_unlock2(
@@ -17,6 +17,10 @@ use std::sync::Arc;
 
 pub type TxnStatusFullEvent = Arc<[(HashValue, transaction::TxStatus)]>;
 
+/// Peer id used for the transactions submitted by the local node, such as by rpc.
+/// The txpool journals these transactions so they survive node restarts.
+pub const LOCAL_RPC_PEER_ID: &str = "local-rpc";
+
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct TxPoolStatus {
     pub txn_count: usize,
@@ -32,7 +36,7 @@ pub trait TxPoolSyncService: Clone + Send + Sync + Unpin {
         &self,
         txns: Vec<SignedUserTransaction>,
     ) -> Result<Vec<Result<(), transaction::TransactionError>>> {
-        let local_peer_id = Some("local-rpc".to_string());
+        let local_peer_id = Some(LOCAL_RPC_PEER_ID.to_string());
         let multi_txns = txns.into_iter().map(|txn| txn.into()).collect();
         let rets = self.add_txns_multi_signed(multi_txns, false, local_peer_id)?;
         let mut results = vec![];
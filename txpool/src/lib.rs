@@ -32,6 +32,7 @@ use tx_pool_service_impl::Inner;
 pub use tx_pool_service_impl::TxPoolService;
 pub use verifier_pool::VerifierPool;
 
+mod local_journal;
 mod metrics;
 mod pending_transaction;
 mod pool;
@@ -166,6 +167,13 @@ impl ActorService for TxPoolActorService {
             myself.try_propagate_txns(ctx)
         });
 
+        // every x seconds, drop the txns no longer in pool from the local txn journal.
+        let inner = self.inner.clone();
+        let journal_interval = self.inner.node_config.tx_pool.local_txn_journal_interval();
+        ctx.run_interval(Duration::from_secs(journal_interval), move |_ctx| {
+            inner.compact_local_journal()
+        });
+
         Ok(())
     }
 
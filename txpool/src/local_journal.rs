@@ -0,0 +1,186 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Journal of the locally submitted transactions, so the pending ones survive node restarts.
+//!
+//! The journal is an append only file of length prefixed BCS encoded
+//! `MultiSignedUserTransaction`s. It is replayed into the pool on startup and periodically
+//! rewritten to keep only the transactions still in the pool.
+
+use anyhow::{ensure, Result};
+use bcs_ext::BCSCodec;
+use parking_lot::Mutex;
+use starcoin_types::multi_transaction::MultiSignedUserTransaction;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Max size of a single journal record, larger records are treated as corruption.
+const MAX_RECORD_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug)]
+pub struct LocalTxnJournal {
+    path: PathBuf,
+    writer: Mutex<Option<BufWriter<File>>>,
+}
+
+impl LocalTxnJournal {
+    pub fn new(path: PathBuf) -> Result<Self> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        Ok(Self {
+            path,
+            writer: Mutex::new(None),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// Load all the transactions in the journal. A truncated or corrupted tail, left by a crash
+    /// while writing, is ignored.
+    pub fn load(&self) -> Result<Vec<MultiSignedUserTransaction>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e.into()),
+        };
+        let mut reader = BufReader::new(file);
+        let mut txns = vec![];
+        loop {
+            match read_record(&mut reader) {
+                Ok(Some(txn)) => txns.push(txn),
+                Ok(None) => break,
+                Err(e) => {
+                    warn!(
+                        "[txpool] Ignore the corrupted tail of local txn journal {}: {}",
+                        self.path.display(),
+                        e
+                    );
+                    break;
+                }
+            }
+        }
+        Ok(txns)
+    }
+
+    /// Append the transactions to the journal, and flush them to the file.
+    pub fn append(&self, txns: &[MultiSignedUserTransaction]) -> Result<()> {
+        if txns.is_empty() {
+            return Ok(());
+        }
+        let mut writer = self.writer.lock();
+        if writer.is_none() {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+            *writer = Some(BufWriter::new(file));
+        }
+        let writer = writer.as_mut().expect("writer must be opened");
+        for txn in txns {
+            write_record(writer, txn)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Replace the journal content with the given transactions.
+    pub fn rewrite(&self, txns: &[MultiSignedUserTransaction]) -> Result<()> {
+        let mut writer = self.writer.lock();
+        self.rewrite_file(&mut writer, txns)
+    }
+
+    /// Keep only the journaled transactions matching `f`, return how many are dropped.
+    pub fn retain<F>(&self, f: F) -> Result<usize>
+    where
+        F: FnMut(&MultiSignedUserTransaction) -> bool,
+    {
+        // hold the writer lock, so no txn is appended between load and rewrite.
+        let mut writer = self.writer.lock();
+        let txns = self.load()?;
+        let journaled = txns.len();
+        let kept = txns.into_iter().filter(f).collect::<Vec<_>>();
+        let dropped = journaled - kept.len();
+        if dropped > 0 {
+            self.rewrite_file(&mut writer, &kept)?;
+        }
+        Ok(dropped)
+    }
+
+    fn rewrite_file(
+        &self,
+        writer: &mut Option<BufWriter<File>>,
+        txns: &[MultiSignedUserTransaction],
+    ) -> Result<()> {
+        // close the append handle, it points to the replaced file after rename.
+        *writer = None;
+        let tmp_path = self.path.with_extension("tmp");
+        {
+            let mut tmp_writer = BufWriter::new(File::create(&tmp_path)?);
+            for txn in txns {
+                write_record(&mut tmp_writer, txn)?;
+            }
+            tmp_writer.flush()?;
+            tmp_writer.get_ref().sync_all()?;
+        }
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+fn write_record<W: Write>(writer: &mut W, txn: &MultiSignedUserTransaction) -> Result<()> {
+    let bytes = txn.encode()?;
+    writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+fn read_record<R: Read>(reader: &mut R) -> Result<Option<MultiSignedUserTransaction>> {
+    let mut len_bytes = [0u8; 4];
+    match reader.read_exact(&mut len_bytes) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+    let len = u32::from_le_bytes(len_bytes) as usize;
+    ensure!(
+        len <= MAX_RECORD_SIZE,
+        "invalid journal record size {}",
+        len
+    );
+    let mut bytes = vec![0u8; len];
+    reader.read_exact(&mut bytes)?;
+    MultiSignedUserTransaction::decode(&bytes).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_journal_append_and_rewrite() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let journal = LocalTxnJournal::new(dir.path().join("txpool").join("local_txns.journal"))?;
+        assert!(journal.load()?.is_empty());
+
+        let txn1 = MultiSignedUserTransaction::mock();
+        let txn2 = MultiSignedUserTransaction::mock();
+        journal.append(&[txn1.clone()])?;
+        journal.append(&[txn2.clone()])?;
+        assert_eq!(journal.load()?, vec![txn1.clone(), txn2.clone()]);
+
+        assert_eq!(journal.retain(|txn| txn == &txn2)?, 1);
+        assert_eq!(journal.load()?, vec![txn2.clone()]);
+        journal.append(&[txn1.clone()])?;
+        assert_eq!(journal.load()?, vec![txn2.clone(), txn1.clone()]);
+
+        // a truncated tail is dropped, the complete records are kept.
+        let mut file = OpenOptions::new().append(true).open(journal.path())?;
+        file.write_all(&[16, 0, 0, 0, 1, 2])?;
+        assert_eq!(journal.load()?, vec![txn2, txn1]);
+        Ok(())
+    }
+}
@@ -114,7 +114,8 @@ impl CachedSeqNumberClient {
             }
             MultiAccountAddress::VM2(address) => {
                 let account_state_reader2 = AccountStateReader2::new(self.statedb2.as_ref());
-                match account_state_reader2.get_account_resource(address) {
+                // The account of a sender whose gas is paid by a fee payer may not exist yet.
+                match account_state_reader2.get_account_resource_opt(address) {
                     Err(e) => {
                         error!(
                     "Get account {} resource from statedb2 error: {:?}, return 0 as sequence_number",
@@ -122,7 +123,9 @@ impl CachedSeqNumberClient {
                 );
                         0
                     }
-                    Ok(account_resource) => account_resource.sequence_number(),
+                    Ok(account_resource) => account_resource
+                        .map(|res| res.sequence_number())
+                        .unwrap_or_default(),
                 }
             }
         }
@@ -11,6 +11,7 @@ use crate::{
     verifier_pool::VerifierPool,
 };
 
+use crate::local_journal::LocalTxnJournal;
 use crate::metrics::TxPoolMetrics;
 use crate::pool::{Client, TransactionQueue};
 use anyhow::Result;
@@ -21,7 +22,7 @@ use starcoin_crypto::hash::HashValue;
 use starcoin_executor::VMMetrics;
 use starcoin_storage::Store;
 use starcoin_storage::Store2;
-use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService, TxnStatusFullEvent};
+use starcoin_txpool_api::{TxPoolStatus, TxPoolSyncService, TxnStatusFullEvent, LOCAL_RPC_PEER_ID};
 use starcoin_types::multi_transaction::{
     APIInterruptedError, MultiAccountAddress, MultiSignatureCheckedTransaction,
     MultiSignedUserTransaction, MultiTransactionError,
@@ -78,6 +79,12 @@ impl TxPoolService {
             pool_config.vm1_peer_blacklist_duration_secs(),
         );
         let queue = Arc::new(queue);
+        let local_journal = pool_config.local_txn_journal_path().and_then(|path| {
+            LocalTxnJournal::new(path)
+                .map(Arc::new)
+                .map_err(|e| error!("[txpool] Failed to open local txn journal: {}", e))
+                .ok()
+        });
         let inner = Inner {
             node_config,
             queue,
@@ -88,7 +95,9 @@ impl TxPoolService {
             metrics,
             vm_metrics,
             verifier_pool,
+            local_journal,
         };
+        inner.replay_local_journal();
 
         Self { inner }
     }
@@ -363,6 +372,7 @@ pub struct Inner {
     pub(crate) metrics: Option<TxPoolMetrics>,
     vm_metrics: Option<VMMetrics>,
     verifier_pool: Option<Arc<VerifierPool>>,
+    local_journal: Option<Arc<LocalTxnJournal>>,
 }
 impl std::fmt::Debug for Inner {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -416,12 +426,126 @@ impl Inner {
         bypass_vm1_limit: bool,
         peer_id: Option<String>,
     ) -> Result<Vec<Result<(), MultiTransactionError>>> {
-        let txns = txns
-            .into_iter()
+        let is_local = peer_id.as_deref() == Some(LOCAL_RPC_PEER_ID);
+        let pool_txns = txns
+            .iter()
+            .cloned()
             .map(|t| PoolTransaction::Unverified(UnverifiedUserTransaction::from(t)));
-        Ok(self
-            .queue
-            .import(self.get_pool_client()?, txns, bypass_vm1_limit, peer_id))
+        let results = self.queue.import(
+            self.get_pool_client()?,
+            pool_txns,
+            bypass_vm1_limit,
+            peer_id,
+        );
+        if is_local {
+            self.journal_local_txns(
+                txns.into_iter()
+                    .zip(results.iter())
+                    .filter(|(_, result)| result.is_ok())
+                    .map(|(txn, _)| txn)
+                    .collect(),
+            );
+        }
+        Ok(results)
+    }
+
+    fn journal_local_txns(&self, txns: Vec<MultiSignedUserTransaction>) {
+        if let Some(journal) = self.local_journal.as_ref() {
+            if let Err(e) = journal.append(&txns) {
+                error!("[txpool] Failed to journal local txns: {}", e);
+            }
+        }
+    }
+
+    /// Re-import the journaled local txns, skip the expired and the already included ones, then
+    /// compact the journal to the txns accepted by the pool.
+    pub(crate) fn replay_local_journal(&self) {
+        let journal = match self.local_journal.as_ref() {
+            Some(journal) => journal,
+            None => return,
+        };
+        let txns = match journal.load() {
+            Ok(txns) => txns,
+            Err(e) => {
+                error!("[txpool] Failed to load local txn journal: {}", e);
+                return;
+            }
+        };
+        if txns.is_empty() {
+            return;
+        }
+        let journaled = txns.len();
+        let now_seconds = self.chain_header.read().timestamp() / 1000;
+        let (vm2_txns, vm1_txns): (Vec<_>, Vec<_>) = txns
+            .into_iter()
+            .filter(|txn| txn.expiration_timestamp_secs() > now_seconds)
+            .filter(|txn| {
+                self.storage
+                    .get_rich_transaction_info_ids_by_txn_hash(txn.id())
+                    .map(|ids| ids.is_empty())
+                    .unwrap_or(true)
+            })
+            .partition(|txn| !txn.is_v1());
+        let mut restored = vec![];
+        for (txns, bypass_vm1_limit) in [(vm1_txns, false), (vm2_txns, true)] {
+            if txns.is_empty() {
+                continue;
+            }
+            let pool_txns = txns
+                .iter()
+                .cloned()
+                .map(|t| PoolTransaction::Unverified(UnverifiedUserTransaction::from(t)));
+            let client = match self.get_pool_client() {
+                Ok(client) => client,
+                Err(e) => {
+                    error!(
+                        "[txpool] Failed to get pool client to replay journal: {}",
+                        e
+                    );
+                    return;
+                }
+            };
+            let results = self.queue.import(
+                client,
+                pool_txns,
+                bypass_vm1_limit,
+                Some(LOCAL_RPC_PEER_ID.to_string()),
+            );
+            restored.extend(
+                txns.into_iter()
+                    .zip(results)
+                    .filter(|(_, result)| result.is_ok())
+                    .map(|(txn, _)| txn),
+            );
+        }
+        info!(
+            "[txpool] Restored {} of {} journaled local txns",
+            restored.len(),
+            journaled
+        );
+        if let Err(e) = journal.rewrite(&restored) {
+            error!("[txpool] Failed to compact local txn journal: {}", e);
+        }
+    }
+
+    /// Rewrite the local txn journal to keep only the txns still in the pool.
+    pub(crate) fn compact_local_journal(&self) {
+        let journal = match self.local_journal.as_ref() {
+            Some(journal) => journal,
+            None => return,
+        };
+        match journal.retain(|txn| self.queue.find(&txn.id()).is_some()) {
+            Ok(dropped) if dropped > 0 => {
+                debug!(
+                    "[txpool] Drop {} txns no longer in pool from local txn journal",
+                    dropped
+                );
+            }
+            Ok(_) => {}
+            Err(e) => {
+                error!("[txpool] Failed to compact local txn journal: {}", e);
+            }
+        }
     }
     pub(crate) fn remove_txn(
         &self,
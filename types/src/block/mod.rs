@@ -317,6 +317,7 @@ impl BlockHeader {
         txn_accumulator_root: HashValue,
         state_root: HashValue,
         difficulty: U256,
+        gas_used: u64,
         body_hash: HashValue,
         chain_id: ChainId,
     ) -> Self {
@@ -328,7 +329,7 @@ impl BlockHeader {
             txn_accumulator_root,
             *ACCUMULATOR_PLACEHOLDER_HASH,
             state_root,
-            0,
+            gas_used,
             difficulty,
             body_hash,
             chain_id,
@@ -775,9 +776,35 @@ impl Block {
         genesis_txn: SignedUserTransaction,
         genesis_txn2: SignedUserTransactionV2,
     ) -> Self {
-        let chain_id = genesis_txn.chain_id();
-        let block_body =
-            BlockBody::new_v2(vec![genesis_txn.clone()], vec![genesis_txn2.clone()], None);
+        Self::genesis_block_with_transactions(
+            parent_hash,
+            timestamp,
+            accumulator_root,
+            state_root,
+            difficulty,
+            0,
+            vec![genesis_txn],
+            vec![genesis_txn2],
+        )
+    }
+
+    /// Build the genesis block with the genesis txns followed by other txns executed in genesis,
+    /// `gas_used` is the total gas used by all the txns.
+    pub fn genesis_block_with_transactions(
+        parent_hash: HashValue,
+        timestamp: u64,
+        accumulator_root: HashValue,
+        state_root: HashValue,
+        difficulty: U256,
+        gas_used: u64,
+        genesis_txns: Vec<SignedUserTransaction>,
+        genesis_txns2: Vec<SignedUserTransactionV2>,
+    ) -> Self {
+        let chain_id = genesis_txns
+            .first()
+            .expect("genesis block must contain the genesis txn")
+            .chain_id();
+        let block_body = BlockBody::new_v2(genesis_txns, genesis_txns2, None);
         let body_hash = block_body.hash();
         let header = BlockHeader::genesis_block_header(
             parent_hash,
@@ -785,6 +812,7 @@ impl Block {
             accumulator_root,
             state_root,
             difficulty,
+            gas_used,
             body_hash,
             chain_id,
         );
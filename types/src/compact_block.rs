@@ -2,7 +2,10 @@ use crate::block::{Block, BlockHeader};
 use crate::multi_transaction::MultiSignedUserTransaction;
 use bcs_ext::Sample;
 use serde::{Deserialize, Serialize};
+use siphasher::sip::SipHasher24;
 use starcoin_crypto::HashValue;
+use std::collections::HashSet;
+use std::hash::Hasher;
 
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct CompactBlock {
@@ -58,3 +61,140 @@ impl Sample for CompactBlock {
         Block::sample().into()
     }
 }
+
+/// Length in bytes of a [`ShortTxnId`].
+pub const SHORT_TXN_ID_LEN: usize = 6;
+
+/// Salted short transaction id, the low 6 bytes of SipHash-2-4 of the transaction id, keyed by
+/// the block id and a per-block nonce (BIP152). The salt makes collisions unpredictable, so a
+/// peer can not craft transactions colliding with the ones of a given block.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ShortTxnId(pub [u8; SHORT_TXN_ID_LEN]);
+
+/// SipHash keys used to compute the short ids of the transactions of a block.
+#[derive(Clone, Copy, Debug)]
+pub struct ShortTxnIdKey {
+    k0: u64,
+    k1: u64,
+}
+
+impl ShortTxnIdKey {
+    pub fn new(block_id: HashValue, nonce: u64) -> Self {
+        let mut buf = block_id.to_vec();
+        buf.extend_from_slice(&nonce.to_le_bytes());
+        let digest = HashValue::sha3_256_of(buf.as_slice()).to_vec();
+        let mut k0 = [0u8; 8];
+        let mut k1 = [0u8; 8];
+        k0.copy_from_slice(&digest[0..8]);
+        k1.copy_from_slice(&digest[8..16]);
+        Self {
+            k0: u64::from_le_bytes(k0),
+            k1: u64::from_le_bytes(k1),
+        }
+    }
+
+    pub fn short_id(&self, txn_id: &HashValue) -> ShortTxnId {
+        let mut hasher = SipHasher24::new_with_keys(self.k0, self.k1);
+        hasher.write(txn_id.as_ref());
+        let mut id = [0u8; SHORT_TXN_ID_LEN];
+        id.copy_from_slice(&hasher.finish().to_le_bytes()[..SHORT_TXN_ID_LEN]);
+        ShortTxnId(id)
+    }
+}
+
+/// Compact block relayed with salted short transaction ids, the receiver rebuilds the block body
+/// from its own transactions and only fetches the missing ones.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CompactBlockV2 {
+    pub header: BlockHeader,
+    pub nonce: u64,
+    pub short_ids: Vec<ShortTxnId>,
+    pub prefilled_txn: Vec<PrefilledTxn>,
+    pub uncles: Option<Vec<BlockHeader>>,
+}
+
+impl CompactBlockV2 {
+    /// Build from a full-hash compact block. The transactions listed in `prefilled_txn` are
+    /// carried as is and get no short id.
+    pub fn from_compact_block(compact_block: &CompactBlock, nonce: u64) -> Self {
+        let key = ShortTxnIdKey::new(compact_block.header.id(), nonce);
+        let prefilled: HashSet<u64> = compact_block
+            .prefilled_txn
+            .iter()
+            .map(|prefilled| prefilled.index)
+            .collect();
+        let short_ids = compact_block
+            .short_ids
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !prefilled.contains(&(*index as u64)))
+            .map(|(_, id)| key.short_id(&id.0))
+            .collect();
+        Self {
+            header: compact_block.header.clone(),
+            nonce,
+            short_ids,
+            prefilled_txn: compact_block.prefilled_txn.clone(),
+            uncles: compact_block.uncles.clone(),
+        }
+    }
+
+    pub fn key(&self) -> ShortTxnIdKey {
+        ShortTxnIdKey::new(self.header.id(), self.nonce)
+    }
+
+    /// Number of transactions of the block, prefilled ones included.
+    pub fn txn_len(&self) -> usize {
+        self.short_ids.len() + self.prefilled_txn.len()
+    }
+}
+
+impl Sample for CompactBlockV2 {
+    fn sample() -> Self {
+        Self::from_compact_block(&CompactBlock::sample(), 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_txn_id_salted() {
+        let block_id = HashValue::random();
+        let txn_id = HashValue::random();
+        let key = ShortTxnIdKey::new(block_id, 1);
+        assert_eq!(key.short_id(&txn_id), key.short_id(&txn_id));
+        assert_ne!(
+            key.short_id(&txn_id),
+            ShortTxnIdKey::new(block_id, 2).short_id(&txn_id)
+        );
+        assert_ne!(
+            key.short_id(&txn_id),
+            ShortTxnIdKey::new(HashValue::random(), 1).short_id(&txn_id)
+        );
+    }
+
+    #[test]
+    fn test_compact_block_v2_skip_prefilled() {
+        let mut compact_block = CompactBlock::sample();
+        compact_block.short_ids = vec![
+            ShortId(HashValue::random()),
+            ShortId(HashValue::random()),
+            ShortId(HashValue::random()),
+        ];
+        let v2 = CompactBlockV2::from_compact_block(&compact_block, 7);
+        assert_eq!(v2.short_ids.len(), 3);
+        let key = v2.key();
+        assert_eq!(v2.short_ids[1], key.short_id(&compact_block.short_ids[1].0));
+
+        compact_block.prefilled_txn = vec![PrefilledTxn {
+            index: 1,
+            tx: MultiSignedUserTransaction::sample(),
+        }];
+        let v2 = CompactBlockV2::from_compact_block(&compact_block, 7);
+        assert_eq!(v2.short_ids.len(), 2);
+        assert_eq!(v2.short_ids[1], key.short_id(&compact_block.short_ids[2].0));
+        assert_eq!(v2.txn_len(), 3);
+    }
+}
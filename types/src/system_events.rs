@@ -1,7 +1,7 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::block::{Block, BlockHeader, BlockHeaderExtra, ExecutedBlock};
+use crate::block::{Block, BlockHeader, BlockHeaderExtra, BlockNumber, ExecutedBlock};
 use crate::genesis_config::ConsensusStrategy;
 use crate::sync_status::SyncStatus;
 use crate::U256;
@@ -28,6 +28,22 @@ pub struct NewDagBlockFromPeer {
 #[derive(Clone, Debug)]
 pub struct NewBranch(pub Arc<ExecutedBlock>);
 
+/// Fire this event when the selected chain switches to a head which does not descend from the
+/// previous head, the blocks after the common ancestor are reverted.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ChainReorgEvent {
+    /// The sequence number of the reorg in the reorg log, assigned when it is saved.
+    pub sequence: u64,
+    pub ancestor: HashValue,
+    pub ancestor_number: BlockNumber,
+    /// The blocks removed from the selected chain, from the child of the ancestor to the old head.
+    pub removed: Vec<HashValue>,
+    /// The blocks added to the selected chain, from the child of the ancestor to the new head.
+    pub added: Vec<HashValue>,
+    /// The time in milliseconds when the reorg happened.
+    pub timestamp: u64,
+}
+
 #[derive(Clone, Debug)]
 pub struct MinedBlock(pub Arc<Block>);
 
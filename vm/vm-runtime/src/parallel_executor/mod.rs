@@ -28,6 +28,10 @@ use std::collections::BTreeMap;
 impl PTransaction for PreprocessedTransaction {
     type Key = StateKey;
     type Value = WriteOp;
+
+    fn is_block_prologue(&self) -> bool {
+        matches!(self, PreprocessedTransaction::BlockMetadata(_))
+    }
 }
 
 // Wrapper to avoid orphan rule
@@ -16,7 +16,6 @@ use move_table_extension::NativeTableContext;
 use move_vm_runtime::move_vm_adapter::{PublishModuleBundleOption, SessionAdapter};
 use move_vm_runtime::session::Session;
 use num_cpus;
-use once_cell::sync::OnceCell;
 use starcoin_config::genesis_config::G_LATEST_GAS_PARAMS;
 use starcoin_crypto::HashValue;
 use starcoin_gas::{NativeGasParameters, StarcoinGasMeter, StarcoinGasParameters};
@@ -67,10 +66,11 @@ use starcoin_vm_types::{
     transaction_metadata::TransactionMetadata,
     vm_status::{StatusCode, VMStatus},
 };
-use std::cmp::min;
+use std::cmp::{max, min};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
-static EXECUTION_CONCURRENCY_LEVEL: OnceCell<usize> = OnceCell::new();
+static EXECUTION_CONCURRENCY_LEVEL: AtomicUsize = AtomicUsize::new(1);
 
 use crate::VMExecutor;
 #[cfg(feature = "metrics")]
@@ -1329,21 +1329,18 @@ impl StarcoinVM {
         })
     }
 
-    /// Sets execution concurrency level when invoked the first time.
-    pub fn set_concurrency_level_once(mut concurrency_level: usize) {
+    /// Sets execution concurrency level, blocks are executed in parallel by the block-STM
+    /// executor when it is greater than 1.
+    pub fn set_concurrency_level(mut concurrency_level: usize) {
         concurrency_level = min(concurrency_level, num_cpus::get());
-        // Only the first call succeeds, due to OnceCell semantics.
-        EXECUTION_CONCURRENCY_LEVEL.set(concurrency_level).ok();
+        concurrency_level = max(concurrency_level, 1);
+        EXECUTION_CONCURRENCY_LEVEL.store(concurrency_level, Ordering::SeqCst);
         info!("TurboSTM executor concurrency_level {}", concurrency_level);
     }
 
-    /// Get the concurrency level if already set, otherwise return default 1
-    /// (sequential execution).
+    /// Get the concurrency level, default 1 (sequential execution).
     pub fn get_concurrency_level() -> usize {
-        match EXECUTION_CONCURRENCY_LEVEL.get() {
-            Some(concurrency_level) => *concurrency_level,
-            None => 1,
-        }
+        EXECUTION_CONCURRENCY_LEVEL.load(Ordering::SeqCst)
     }
 
     /// Alternate form of 'execute_block' that keeps the vm_status before it goes into the
@@ -24,6 +24,8 @@ pub enum DecodedTransactionPayload {
     Package(DecodedPackage),
     /// A transaction that executes an existing script function published on-chain.
     ScriptFunction(DecodedScriptFunction),
+    /// A transaction that executes script functions one by one as a batch.
+    ScriptFunctionBatch(Vec<DecodedScriptFunction>),
 }
 impl From<DecodedScript> for DecodedTransactionPayload {
     fn from(d: DecodedScript) -> Self {
@@ -126,6 +128,11 @@ pub fn decode_txn_payload(
         TransactionPayload::Script(s) => decode_script(state, s).map(Into::into),
         TransactionPayload::Package(pkg) => decode_package(state, pkg).map(Into::into),
         TransactionPayload::EntryFunction(sf) => decode_script_function(state, sf).map(Into::into),
+        TransactionPayload::EntryFunctionBatch(sfs) => sfs
+            .iter()
+            .map(|sf| decode_script_function(state, sf))
+            .collect::<Result<Vec<_>>>()
+            .map(DecodedTransactionPayload::ScriptFunctionBatch),
     }
 }
 
@@ -1,4 +1,5 @@
 use starcoin_vm2_types::account_address::AccountAddress;
+use starcoin_vm2_types::transaction::authenticator::Scheme;
 use thiserror::Error;
 
 /// wallet error is used in wallet impl, to decouple from service.
@@ -23,6 +24,9 @@ pub enum AccountError {
 
     #[error("invalid public key: {0:?}")]
     InvalidPublicKey(starcoin_crypto::CryptoMaterialError),
+
+    #[error("can not create account key of {0}")]
+    UnsupportedKeyScheme(Scheme),
     // logic error
     #[error("transaction sign error, {0:?}")]
     TransactionSignError(anyhow::Error),
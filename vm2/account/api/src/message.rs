@@ -7,12 +7,17 @@ use starcoin_service_registry::ServiceRequest;
 use starcoin_vm2_types::account_address::AccountAddress;
 use starcoin_vm2_types::account_config::token_code::TokenCode;
 use starcoin_vm2_types::sign_message::{SignedMessage, SigningMessage};
+use starcoin_vm2_types::transaction::authenticator::{AccountAuthenticator, Scheme};
 use starcoin_vm2_types::transaction::{RawUserTransaction, SignedUserTransaction};
 use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub enum AccountRequest {
-    CreateAccount(String),
+    /// Create an account whose key of `key_type` is generated by the wallet.
+    CreateAccount {
+        password: String,
+        key_type: Scheme,
+    },
     GetDefaultAccount(),
     SetDefaultAccount(AccountAddress),
     RemoveAccount(AccountAddress, Option<String>),
@@ -22,6 +27,18 @@ pub enum AccountRequest {
         txn: Box<RawUserTransaction>,
         signer: AccountAddress,
     },
+    /// Sign a txn whose gas is paid by `fee_payer`, the signer is the sender or the fee payer.
+    SignFeePayerTxn {
+        txn: Box<RawUserTransaction>,
+        fee_payer: AccountAddress,
+        signer: AccountAddress,
+    },
+    /// Sign a multi-agent txn, the signer is the sender or one of the `secondary_signers`.
+    SignMultiAgentTxn {
+        txn: Box<RawUserTransaction>,
+        secondary_signers: Vec<AccountAddress>,
+        signer: AccountAddress,
+    },
     SignMessage {
         signer: AccountAddress,
         message: SigningMessage,
@@ -60,6 +77,7 @@ pub enum AccountResponse {
     AccountInfoOption(Box<Option<AccountInfo>>),
     AccountList(Vec<AccountInfo>),
     SignedTxn(Box<SignedUserTransaction>),
+    AccountAuthenticator(Box<AccountAuthenticator>),
     UnlockAccountResponse,
     ExportAccountResponse(Vec<u8>),
     AcceptedTokens(Vec<TokenCode>),
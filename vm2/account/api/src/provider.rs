@@ -3,6 +3,7 @@ use anyhow::Result;
 use starcoin_vm2_types::account_address::AccountAddress;
 use starcoin_vm2_types::account_config::token_code::TokenCode;
 use starcoin_vm2_types::sign_message::{SignedMessage, SigningMessage};
+use starcoin_vm2_types::transaction::authenticator::{AccountAuthenticator, Scheme};
 use starcoin_vm2_types::transaction::{RawUserTransaction, SignedUserTransaction};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -14,7 +15,8 @@ pub enum AccountProviderStrategy {
 }
 
 pub trait AccountProvider {
-    fn create_account(&self, password: String) -> Result<AccountInfo>;
+    /// Create an account with a new key of `key_type`.
+    fn create_account(&self, password: String, key_type: Scheme) -> Result<AccountInfo>;
 
     fn get_default_account(&self) -> Result<Option<AccountInfo>>;
     fn set_default_account(&self, address: AccountAddress) -> Result<AccountInfo>;
@@ -34,6 +36,22 @@ pub trait AccountProvider {
         raw_txn: RawUserTransaction,
         signer_address: AccountAddress,
     ) -> Result<SignedUserTransaction>;
+
+    /// Sign a txn whose gas is paid by `fee_payer_address` as its sender or as the fee payer.
+    fn sign_fee_payer_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        fee_payer_address: AccountAddress,
+        signer_address: AccountAddress,
+    ) -> Result<AccountAuthenticator>;
+
+    /// Sign a multi-agent txn as its sender or as one of `secondary_signer_addresses`.
+    fn sign_multi_agent_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        signer_address: AccountAddress,
+    ) -> Result<AccountAuthenticator>;
     fn unlock_account(
         &self,
         address: AccountAddress,
@@ -8,14 +8,17 @@ use starcoin_service_registry::{ActorService, ServiceHandler, ServiceRef};
 use starcoin_vm2_types::account_address::AccountAddress;
 use starcoin_vm2_types::account_config::token_code::TokenCode;
 use starcoin_vm2_types::sign_message::{SignedMessage, SigningMessage};
+use starcoin_vm2_types::transaction::authenticator::{AccountAuthenticator, Scheme};
 use starcoin_vm2_types::transaction::{RawUserTransaction, SignedUserTransaction};
 
 pub trait AccountAsyncService:
     Clone + std::marker::Unpin + std::marker::Sync + std::marker::Send
 {
+    /// Create an account with a new key of `key_type`.
     fn create_account(
         &self,
         password: String,
+        key_type: Scheme,
     ) -> impl std::future::Future<Output = Result<AccountInfo>> + Send;
 
     fn get_default_account(
@@ -44,6 +47,22 @@ pub trait AccountAsyncService:
         raw_txn: RawUserTransaction,
         signer_address: AccountAddress,
     ) -> impl std::future::Future<Output = Result<SignedUserTransaction>> + Send;
+
+    /// Sign a txn whose gas is paid by `fee_payer_address` as its sender or as the fee payer.
+    fn sign_fee_payer_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        fee_payer_address: AccountAddress,
+        signer_address: AccountAddress,
+    ) -> impl std::future::Future<Output = Result<AccountAuthenticator>> + Send;
+
+    /// Sign a multi-agent txn as its sender or as one of `secondary_signer_addresses`.
+    fn sign_multi_agent_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        signer_address: AccountAddress,
+    ) -> impl std::future::Future<Output = Result<AccountAuthenticator>> + Send;
     fn unlock_account(
         &self,
         address: AccountAddress,
@@ -98,8 +117,10 @@ where
     S: ActorService,
     S: ServiceHandler<S, AccountRequest>,
 {
-    async fn create_account(&self, password: String) -> Result<AccountInfo> {
-        let response = self.send(AccountRequest::CreateAccount(password)).await??;
+    async fn create_account(&self, password: String, key_type: Scheme) -> Result<AccountInfo> {
+        let response = self
+            .send(AccountRequest::CreateAccount { password, key_type })
+            .await??;
         if let AccountResponse::AccountInfo(account) = response {
             Ok(*account)
         } else {
@@ -180,6 +201,46 @@ where
         }
     }
 
+    async fn sign_fee_payer_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        fee_payer_address: AccountAddress,
+        signer_address: AccountAddress,
+    ) -> Result<AccountAuthenticator> {
+        let response = self
+            .send(AccountRequest::SignFeePayerTxn {
+                txn: Box::new(raw_txn),
+                fee_payer: fee_payer_address,
+                signer: signer_address,
+            })
+            .await??;
+        if let AccountResponse::AccountAuthenticator(authenticator) = response {
+            Ok(*authenticator)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
+    async fn sign_multi_agent_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        signer_address: AccountAddress,
+    ) -> Result<AccountAuthenticator> {
+        let response = self
+            .send(AccountRequest::SignMultiAgentTxn {
+                txn: Box::new(raw_txn),
+                secondary_signers: secondary_signer_addresses,
+                signer: signer_address,
+            })
+            .await??;
+        if let AccountResponse::AccountAuthenticator(authenticator) = response {
+            Ok(*authenticator)
+        } else {
+            panic!("Unexpect response type.")
+        }
+    }
+
     async fn unlock_account(
         &self,
         address: AccountAddress,
@@ -6,6 +6,7 @@ use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::token_code::TokenCode;
 use starcoin_types::genesis_config::ChainId;
 use starcoin_types::sign_message::{SignedMessage, SigningMessage};
+use starcoin_types::transaction::authenticator::{AccountAuthenticator, Scheme};
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
 use std::path::Path;
 use std::time::Duration;
@@ -22,10 +23,10 @@ impl AccountLocalProvider {
 }
 
 impl AccountProvider for AccountLocalProvider {
-    fn create_account(&self, password: String) -> anyhow::Result<AccountInfo> {
+    fn create_account(&self, password: String, key_type: Scheme) -> anyhow::Result<AccountInfo> {
         let account_info = self
             .manager
-            .create_account(password.as_str())
+            .create_account(password.as_str(), key_type)
             .map(|account| account.info())?;
         Ok(account_info)
     }
@@ -68,6 +69,28 @@ impl AccountProvider for AccountLocalProvider {
             .map_err(|e| e.into())
     }
 
+    fn sign_fee_payer_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        fee_payer_address: AccountAddress,
+        signer_address: AccountAddress,
+    ) -> anyhow::Result<AccountAuthenticator> {
+        self.manager
+            .sign_fee_payer_txn(signer_address, raw_txn, fee_payer_address)
+            .map_err(|e| e.into())
+    }
+
+    fn sign_multi_agent_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        signer_address: AccountAddress,
+    ) -> anyhow::Result<AccountAuthenticator> {
+        self.manager
+            .sign_multi_agent_txn(signer_address, raw_txn, secondary_signer_addresses)
+            .map_err(|e| e.into())
+    }
+
     fn unlock_account(
         &self,
         address: AccountAddress,
@@ -6,6 +6,7 @@ use starcoin_types::account_address::AccountAddress;
 use starcoin_types::account_config::token_code::TokenCode;
 use starcoin_types::genesis_config::ChainId;
 use starcoin_types::sign_message::{SignedMessage, SigningMessage};
+use starcoin_types::transaction::authenticator::{AccountAuthenticator, Scheme};
 use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
 use std::env;
 use std::path::PathBuf;
@@ -49,7 +50,7 @@ impl AccountPrivateKeyProvider {
     }
 }
 impl AccountProvider for AccountPrivateKeyProvider {
-    fn create_account(&self, _password: String) -> anyhow::Result<AccountInfo> {
+    fn create_account(&self, _password: String, _key_type: Scheme) -> anyhow::Result<AccountInfo> {
         bail!("Unsupported")
     }
 
@@ -89,6 +90,28 @@ impl AccountProvider for AccountPrivateKeyProvider {
             .map_err(|e| e.into())
     }
 
+    fn sign_fee_payer_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        fee_payer_address: AccountAddress,
+        signer_address: AccountAddress,
+    ) -> anyhow::Result<AccountAuthenticator> {
+        self.manager
+            .sign_fee_payer_txn(signer_address, raw_txn, fee_payer_address)
+            .map_err(|e| e.into())
+    }
+
+    fn sign_multi_agent_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        signer_address: AccountAddress,
+    ) -> anyhow::Result<AccountAuthenticator> {
+        self.manager
+            .sign_multi_agent_txn(signer_address, raw_txn, secondary_signer_addresses)
+            .map_err(|e| e.into())
+    }
+
     fn unlock_account(
         &self,
         address: AccountAddress,
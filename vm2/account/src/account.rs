@@ -15,8 +15,11 @@ use starcoin_types::account_address;
 use starcoin_types::account_address::AccountAddress;
 use starcoin_types::genesis_config::ChainId;
 use starcoin_types::sign_message::{SignedMessage, SigningMessage};
+use starcoin_types::transaction::authenticator::AccountAuthenticator;
 use starcoin_types::transaction::authenticator::AuthenticationKey;
-use starcoin_types::transaction::{RawUserTransaction, SignedUserTransaction};
+use starcoin_types::transaction::{
+    RawUserTransaction, RawUserTransactionWithData, SignedUserTransaction,
+};
 
 pub struct Account {
     addr: AccountAddress,
@@ -145,6 +148,33 @@ impl Account {
         Ok(SignedUserTransaction::new(raw_txn, signature))
     }
 
+    /// Sign `raw_txn` as its sender or as `fee_payer_address`, the account paying its gas.
+    pub fn sign_fee_payer_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        fee_payer_address: AccountAddress,
+    ) -> Result<AccountAuthenticator> {
+        let message = RawUserTransactionWithData::new_fee_payer(raw_txn, fee_payer_address);
+        match self.private_key.as_ref() {
+            Some(private_key) => private_key.sign_account(&message),
+            None => Err(format_err!("Readonly account can not sign txn.")),
+        }
+    }
+
+    /// Sign `raw_txn` as its sender or as one of `secondary_signer_addresses`.
+    pub fn sign_multi_agent_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        secondary_signer_addresses: Vec<AccountAddress>,
+    ) -> Result<AccountAuthenticator> {
+        let message =
+            RawUserTransactionWithData::new_multi_agent(raw_txn, secondary_signer_addresses);
+        match self.private_key.as_ref() {
+            Some(private_key) => private_key.sign_account(&message),
+            None => Err(format_err!("Readonly account can not sign txn.")),
+        }
+    }
+
     pub fn destroy(self) -> Result<()> {
         self.store.destroy_account(self.addr)
     }
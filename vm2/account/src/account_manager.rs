@@ -16,7 +16,10 @@ use starcoin_types::sign_message::{SignedMessage, SigningMessage};
 use starcoin_types::{
     account_address::AccountAddress,
     account_config::token_code::TokenCode,
-    transaction::{RawUserTransaction, SignedUserTransaction},
+    transaction::{
+        authenticator::{AccountAuthenticator, Scheme},
+        RawUserTransaction, SignedUserTransaction,
+    },
 };
 use std::collections::HashMap;
 use std::convert::TryFrom;
@@ -73,9 +76,14 @@ impl AccountManager {
         Ok(manager)
     }
 
-    pub fn create_account(&self, password: &str) -> AccountResult<Account> {
-        let private_key = gen_private_key();
-        let private_key = AccountPrivateKey::Single(private_key);
+    pub fn create_account(&self, password: &str, key_type: Scheme) -> AccountResult<Account> {
+        let private_key = match key_type {
+            Scheme::Ed25519 => AccountPrivateKey::Single(gen_private_key()),
+            Scheme::Secp256k1Ecdsa | Scheme::Secp256r1WebAuthn => {
+                AccountPrivateKey::generate(key_type)?
+            }
+            _ => return Err(AccountError::UnsupportedKeyScheme(key_type)),
+        };
         let address = private_key.public_key().derived_address();
         self.save_account(
             address,
@@ -254,6 +262,44 @@ impl AccountManager {
         }
     }
 
+    pub fn sign_fee_payer_txn(
+        &self,
+        signer_address: AccountAddress,
+        raw_txn: RawUserTransaction,
+        fee_payer_address: AccountAddress,
+    ) -> AccountResult<AccountAuthenticator> {
+        let pass = self.key_cache.write().get_pass(&signer_address);
+        match pass {
+            None => Err(AccountError::AccountLocked(signer_address)),
+            Some(p) => {
+                let account = Account::load(signer_address, Some(p), self.store.clone())?
+                    .ok_or(AccountError::AccountNotExist(signer_address))?;
+                account
+                    .sign_fee_payer_txn(raw_txn, fee_payer_address)
+                    .map_err(AccountError::TransactionSignError)
+            }
+        }
+    }
+
+    pub fn sign_multi_agent_txn(
+        &self,
+        signer_address: AccountAddress,
+        raw_txn: RawUserTransaction,
+        secondary_signer_addresses: Vec<AccountAddress>,
+    ) -> AccountResult<AccountAuthenticator> {
+        let pass = self.key_cache.write().get_pass(&signer_address);
+        match pass {
+            None => Err(AccountError::AccountLocked(signer_address)),
+            Some(p) => {
+                let account = Account::load(signer_address, Some(p), self.store.clone())?
+                    .ok_or(AccountError::AccountNotExist(signer_address))?;
+                account
+                    .sign_multi_agent_txn(raw_txn, secondary_signer_addresses)
+                    .map_err(AccountError::TransactionSignError)
+            }
+        }
+    }
+
     pub fn set_default_account(&self, address: AccountAddress) -> AccountResult<AccountInfo> {
         let mut account_info = self
             .account_info(address)?
@@ -16,6 +16,7 @@ use starcoin_types::genesis_config::ChainId;
 use starcoin_types::identifier::{IdentStr, Identifier};
 use starcoin_types::language_storage::{StructTag, CORE_CODE_ADDRESS};
 use starcoin_types::sign_message::{SignedMessage, SigningMessage};
+use starcoin_types::transaction::authenticator::{AccountPrivateKey, Scheme};
 use starcoin_types::transaction::{
     RawUserTransaction, Script, SignedUserTransaction, TransactionPayload,
 };
@@ -29,7 +30,7 @@ pub fn test_import_account() -> Result<()> {
     let manager = AccountManager::new(storage, ChainId::test())?;
 
     // should success
-    let wallet = manager.create_account("hello")?;
+    let wallet = manager.create_account("hello", Scheme::Ed25519)?;
     let private_key = super::account_manager::gen_private_key();
     let result = manager.import_account(*wallet.address(), private_key.to_bytes().to_vec(), "abc");
     assert!(result.is_err());
@@ -52,7 +53,7 @@ pub fn test_wallet() -> Result<()> {
     let manager = AccountManager::new(storage.clone(), ChainId::test())?;
 
     // should success
-    let wallet = manager.create_account("hello")?;
+    let wallet = manager.create_account("hello", Scheme::Ed25519)?;
 
     let wallet_address = wallet.address();
 
@@ -113,7 +114,7 @@ pub fn test_wallet_unlock() -> Result<()> {
     let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
     let manager = AccountManager::new(storage, ChainId::test())?;
 
-    let wallet = manager.create_account("hello")?;
+    let wallet = manager.create_account("hello", Scheme::Ed25519)?;
 
     let unlock_result = manager.unlock_account(*wallet.address(), "hell0", Duration::from_secs(1));
     assert!(unlock_result.is_err());
@@ -150,7 +151,7 @@ pub fn test_sign_message() -> Result<()> {
     let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
     let manager = AccountManager::new(storage, ChainId::test())?;
 
-    let account = manager.create_account("hello")?;
+    let account = manager.create_account("hello", Scheme::Ed25519)?;
     let _unlock_result =
         manager.unlock_account(*account.address(), "hello", Duration::from_secs(100))?;
     let signed_message =
@@ -165,6 +166,82 @@ pub fn test_sign_message() -> Result<()> {
     Ok(())
 }
 
+#[test]
+pub fn test_import_secp256_account() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage.clone(), ChainId::test())?;
+
+    for scheme in [Scheme::Secp256k1Ecdsa, Scheme::Secp256r1WebAuthn] {
+        let private_key = AccountPrivateKey::generate(scheme)?;
+        let address = private_key.public_key().derived_address();
+        manager.import_account(address, private_key.to_bytes(), "hello")?;
+
+        // the scheme of the key is kept by the storage
+        let loaded_account = Account::load(address, Some("hello".to_string()), storage.clone())?
+            .expect("imported account should exist");
+        assert_eq!(loaded_account.private_key(), Some(&private_key));
+        assert_eq!(loaded_account.public_key().scheme(), scheme);
+
+        manager.unlock_account(address, "hello", Duration::from_secs(100))?;
+        let fake_txn = RawUserTransaction::new_with_default_gas_token(
+            address,
+            1,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            1000,
+            1,
+            100000,
+            ChainId::test(),
+        );
+        let signed = manager.sign_txn(address, fake_txn)?;
+        assert_eq!(signed.authenticator().scheme(), scheme);
+        assert_eq!(
+            signed
+                .authenticator()
+                .authentication_key()
+                .derived_address(),
+            address
+        );
+        signed.check_signature()?;
+    }
+    Ok(())
+}
+
+#[test]
+pub fn test_create_secp256_account() -> Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
+    let manager = AccountManager::new(storage, ChainId::test())?;
+
+    for scheme in [Scheme::Secp256k1Ecdsa, Scheme::Secp256r1WebAuthn] {
+        let account = manager.create_account("hello", scheme)?;
+        let address = *account.address();
+        assert_eq!(account.public_key().scheme(), scheme);
+        assert_eq!(account.public_key().derived_address(), address);
+
+        manager.unlock_account(address, "hello", Duration::from_secs(100))?;
+        let fake_txn = RawUserTransaction::new_with_default_gas_token(
+            address,
+            1,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            1000,
+            1,
+            100000,
+            ChainId::test(),
+        );
+        let signed = manager.sign_txn(address, fake_txn)?;
+        assert_eq!(signed.authenticator().scheme(), scheme);
+        signed.check_signature()?;
+    }
+
+    let result = manager.create_account("hello", Scheme::MultiEd25519);
+    assert!(matches!(
+        result.err().unwrap(),
+        AccountError::UnsupportedKeyScheme(Scheme::MultiEd25519)
+    ));
+    Ok(())
+}
+
 // ignore for now.
 #[ignore]
 #[test]
@@ -274,7 +351,7 @@ pub fn test_account_info_between_lock_and_unlock() -> Result<()> {
     let storage = AccountStorage::create_from_path(tempdir.path(), RocksdbConfig::default())?;
     let manager = AccountManager::new(storage, ChainId::test())?;
 
-    let account = manager.create_account("hello")?;
+    let account = manager.create_account("hello", Scheme::Ed25519)?;
 
     let lock_result = manager.lock_account(*account.address());
     assert!(lock_result.is_ok());
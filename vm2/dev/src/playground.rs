@@ -12,7 +12,8 @@ use starcoin_vm2_resource_viewer::module_cache::ModuleCache;
 use starcoin_vm2_resource_viewer::{AnnotatedMoveStruct, AnnotatedMoveValue, MoveValueAnnotator};
 use starcoin_vm2_statedb::ChainStateDB;
 use starcoin_vm2_types::view::{
-    dry_run_output_view::DryRunOutputView, transaction_output_view::TransactionOutputView,
+    dry_run_output_view::{access_list_view, DryRunOutputView},
+    transaction_output_view::TransactionOutputView,
     write_op_value_view::WriteOpValueView,
 };
 use starcoin_vm2_vm_types::file_format::CompiledModule;
@@ -114,6 +115,7 @@ pub fn dry_run_explain<S: StateView>(
 ) -> anyhow::Result<DryRunOutputView> {
     let (vm_status, output) = dry_run(state_view, txn.clone(), metrics)?;
     let vm_status_explain = vm_status_translator::explain_vm_status(state_view, vm_status)?;
+    let access_list = access_list_view(&output);
     let mut txn_output: TransactionOutputView = output.into();
 
     let resolver = {
@@ -154,6 +156,7 @@ pub fn dry_run_explain<S: StateView>(
     Ok(DryRunOutputView {
         explained_status: vm_status_explain,
         txn_output,
+        access_list: Some(access_list),
     })
 }
 
@@ -5,7 +5,7 @@ use starcoin_metrics::metrics::VMMetrics;
 use starcoin_vm2_types::{
     identifier::Identifier,
     language_storage::{ModuleId, TypeTag},
-    transaction::{SignedUserTransaction, Transaction, TransactionOutput},
+    transaction::{AccessList, SignedUserTransaction, Transaction, TransactionOutput},
     vm_error::VMStatus,
 };
 use starcoin_vm2_vm_runtime::starcoin_vm::StarcoinVM;
@@ -26,6 +26,25 @@ pub fn do_execute_block_transactions<S: StateView + Sync>(
     Ok(result)
 }
 
+/// Predict the access list of `txn` by the write set of executing it alone on `chain_state`,
+/// like a dry run of the signed txn. A txn failing the prologue gets an empty access list.
+pub fn predict_access_list<S: StateView>(
+    chain_state: &S,
+    txn: SignedUserTransaction,
+    metrics: Option<VMMetrics>,
+) -> anyhow::Result<AccessList> {
+    let mut outputs = StarcoinVM::execute_block_and_keep_vm_status(
+        vec![Transaction::UserTransaction(txn)],
+        chain_state,
+        None,
+        metrics,
+    )?;
+    Ok(outputs
+        .pop()
+        .map(|(_, output)| AccessList::from_write_set(output.write_set()))
+        .unwrap_or_default())
+}
+
 #[allow(dead_code)]
 pub fn validate_transaction<S: StateView>(
     chain_state: &S,
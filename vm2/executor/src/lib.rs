@@ -7,5 +7,6 @@ pub mod block_executor;
 
 pub use block_executor::{block_execute, block_execute_with_outputs};
 pub use executor::{
-    do_execute_block_transactions, execute_readonly_function, validate_transaction,
+    do_execute_block_transactions, execute_readonly_function, predict_access_list,
+    validate_transaction,
 };
@@ -0,0 +1,149 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_config::genesis_config::ChainNetwork;
+use starcoin_vm2_statedb::ChainStateDB;
+use starcoin_vm2_test_helper::executor::{
+    compile_script, execute_and_apply, get_balance, get_sequence_number, prepare_genesis,
+};
+use starcoin_vm2_test_helper::txn::create_account_txn_sent_as_association;
+use starcoin_vm2_types::{
+    account::Account,
+    transaction::{
+        RawUserTransactionWithData, Script, SignedUserTransaction, Transaction, TransactionPayload,
+        TransactionStatus,
+    },
+    vm_error::{KeptVMStatus, StatusCode},
+};
+use starcoin_vm2_vm_types::state_view::StateReaderExt;
+
+/// A txn of `sender` doing nothing, whose gas is paid by `fee_payer` and authorized by
+/// `fee_payer_signer`.
+fn fee_payer_txn(
+    net: &ChainNetwork,
+    sender: &Account,
+    sequence_number: u64,
+    fee_payer: &Account,
+    fee_payer_signer: &Account,
+) -> Result<Transaction> {
+    let script = compile_script(
+        r#"
+        script{
+            fun main(_sender: signer) {
+            }
+        }
+        "#,
+    )?;
+    let raw_txn = Account::create_raw_txn_impl(
+        *sender.address(),
+        TransactionPayload::Script(Script::new(script, vec![], vec![])),
+        sequence_number,
+        100_000,
+        1,
+        1,
+        net.chain_id().id().into(),
+    );
+    let message = RawUserTransactionWithData::new_fee_payer(raw_txn.clone(), *fee_payer.address());
+    Ok(Transaction::UserTransaction(
+        SignedUserTransaction::fee_payer(
+            raw_txn,
+            sender.private_key().sign_account(&message)?,
+            *fee_payer.address(),
+            fee_payer_signer.private_key().sign_account(&message)?,
+        ),
+    ))
+}
+
+fn create_account(
+    chain_state: &ChainStateDB,
+    net: &ChainNetwork,
+    account: &Account,
+    sequence_number: u64,
+) {
+    let output = execute_and_apply(
+        chain_state,
+        Transaction::UserTransaction(create_account_txn_sent_as_association(
+            account,
+            sequence_number,
+            50_000_000,
+            1,
+            net,
+        )),
+    );
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+}
+
+#[stest::test]
+fn test_fee_payer_pays_the_gas() -> Result<()> {
+    let (chain_state, net) = prepare_genesis()?;
+    let sender = Account::new();
+    let fee_payer = Account::new();
+    create_account(&chain_state, &net, &sender, 0);
+    create_account(&chain_state, &net, &fee_payer, 1);
+
+    let sender_balance = get_balance(*sender.address(), &chain_state);
+    let fee_payer_balance = get_balance(*fee_payer.address(), &chain_state);
+    let txn = fee_payer_txn(&net, &sender, 0, &fee_payer, &fee_payer)?;
+    let output = execute_and_apply(&chain_state, txn);
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    assert!(output.gas_used() > 0);
+
+    assert_eq!(get_balance(*sender.address(), &chain_state), sender_balance);
+    assert_eq!(
+        get_balance(*fee_payer.address(), &chain_state),
+        fee_payer_balance - u128::from(output.gas_used())
+    );
+    assert_eq!(get_sequence_number(*sender.address(), &chain_state), 1);
+    assert_eq!(get_sequence_number(*fee_payer.address(), &chain_state), 0);
+    Ok(())
+}
+
+#[stest::test]
+fn test_fee_payer_creates_the_sender_account() -> Result<()> {
+    let (chain_state, net) = prepare_genesis()?;
+    let sender = Account::new();
+    let fee_payer = Account::new();
+    create_account(&chain_state, &net, &fee_payer, 0);
+    assert!(chain_state.get_account_resource(*sender.address()).is_err());
+
+    let fee_payer_balance = get_balance(*fee_payer.address(), &chain_state);
+    let txn = fee_payer_txn(&net, &sender, 0, &fee_payer, &fee_payer)?;
+    let output = execute_and_apply(&chain_state, txn);
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+
+    let account = chain_state.get_account_resource(*sender.address())?;
+    assert_eq!(account.sequence_number(), 1);
+    assert_eq!(
+        account.authentication_key(),
+        sender.auth_key().to_vec().as_slice()
+    );
+    assert_eq!(
+        get_balance(*fee_payer.address(), &chain_state),
+        fee_payer_balance - u128::from(output.gas_used())
+    );
+    Ok(())
+}
+
+#[stest::test]
+fn test_fee_payer_with_wrong_auth_key_is_rejected() -> Result<()> {
+    let (chain_state, net) = prepare_genesis()?;
+    let sender = Account::new();
+    let fee_payer = Account::new();
+    create_account(&chain_state, &net, &sender, 0);
+    create_account(&chain_state, &net, &fee_payer, 1);
+
+    let fee_payer_balance = get_balance(*fee_payer.address(), &chain_state);
+    let txn = fee_payer_txn(&net, &sender, 0, &fee_payer, &Account::new())?;
+    let output = execute_and_apply(&chain_state, txn);
+    assert_eq!(
+        &TransactionStatus::Discard(StatusCode::INVALID_AUTH_KEY),
+        output.status()
+    );
+    assert_eq!(
+        get_balance(*fee_payer.address(), &chain_state),
+        fee_payer_balance
+    );
+    assert_eq!(get_sequence_number(*sender.address(), &chain_state), 0);
+    Ok(())
+}
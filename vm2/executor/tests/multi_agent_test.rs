@@ -0,0 +1,163 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use starcoin_config::genesis_config::ChainNetwork;
+use starcoin_transaction_builder::vm2::create_signed_txn_with_association_account;
+use starcoin_vm2_statedb::ChainStateDB;
+use starcoin_vm2_test_helper::executor::{
+    compile_modules_with_address, execute_and_apply, get_sequence_number, prepare_genesis,
+};
+use starcoin_vm2_test_helper::txn::create_account_txn_sent_as_association;
+use starcoin_vm2_types::{
+    account::{Account, DEFAULT_MAX_GAS_AMOUNT},
+    account_address::AccountAddress,
+    account_config::association_address,
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag},
+    transaction::{
+        EntryFunction, Package, RawUserTransactionWithData, SignedUserTransaction, Transaction,
+        TransactionPayload, TransactionStatus,
+    },
+    vm_error::{KeptVMStatus, StatusCode},
+};
+use starcoin_vm2_vm_types::state_view::StateReaderExt;
+
+/// Publish a module whose entry function moves a resource holding the sender address to the
+/// secondary signer.
+fn prepare_module(chain_state: &ChainStateDB, net: &ChainNetwork) -> ModuleId {
+    let module_source = r#"
+        module 0xA550C18::MultiAgent {
+            use std::signer;
+
+            struct Received has key {
+                from: address,
+            }
+
+            public entry fun receive(sender: signer, secondary: signer) {
+                move_to(&secondary, Received { from: signer::address_of(&sender) });
+            }
+        }
+        "#;
+    let compiled_module = compile_modules_with_address(association_address(), module_source)
+        .pop()
+        .unwrap();
+    let txn = create_signed_txn_with_association_account(
+        TransactionPayload::Package(Package::new_with_module(compiled_module).unwrap()),
+        0,
+        DEFAULT_MAX_GAS_AMOUNT,
+        1,
+        1,
+        net.chain_id().id().into(),
+        net.genesis_config2(),
+    );
+    let output = execute_and_apply(chain_state, Transaction::UserTransaction(txn));
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    ModuleId::new(
+        association_address(),
+        Identifier::new("MultiAgent").unwrap(),
+    )
+}
+
+/// A txn of `sender` calling `receive` with `secondary` as the secondary signer, whose signature
+/// is made by `secondary_signer`.
+fn multi_agent_txn(
+    net: &ChainNetwork,
+    module_id: ModuleId,
+    sender: &Account,
+    secondary: &Account,
+    secondary_signer: &Account,
+) -> Result<Transaction> {
+    let raw_txn = Account::create_raw_txn_impl(
+        *sender.address(),
+        TransactionPayload::EntryFunction(EntryFunction::new(
+            module_id,
+            Identifier::new("receive").unwrap(),
+            vec![],
+            vec![],
+        )),
+        0,
+        100_000,
+        1,
+        1,
+        net.chain_id().id().into(),
+    );
+    let message =
+        RawUserTransactionWithData::new_multi_agent(raw_txn.clone(), vec![*secondary.address()]);
+    Ok(Transaction::UserTransaction(
+        SignedUserTransaction::multi_agent(
+            raw_txn,
+            sender.private_key().sign_account(&message)?,
+            vec![*secondary.address()],
+            vec![secondary_signer.private_key().sign_account(&message)?],
+        ),
+    ))
+}
+
+fn create_accounts(chain_state: &ChainStateDB, net: &ChainNetwork, accounts: &[&Account]) {
+    for (i, account) in accounts.iter().enumerate() {
+        let output = execute_and_apply(
+            chain_state,
+            Transaction::UserTransaction(create_account_txn_sent_as_association(
+                account,
+                i as u64 + 1,
+                50_000_000,
+                1,
+                net,
+            )),
+        );
+        assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+    }
+}
+
+fn received_tag() -> StructTag {
+    StructTag {
+        address: association_address(),
+        module: Identifier::new("MultiAgent").unwrap(),
+        name: Identifier::new("Received").unwrap(),
+        type_args: vec![],
+    }
+}
+
+#[stest::test]
+fn test_multi_agent_entry_function_receives_every_signer() -> Result<()> {
+    let (chain_state, net) = prepare_genesis()?;
+    let module_id = prepare_module(&chain_state, &net);
+    let sender = Account::new();
+    let secondary = Account::new();
+    create_accounts(&chain_state, &net, &[&sender, &secondary]);
+
+    let txn = multi_agent_txn(&net, module_id, &sender, &secondary, &secondary)?;
+    let output = execute_and_apply(&chain_state, txn);
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+
+    let received = chain_state.get_resource(*secondary.address(), &received_tag())?;
+    assert_eq!(
+        bcs_ext::from_bytes::<AccountAddress>(&received)?,
+        *sender.address()
+    );
+    assert_eq!(get_sequence_number(*sender.address(), &chain_state), 1);
+    assert_eq!(get_sequence_number(*secondary.address(), &chain_state), 0);
+    Ok(())
+}
+
+#[stest::test]
+fn test_multi_agent_with_wrong_secondary_auth_key_is_rejected() -> Result<()> {
+    let (chain_state, net) = prepare_genesis()?;
+    let module_id = prepare_module(&chain_state, &net);
+    let sender = Account::new();
+    let secondary = Account::new();
+    create_accounts(&chain_state, &net, &[&sender, &secondary]);
+
+    let txn = multi_agent_txn(&net, module_id, &sender, &secondary, &Account::new())?;
+    let output = execute_and_apply(&chain_state, txn);
+    assert_eq!(
+        &TransactionStatus::Discard(StatusCode::INVALID_AUTH_KEY),
+        output.status()
+    );
+    assert!(chain_state
+        .get_resource(*secondary.address(), &received_tag())
+        .is_err());
+    assert_eq!(get_sequence_number(*sender.address(), &chain_state), 0);
+    Ok(())
+}
@@ -6,7 +6,7 @@ use anyhow::Result;
 use starcoin_vm2_types::{
     account::{Account, DEFAULT_MAX_GAS_AMOUNT},
     identifier::Identifier,
-    vm_error::KeptVMStatus,
+    vm_error::{KeptVMStatus, StatusCode},
 };
 
 use move_vm2_transactional_test_runner::tasks::SyntaxChoice;
@@ -114,6 +114,84 @@ fn test_invoke_script_function() -> Result<()> {
     Ok(())
 }
 
+#[stest::test]
+fn test_invoke_entry_function_batch() -> Result<()> {
+    let (chain_state, net) = prepare_genesis()?;
+    let module_id = prepare_module(&chain_state, &net);
+
+    let account1 = Account::new();
+    let txn1 = Transaction::UserTransaction(create_account_txn_sent_as_association(
+        &account1, 1, 50_000_000, 1, &net,
+    ));
+    let output1 = execute_and_apply(&chain_state, txn1);
+    assert_eq!(KeptVMStatus::Executed, output1.status().status().unwrap());
+
+    let fn_script_with_args = |i: u64| {
+        EntryFunction::new(
+            module_id.clone(),
+            Identifier::new("fn_script_with_args").unwrap(),
+            vec![],
+            vec![bcs_ext::to_bytes(&i).unwrap()],
+        )
+    };
+
+    // the second call fails for R already exists, so the whole batch fails.
+    let payload = TransactionPayload::EntryFunctionBatch(vec![
+        fn_script_with_args(1),
+        fn_script_with_args(2),
+    ]);
+    let txn = Transaction::UserTransaction(account1.create_signed_txn_impl(
+        *account1.address(),
+        payload,
+        0,
+        100_000,
+        1,
+        1,
+        net.chain_id().id().into(),
+    ));
+    let output = execute_and_apply(&chain_state, txn);
+    assert_ne!(KeptVMStatus::Executed, output.status().status().unwrap());
+
+    // R moved by the failed batch is discarded, so it can be moved again.
+    let payload = TransactionPayload::EntryFunctionBatch(vec![
+        EntryFunction::new(
+            module_id.clone(),
+            Identifier::new("fn_script").unwrap(),
+            vec![],
+            vec![],
+        ),
+        fn_script_with_args(3),
+    ]);
+    let txn = Transaction::UserTransaction(account1.create_signed_txn_impl(
+        *account1.address(),
+        payload,
+        1,
+        100_000,
+        1,
+        1,
+        net.chain_id().id().into(),
+    ));
+    let output = execute_and_apply(&chain_state, txn);
+    assert_eq!(KeptVMStatus::Executed, output.status().status().unwrap());
+
+    // an empty batch is discarded without charging the gas.
+    let txn = Transaction::UserTransaction(account1.create_signed_txn_impl(
+        *account1.address(),
+        TransactionPayload::EntryFunctionBatch(vec![]),
+        2,
+        100_000,
+        1,
+        1,
+        net.chain_id().id().into(),
+    ));
+    let output = execute_and_apply(&chain_state, txn);
+    assert_eq!(
+        &TransactionStatus::Discard(StatusCode::UNKNOWN_SCRIPT),
+        output.status()
+    );
+    Ok(())
+}
+
 #[stest::test]
 fn test_invoke_public_function() -> Result<()> {
     let (chain_state, net) = prepare_genesis()?;
@@ -47,6 +47,11 @@ module starcoin_framework::stc_transaction_validation {
     const EDEPRECATED_FUNCTION: u64 = 1019;
 
     const EPROLOGUE_SIGNER_ALREADY_DELEGATED: u64 = 1200;
+    const EPROLOGUE_FEE_PAYER_DOES_NOT_EXIST: u64 = 1201;
+    const EPROLOGUE_INVALID_FEE_PAYER_AUTH_KEY: u64 = 1202;
+    const EPROLOGUE_SECONDARY_SIGNER_DOES_NOT_EXIST: u64 = 1203;
+    const EPROLOGUE_INVALID_SECONDARY_SIGNER_AUTH_KEY: u64 = 1204;
+    const EPROLOGUE_SECONDARY_SIGNERS_COUNT_MISMATCH: u64 = 1205;
 
 
     /// The prologue is invoked at the beginning of every transaction
@@ -89,6 +94,195 @@ module starcoin_framework::stc_transaction_validation {
             txn_max_gas_units,
         );
 
+        payload_prologue(
+            &account,
+            txn_sender,
+            txn_expiration_time,
+            txn_payload_type,
+            txn_script_or_package_hash,
+            txn_package_address,
+        );
+        debug::print(&std::string::utf8(b"transaction_validation::prologue | Exited"));
+    }
+
+    /// The prologue of a transaction whose gas is paid by `txn_fee_payer` instead of the sender.
+    /// It verifies the sender like `prologue` except the gas balance, which is checked on the
+    /// fee payer after its auth key. A sender without an account can send its first transaction,
+    /// the account is created by `fee_payer_epilogue`.
+    public fun fee_payer_prologue<TokenType>(
+        account: signer,
+        txn_sender: address,
+        txn_sequence_number: u64,
+        txn_authentication_key_preimage: vector<u8>,
+        txn_fee_payer: address,
+        txn_fee_payer_authentication_key_preimage: vector<u8>,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        txn_expiration_time: u64,
+        chain_id: u8,
+        txn_payload_type: u8,
+        txn_script_or_package_hash: vector<u8>,
+        txn_package_address: address,
+    ) {
+        debug::print(&std::string::utf8(b"transaction_validation::fee_payer_prologue | Entered"));
+        system_addresses::assert_starcoin_framework(&account);
+        assert!(chain_id::get() == chain_id, error::invalid_argument(EPROLOGUE_BAD_CHAIN_ID));
+
+        if (account::exists_at(txn_sender)) {
+            // The sender pays no gas, so only its auth key and sequence number are checked.
+            txn_prologue<TokenType>(
+                &account,
+                txn_sender,
+                txn_sequence_number,
+                txn_authentication_key_preimage,
+                0,
+                0,
+            );
+        } else {
+            assert!(
+                account::auth_key_to_address(hash::sha3_256(txn_authentication_key_preimage)) == txn_sender,
+                error::invalid_argument(EPROLOGUE_INVALID_ACCOUNT_AUTH_KEY)
+            );
+            assert!(txn_sequence_number == 0, error::invalid_argument(EPROLOGUE_SEQUENCE_NUMBER_TOO_NEW));
+        };
+
+        assert!(account::exists_at(txn_fee_payer), error::not_found(EPROLOGUE_FEE_PAYER_DOES_NOT_EXIST));
+        if (account::is_account_zero_auth_key(txn_fee_payer)) {
+            assert!(
+                account::auth_key_to_address(
+                    hash::sha3_256(txn_fee_payer_authentication_key_preimage)
+                ) == txn_fee_payer,
+                error::invalid_argument(EPROLOGUE_INVALID_FEE_PAYER_AUTH_KEY)
+            );
+        } else {
+            assert!(
+                account::get_authentication_key(txn_fee_payer) == hash::sha3_256(
+                    txn_fee_payer_authentication_key_preimage
+                ),
+                error::invalid_argument(EPROLOGUE_INVALID_FEE_PAYER_AUTH_KEY)
+            );
+        };
+
+        assert!(
+            (txn_gas_price as u128) * (txn_max_gas_units as u128) <= MAX_U64,
+            error::invalid_argument(EPROLOGUE_CANT_PAY_GAS_DEPOSIT),
+        );
+        let max_transaction_fee = txn_gas_price * txn_max_gas_units;
+        if (max_transaction_fee > 0) {
+            assert!(
+                stc_util::is_stc<TokenType>(),
+                error::invalid_argument(EBAD_TRANSACTION_FEE_TOKEN)
+            );
+            assert!(
+                primary_fungible_store::is_balance_at_least(
+                    txn_fee_payer,
+                    starcoin_coin::get_stc_fa_metadata(),
+                    max_transaction_fee
+                ),
+                error::invalid_argument(EPROLOGUE_CANT_PAY_GAS_DEPOSIT)
+            );
+            assert!(
+                (txn_sequence_number as u128) < MAX_U64,
+                error::out_of_range(EPROLOGUE_SEQUENCE_NUMBER_TOO_BIG)
+            );
+        };
+
+        payload_prologue(
+            &account,
+            txn_sender,
+            txn_expiration_time,
+            txn_payload_type,
+            txn_script_or_package_hash,
+            txn_package_address,
+        );
+        debug::print(&std::string::utf8(b"transaction_validation::fee_payer_prologue | Exited"));
+    }
+
+    /// The prologue of a transaction signed by the sender and by `secondary_signer_addresses`,
+    /// which are all passed to the entry function or script as signers. It verifies the sender
+    /// like `prologue`, then that every secondary signer exists and that its auth key matches
+    /// the preimage at the same position. The sender pays the gas.
+    public fun multi_agent_prologue<TokenType>(
+        account: signer,
+        txn_sender: address,
+        txn_sequence_number: u64,
+        txn_authentication_key_preimage: vector<u8>,
+        secondary_signer_addresses: vector<address>,
+        secondary_signer_authentication_key_preimages: vector<vector<u8>>,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        txn_expiration_time: u64,
+        chain_id: u8,
+        txn_payload_type: u8,
+        txn_script_or_package_hash: vector<u8>,
+        txn_package_address: address,
+    ) {
+        debug::print(&std::string::utf8(b"transaction_validation::multi_agent_prologue | Entered"));
+        system_addresses::assert_starcoin_framework(&account);
+        assert!(chain_id::get() == chain_id, error::invalid_argument(EPROLOGUE_BAD_CHAIN_ID));
+
+        txn_prologue<TokenType>(
+            &account,
+            txn_sender,
+            txn_sequence_number,
+            txn_authentication_key_preimage,
+            txn_gas_price,
+            txn_max_gas_units,
+        );
+
+        let num_secondary_signers = vector::length(&secondary_signer_addresses);
+        assert!(
+            vector::length(&secondary_signer_authentication_key_preimages) == num_secondary_signers,
+            error::invalid_argument(EPROLOGUE_SECONDARY_SIGNERS_COUNT_MISMATCH),
+        );
+        let i = 0;
+        while (i < num_secondary_signers) {
+            let secondary_signer = *vector::borrow(&secondary_signer_addresses, i);
+            assert!(
+                account::exists_at(secondary_signer),
+                error::not_found(EPROLOGUE_SECONDARY_SIGNER_DOES_NOT_EXIST)
+            );
+            assert!(
+                !account::is_signer_capability_offered(secondary_signer),
+                error::invalid_state(EPROLOGUE_SIGNER_ALREADY_DELEGATED)
+            );
+            let authentication_key = hash::sha3_256(
+                *vector::borrow(&secondary_signer_authentication_key_preimages, i)
+            );
+            if (account::is_account_zero_auth_key(secondary_signer)) {
+                assert!(
+                    account::auth_key_to_address(authentication_key) == secondary_signer,
+                    error::invalid_argument(EPROLOGUE_INVALID_SECONDARY_SIGNER_AUTH_KEY)
+                );
+            } else {
+                assert!(
+                    account::get_authentication_key(secondary_signer) == authentication_key,
+                    error::invalid_argument(EPROLOGUE_INVALID_SECONDARY_SIGNER_AUTH_KEY)
+                );
+            };
+            i = i + 1;
+        };
+
+        payload_prologue(
+            &account,
+            txn_sender,
+            txn_expiration_time,
+            txn_payload_type,
+            txn_script_or_package_hash,
+            txn_package_address,
+        );
+        debug::print(&std::string::utf8(b"transaction_validation::multi_agent_prologue | Exited"));
+    }
+
+    /// Check the expiration time and the payload of a transaction.
+    fun payload_prologue(
+        account: &signer,
+        txn_sender: address,
+        txn_expiration_time: u64,
+        txn_payload_type: u8,
+        txn_script_or_package_hash: vector<u8>,
+        txn_package_address: address,
+    ) {
         assert!(
             stc_transaction_timeout::is_valid_transaction_timestamp(txn_expiration_time),
             error::invalid_argument(EPROLOGUE_TRANSACTION_EXPIRED),
@@ -98,23 +292,22 @@ module starcoin_framework::stc_transaction_validation {
             // stdlib upgrade is not affected by PublishOption
             if (txn_package_address != system_addresses::get_starcoin_framework()) {
                 assert!(
-                    transaction_publish_option::is_module_allowed(signer::address_of(&account)),
+                    transaction_publish_option::is_module_allowed(signer::address_of(account)),
                     error::invalid_argument(EPROLOGUE_MODULE_NOT_ALLOWED),
                 );
             };
             stc_transaction_package_validation::package_txn_prologue_v2(
-                &account,
+                account,
                 txn_sender,
                 txn_package_address,
                 txn_script_or_package_hash,
             );
         } else if (txn_payload_type == TXN_PAYLOAD_TYPE_SCRIPT) {
             assert!(
-                transaction_publish_option::is_script_allowed(signer::address_of(&account), ),
+                transaction_publish_option::is_script_allowed(signer::address_of(account), ),
                 error::invalid_argument(EPROLOGUE_SCRIPT_NOT_ALLOWED),
             );
         };
-        debug::print(&std::string::utf8(b"transaction_validation::prologue | Exited"));
         // do nothing for TXN_PAYLOAD_TYPE_SCRIPT_FUNCTION
     }
 
@@ -159,6 +352,48 @@ module starcoin_framework::stc_transaction_validation {
         debug::print(&std::string::utf8(b"stc_transaction_validation::epilogue | Exited"));
     }
 
+    /// The epilogue of a transaction whose gas is paid by `txn_fee_payer`, it collects gas from
+    /// the fee payer and bumps the sequence number of the sender, creating its account if absent.
+    public fun fee_payer_epilogue<TokenType>(
+        account: signer,
+        txn_sender: address,
+        txn_sequence_number: u64,
+        txn_authentication_key_preimage: vector<u8>,
+        txn_fee_payer: address,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        gas_units_remaining: u64,
+        txn_payload_type: u8,
+        _txn_script_or_package_hash: vector<u8>,
+        txn_package_address: address,
+        // txn execute success or fail.
+        success: bool,
+    ) {
+        debug::print(&std::string::utf8(b"stc_transaction_validation::fee_payer_epilogue | Entered"));
+
+        system_addresses::assert_starcoin_framework(&account);
+        account::create_account_if_does_not_exist(txn_sender);
+        charge_and_bump_sequence_number(
+            txn_sender,
+            txn_fee_payer,
+            txn_sequence_number,
+            txn_authentication_key_preimage,
+            txn_gas_price,
+            txn_max_gas_units,
+            gas_units_remaining,
+        );
+        if (txn_payload_type == TXN_PAYLOAD_TYPE_PACKAGE) {
+            stc_transaction_package_validation::package_txn_epilogue(
+                &account,
+                txn_sender,
+                txn_package_address,
+                success,
+            );
+        };
+
+        debug::print(&std::string::utf8(b"stc_transaction_validation::fee_payer_epilogue | Exited"));
+    }
+
     /// Migration from old StarcoinFramework Account::txn_prologue
     public fun txn_prologue<TokenType>(
         account: &signer,
@@ -248,13 +483,33 @@ module starcoin_framework::stc_transaction_validation {
         gas_units_remaining: u64,
     ) {
         system_addresses::assert_starcoin_framework(account);
+        charge_and_bump_sequence_number(
+            txn_sender,
+            txn_sender,
+            _txn_sequence_number,
+            txn_authentication_key_preimage,
+            txn_gas_price,
+            txn_max_gas_units,
+            gas_units_remaining,
+        );
+    }
 
+    /// Charge the gas from `txn_fee_payer` and bump the sequence number of `txn_sender`.
+    fun charge_and_bump_sequence_number(
+        txn_sender: address,
+        txn_fee_payer: address,
+        _txn_sequence_number: u64,
+        txn_authentication_key_preimage: vector<u8>,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        gas_units_remaining: u64,
+    ) {
         // Charge for gas
         let transaction_fee_amount = (txn_gas_price * (txn_max_gas_units - gas_units_remaining) as u128);
         let transaction_fee_amount_u64 = (transaction_fee_amount as u64);
         assert!(
             primary_fungible_store::is_balance_at_least(
-                txn_sender,
+                txn_fee_payer,
                 starcoin_coin::get_stc_fa_metadata(),
                 transaction_fee_amount_u64
             ),
@@ -274,7 +529,7 @@ module starcoin_framework::stc_transaction_validation {
         };
 
         if (transaction_fee_amount_u64 > 0) {
-            transaction_fee::burn_fee_from(txn_sender, transaction_fee_amount_u64);
+            transaction_fee::burn_fee_from(txn_fee_payer, transaction_fee_amount_u64);
         };
     }
 }
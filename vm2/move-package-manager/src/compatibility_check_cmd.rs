@@ -1,16 +1,17 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::release::module;
+use crate::{compatibility_report::build_report, release::module};
 use anyhow::{ensure, Ok};
 use clap::Parser;
 use itertools::Itertools;
 use move_binary_format::CompiledModule;
 use move_cli::Move;
-use move_core_types::resolver::ModuleResolver;
+use move_core_types::{account_address::AccountAddress, resolver::ModuleResolver};
 use move_package::compilation::compiled_package::CompiledUnitWithSource;
 use starcoin_cmd::dev::dev_helper::{self};
 use starcoin_config::BuiltinNetworkID;
+use starcoin_rpc_client::RpcClient;
 use starcoin_types::transaction::Package;
 use starcoin_vm2_move_compiler::check_compiled_module_compat;
 use starcoin_vm2_transactional_test_harness::remote_state::RemoteViewer;
@@ -32,6 +33,15 @@ pub struct CompatibilityCheckCommand {
     #[clap(long = "pre-modules")]
     /// use to check pre modules compatibility.
     pre_modules: Option<PathBuf>,
+
+    #[clap(long = "report")]
+    /// write a json report of the changed structs and functions of each module to the file,
+    /// comparing with the modules and the upgrade policy on chain.
+    report: Option<PathBuf>,
+
+    #[clap(long = "address", requires("report"))]
+    /// the address the package is deployed at, default to the address of the modules.
+    address: Option<AccountAddress>,
 }
 
 pub fn handle_compatibility_check(
@@ -74,6 +84,26 @@ pub fn handle_compatibility_check(
         }
     }
 
+    if let Some(report_path) = cmd.report.as_ref() {
+        let client = if rpc.starts_with("ws") {
+            RpcClient::connect_websocket(&rpc)?
+        } else {
+            RpcClient::connect_http(&rpc)?
+        };
+        let modules = pkg
+            .root_compiled_units
+            .iter()
+            .map(|m| module(&m.unit))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let report = build_report(&client, cmd.address, &modules)?;
+        std::fs::write(report_path, serde_json::to_string_pretty(&report)?)?;
+        eprintln!(
+            "Compatibility report is written to {}, upgrade allowed: {}",
+            report_path.display(),
+            report.allowed
+        );
+    }
+
     if !incompatible_module_ids.is_empty() {
         eprintln!(
             "Modules {} is incompatible with remote chain: {}!",
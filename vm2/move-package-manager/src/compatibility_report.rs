@@ -0,0 +1,610 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{
+        AbilitySet, SignatureToken, StructFieldInformation, StructTypeParameter, Visibility,
+    },
+    CompiledModule,
+};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag, CORE_CODE_ADDRESS},
+};
+use serde::Serialize;
+use starcoin_rpc_client::RpcClient;
+use starcoin_vm2_move_compiler::check_compiled_module_compat;
+use starcoin_vm2_vm_types::account_config::ModuleUpgradeStrategy;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Clone, Copy, Debug, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Compatible,
+    BreakingLayout,
+    SignatureChange,
+    VisibilityChange,
+    AbilityChange,
+    FriendChange,
+}
+
+/// The module upgrade strategy of an account, stored in the
+/// `0x1::stc_transaction_package_validation::ModuleUpgradeStrategy` resource. The account without
+/// the resource is `Arbitrary`.
+#[derive(Clone, Copy, Debug, Default, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UpgradeStrategy {
+    /// the modules can be upgraded directly if they are compatible.
+    #[default]
+    Arbitrary,
+    /// the modules can be upgraded by a submitted upgrade plan if they are compatible.
+    TwoPhase,
+    /// only new modules can be published, the existing modules can not be changed.
+    NewModule,
+    /// no module can be published any more.
+    Freeze,
+}
+
+impl From<&ModuleUpgradeStrategy> for UpgradeStrategy {
+    fn from(strategy: &ModuleUpgradeStrategy) -> Self {
+        if strategy.two_phase() {
+            Self::TwoPhase
+        } else if strategy.only_new_module() {
+            Self::NewModule
+        } else if strategy.freeze() {
+            Self::Freeze
+        } else {
+            Self::Arbitrary
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ItemChange {
+    /// the changed item, such as `struct Coin`, `function transfer` or `friend 0x1::coin`.
+    pub item: String,
+    pub kind: ChangeKind,
+    /// whether the change breaks the modules or txns depending on the item.
+    pub breaking: bool,
+    pub detail: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ModuleReport {
+    pub module: String,
+    /// the module is not published on chain yet.
+    pub is_new: bool,
+    /// the upgrade strategy of the account publishing the module.
+    pub upgrade_strategy: UpgradeStrategy,
+    /// the result of the compatibility check done by the vm when publishing.
+    pub vm_compatible: bool,
+    /// whether the upgrade is allowed by the upgrade strategy.
+    pub allowed: bool,
+    pub changes: Vec<ItemChange>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CompatibilityReport {
+    pub allowed: bool,
+    pub modules: Vec<ModuleReport>,
+}
+
+/// Compare the `modules` with the modules on chain, fetched from the `address` or the address of
+/// each module if not given.
+pub fn build_report(
+    client: &RpcClient,
+    address: Option<AccountAddress>,
+    modules: &[&CompiledModule],
+) -> Result<CompatibilityReport> {
+    let mut strategies = BTreeMap::new();
+    let mut reports = vec![];
+    for module in modules {
+        let address = address.unwrap_or_else(|| *module.address());
+        let module_id = ModuleId::new(address, module.name().to_owned());
+        let old_module = client
+            .state_get_code2(module_id.clone(), false, None)?
+            .map(|code| CompiledModule::deserialize(&code.code.0))
+            .transpose()?;
+        if !strategies.contains_key(&address) {
+            strategies.insert(address, upgrade_strategy(client, address)?);
+        }
+        reports.push(module_report(
+            &module_id,
+            old_module.as_ref(),
+            module,
+            strategies[&address],
+        ));
+    }
+    Ok(CompatibilityReport {
+        allowed: reports.iter().all(|r| r.allowed),
+        modules: reports,
+    })
+}
+
+fn upgrade_strategy(client: &RpcClient, address: AccountAddress) -> Result<UpgradeStrategy> {
+    let struct_tag = StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new("stc_transaction_package_validation")?,
+        name: Identifier::new("ModuleUpgradeStrategy")?,
+        type_args: vec![],
+    };
+    Ok(client
+        .state_get_resource2(address, struct_tag, false, None, None, None)?
+        .map(|resource| bcs_ext::from_bytes::<ModuleUpgradeStrategy>(&resource.raw.0))
+        .transpose()?
+        .map(|strategy| UpgradeStrategy::from(&strategy))
+        .unwrap_or_default())
+}
+
+pub fn module_report(
+    module_id: &ModuleId,
+    old: Option<&CompiledModule>,
+    new: &CompiledModule,
+    upgrade_strategy: UpgradeStrategy,
+) -> ModuleReport {
+    let old = match old {
+        Some(old) => old,
+        None => {
+            return ModuleReport {
+                module: module_id.to_string(),
+                is_new: true,
+                upgrade_strategy,
+                vm_compatible: true,
+                allowed: upgrade_strategy != UpgradeStrategy::Freeze,
+                changes: vec![],
+            }
+        }
+    };
+    let changes = diff_modules(old, new);
+    let vm_compatible = check_compiled_module_compat(old, new).is_ok();
+    let allowed = match upgrade_strategy {
+        UpgradeStrategy::Arbitrary | UpgradeStrategy::TwoPhase => vm_compatible,
+        // the function bodies are not diffed, so compare the whole module.
+        UpgradeStrategy::NewModule | UpgradeStrategy::Freeze => old == new,
+    };
+    ModuleReport {
+        module: module_id.to_string(),
+        is_new: false,
+        upgrade_strategy,
+        vm_compatible,
+        allowed,
+        changes,
+    }
+}
+
+/// List the changed structs, functions and friends from `old` to `new`.
+pub fn diff_modules(old: &CompiledModule, new: &CompiledModule) -> Vec<ItemChange> {
+    let mut changes = vec![];
+    diff_structs(&structs(old), &structs(new), &mut changes);
+    diff_functions(&functions(old), &functions(new), &mut changes);
+    diff_friends(&friends(old), &friends(new), &mut changes);
+    changes
+}
+
+struct StructInfo {
+    abilities: AbilitySet,
+    type_parameters: Vec<StructTypeParameter>,
+    /// None for native structs.
+    fields: Option<Vec<String>>,
+}
+
+struct FunctionInfo {
+    visibility: Visibility,
+    is_entry: bool,
+    signature: String,
+}
+
+fn structs(module: &CompiledModule) -> BTreeMap<String, StructInfo> {
+    module
+        .struct_defs()
+        .iter()
+        .map(|def| {
+            let handle = module.struct_handle_at(def.struct_handle);
+            let fields = match &def.field_information {
+                StructFieldInformation::Native => None,
+                StructFieldInformation::Declared(fields) => Some(
+                    fields
+                        .iter()
+                        .map(|f| {
+                            format!(
+                                "{}: {}",
+                                module.identifier_at(f.name),
+                                token_str(module, &f.signature.0)
+                            )
+                        })
+                        .collect(),
+                ),
+            };
+            (
+                module.identifier_at(handle.name).to_string(),
+                StructInfo {
+                    abilities: handle.abilities,
+                    type_parameters: handle.type_parameters.clone(),
+                    fields,
+                },
+            )
+        })
+        .collect()
+}
+
+fn functions(module: &CompiledModule) -> BTreeMap<String, FunctionInfo> {
+    module
+        .function_defs()
+        .iter()
+        .map(|def| {
+            let handle = module.function_handle_at(def.function);
+            let tokens = |idx| {
+                module
+                    .signature_at(idx)
+                    .0
+                    .iter()
+                    .map(|tok| token_str(module, tok))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            let type_parameters = handle
+                .type_parameters
+                .iter()
+                .map(|abilities| format!("{:?}", abilities))
+                .collect::<Vec<_>>()
+                .join(", ");
+            (
+                module.identifier_at(handle.name).to_string(),
+                FunctionInfo {
+                    visibility: def.visibility,
+                    is_entry: def.is_entry,
+                    signature: format!(
+                        "<{}>({}): ({})",
+                        type_parameters,
+                        tokens(handle.parameters),
+                        tokens(handle.return_)
+                    ),
+                },
+            )
+        })
+        .collect()
+}
+
+fn friends(module: &CompiledModule) -> BTreeSet<String> {
+    module
+        .friend_decls()
+        .iter()
+        .map(|handle| module.module_id_for_handle(handle).to_string())
+        .collect()
+}
+
+fn token_str(module: &CompiledModule, tok: &SignatureToken) -> String {
+    let struct_name = |idx| {
+        let handle = module.struct_handle_at(idx);
+        let module_handle = module.module_handle_at(handle.module);
+        format!(
+            "{}::{}::{}",
+            module
+                .address_identifier_at(module_handle.address)
+                .to_hex_literal(),
+            module.identifier_at(module_handle.name),
+            module.identifier_at(handle.name)
+        )
+    };
+    match tok {
+        SignatureToken::Bool => "bool".to_string(),
+        SignatureToken::U8 => "u8".to_string(),
+        SignatureToken::U16 => "u16".to_string(),
+        SignatureToken::U32 => "u32".to_string(),
+        SignatureToken::U64 => "u64".to_string(),
+        SignatureToken::U128 => "u128".to_string(),
+        SignatureToken::U256 => "u256".to_string(),
+        SignatureToken::Address => "address".to_string(),
+        SignatureToken::Signer => "signer".to_string(),
+        SignatureToken::Vector(ty) => format!("vector<{}>", token_str(module, ty)),
+        SignatureToken::Struct(idx) => struct_name(*idx),
+        SignatureToken::StructInstantiation(idx, tys) => format!(
+            "{}<{}>",
+            struct_name(*idx),
+            tys.iter()
+                .map(|ty| token_str(module, ty))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        SignatureToken::Reference(ty) => format!("&{}", token_str(module, ty)),
+        SignatureToken::MutableReference(ty) => format!("&mut {}", token_str(module, ty)),
+        SignatureToken::TypeParameter(idx) => format!("T{}", idx),
+    }
+}
+
+fn diff_structs(
+    old: &BTreeMap<String, StructInfo>,
+    new: &BTreeMap<String, StructInfo>,
+    changes: &mut Vec<ItemChange>,
+) {
+    for (name, old_struct) in old {
+        let item = format!("struct {}", name);
+        let new_struct = match new.get(name) {
+            Some(new_struct) => new_struct,
+            None => {
+                changes.push(ItemChange {
+                    item,
+                    kind: ChangeKind::BreakingLayout,
+                    breaking: true,
+                    detail: "struct is removed".to_string(),
+                });
+                continue;
+            }
+        };
+        if old_struct.fields != new_struct.fields {
+            changes.push(ItemChange {
+                item: item.clone(),
+                kind: ChangeKind::BreakingLayout,
+                breaking: true,
+                detail: format!(
+                    "fields are changed from {:?} to {:?}",
+                    old_struct.fields, new_struct.fields
+                ),
+            });
+        }
+        if old_struct.abilities != new_struct.abilities {
+            changes.push(ItemChange {
+                item: item.clone(),
+                kind: ChangeKind::AbilityChange,
+                // adding abilities is allowed, but removing is not.
+                breaking: !old_struct.abilities.is_subset(new_struct.abilities),
+                detail: format!(
+                    "abilities are changed from {:?} to {:?}",
+                    old_struct.abilities, new_struct.abilities
+                ),
+            });
+        }
+        if old_struct.type_parameters != new_struct.type_parameters {
+            // type parameters can only relax their constraints.
+            let breaking = old_struct.type_parameters.len() != new_struct.type_parameters.len()
+                || old_struct
+                    .type_parameters
+                    .iter()
+                    .zip(new_struct.type_parameters.iter())
+                    .any(|(o, n)| {
+                        !n.constraints.is_subset(o.constraints) || (o.is_phantom && !n.is_phantom)
+                    });
+            changes.push(ItemChange {
+                item,
+                kind: ChangeKind::AbilityChange,
+                breaking,
+                detail: format!(
+                    "type parameters are changed from {:?} to {:?}",
+                    old_struct.type_parameters, new_struct.type_parameters
+                ),
+            });
+        }
+    }
+    for name in new.keys().filter(|name| !old.contains_key(*name)) {
+        changes.push(ItemChange {
+            item: format!("struct {}", name),
+            kind: ChangeKind::Compatible,
+            breaking: false,
+            detail: "struct is added".to_string(),
+        });
+    }
+}
+
+fn diff_functions(
+    old: &BTreeMap<String, FunctionInfo>,
+    new: &BTreeMap<String, FunctionInfo>,
+    changes: &mut Vec<ItemChange>,
+) {
+    for (name, old_fun) in old {
+        let item = format!("function {}", name);
+        // public functions are linked by other modules, entry functions are called by txns.
+        let is_linked = old_fun.visibility == Visibility::Public || old_fun.is_entry;
+        let new_fun = match new.get(name) {
+            Some(new_fun) => new_fun,
+            None => {
+                changes.push(ItemChange {
+                    item,
+                    kind: ChangeKind::VisibilityChange,
+                    breaking: is_linked,
+                    detail: format!("{:?} function is removed", old_fun.visibility),
+                });
+                continue;
+            }
+        };
+        if old_fun.visibility != new_fun.visibility || old_fun.is_entry != new_fun.is_entry {
+            changes.push(ItemChange {
+                item: item.clone(),
+                kind: ChangeKind::VisibilityChange,
+                breaking: (old_fun.visibility == Visibility::Public
+                    && new_fun.visibility != Visibility::Public)
+                    || (old_fun.is_entry && !new_fun.is_entry),
+                detail: format!(
+                    "visibility is changed from {:?}{} to {:?}{}",
+                    old_fun.visibility,
+                    if old_fun.is_entry { " entry" } else { "" },
+                    new_fun.visibility,
+                    if new_fun.is_entry { " entry" } else { "" }
+                ),
+            });
+        }
+        if old_fun.signature != new_fun.signature {
+            changes.push(ItemChange {
+                item,
+                kind: ChangeKind::SignatureChange,
+                breaking: is_linked,
+                detail: format!(
+                    "signature is changed from {} to {}",
+                    old_fun.signature, new_fun.signature
+                ),
+            });
+        }
+    }
+    for name in new.keys().filter(|name| !old.contains_key(*name)) {
+        changes.push(ItemChange {
+            item: format!("function {}", name),
+            kind: ChangeKind::Compatible,
+            breaking: false,
+            detail: "function is added".to_string(),
+        });
+    }
+}
+
+fn diff_friends(old: &BTreeSet<String>, new: &BTreeSet<String>, changes: &mut Vec<ItemChange>) {
+    for friend in old.difference(new) {
+        changes.push(ItemChange {
+            item: format!("friend {}", friend),
+            kind: ChangeKind::FriendChange,
+            breaking: false,
+            detail: "friend is removed".to_string(),
+        });
+    }
+    for friend in new.difference(old) {
+        changes.push(ItemChange {
+            item: format!("friend {}", friend),
+            kind: ChangeKind::FriendChange,
+            breaking: false,
+            detail: "friend is added".to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff_modules, module_report, ChangeKind, UpgradeStrategy};
+    use crate::{release::module, test_utils::compile_fixture_package};
+    use move_binary_format::{access::ModuleAccess, CompiledModule};
+
+    const BASE: &str = "module fixture::m {
+        struct S has drop { a: u64 }
+        public fun f(x: u64): u64 { x }
+    }";
+
+    /// Compile the `source` in a package and return the module `m`.
+    fn compile(source: &str) -> CompiledModule {
+        let (_dir, package) = compile_fixture_package(source).unwrap();
+        package
+            .root_compiled_units
+            .iter()
+            .map(|unit| module(&unit.unit).unwrap())
+            .find(|module| module.self_id().name().as_str() == "m")
+            .cloned()
+            .expect("module m should be compiled")
+    }
+
+    fn changes(new: &str) -> Vec<(String, ChangeKind, bool)> {
+        diff_modules(&compile(BASE), &compile(new))
+            .into_iter()
+            .map(|change| (change.item, change.kind, change.breaking))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_modules() {
+        assert!(changes(BASE).is_empty());
+        assert_eq!(
+            changes(
+                "module fixture::m {
+                    struct S has drop { a: u64 }
+                    public fun f(x: u64): u64 { x }
+                    public fun g(): u64 { 1 }
+                }"
+            ),
+            vec![("function g".to_string(), ChangeKind::Compatible, false)]
+        );
+        assert_eq!(
+            changes(
+                "module fixture::m {
+                    struct S has drop { a: u64, b: u64 }
+                    public fun f(x: u64): u64 { x }
+                }"
+            ),
+            vec![("struct S".to_string(), ChangeKind::BreakingLayout, true)]
+        );
+        assert_eq!(
+            changes(
+                "module fixture::m {
+                    struct S has drop { a: u64 }
+                    public fun f(x: u64, _y: u64): u64 { x }
+                }"
+            ),
+            vec![("function f".to_string(), ChangeKind::SignatureChange, true)]
+        );
+        assert_eq!(
+            changes(
+                "module fixture::m {
+                    struct S has drop { a: u64 }
+                    fun f(x: u64): u64 { x }
+                }"
+            ),
+            vec![("function f".to_string(), ChangeKind::VisibilityChange, true)]
+        );
+        // adding abilities is compatible, removing is not.
+        assert_eq!(
+            changes(
+                "module fixture::m {
+                    struct S has copy, drop { a: u64 }
+                    public fun f(x: u64): u64 { x }
+                }"
+            ),
+            vec![("struct S".to_string(), ChangeKind::AbilityChange, false)]
+        );
+        assert_eq!(
+            changes(
+                "module fixture::m {
+                    struct S { a: u64 }
+                    public fun f(x: u64): u64 { x }
+                }"
+            ),
+            vec![("struct S".to_string(), ChangeKind::AbilityChange, true)]
+        );
+        assert_eq!(
+            changes(
+                "module cov::n {}
+                module fixture::m {
+                    friend cov::n;
+                    struct S has drop { a: u64 }
+                    public fun f(x: u64): u64 { x }
+                }"
+            ),
+            vec![(
+                "friend 0x42::n".to_string(),
+                ChangeKind::FriendChange,
+                false
+            )]
+        );
+    }
+
+    #[test]
+    fn test_module_report_by_upgrade_strategy() {
+        let old = compile(BASE);
+        let module_id = old.self_id();
+        let compatible = compile(
+            "module fixture::m {
+                struct S has drop { a: u64 }
+                public fun f(x: u64): u64 { x }
+                public fun g(): u64 { 1 }
+            }",
+        );
+        let incompatible = compile(
+            "module fixture::m {
+                struct S has drop { a: u64, b: u64 }
+                public fun f(x: u64): u64 { x }
+            }",
+        );
+        let allowed = |old: Option<&CompiledModule>, new, strategy| {
+            module_report(&module_id, old, new, strategy).allowed
+        };
+
+        for strategy in [UpgradeStrategy::Arbitrary, UpgradeStrategy::TwoPhase] {
+            assert!(allowed(Some(&old), &compatible, strategy));
+            assert!(!allowed(Some(&old), &incompatible, strategy));
+            assert!(allowed(None, &old, strategy));
+        }
+        // the existing modules can not be changed, even compatibly.
+        for strategy in [UpgradeStrategy::NewModule, UpgradeStrategy::Freeze] {
+            assert!(allowed(Some(&old), &old, strategy));
+            assert!(!allowed(Some(&old), &compatible, strategy));
+            assert!(!allowed(Some(&old), &incompatible, strategy));
+        }
+        assert!(allowed(None, &old, UpgradeStrategy::NewModule));
+        assert!(!allowed(None, &old, UpgradeStrategy::Freeze));
+    }
+}
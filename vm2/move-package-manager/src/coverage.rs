@@ -0,0 +1,471 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{package::run_unit_test, prepare_integration_test, G_PRE_COMPILED_LIB};
+use anyhow::{bail, Result};
+use clap::Parser;
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{CodeOffset, FunctionDefinitionIndex},
+    CompiledModule,
+};
+use move_bytecode_source_map::source_map::SourceMap;
+use move_cli::{base::test::Test, Move};
+use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
+use move_core_types::language_storage::ModuleId;
+use move_coverage::coverage_map::{output_map_to_file, CoverageMap, ExecCoverageMap};
+use move_package::{source_package::layout::SourcePackageLayout, BuildConfig};
+use move_vm_runtime::native_functions::NativeFunctionTable;
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+/// The env var read by the move vm to write the execution trace.
+const MOVE_VM_TRACE_ENV: &str = "MOVE_VM_TRACE";
+const TRACE_FILE: &str = ".trace";
+/// Same as the coverage map written by `package test --coverage`, so `package coverage` can
+/// inspect the result too.
+const COVERAGE_MAP_FILE: &str = ".coverage_map.mvcov";
+
+#[derive(Parser)]
+pub struct CoverageCommand {
+    #[command(flatten)]
+    test: Test,
+
+    #[clap(long = "skip-unit-test")]
+    /// do not run the unit tests of the package.
+    skip_unit_test: bool,
+
+    #[clap(long = "skip-integration-test")]
+    /// do not run the integration tests in the `integration-tests` dir.
+    skip_integration_test: bool,
+
+    #[clap(long = "functions")]
+    /// also report the coverage of each function.
+    functions: bool,
+
+    #[clap(long = "lcov")]
+    /// write the coverage in lcov format to the file.
+    lcov: Option<PathBuf>,
+}
+
+/// Run the unit tests and the integration tests of the package with the vm trace enabled, and
+/// report the instruction and line coverage of the package modules.
+pub fn handle_coverage(
+    natives: NativeFunctionTable,
+    move_args: Move,
+    cmd: CoverageCommand,
+) -> Result<()> {
+    let package_path = match move_args.package_path {
+        Some(path) => path,
+        None => std::env::current_dir()?,
+    };
+    let package_root = SourcePackageLayout::try_find_root(&package_path.canonicalize()?)?;
+    let trace_path = package_root.join(TRACE_FILE);
+    if trace_path.exists() {
+        std::fs::remove_file(&trace_path)?;
+    }
+    // the vm reads the env only once, so it must be set before any test is run.
+    std::env::set_var(MOVE_VM_TRACE_ENV, &trace_path);
+
+    if !cmd.skip_unit_test {
+        run_unit_test(
+            natives,
+            Some(package_root.clone()),
+            move_args.build_config.clone(),
+            cmd.test,
+        )?;
+    }
+    if !cmd.skip_integration_test {
+        run_integration_test(package_root.clone(), move_args.build_config.clone())?;
+    }
+    move_vm_runtime::tracing::flush_tracing_buffer();
+    std::env::remove_var(MOVE_VM_TRACE_ENV);
+
+    if !trace_path.exists() {
+        bail!("No execution trace is collected, the package has no test to run.");
+    }
+    let coverage_map = CoverageMap::from_trace_file(&trace_path);
+    output_map_to_file(package_root.join(COVERAGE_MAP_FILE), &coverage_map)?;
+    let exec_map = coverage_map.to_unified_exec_map();
+
+    let package = move_args
+        .build_config
+        .compile_package(&package_root, &mut Vec::new())?;
+    let reports = package
+        .root_compiled_units
+        .iter()
+        .filter_map(|unit| match &unit.unit {
+            CompiledUnit::Module(NamedCompiledModule {
+                module, source_map, ..
+            }) => Some(ModuleReport::new(
+                module,
+                source_map,
+                &unit.source_path,
+                &exec_map,
+            )),
+            CompiledUnit::Script(_) => None,
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    print!("{}", summary(&reports, cmd.functions));
+    if let Some(lcov) = cmd.lcov {
+        std::fs::write(&lcov, to_lcov(&reports))?;
+        eprintln!("lcov report is written to {}", lcov.display());
+    }
+    Ok(())
+}
+
+/// Run all the integration tests one by one in current process, for the vm traces are collected
+/// per process.
+fn run_integration_test(package_root: PathBuf, build_config: BuildConfig) -> Result<()> {
+    let tests_dir = match prepare_integration_test(Some(package_root), build_config)? {
+        Some(tests_dir) => tests_dir,
+        None => return Ok(()),
+    };
+    let mut failed = 0;
+    for path in move_files(&tests_dir)? {
+        if let Err(e) = starcoin_vm2_transactional_test_harness::run_test_impl(
+            &path,
+            G_PRE_COMPILED_LIB.lock().unwrap().as_ref(),
+        ) {
+            eprintln!("integration test {} failed: {}", path.display(), e);
+            failed += 1;
+        }
+    }
+    if failed > 0 {
+        bail!("{} integration tests failed", failed);
+    }
+    Ok(())
+}
+
+fn move_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(move_files(&path)?);
+        } else if path.extension().map_or(false, |ext| ext == "move") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+struct FunctionReport {
+    name: String,
+    /// the first source line of the function body.
+    line: Option<usize>,
+    calls: u64,
+    covered_instructions: usize,
+    total_instructions: usize,
+}
+
+struct ModuleReport {
+    module_id: ModuleId,
+    source_path: PathBuf,
+    functions: Vec<FunctionReport>,
+    /// execution count of each source line which has code.
+    lines: BTreeMap<usize, u64>,
+}
+
+impl ModuleReport {
+    fn new(
+        module: &CompiledModule,
+        source_map: &SourceMap,
+        source_path: &Path,
+        exec_map: &ExecCoverageMap,
+    ) -> Result<Self> {
+        let module_id = module.self_id();
+        let line_starts = line_starts(&std::fs::read_to_string(source_path)?);
+        let module_map = exec_map
+            .module_maps
+            .get(&(*module_id.address(), module_id.name().to_owned()));
+
+        let mut functions = vec![];
+        let mut lines = BTreeMap::new();
+        for (idx, def) in module.function_defs().iter().enumerate() {
+            // native functions have no code to cover.
+            let code = match &def.code {
+                Some(code) => &code.code,
+                None => continue,
+            };
+            let name = module.identifier_at(module.function_handle_at(def.function).name);
+            let hits = module_map.and_then(|m| m.function_maps.get(name));
+            let count_at = |offset: u64| hits.and_then(|h| h.get(&offset)).copied().unwrap_or(0);
+
+            let mut line = None;
+            let mut covered_instructions = 0;
+            for offset in 0..code.len() {
+                let count = count_at(offset as u64);
+                if count > 0 {
+                    covered_instructions += 1;
+                }
+                if let Ok(loc) = source_map
+                    .get_code_location(FunctionDefinitionIndex(idx as u16), offset as CodeOffset)
+                {
+                    let code_line = line_of(&line_starts, loc.start() as usize);
+                    line = Some(line.map_or(code_line, |l: usize| l.min(code_line)));
+                    let line_count = lines.entry(code_line).or_insert(0);
+                    *line_count = count.max(*line_count);
+                }
+            }
+            functions.push(FunctionReport {
+                name: name.to_string(),
+                line,
+                calls: count_at(0),
+                covered_instructions,
+                total_instructions: code.len(),
+            });
+        }
+        Ok(Self {
+            module_id,
+            source_path: source_path.to_path_buf(),
+            functions,
+            lines,
+        })
+    }
+
+    fn instructions(&self) -> (usize, usize) {
+        self.functions.iter().fold((0, 0), |(covered, total), f| {
+            (
+                covered + f.covered_instructions,
+                total + f.total_instructions,
+            )
+        })
+    }
+
+    fn lines(&self) -> (usize, usize) {
+        (
+            self.lines.values().filter(|count| **count > 0).count(),
+            self.lines.len(),
+        )
+    }
+}
+
+/// The byte offsets where each line starts.
+fn line_starts(source: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+        .collect()
+}
+
+/// The 1-based line number of the byte offset.
+fn line_of(line_starts: &[usize], offset: usize) -> usize {
+    match line_starts.binary_search(&offset) {
+        Ok(line) => line + 1,
+        Err(line) => line,
+    }
+}
+
+fn percent(covered: usize, total: usize) -> f64 {
+    if total == 0 {
+        100.0
+    } else {
+        covered as f64 * 100.0 / total as f64
+    }
+}
+
+fn summary(reports: &[ModuleReport], functions: bool) -> String {
+    let mut out = String::new();
+    let (mut covered_instructions, mut total_instructions) = (0, 0);
+    let (mut covered_lines, mut total_lines) = (0, 0);
+    for report in reports {
+        let (ci, ti) = report.instructions();
+        let (cl, tl) = report.lines();
+        let _ = writeln!(
+            out,
+            "Module {}: instructions {}/{} ({:.2}%), lines {}/{} ({:.2}%)",
+            report.module_id,
+            ci,
+            ti,
+            percent(ci, ti),
+            cl,
+            tl,
+            percent(cl, tl)
+        );
+        if functions {
+            for f in &report.functions {
+                let _ = writeln!(
+                    out,
+                    "    fun {}: instructions {}/{} ({:.2}%), calls {}",
+                    f.name,
+                    f.covered_instructions,
+                    f.total_instructions,
+                    percent(f.covered_instructions, f.total_instructions),
+                    f.calls
+                );
+            }
+        }
+        covered_instructions += ci;
+        total_instructions += ti;
+        covered_lines += cl;
+        total_lines += tl;
+    }
+    let _ = writeln!(
+        out,
+        "Total: instructions {}/{} ({:.2}%), lines {}/{} ({:.2}%)",
+        covered_instructions,
+        total_instructions,
+        percent(covered_instructions, total_instructions),
+        covered_lines,
+        total_lines,
+        percent(covered_lines, total_lines)
+    );
+    out
+}
+
+fn to_lcov(reports: &[ModuleReport]) -> String {
+    let mut out = String::new();
+    for report in reports {
+        let _ = writeln!(out, "TN:{}", report.module_id);
+        let _ = writeln!(out, "SF:{}", report.source_path.display());
+        for f in &report.functions {
+            if let Some(line) = f.line {
+                let _ = writeln!(out, "FN:{},{}", line, f.name);
+            }
+        }
+        for f in &report.functions {
+            if f.line.is_some() {
+                let _ = writeln!(out, "FNDA:{},{}", f.calls, f.name);
+            }
+        }
+        let functions = report.functions.iter().filter(|f| f.line.is_some());
+        let _ = writeln!(out, "FNF:{}", functions.clone().count());
+        let _ = writeln!(out, "FNH:{}", functions.filter(|f| f.calls > 0).count());
+        for (line, count) in &report.lines {
+            let _ = writeln!(out, "DA:{},{}", line, count);
+        }
+        let (covered, total) = report.lines();
+        let _ = writeln!(out, "LF:{}", total);
+        let _ = writeln!(out, "LH:{}", covered);
+        let _ = writeln!(out, "end_of_record");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{line_of, line_starts, to_lcov, FunctionReport, ModuleReport};
+    use crate::test_utils::compile_fixture_package;
+    use move_binary_format::access::ModuleAccess;
+    use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
+    use move_core_types::{
+        account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
+    };
+    use move_coverage::coverage_map::ExecCoverageMap;
+    use std::{collections::BTreeMap, path::PathBuf};
+
+    #[test]
+    fn test_line_of() {
+        let starts = line_starts("ab\ncd\n\nef");
+        assert_eq!(starts, vec![0, 3, 6, 7]);
+        assert_eq!(line_of(&starts, 0), 1);
+        assert_eq!(line_of(&starts, 2), 1);
+        assert_eq!(line_of(&starts, 3), 2);
+        assert_eq!(line_of(&starts, 6), 3);
+        assert_eq!(line_of(&starts, 8), 4);
+    }
+
+    #[test]
+    fn test_to_lcov() {
+        let report = ModuleReport {
+            module_id: ModuleId::new(AccountAddress::ONE, Identifier::new("m").unwrap()),
+            source_path: PathBuf::from("sources/m.move"),
+            functions: vec![
+                FunctionReport {
+                    name: "f".to_string(),
+                    line: Some(2),
+                    calls: 3,
+                    covered_instructions: 4,
+                    total_instructions: 4,
+                },
+                FunctionReport {
+                    name: "g".to_string(),
+                    line: Some(3),
+                    calls: 0,
+                    covered_instructions: 0,
+                    total_instructions: 2,
+                },
+                // functions without source line are not reported.
+                FunctionReport {
+                    name: "h".to_string(),
+                    line: None,
+                    calls: 0,
+                    covered_instructions: 0,
+                    total_instructions: 0,
+                },
+            ],
+            lines: BTreeMap::from([(2, 3), (3, 0)]),
+        };
+        assert_eq!(
+            to_lcov(&[report]),
+            "TN:0x1::m\n\
+             SF:sources/m.move\n\
+             FN:2,f\n\
+             FN:3,g\n\
+             FNDA:3,f\n\
+             FNDA:0,g\n\
+             FNF:2\n\
+             FNH:1\n\
+             DA:2,3\n\
+             DA:3,0\n\
+             LF:2\n\
+             LH:1\n\
+             end_of_record\n"
+        );
+    }
+
+    #[test]
+    fn test_module_report() -> anyhow::Result<()> {
+        let (_dir, package) = compile_fixture_package(
+            "module fixture::m {\n    public fun f(x: u64): u64 { x + 1 }\n    public fun g(x: u64): u64 { x * 2 }\n}\n",
+        )?;
+        let unit = &package.root_compiled_units[0];
+        let (module, source_map) = match &unit.unit {
+            CompiledUnit::Module(NamedCompiledModule {
+                module, source_map, ..
+            }) => (module, source_map),
+            CompiledUnit::Script(_) => panic!("expect a module"),
+        };
+
+        // `f` is executed once, `g` is never executed.
+        let module_id = module.self_id();
+        let f = module
+            .function_defs()
+            .iter()
+            .find(|def| {
+                module
+                    .identifier_at(module.function_handle_at(def.function).name)
+                    .as_str()
+                    == "f"
+            })
+            .expect("f should exist");
+        let mut exec_map = ExecCoverageMap::new("test".to_string());
+        for offset in 0..f.code.as_ref().unwrap().code.len() {
+            exec_map.insert(
+                *module_id.address(),
+                module_id.name().to_owned(),
+                Identifier::new("f").unwrap(),
+                offset as u64,
+            );
+        }
+
+        let report = ModuleReport::new(module, source_map, &unit.source_path, &exec_map)?;
+        assert_eq!(report.module_id, module_id);
+        let functions = report
+            .functions
+            .iter()
+            .map(|f| (f.name.as_str(), f.line, f.calls))
+            .collect::<Vec<_>>();
+        assert_eq!(functions, vec![("f", Some(2), 1), ("g", Some(3), 0)]);
+        let (covered, total) = report.instructions();
+        assert_eq!(covered, report.functions[0].total_instructions);
+        assert!(total > covered);
+        assert_eq!(report.lines, BTreeMap::from([(2, 1), (3, 0)]));
+        Ok(())
+    }
+}
@@ -0,0 +1,393 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::package::run_unit_test;
+use anyhow::{bail, format_err, Result};
+use clap::Parser;
+use move_binary_format::{access::ModuleAccess, CompiledModule};
+use move_cli::{base::test::Test, Move};
+use move_compiler::{
+    compiled_unit::{CompiledUnit, NamedCompiledModule},
+    diagnostics::{report_warnings, unwrap_or_report_diagnostics},
+    unit_test::{plan_builder::construct_test_plan, ModuleTestPlan},
+    Compiler, PASS_CFGIR,
+};
+use move_core_types::value::MoveValue;
+use move_package::{
+    compilation::build_plan::BuildPlan, source_package::layout::SourcePackageLayout, BuildConfig,
+};
+use move_vm_runtime::native_functions::NativeFunctionTable;
+use serde::{Deserialize, Serialize};
+use starcoin_vm2_framework::extended_checks;
+use starcoin_vm2_vm_runtime::starcoin_vm::StarcoinVM;
+use starcoin_vm2_vm_types::{
+    identifier::Identifier,
+    language_storage::CORE_CODE_ADDRESS,
+    on_chain_config::Features,
+    state_store::{
+        self, state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+        TStateView,
+    },
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+#[derive(Parser)]
+pub struct TestCommand {
+    #[command(flatten)]
+    test: Test,
+
+    #[clap(long = "gas-report")]
+    /// measure the gas of each unit test with the latest gas schedule after the tests pass.
+    gas_report: bool,
+
+    #[clap(long = "gas-baseline", requires = "gas_report")]
+    /// the gas report saved before, the gas of each test is compared with it.
+    gas_baseline: Option<PathBuf>,
+
+    #[clap(long = "update-gas-baseline", requires = "gas_baseline")]
+    /// write the gas report to the baseline file instead of comparing with it.
+    update_gas_baseline: bool,
+
+    #[clap(long = "max-gas-regression", requires = "gas_baseline")]
+    /// fail if the total gas of a test increases by more than this percentage over the baseline.
+    max_gas_regression: Option<f64>,
+}
+
+/// The gas of a unit test, in gas units of the latest gas schedule, split as the gas meter of the
+/// chain charges it.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TestGasUsage {
+    /// gas of the instructions and the native functions.
+    pub execution: u64,
+    /// gas of loading the resources, always 0 for the gas meter of the chain does not charge it.
+    pub io: u64,
+    /// gas of the global writes, charged for each account mutated by the test.
+    pub storage: u64,
+}
+
+impl TestGasUsage {
+    pub fn total(&self) -> u64 {
+        self.execution + self.io + self.storage
+    }
+}
+
+/// The gas of each unit test, keyed by `address::module::function`. It is also the format of the
+/// baseline file.
+pub type GasReport = BTreeMap<String, TestGasUsage>;
+
+/// Run the unit tests of the package, then measure the gas of each test with the vm and the gas
+/// schedule used by the chain, and compare it with the baseline.
+pub fn handle_test(natives: NativeFunctionTable, move_args: Move, cmd: TestCommand) -> Result<()> {
+    let package_path = match move_args.package_path {
+        Some(path) => path,
+        None => std::env::current_dir()?,
+    };
+    let package_root = SourcePackageLayout::try_find_root(&package_path.canonicalize()?)?;
+    run_unit_test(
+        natives,
+        Some(package_root.clone()),
+        move_args.build_config.clone(),
+        cmd.test,
+    )?;
+    if !cmd.gas_report {
+        return Ok(());
+    }
+
+    let report = measure_gas(&package_root, move_args.build_config)?;
+    match cmd.gas_baseline {
+        Some(baseline) if cmd.update_gas_baseline => {
+            print!("{}", summary(&report, None, None));
+            std::fs::write(&baseline, serde_json::to_string_pretty(&report)? + "\n")?;
+            eprintln!("gas baseline is written to {}", baseline.display());
+            Ok(())
+        }
+        Some(baseline) => {
+            let baseline: GasReport = serde_json::from_slice(&std::fs::read(&baseline)?)
+                .map_err(|e| format_err!("invalid gas baseline {}: {}", baseline.display(), e))?;
+            print!(
+                "{}",
+                summary(&report, Some(&baseline), cmd.max_gas_regression)
+            );
+            if let Some(threshold) = cmd.max_gas_regression {
+                let regressions = report
+                    .iter()
+                    .filter(|(name, usage)| {
+                        baseline
+                            .get(*name)
+                            .map_or(false, |base| regression(base, usage) > threshold)
+                    })
+                    .count();
+                if regressions > 0 {
+                    bail!(
+                        "the gas of {} tests increases by more than {}% over the baseline",
+                        regressions,
+                        threshold
+                    );
+                }
+            }
+            Ok(())
+        }
+        None => {
+            print!("{}", summary(&report, None, None));
+            Ok(())
+        }
+    }
+}
+
+/// Execute every unit test of the package in a fresh state holding the test modules, and
+/// collect the gas charged by the vm. The vm is built with the unit test natives, for mpm2
+/// enables the `testing` feature of the vm runtime. A test failing in the vm without expecting
+/// the failure fails the report, for its gas would be measured up to the error only.
+fn measure_gas(package_root: &Path, build_config: BuildConfig) -> Result<GasReport> {
+    let (test_plan, modules) = build_test_plan(package_root, build_config)?;
+
+    let mut state = HashMap::new();
+    for module in modules {
+        let mut bytes = vec![];
+        module.serialize(&mut bytes)?;
+        state.insert(
+            StateKey::module_id(&module.self_id()),
+            StateValue::new_legacy(bytes.into()),
+        );
+    }
+    // same as the genesis of the unit tests, only the features are initialized.
+    state.insert(
+        StateKey::on_chain_config::<Features>()?,
+        StateValue::new_legacy(bcs_ext::to_bytes(&Features::default())?.into()),
+    );
+    let state = UnitTestStateView { state };
+    let vm = StarcoinVM::new(None, &state);
+
+    let mut report = GasReport::new();
+    for module_plan in test_plan {
+        for (test_name, test_case) in &module_plan.tests {
+            let name = format!(
+                "{}::{}",
+                module_plan.module_id.short_str_lossless(),
+                test_name
+            );
+            // the storage gas is charged as the transaction sent by the first signer.
+            let sender = test_case
+                .arguments
+                .iter()
+                .find_map(|arg| match arg {
+                    MoveValue::Signer(address) => Some(*address),
+                    _ => None,
+                })
+                .unwrap_or(CORE_CODE_ADDRESS);
+            let args = test_case
+                .arguments
+                .iter()
+                .map(|arg| {
+                    arg.simple_serialize()
+                        .ok_or_else(|| format_err!("failed to serialize the arguments of {}", name))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let (result, gas_usage) = vm.execute_function_with_gas_usage(
+                &state,
+                &sender,
+                &module_plan.module_id,
+                &Identifier::new(test_name.as_str())?,
+                vec![],
+                args,
+            );
+            if let (Err(status), None) = (&result, &test_case.expected_failure) {
+                bail!("test {} fails in the vm: {}", name, status);
+            }
+            report.insert(
+                name,
+                TestGasUsage {
+                    execution: gas_usage.execution,
+                    io: 0,
+                    storage: gas_usage.storage,
+                },
+            );
+        }
+    }
+    Ok(report)
+}
+
+/// Compile the package in test mode, return the unit tests of the root package and all the
+/// compiled modules.
+fn build_test_plan(
+    package_root: &Path,
+    build_config: BuildConfig,
+) -> Result<(Vec<ModuleTestPlan>, Vec<CompiledModule>)> {
+    let mut build_config = build_config;
+    build_config.test_mode = true;
+    // the test plan is built by the compiler driver, so the package must be compiled.
+    build_config.force_recompilation = true;
+    build_config
+        .compiler_config
+        .known_attributes
+        .clone_from(extended_checks::get_all_attribute_names());
+    let resolved_graph = build_config
+        .clone()
+        .resolution_graph_for_package(package_root, &mut std::io::stdout())?;
+    let root_package = resolved_graph.root_package.package.name;
+
+    let mut test_plan = None;
+    let compiled = BuildPlan::create(resolved_graph)?.compile_with_driver(
+        &mut std::io::stdout(),
+        &build_config.compiler_config,
+        |compiler: Compiler| {
+            let (files, compiler_result) = compiler.run::<PASS_CFGIR>()?;
+            let (_, compiler) = unwrap_or_report_diagnostics(&files, compiler_result);
+            let (mut compiler, cfgir) = compiler.into_ast();
+            test_plan = construct_test_plan(compiler.compilation_env(), Some(root_package), &cfgir);
+            let (units, warnings) =
+                unwrap_or_report_diagnostics(&files, compiler.at_cfgir(cfgir).build());
+            report_warnings(&files, warnings);
+            Ok((files, units, None))
+        },
+        |_options| {
+            Err(format_err!(
+                "compiler v2 is not supported by the gas report"
+            ))
+        },
+    )?;
+
+    let modules = compiled
+        .root_compiled_units
+        .iter()
+        .chain(compiled.deps_compiled_units.iter().map(|(_, unit)| unit))
+        .filter_map(|unit| match &unit.unit {
+            CompiledUnit::Module(NamedCompiledModule { module, .. }) => Some(module.clone()),
+            CompiledUnit::Script(_) => None,
+        })
+        .collect();
+    Ok((test_plan.unwrap_or_default(), modules))
+}
+
+/// The state of the unit tests.
+struct UnitTestStateView {
+    state: HashMap<StateKey, StateValue>,
+}
+
+impl TStateView for UnitTestStateView {
+    type Key = StateKey;
+
+    fn get_state_value(&self, state_key: &StateKey) -> state_store::Result<Option<StateValue>> {
+        Ok(self.state.get(state_key).cloned())
+    }
+
+    fn get_usage(&self) -> state_store::Result<StateStorageUsage> {
+        Ok(StateStorageUsage::new_untracked())
+    }
+
+    fn is_genesis(&self) -> bool {
+        false
+    }
+}
+
+/// The increase of the total gas over the baseline in percentage.
+fn regression(base: &TestGasUsage, usage: &TestGasUsage) -> f64 {
+    match (base.total(), usage.total()) {
+        (0, 0) => 0.0,
+        (0, _) => f64::INFINITY,
+        (base, total) => (total as f64 - base as f64) * 100.0 / base as f64,
+    }
+}
+
+fn summary(report: &GasReport, baseline: Option<&GasReport>, threshold: Option<f64>) -> String {
+    let mut out = String::new();
+    let mut total = TestGasUsage::default();
+    for (name, usage) in report {
+        let _ = write!(
+            out,
+            "Test {}: execution {}, io {}, storage {}, total {}",
+            name,
+            usage.execution,
+            usage.io,
+            usage.storage,
+            usage.total()
+        );
+        if let Some(baseline) = baseline {
+            match baseline.get(name) {
+                Some(base) => {
+                    let diff = regression(base, usage);
+                    let _ = write!(out, ", baseline {} ({:+.2}%)", base.total(), diff);
+                    if threshold.map_or(false, |t| diff > t) {
+                        let _ = write!(out, " REGRESSION");
+                    }
+                }
+                None => {
+                    let _ = write!(out, ", new test");
+                }
+            }
+        }
+        let _ = writeln!(out);
+        total.execution += usage.execution;
+        total.io += usage.io;
+        total.storage += usage.storage;
+    }
+    if let Some(baseline) = baseline {
+        for name in baseline.keys().filter(|name| !report.contains_key(*name)) {
+            let _ = writeln!(out, "Test {}: removed", name);
+        }
+    }
+    let _ = writeln!(
+        out,
+        "Total: execution {}, io {}, storage {}, total {}",
+        total.execution,
+        total.io,
+        total.storage,
+        total.total()
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{regression, summary, GasReport, TestGasUsage};
+
+    fn usage(execution: u64, storage: u64) -> TestGasUsage {
+        TestGasUsage {
+            execution,
+            io: 0,
+            storage,
+        }
+    }
+
+    #[test]
+    fn test_regression() {
+        assert_eq!(regression(&usage(0, 0), &usage(0, 0)), 0.0);
+        assert_eq!(regression(&usage(0, 0), &usage(1, 0)), f64::INFINITY);
+        assert_eq!(regression(&usage(80, 20), &usage(100, 50)), 50.0);
+        assert_eq!(regression(&usage(80, 20), &usage(50, 0)), -50.0);
+    }
+
+    #[test]
+    fn test_summary() {
+        let report = GasReport::from([
+            ("0x1::m::a".to_string(), usage(80, 20)),
+            ("0x1::m::b".to_string(), usage(150, 0)),
+            ("0x1::m::c".to_string(), usage(10, 0)),
+        ]);
+        let baseline = GasReport::from([
+            ("0x1::m::a".to_string(), usage(100, 0)),
+            ("0x1::m::b".to_string(), usage(100, 0)),
+            ("0x1::m::d".to_string(), usage(1, 0)),
+        ]);
+        assert_eq!(
+            summary(&report, Some(&baseline), Some(10.0)),
+            "Test 0x1::m::a: execution 80, io 0, storage 20, total 100, baseline 100 (+0.00%)\n\
+             Test 0x1::m::b: execution 150, io 0, storage 0, total 150, baseline 100 (+50.00%) REGRESSION\n\
+             Test 0x1::m::c: execution 10, io 0, storage 0, total 10, new test\n\
+             Test 0x1::m::d: removed\n\
+             Total: execution 240, io 0, storage 20, total 260\n"
+        );
+        assert_eq!(
+            summary(&report, None, None),
+            "Test 0x1::m::a: execution 80, io 0, storage 20, total 100\n\
+             Test 0x1::m::b: execution 150, io 0, storage 0, total 150\n\
+             Test 0x1::m::c: execution 10, io 0, storage 0, total 10\n\
+             Total: execution 240, io 0, storage 20, total 260\n"
+        );
+    }
+}
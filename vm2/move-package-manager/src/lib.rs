@@ -15,15 +15,22 @@ use move_compiler::{
     typing, Compiler, FullyCompiledProgram,
 };
 use move_package::{
-    compilation::build_plan::BuildPlan, source_package::layout::SourcePackageLayout,
+    compilation::build_plan::BuildPlan, source_package::layout::SourcePackageLayout, BuildConfig,
 };
 use once_cell::sync::Lazy;
 use std::{fmt::Display, num::NonZeroUsize, path::PathBuf, str::FromStr, sync::Mutex};
 
 pub mod compatibility_check_cmd;
+pub mod compatibility_report;
+#[cfg(feature = "coverage")]
+pub mod coverage;
 pub mod deployment;
+pub mod gas_report;
 pub mod package;
+pub mod package_hooks;
 pub mod release;
+#[cfg(test)]
+mod test_utils;
 
 // use `integration-tests` rather than `tests`, for avoid conflict with `mpm package test`
 pub const INTEGRATION_TESTS_DIR: &str = "integration-tests";
@@ -118,9 +125,64 @@ pub fn run_integration_test(move_arg: Move, cmd: IntegrationTestCommand) -> Resu
     if cmd.task_help {
         return starcoin_vm2_transactional_test_harness::print_help(cmd.task_name);
     };
+    let tests_dir = match prepare_integration_test(move_arg.package_path, move_arg.build_config)? {
+        Some(tests_dir) => tests_dir,
+        None => return Ok(()),
+    };
+    *starcoin_vm2_transactional_test_harness::G_FLAG_RELOAD_STDLIB
+        .lock()
+        .unwrap() = cmd.current_as_stdlib;
+    let requirements = datatest_stable::Requirements::new(
+        move |path| {
+            starcoin_vm2_transactional_test_harness::run_test_impl(
+                path,
+                G_PRE_COMPILED_LIB.lock().unwrap().as_ref(),
+            )
+        },
+        "integration-test".to_string(),
+        tests_dir.display().to_string(),
+        r".*\.move".to_string(),
+    );
+
+    if cmd.update_baseline {
+        std::env::set_var(UPDATE_BASELINE, "true");
+    }
+    let mut test_args = vec![
+        "test_runner".to_string(),
+        "--format".to_string(),
+        cmd.test_opts.format.to_string(),
+        "--test-threads".to_string(),
+        cmd.test_opts.test_threads.to_string(),
+    ];
+    if cmd.test_opts.list {
+        test_args.push("--list".to_string());
+    }
+    if cmd.test_opts.quiet {
+        test_args.push("--quiet".to_string());
+    }
+    if cmd.test_opts.filter_exact {
+        test_args.push("--exact".to_string());
+    }
+
+    if let Some(filter) = cmd.test_opts.filter {
+        test_args.push("--".to_string());
+        test_args.push(filter);
+    }
+
+    let test_opts = datatest_stable::TestOpts::parse_from(test_args.as_slice());
+    datatest_stable::runner_with_opts(&[requirements], test_opts);
+    Ok(())
+}
+
+/// Root to the package and compile it as the pre compiled lib of the integration tests,
+/// return the integration tests dir, or None if the package has no integration tests.
+fn prepare_integration_test(
+    package_path: Option<PathBuf>,
+    build_config: BuildConfig,
+) -> Result<Option<PathBuf>> {
     let rerooted_path = {
-        let path = match move_arg.package_path {
-            Some(_) => move_arg.package_path.clone(),
+        let path = match package_path {
+            Some(_) => package_path,
             None => Some(std::env::current_dir()?),
         };
         // Always root ourselves to the package root, and then compile relative to that.
@@ -131,7 +193,7 @@ pub fn run_integration_test(move_arg: Move, cmd: IntegrationTestCommand) -> Resu
     };
     let (pre_compiled_lib, _compiled_package) = {
         // force move to rebuild all packages, so that we can use compile_driver to generate the full compiled program.
-        let mut build_config = move_arg.build_config;
+        let mut build_config = build_config;
         build_config.force_recompilation = true;
         build_config
             .compiler_config
@@ -272,49 +334,7 @@ pub fn run_integration_test(move_arg: Move, cmd: IntegrationTestCommand) -> Resu
 
     if !tests_dir.exists() || !tests_dir.is_dir() {
         eprintln!("No integration tests file in the dir `integration-tests`.");
-        return Ok(());
-    }
-    *starcoin_vm2_transactional_test_harness::G_FLAG_RELOAD_STDLIB
-        .lock()
-        .unwrap() = cmd.current_as_stdlib;
-    let requirements = datatest_stable::Requirements::new(
-        move |path| {
-            starcoin_vm2_transactional_test_harness::run_test_impl(
-                path,
-                G_PRE_COMPILED_LIB.lock().unwrap().as_ref(),
-            )
-        },
-        "integration-test".to_string(),
-        tests_dir.display().to_string(),
-        r".*\.move".to_string(),
-    );
-
-    if cmd.update_baseline {
-        std::env::set_var(UPDATE_BASELINE, "true");
-    }
-    let mut test_args = vec![
-        "test_runner".to_string(),
-        "--format".to_string(),
-        cmd.test_opts.format.to_string(),
-        "--test-threads".to_string(),
-        cmd.test_opts.test_threads.to_string(),
-    ];
-    if cmd.test_opts.list {
-        test_args.push("--list".to_string());
-    }
-    if cmd.test_opts.quiet {
-        test_args.push("--quiet".to_string());
-    }
-    if cmd.test_opts.filter_exact {
-        test_args.push("--exact".to_string());
+        return Ok(None);
     }
-
-    if let Some(filter) = cmd.test_opts.filter {
-        test_args.push("--".to_string());
-        test_args.push(filter);
-    }
-
-    let test_opts = datatest_stable::TestOpts::parse_from(test_args.as_slice());
-    datatest_stable::runner_with_opts(&[requirements], test_opts);
-    Ok(())
+    Ok(Some(tests_dir))
 }
@@ -4,10 +4,14 @@
 use anyhow::Result;
 use clap::Parser;
 use move_cli::Move;
+#[cfg(feature = "coverage")]
+use move_package_manager2::coverage::{handle_coverage, CoverageCommand};
 use move_package_manager2::{
     compatibility_check_cmd::{handle_compatibility_check, CompatibilityCheckCommand},
     deployment::{handle_deployment, DeploymentCommand},
+    gas_report::{handle_test, TestCommand},
     package::{handle_package_commands, PackageCommand},
+    package_hooks::register_package_hooks,
     release::{handle_release, Release},
     run_integration_test, IntegrationTestCommand,
 };
@@ -49,6 +53,17 @@ pub enum Commands {
     /// Deploy package to chain
     #[clap(name = "deploy")]
     Deploy(DeploymentCommand),
+
+    /// Run unit tests and integration tests with the vm trace enabled, and report the code
+    /// coverage of the package modules.
+    #[cfg(feature = "coverage")]
+    #[clap(name = "coverage")]
+    Coverage(CoverageCommand),
+
+    /// Run the unit tests of the package, with `--gas-report` the gas of each test is measured
+    /// with the latest gas schedule and compared with the saved baseline.
+    #[clap(name = "test")]
+    Test(TestCommand),
 }
 
 fn main() -> Result<()> {
@@ -58,8 +73,8 @@ fn main() -> Result<()> {
     //     bcs_ext::from_bytes(stdlib::ERROR_DESCRIPTIONS).expect("Decode err map failed");
 
     starcoin_logger::init();
-
     let args: CliOptions = CliOptions::parse();
+    register_package_hooks(args.move_args.package_path.clone())?;
 
     let move_args = &args.move_args;
     let gas_params = G_LATEST_GAS_PARAMS.clone();
@@ -79,5 +94,8 @@ fn main() -> Result<()> {
         Commands::Release(release) => handle_release(move_args, release),
         Commands::CompatibilityCheck(cmd) => handle_compatibility_check(move_args, cmd),
         Commands::Deploy(cmd) => handle_deployment(move_args, cmd),
+        #[cfg(feature = "coverage")]
+        Commands::Coverage(cmd) => handle_coverage(natives, args.move_args, cmd),
+        Commands::Test(cmd) => handle_test(natives, args.move_args, cmd),
     }
 }
@@ -6,10 +6,12 @@ use move_cli::{
     },
     Move,
 };
+use move_package::BuildConfig;
 use move_vm_runtime::native_functions::NativeFunctionTable;
 use starcoin_vm2_framework::extended_checks;
 use starcoin_vm2_vm_runtime::natives;
 use starcoin_vm2_vm_types::on_chain_config::starcoin_test_feature_flags_genesis;
+use std::path::PathBuf;
 
 pub const STARCOIN_STDLIB_PACKAGE_NAME: &str = "starcoin_framework";
 pub const STARCOIN_STDLIB_PACKAGE_PATH: &str = "{ \
@@ -72,22 +74,31 @@ pub fn handle_package_commands(
         PackageCommand::Coverage(c) => c.execute(move_args.package_path, move_args.build_config),
         // XXX FIXME YSG
         PackageCommand::Test(c) => {
-            natives::configure_for_unit_test();
-            extended_checks::configure_extended_checks_for_unit_test();
-
-            let mut build_config = move_args.build_config.clone();
-            build_config
-                .compiler_config
-                .known_attributes
-                .clone_from(extended_checks::get_all_attribute_names());
-            c.execute(
-                move_args.package_path,
-                build_config,
-                natives,
-                starcoin_test_feature_flags_genesis(),
-                None,
-            )
+            run_unit_test(natives, move_args.package_path, move_args.build_config, c)
         }
         PackageCommand::Disassemble(c) => c.execute(move_args.package_path, move_args.build_config),
     }
 }
+
+pub fn run_unit_test(
+    natives: NativeFunctionTable,
+    package_path: Option<PathBuf>,
+    build_config: BuildConfig,
+    test: Test,
+) -> anyhow::Result<()> {
+    natives::configure_for_unit_test();
+    extended_checks::configure_extended_checks_for_unit_test();
+
+    let mut build_config = build_config;
+    build_config
+        .compiler_config
+        .known_attributes
+        .clone_from(extended_checks::get_all_attribute_names());
+    test.execute(
+        package_path,
+        build_config,
+        natives,
+        starcoin_test_feature_flags_genesis(),
+        None,
+    )
+}
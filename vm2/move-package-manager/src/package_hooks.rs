@@ -0,0 +1,279 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::package::{STARCOIN_STDLIB_PACKAGE_NAME, STARCOIN_STDLIB_PACKAGE_PATH};
+use anyhow::{bail, ensure, format_err, Result};
+use move_binary_format::{access::ModuleAccess, CompiledModule};
+use move_command_line_common::files::{MOVE_COMPILED_EXTENSION, MOVE_EXTENSION};
+use move_compiler::interface_generator::write_module_to_string;
+use move_core_types::{
+    account_address::AccountAddress,
+    language_storage::{ModuleId, CORE_CODE_ADDRESS},
+};
+use move_package::{
+    compilation::package_layout::CompiledPackageLayout,
+    package_hooks::PackageHooks,
+    source_package::{layout::SourcePackageLayout, parsed_manifest::CustomDepInfo},
+};
+use move_symbol_pool::Symbol;
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_rpc_client::RpcClient;
+use starcoin_vm2_framework::{check_metadata_format, UPGRADE_POLICY_CUSTOM_FIELD};
+use starcoin_vm2_vm_types::on_chain_config::Features;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// The key of the dependency published on chain, example:
+/// `MyDep = { starcoin = "http://127.0.0.1:9850", address = "0x123" }`
+pub const ONCHAIN_DEPENDENCY_KEY: &str = "starcoin";
+/// The lock file in the root package, pins the bytecode hashes of the packages resolved from
+/// chain. A package is pinned when it is downloaded the first time, remove its entry to update it.
+pub const ONCHAIN_PACKAGE_LOCK_FILE: &str = "onchain-package.lock";
+const BYTECODE_DIR: &str = "bytecode_modules";
+
+/// Register the hooks resolving the dependencies published on chain, pinned by the lock file of
+/// the package at `package_path`, or the current dir if not given.
+pub fn register_package_hooks(package_path: Option<PathBuf>) -> Result<()> {
+    let path = match package_path {
+        Some(path) => path,
+        None => std::env::current_dir()?,
+    };
+    let root = path
+        .canonicalize()
+        .ok()
+        .and_then(|path| SourcePackageLayout::try_find_root(&path).ok())
+        .unwrap_or(path);
+    move_package::package_hooks::register_package_hooks(Box::new(StarcoinPackageHooks {
+        lock_path: root.join(ONCHAIN_PACKAGE_LOCK_FILE),
+    }));
+    Ok(())
+}
+
+struct StarcoinPackageHooks {
+    lock_path: PathBuf,
+}
+
+impl PackageHooks for StarcoinPackageHooks {
+    fn custom_package_info_fields(&self) -> Vec<String> {
+        vec![UPGRADE_POLICY_CUSTOM_FIELD.to_string()]
+    }
+
+    fn custom_dependency_key(&self) -> Option<String> {
+        Some(ONCHAIN_DEPENDENCY_KEY.to_string())
+    }
+
+    fn resolve_custom_dependency(&self, _dep_name: Symbol, info: &CustomDepInfo) -> Result<()> {
+        download_package(
+            info.node_url.as_str(),
+            AccountAddress::from_str(info.package_address.as_str())?,
+            info.package_name.as_str(),
+            info.download_to.as_path(),
+            self.lock_path.as_path(),
+        )
+    }
+}
+
+/// The bytecode pinned for a package published on chain, the package is all the modules published
+/// at the address.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct OnChainPackageLock {
+    pub node_url: String,
+    pub address: AccountAddress,
+    /// sha3 hash of the bytecode of each module.
+    pub modules: BTreeMap<String, HashValue>,
+}
+
+impl OnChainPackageLock {
+    /// Check the cached bytecode and sources in `dir` are the pinned ones.
+    pub fn verify(&self, dir: &Path) -> Result<()> {
+        for (name, hash) in &self.modules {
+            let code = std::fs::read(bytecode_path(dir, name))?;
+            ensure!(
+                HashValue::sha3_256_of(&code) == *hash,
+                "bytecode hash of cached module {} mismatch",
+                name
+            );
+            ensure!(
+                source_path(dir, name).exists(),
+                "source of cached module {} is missing",
+                name
+            );
+        }
+        ensure!(
+            dir.join(SourcePackageLayout::Manifest.path()).exists(),
+            "manifest of cached package is missing"
+        );
+        Ok(())
+    }
+}
+
+/// The pinned packages of the lock file, keyed by the package name.
+pub type OnChainPackageLocks = BTreeMap<String, OnChainPackageLock>;
+
+pub fn read_locks(lock_path: &Path) -> Result<OnChainPackageLocks> {
+    if lock_path.exists() {
+        Ok(serde_json::from_slice(&std::fs::read(lock_path)?)?)
+    } else {
+        Ok(BTreeMap::new())
+    }
+}
+
+/// Download the modules published at `address` to `download_to` as the package `package_name`,
+/// with their interfaces generated from the bytecode as the sources. The bytecode must match the
+/// hashes pinned in the lock file at `lock_path`, the package is pinned if it is not yet.
+pub fn download_package(
+    node_url: &str,
+    address: AccountAddress,
+    package_name: &str,
+    download_to: &Path,
+    lock_path: &Path,
+) -> Result<()> {
+    let mut locks = read_locks(lock_path)?;
+    let pinned = locks.get(package_name);
+    if let Some(pinned) = pinned {
+        ensure!(
+            pinned.address == address,
+            "Package {} is pinned at {} in {}, but depended at {}",
+            package_name,
+            pinned.address,
+            lock_path.display(),
+            address
+        );
+        match pinned.verify(download_to) {
+            Ok(()) => return Ok(()),
+            Err(e) => eprintln!("Download package {} again: {}", package_name, e),
+        }
+    }
+
+    let client = if node_url.starts_with("ws") {
+        RpcClient::connect_websocket(node_url)?
+    } else {
+        RpcClient::connect_http(node_url)?
+    };
+    let codes = client.state_list_code2(address, false, None)?.codes;
+    ensure!(!codes.is_empty(), "No module is published at {}", address);
+
+    let mut modules = BTreeMap::new();
+    let mut hashes = BTreeMap::new();
+    for (name, code) in codes {
+        let module = CompiledModule::deserialize(&code.code.0)?;
+        let expect_id = ModuleId::new(address, name.clone());
+        ensure!(
+            module.self_id() == expect_id,
+            "Module {} is published as {}",
+            module.self_id(),
+            expect_id
+        );
+        check_metadata_format(&module, &Features::default())
+            .map_err(|e| format_err!("Module {} has malformed metadata: {:?}", expect_id, e))?;
+        hashes.insert(name.to_string(), HashValue::sha3_256_of(&code.code.0));
+        modules.insert(name.to_string(), (module, code.code.0));
+    }
+    if let Some(pinned) = pinned {
+        if pinned.modules != hashes {
+            bail!(
+                "The modules at {} mismatch the ones pinned in {}, remove the entry of package {} \
+                 to update it",
+                address,
+                lock_path.display(),
+                package_name
+            );
+        }
+    }
+
+    let sources_dir = download_to.join(SourcePackageLayout::Sources.path());
+    if sources_dir.exists() {
+        std::fs::remove_dir_all(&sources_dir)?;
+    }
+    std::fs::create_dir_all(&sources_dir)?;
+    std::fs::create_dir_all(download_to.join(BYTECODE_DIR))?;
+    let mut dep_addresses = BTreeSet::new();
+    for (name, (module, code)) in &modules {
+        let (_, interface) = write_module_to_string(&BTreeMap::<ModuleId, String>::new(), module)?;
+        std::fs::write(source_path(download_to, name), interface)?;
+        std::fs::write(bytecode_path(download_to, name), code)?;
+        dep_addresses.extend(
+            module
+                .module_handles()
+                .iter()
+                .map(|handle| *module.address_identifier_at(handle.address))
+                .filter(|dep_address| *dep_address != address),
+        );
+    }
+    std::fs::write(
+        download_to.join(SourcePackageLayout::Manifest.path()),
+        manifest(node_url, package_name, &dep_addresses),
+    )?;
+    // remove the stale build output of the previous download.
+    let build_dir = download_to.join(CompiledPackageLayout::Root.path());
+    if build_dir.exists() {
+        std::fs::remove_dir_all(build_dir)?;
+    }
+
+    if pinned.is_none() {
+        locks.insert(
+            package_name.to_string(),
+            OnChainPackageLock {
+                node_url: node_url.to_string(),
+                address,
+                modules: hashes,
+            },
+        );
+        std::fs::write(lock_path, serde_json::to_string_pretty(&locks)?)?;
+    }
+    Ok(())
+}
+
+/// The manifest of the downloaded package, the modules at `0x1` are resolved from the starcoin
+/// framework, and the modules at other addresses are resolved from chain too.
+fn manifest(
+    node_url: &str,
+    package_name: &str,
+    dep_addresses: &BTreeSet<AccountAddress>,
+) -> String {
+    let dependencies = dep_addresses
+        .iter()
+        .map(|dep_address| {
+            if *dep_address == CORE_CODE_ADDRESS {
+                format!(
+                    "{} = {}",
+                    STARCOIN_STDLIB_PACKAGE_NAME, STARCOIN_STDLIB_PACKAGE_PATH
+                )
+            } else {
+                format!(
+                    "{} = {{ {} = \"{}\", address = \"{}\" }}",
+                    onchain_package_name(dep_address),
+                    ONCHAIN_DEPENDENCY_KEY,
+                    node_url,
+                    dep_address.to_hex_literal()
+                )
+            }
+        })
+        .collect::<Vec<_>>();
+    format!(
+        "[package]\nname = \"{}\"\nversion = \"0.0.0\"\n\n[dependencies]\n{}\n",
+        package_name,
+        dependencies.join("\n")
+    )
+}
+
+/// The package name of the modules at `address` depended by a downloaded package.
+fn onchain_package_name(address: &AccountAddress) -> String {
+    format!("onchain_{}", address.short_str_lossless())
+}
+
+fn source_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(SourcePackageLayout::Sources.path())
+        .join(name)
+        .with_extension(MOVE_EXTENSION)
+}
+
+fn bytecode_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(BYTECODE_DIR)
+        .join(name)
+        .with_extension(MOVE_COMPILED_EXTENSION)
+}
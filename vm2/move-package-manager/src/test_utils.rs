@@ -0,0 +1,20 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_package::{compilation::compiled_package::CompiledPackage, BuildConfig};
+use tempfile::TempDir;
+
+/// Compile a package named `Fixture` holding the `source` in `sources/m.move`, the named address
+/// `fixture` is `0x42`. The package dir is returned too, for the source paths of the compiled units
+/// are valid only while it lives.
+pub(crate) fn compile_fixture_package(source: &str) -> anyhow::Result<(TempDir, CompiledPackage)> {
+    let dir = tempfile::tempdir()?;
+    std::fs::write(
+        dir.path().join("Move.toml"),
+        "[package]\nname = \"Fixture\"\nversion = \"0.0.0\"\n\n[addresses]\nfixture = \"0x42\"\n",
+    )?;
+    std::fs::create_dir(dir.path().join("sources"))?;
+    std::fs::write(dir.path().join("sources").join("m.move"), source)?;
+    let package = BuildConfig::default().compile_package(dir.path(), &mut Vec::new())?;
+    Ok((dir, package))
+}
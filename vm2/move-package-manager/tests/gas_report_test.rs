@@ -0,0 +1,195 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use clap::Parser;
+use move_cli::Move;
+use move_package_manager2::gas_report::{handle_test, GasReport, TestCommand};
+use starcoin_vm2_gas_schedule::{G_LATEST_GAS_PARAMS, LATEST_GAS_FEATURE_VERSION};
+use starcoin_vm2_vm_runtime::natives::starcoin_natives;
+use starcoin_vm2_vm_types::on_chain_config::TimedFeaturesBuilder;
+use std::path::{Path, PathBuf};
+
+#[derive(Parser)]
+struct TestCli {
+    #[command(flatten)]
+    move_args: Move,
+
+    #[command(flatten)]
+    cmd: TestCommand,
+}
+
+/// Run `mpm2 test` on the package with the given options, the build output goes to `install_dir`.
+fn run_test(package: &Path, install_dir: &Path, options: &[&str]) -> Result<()> {
+    let mut args = vec![
+        "test",
+        "--path",
+        path_str(package),
+        "--install-dir",
+        path_str(install_dir),
+    ];
+    args.extend_from_slice(options);
+    let cli = TestCli::try_parse_from(args)?;
+    let gas_params = G_LATEST_GAS_PARAMS.clone();
+    let natives = starcoin_natives(
+        LATEST_GAS_FEATURE_VERSION,
+        gas_params.natives,
+        gas_params.vm.misc,
+        TimedFeaturesBuilder::enable_all().build(),
+        Default::default(),
+    );
+    handle_test(natives, cli.move_args, cli.cmd)
+}
+
+fn path_str(path: &Path) -> &str {
+    path.to_str().expect("the test paths are valid utf8")
+}
+
+fn framework_package(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../framework")
+        .join(name)
+        .canonicalize()
+        .unwrap()
+}
+
+fn read_report(path: &Path) -> Result<GasReport> {
+    Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+}
+
+#[test]
+fn test_gas_report_of_package() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let package = dir.path().join("fixture");
+    std::fs::create_dir_all(package.join("sources"))?;
+    std::fs::write(
+        package.join("Move.toml"),
+        format!(
+            "[package]\nname = \"Fixture\"\nversion = \"0.0.0\"\n\n[addresses]\nfixture = \"0x42\"\n\n\
+             [dependencies]\nStarcoinFramework = {{ local = \"{}\" }}\n",
+            path_str(&framework_package("starcoin-framework"))
+        ),
+    )?;
+    // the tests call the natives only registered for the unit tests.
+    std::fs::write(
+        package.join("sources").join("m.move"),
+        r#"
+        module fixture::m {
+            use starcoin_framework::event;
+
+            struct Counter has key {
+                value: u64,
+            }
+
+            #[event]
+            struct Published has drop, store {
+                value: u64,
+            }
+
+            public fun publish(account: &signer, value: u64) {
+                move_to(account, Counter { value });
+                event::emit(Published { value });
+            }
+
+            #[test(account = @0x42)]
+            fun test_publish(account: &signer) acquires Counter {
+                publish(account, 1);
+                assert!(borrow_global<Counter>(@0x42).value == 1, 1);
+                assert!(event::was_event_emitted(&Published { value: 1 }), 2);
+            }
+
+            #[test]
+            fun test_create_signers() {
+                let signers = std::unit_test::create_signers_for_testing(2);
+                publish(std::vector::borrow(&signers, 1), 2);
+            }
+
+            #[test]
+            #[expected_failure(abort_code = 7, location = Self)]
+            fun test_abort() {
+                abort 7
+            }
+        }
+        "#,
+    )?;
+    let install_dir = dir.path().join("build");
+    let baseline = dir.path().join("gas_baseline.json");
+
+    run_test(
+        &package,
+        &install_dir,
+        &[
+            "--gas-report",
+            "--gas-baseline",
+            path_str(&baseline),
+            "--update-gas-baseline",
+        ],
+    )?;
+    let report = read_report(&baseline)?;
+    assert_eq!(
+        report.keys().collect::<Vec<_>>(),
+        vec![
+            "0x42::m::test_abort",
+            "0x42::m::test_create_signers",
+            "0x42::m::test_publish"
+        ]
+    );
+    let publish = report["0x42::m::test_publish"];
+    assert!(publish.execution > 0);
+    assert_eq!(publish.io, 0);
+    assert!(publish.storage > 0);
+    assert!(report["0x42::m::test_create_signers"].execution > 0);
+
+    // the gas is the same in every run.
+    run_test(
+        &package,
+        &install_dir,
+        &[
+            "--gas-report",
+            "--gas-baseline",
+            path_str(&baseline),
+            "--max-gas-regression",
+            "0",
+        ],
+    )?;
+
+    let mut lower = report.clone();
+    lower.get_mut("0x42::m::test_publish").unwrap().execution = 1;
+    std::fs::write(&baseline, serde_json::to_string_pretty(&lower)?)?;
+    assert!(run_test(
+        &package,
+        &install_dir,
+        &[
+            "--gas-report",
+            "--gas-baseline",
+            path_str(&baseline),
+            "--max-gas-regression",
+            "10",
+        ],
+    )
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_gas_report_of_framework() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let baseline = dir.path().join("gas_baseline.json");
+    run_test(
+        &framework_package("starcoin-framework"),
+        &dir.path().join("build"),
+        &[
+            "--gas-report",
+            "--gas-baseline",
+            path_str(&baseline),
+            "--update-gas-baseline",
+        ],
+    )?;
+    let report = read_report(&baseline)?;
+    // `test_events` reads the emitted events with a unit test native.
+    let events = report["0x1::account::test_events"];
+    assert!(events.execution > 0);
+    assert_eq!(events.io, 0);
+    assert!(report.values().all(|usage| usage.io == 0));
+    Ok(())
+}
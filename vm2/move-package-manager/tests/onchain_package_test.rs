@@ -0,0 +1,152 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use move_command_line_common::files::MOVE_COMPILED_EXTENSION;
+use move_core_types::language_storage::CORE_CODE_ADDRESS;
+use move_package::BuildConfig;
+use move_package_manager2::package_hooks::{
+    download_package, read_locks, register_package_hooks, ONCHAIN_PACKAGE_LOCK_FILE,
+};
+use starcoin_config::NodeConfig;
+use starcoin_crypto::HashValue;
+use starcoin_package_builder::PackageBuilder;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const PACKAGE_NAME: &str = "onchain_framework";
+
+#[stest::test]
+fn test_download_package_from_dev_node() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_url = format!(
+        "http://127.0.0.1:{}",
+        config.rpc.get_http_address().unwrap().port
+    );
+    let node_handle = test_helper::run_node_by_config(config)?;
+
+    let dir = tempfile::tempdir()?;
+    let download_to = dir.path().join(PACKAGE_NAME);
+    let lock_path = dir.path().join(ONCHAIN_PACKAGE_LOCK_FILE);
+    download_package(
+        &node_url,
+        CORE_CODE_ADDRESS,
+        PACKAGE_NAME,
+        &download_to,
+        &lock_path,
+    )?;
+
+    // The package is pinned by the first download.
+    let locks = read_locks(&lock_path)?;
+    let lock = &locks[PACKAGE_NAME];
+    assert_eq!(lock.address, CORE_CODE_ADDRESS);
+    assert!(lock.modules.contains_key("coin"));
+    lock.verify(&download_to)?;
+    let interface = std::fs::read_to_string(download_to.join("sources").join("coin.move"))?;
+    assert!(interface.contains("::coin"));
+
+    // The cached package is reused if it is the pinned one.
+    download_package(
+        &node_url,
+        CORE_CODE_ADDRESS,
+        PACKAGE_NAME,
+        &download_to,
+        &lock_path,
+    )?;
+
+    // The bytecode on chain must match the pinned hashes.
+    let mut tampered = locks.clone();
+    tampered
+        .get_mut(PACKAGE_NAME)
+        .unwrap()
+        .modules
+        .insert("coin".to_string(), HashValue::zero());
+    std::fs::write(&lock_path, serde_json::to_string_pretty(&tampered)?)?;
+    assert!(download_package(
+        &node_url,
+        CORE_CODE_ADDRESS,
+        PACKAGE_NAME,
+        &download_to,
+        &lock_path,
+    )
+    .is_err());
+
+    node_handle.stop()?;
+    Ok(())
+}
+
+/// The dir the dependency `package_name` of the compiled root package is downloaded to.
+fn download_dir_of(root: &Path, package_name: &str) -> Result<PathBuf> {
+    let package = BuildConfig::default().compile_package(root, &mut Vec::new())?;
+    let (_, unit) = package
+        .deps_compiled_units
+        .iter()
+        .find(|(name, unit)| name.as_str() == package_name && unit.unit.name().as_str() == "coin")
+        .expect("coin should be compiled from the on-chain package");
+    Ok(unit
+        .source_path
+        .parent()
+        .and_then(|sources| sources.parent())
+        .expect("source of a module is in the sources dir of its package")
+        .to_path_buf())
+}
+
+#[stest::test]
+fn test_build_package_depending_on_onchain_package() -> Result<()> {
+    let config = Arc::new(NodeConfig::random_for_test());
+    let node_url = format!(
+        "http://127.0.0.1:{}",
+        config.rpc.get_http_address().unwrap().port
+    );
+    let node_handle = test_helper::run_node_by_config(config)?;
+
+    let mut builder = PackageBuilder::new("Dependent");
+    builder.add_onchain_dep(PACKAGE_NAME, &node_url, "0x1");
+    builder.add_source(
+        "dependent",
+        "module 0x42::dependent {
+            use 0x1::coin;
+            use 0x1::starcoin_coin::STC;
+
+            public fun stc_initialized(): bool {
+                coin::is_coin_initialized<STC>()
+            }
+        }",
+    );
+    let dir = builder.write_to_temp()?;
+    register_package_hooks(Some(dir.path().to_path_buf()))?;
+
+    // The first build pins the bytecode of the dependency in the lock file of the root package.
+    let download_to = download_dir_of(dir.path(), PACKAGE_NAME)?;
+    let lock_path = dir.path().join(ONCHAIN_PACKAGE_LOCK_FILE);
+    let locks = read_locks(&lock_path)?;
+    let lock = &locks[PACKAGE_NAME];
+    assert_eq!(lock.address, CORE_CODE_ADDRESS);
+    lock.verify(&download_to)?;
+
+    // A cached module differing from the pinned one is downloaded again.
+    let coin_bytecode = download_to
+        .join("bytecode_modules")
+        .join("coin")
+        .with_extension(MOVE_COMPILED_EXTENSION);
+    std::fs::write(&coin_bytecode, b"tampered")?;
+    assert_eq!(download_dir_of(dir.path(), PACKAGE_NAME)?, download_to);
+    assert_eq!(
+        HashValue::sha3_256_of(&std::fs::read(&coin_bytecode)?),
+        lock.modules["coin"]
+    );
+    assert_eq!(read_locks(&lock_path)?, locks);
+
+    // The build fails if the modules on chain are not the pinned ones.
+    let mut tampered = locks.clone();
+    tampered
+        .get_mut(PACKAGE_NAME)
+        .unwrap()
+        .modules
+        .insert("coin".to_string(), HashValue::zero());
+    std::fs::write(&lock_path, serde_json::to_string_pretty(&tampered)?)?;
+    assert!(download_dir_of(dir.path(), PACKAGE_NAME).is_err());
+
+    node_handle.stop()?;
+    Ok(())
+}
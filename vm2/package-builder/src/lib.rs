@@ -34,7 +34,16 @@ impl PackageBuilder {
     }
 
     pub fn add_local_dep(&mut self, name: &str, path: &str) {
-        self.deps.push((name.to_string(), path.to_string()))
+        self.deps
+            .push((name.to_string(), format!("{{ local = \"{}\" }}", path)))
+    }
+
+    /// Depend on the package `name` published at `addr`, downloaded from the node at `node_url`.
+    pub fn add_onchain_dep(&mut self, name: &str, node_url: &str, addr: &str) {
+        self.deps.push((
+            name.to_string(),
+            format!("{{ starcoin = \"{}\", address = \"{}\" }}", node_url, addr),
+        ))
     }
 
     pub fn add_alias(&mut self, name: &str, addr: &str) {
@@ -70,7 +79,7 @@ upgrade_policy = \"{}\"
                     .join("\n"),
                 self.deps
                     .into_iter()
-                    .map(|(name, dep_path)| format!("{} = {{ local = \"{}\" }}", name, dep_path))
+                    .map(|(name, dep)| format!("{} = {}", name, dep))
                     .join("\n")
             ),
         )?;
@@ -0,0 +1,608 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{AnnotatedMoveStruct, AnnotatedMoveValue, MoveValueAnnotator};
+use anyhow::Result;
+use serde::Deserialize;
+use starcoin_vm_types::{
+    account_address::AccountAddress,
+    account_config::resources::{
+        ConcurrentFungibleBalanceResource, FungibleStoreResource, ObjectGroupResource,
+    },
+    identifier::Identifier,
+    language_storage::{StructTag, CORE_CODE_ADDRESS},
+    move_resource::MoveStructType,
+    state_store::{
+        state_key::StateKey,
+        table::{TableHandle, TableInfo},
+        StateView,
+    },
+};
+use std::collections::BTreeMap;
+
+/// Which references in a resource are expanded, and how deep they are followed.
+#[derive(Clone, Copy, Debug)]
+pub struct ExpandConfig {
+    /// How many levels of references are followed, 0 means nothing is expanded.
+    pub depth: usize,
+    /// Expand `Object<T>` to the resources stored at the object address.
+    pub objects: bool,
+    /// Expand `Object<T>` holding a `FungibleStore` to its token balance.
+    pub fungible_stores: bool,
+    /// Expand `Table` handles to the key and value types of the table.
+    pub tables: bool,
+}
+
+/// The key and value types of a table are not stored on chain, they are recorded by the node
+/// when the table is created.
+pub trait TableInfoResolver {
+    fn resolve_table_info(&self, handle: &TableHandle) -> Result<Option<TableInfo>>;
+}
+
+/// The location of a value inside a struct.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PathElement {
+    Field(Identifier),
+    Index(usize),
+}
+
+#[derive(Clone, Debug)]
+pub struct ExpandedMoveStruct {
+    pub value: AnnotatedMoveStruct,
+    /// The expanded references inside `value`, with the path of the `Object` or `Table` struct.
+    pub expansions: Vec<(Vec<PathElement>, Expansion)>,
+}
+
+#[derive(Clone, Debug)]
+pub enum Expansion {
+    Object(ExpandedObject),
+    Table(TableHandle, TableInfo),
+}
+
+#[derive(Clone, Debug)]
+pub struct ExpandedObject {
+    pub address: AccountAddress,
+    pub owner: AccountAddress,
+    /// The resources of the object group, empty if objects are not expanded.
+    pub resources: Vec<ExpandedMoveStruct>,
+    pub fungible_store: Option<FungibleStoreBalance>,
+}
+
+#[derive(Clone, Debug)]
+pub struct FungibleStoreBalance {
+    pub metadata: AccountAddress,
+    pub balance: u64,
+    pub frozen: bool,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+}
+
+/// The Rust representation of `0x1::fungible_asset::Metadata`.
+#[derive(Deserialize)]
+struct MetadataResource {
+    name: String,
+    symbol: String,
+    decimals: u8,
+    _icon_uri: String,
+    _project_uri: String,
+}
+
+pub struct ResourceExpander<'a> {
+    annotator: MoveValueAnnotator<'a>,
+    state: &'a dyn StateView,
+    table_info_resolver: Option<&'a dyn TableInfoResolver>,
+    config: ExpandConfig,
+}
+
+impl<'a> ResourceExpander<'a> {
+    pub fn new(state: &'a dyn StateView, config: ExpandConfig) -> Self {
+        Self {
+            annotator: MoveValueAnnotator::new(state),
+            state,
+            table_info_resolver: None,
+            config,
+        }
+    }
+
+    pub fn with_table_info_resolver(mut self, resolver: &'a dyn TableInfoResolver) -> Self {
+        self.table_info_resolver = Some(resolver);
+        self
+    }
+
+    pub fn expand_struct(&self, value: AnnotatedMoveStruct) -> Result<ExpandedMoveStruct> {
+        self.expand(value, self.config.depth)
+    }
+
+    fn expand(&self, value: AnnotatedMoveStruct, depth: usize) -> Result<ExpandedMoveStruct> {
+        let mut expansions = vec![];
+        if depth > 0 {
+            let mut path = vec![];
+            self.collect_fields(&value, depth, &mut path, &mut expansions)?;
+        }
+        Ok(ExpandedMoveStruct { value, expansions })
+    }
+
+    fn collect_fields(
+        &self,
+        value: &AnnotatedMoveStruct,
+        depth: usize,
+        path: &mut Vec<PathElement>,
+        expansions: &mut Vec<(Vec<PathElement>, Expansion)>,
+    ) -> Result<()> {
+        for (name, field) in &value.value {
+            path.push(PathElement::Field(name.clone()));
+            self.collect(field, depth, path, expansions)?;
+            path.pop();
+        }
+        Ok(())
+    }
+
+    fn collect(
+        &self,
+        value: &AnnotatedMoveValue,
+        depth: usize,
+        path: &mut Vec<PathElement>,
+        expansions: &mut Vec<(Vec<PathElement>, Expansion)>,
+    ) -> Result<()> {
+        match value {
+            AnnotatedMoveValue::Vector(values) => {
+                for (idx, v) in values.iter().enumerate() {
+                    path.push(PathElement::Index(idx));
+                    self.collect(v, depth, path, expansions)?;
+                    path.pop();
+                }
+            }
+            AnnotatedMoveValue::Struct(s) => match self.expand_reference(s, depth)? {
+                Some(expansion) => expansions.push((path.clone(), expansion)),
+                None => self.collect_fields(s, depth, path, expansions)?,
+            },
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn expand_reference(
+        &self,
+        value: &AnnotatedMoveStruct,
+        depth: usize,
+    ) -> Result<Option<Expansion>> {
+        if value.type_.address != CORE_CODE_ADDRESS {
+            return Ok(None);
+        }
+        let address = match value.value.as_slice() {
+            [(_, AnnotatedMoveValue::Address(address))] => *address,
+            _ => return Ok(None),
+        };
+        match (value.type_.module.as_str(), value.type_.name.as_str()) {
+            ("object", "Object") if self.config.objects || self.config.fungible_stores => {
+                Ok(self.expand_object(address, depth)?.map(Expansion::Object))
+            }
+            ("table", "Table") if self.config.tables => {
+                let handle = TableHandle(address);
+                Ok(match self.table_info_resolver {
+                    Some(resolver) => resolver
+                        .resolve_table_info(&handle)?
+                        .map(|info| Expansion::Table(handle, info)),
+                    None => None,
+                })
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn expand_object(
+        &self,
+        address: AccountAddress,
+        depth: usize,
+    ) -> Result<Option<ExpandedObject>> {
+        let members = object_group_members(self.state, address, &[])?;
+        // an address without ObjectCore is not an object, or the object is deleted.
+        let owner = match object_owner(&self.annotator, &members)? {
+            Some(owner) => owner,
+            None => return Ok(None),
+        };
+        let fungible_store = if self.config.fungible_stores {
+            fungible_store_balance(self.state, &members)?
+        } else {
+            None
+        };
+        let resources = if self.config.objects {
+            members
+                .into_iter()
+                .map(|(tag, bytes)| {
+                    let value = self.annotator.view_struct(tag, bytes.as_slice())?;
+                    self.expand(value, depth - 1)
+                })
+                .collect::<Result<Vec<_>>>()?
+        } else {
+            vec![]
+        };
+        Ok(Some(ExpandedObject {
+            address,
+            owner,
+            resources,
+            fungible_store,
+        }))
+    }
+}
+
+/// The resources in the object group at `address`. If resource groups are split, the members are
+/// stored under their own keys, only the fungible store members and `known_members` are found.
+pub fn object_group_members(
+    state: &dyn StateView,
+    address: AccountAddress,
+    known_members: &[StructTag],
+) -> Result<BTreeMap<StructTag, Vec<u8>>> {
+    let group_key = StateKey::resource_group(&address, &ObjectGroupResource::struct_tag());
+    let mut members = match state.get_state_value_bytes(&group_key)? {
+        Some(bytes) => bcs_ext::from_bytes::<BTreeMap<StructTag, Vec<u8>>>(&bytes)?,
+        None => BTreeMap::new(),
+    };
+    let fungible_members = [
+        object_core_tag(),
+        FungibleStoreResource::struct_tag(),
+        ConcurrentFungibleBalanceResource::struct_tag(),
+        metadata_tag(),
+    ];
+    for tag in fungible_members.iter().chain(known_members) {
+        if members.contains_key(tag) {
+            continue;
+        }
+        if let Some(bytes) =
+            state.get_state_value_bytes(&StateKey::resource_group(&address, tag))?
+        {
+            members.insert(tag.clone(), bytes.to_vec());
+        }
+    }
+    Ok(members)
+}
+
+/// The balance and the token metadata of the `FungibleStore` in the object group `members`.
+pub fn fungible_store_balance(
+    state: &dyn StateView,
+    members: &BTreeMap<StructTag, Vec<u8>>,
+) -> Result<Option<FungibleStoreBalance>> {
+    let store = match members.get(&FungibleStoreResource::struct_tag()) {
+        Some(bytes) => bcs_ext::from_bytes::<FungibleStoreResource>(bytes)?,
+        None => return Ok(None),
+    };
+    // the balance of a concurrent store is kept in the aggregator.
+    let balance = match members.get(&ConcurrentFungibleBalanceResource::struct_tag()) {
+        Some(bytes) => bcs_ext::from_bytes::<ConcurrentFungibleBalanceResource>(bytes)?.balance(),
+        None => store.balance(),
+    };
+    let metadata = object_group_members(state, store.metadata(), &[])?
+        .get(&metadata_tag())
+        .map(|bytes| bcs_ext::from_bytes::<MetadataResource>(bytes))
+        .transpose()?;
+    Ok(Some(FungibleStoreBalance {
+        metadata: store.metadata(),
+        balance,
+        frozen: store.frozen(),
+        name: metadata.as_ref().map(|m| m.name.clone()),
+        symbol: metadata.as_ref().map(|m| m.symbol.clone()),
+        decimals: metadata.as_ref().map(|m| m.decimals),
+    }))
+}
+
+/// The owner in the `ObjectCore` of the object group `members`, None if it is not an object.
+pub fn object_owner(
+    annotator: &MoveValueAnnotator,
+    members: &BTreeMap<StructTag, Vec<u8>>,
+) -> Result<Option<AccountAddress>> {
+    Ok(match members.get(&object_core_tag()) {
+        Some(bytes) => annotator
+            .view_struct(object_core_tag(), bytes.as_slice())?
+            .value
+            .into_iter()
+            .find_map(|(name, value)| match value {
+                AnnotatedMoveValue::Address(owner) if name.as_str() == "owner" => Some(owner),
+                _ => None,
+            }),
+        None => None,
+    })
+}
+
+fn object_core_tag() -> StructTag {
+    StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new("object").expect("valid identifier"),
+        name: Identifier::new("ObjectCore").expect("valid identifier"),
+        type_args: vec![],
+    }
+}
+
+fn metadata_tag() -> StructTag {
+    StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new("fungible_asset").expect("valid identifier"),
+        name: Identifier::new("Metadata").expect("valid identifier"),
+        type_args: vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        metadata_tag, object_core_tag, ExpandConfig, Expansion, PathElement, ResourceExpander,
+        TableInfoResolver,
+    };
+    use crate::{AnnotatedMoveStruct, AnnotatedMoveValue};
+    use anyhow::Result;
+    use starcoin_vm2_state_api::ChainStateWriter;
+    use starcoin_vm2_statedb::ChainStateDB;
+    use starcoin_vm2_test_helper::executor::prepare_genesis;
+    use starcoin_vm_types::{
+        account_address::AccountAddress,
+        account_config::resources::{FungibleStoreResource, ObjectGroupResource},
+        file_format::AbilitySet,
+        identifier::Identifier,
+        language_storage::{StructTag, TypeTag, CORE_CODE_ADDRESS},
+        move_resource::MoveStructType,
+        state_store::{
+            state_key::StateKey,
+            table::{TableHandle, TableInfo},
+        },
+        write_set::{WriteOp, WriteSetMut},
+    };
+    use std::collections::BTreeMap;
+
+    struct MockTableInfoResolver(BTreeMap<TableHandle, TableInfo>);
+
+    impl TableInfoResolver for MockTableInfoResolver {
+        fn resolve_table_info(&self, handle: &TableHandle) -> Result<Option<TableInfo>> {
+            Ok(self.0.get(handle).cloned())
+        }
+    }
+
+    fn put_object(
+        state: &ChainStateDB,
+        address: AccountAddress,
+        members: Vec<(StructTag, Vec<u8>)>,
+    ) {
+        let members = members.into_iter().collect::<BTreeMap<_, _>>();
+        let key = StateKey::resource_group(&address, &ObjectGroupResource::struct_tag());
+        let write_set = WriteSetMut::new(vec![(
+            key,
+            WriteOp::legacy_creation(bcs_ext::to_bytes(&members).unwrap().into()),
+        )])
+        .freeze()
+        .unwrap();
+        state.apply_write_set(write_set).unwrap();
+    }
+
+    /// The bcs of `0x1::object::ObjectCore`.
+    fn object_core(address: AccountAddress, owner: AccountAddress) -> Vec<u8> {
+        bcs_ext::to_bytes(&(0u64, owner, true, (0u64, 0u64, address))).unwrap()
+    }
+
+    fn reference(
+        module: &str,
+        name: &str,
+        field: &str,
+        address: AccountAddress,
+    ) -> AnnotatedMoveValue {
+        AnnotatedMoveValue::Struct(AnnotatedMoveStruct {
+            abilities: AbilitySet::EMPTY,
+            type_: StructTag {
+                address: CORE_CODE_ADDRESS,
+                module: Identifier::new(module).unwrap(),
+                name: Identifier::new(name).unwrap(),
+                type_args: vec![],
+            },
+            value: vec![(
+                Identifier::new(field).unwrap(),
+                AnnotatedMoveValue::Address(address),
+            )],
+        })
+    }
+
+    /// A fungible store object of `owner`, with the metadata object of the token.
+    fn setup() -> (ChainStateDB, AccountAddress, AccountAddress, AccountAddress) {
+        let (state, _net) = prepare_genesis().unwrap();
+        let owner = AccountAddress::random();
+        let metadata = AccountAddress::random();
+        let store = AccountAddress::random();
+        put_object(
+            &state,
+            metadata,
+            vec![
+                (object_core_tag(), object_core(metadata, owner)),
+                (
+                    metadata_tag(),
+                    bcs_ext::to_bytes(&("Test Coin", "TC", 8u8, "", "")).unwrap(),
+                ),
+            ],
+        );
+        put_object(
+            &state,
+            store,
+            vec![
+                (object_core_tag(), object_core(store, owner)),
+                (
+                    FungibleStoreResource::struct_tag(),
+                    bcs_ext::to_bytes(&FungibleStoreResource::new(metadata, 100, false)).unwrap(),
+                ),
+            ],
+        );
+        (state, owner, metadata, store)
+    }
+
+    /// A struct holding the object `store` and the table `handle`.
+    fn holder(store: AccountAddress, handle: AccountAddress) -> AnnotatedMoveStruct {
+        AnnotatedMoveStruct {
+            abilities: AbilitySet::EMPTY,
+            type_: StructTag {
+                address: AccountAddress::random(),
+                module: Identifier::new("holder").unwrap(),
+                name: Identifier::new("Holder").unwrap(),
+                type_args: vec![],
+            },
+            value: vec![
+                (
+                    Identifier::new("store").unwrap(),
+                    reference("object", "Object", "inner", store),
+                ),
+                (
+                    Identifier::new("tables").unwrap(),
+                    AnnotatedMoveValue::Vector(vec![reference("table", "Table", "handle", handle)]),
+                ),
+            ],
+        }
+    }
+
+    fn config(depth: usize) -> ExpandConfig {
+        ExpandConfig {
+            depth,
+            objects: false,
+            fungible_stores: false,
+            tables: false,
+        }
+    }
+
+    #[test]
+    fn test_expand_fungible_store() {
+        let (state, owner, metadata, store) = setup();
+        let expander = ResourceExpander::new(
+            &state,
+            ExpandConfig {
+                fungible_stores: true,
+                ..config(1)
+            },
+        );
+        let expanded = expander
+            .expand_struct(holder(store, AccountAddress::random()))
+            .unwrap();
+        assert_eq!(expanded.expansions.len(), 1);
+        let (path, expansion) = &expanded.expansions[0];
+        assert_eq!(
+            path,
+            &vec![PathElement::Field(Identifier::new("store").unwrap())]
+        );
+        let object = match expansion {
+            Expansion::Object(object) => object,
+            Expansion::Table(..) => panic!("expect an object"),
+        };
+        assert_eq!(object.address, store);
+        assert_eq!(object.owner, owner);
+        // the resources are not expanded without `objects`.
+        assert!(object.resources.is_empty());
+        let balance = object.fungible_store.as_ref().unwrap();
+        assert_eq!(balance.metadata, metadata);
+        assert_eq!(balance.balance, 100);
+        assert!(!balance.frozen);
+        assert_eq!(balance.name.as_deref(), Some("Test Coin"));
+        assert_eq!(balance.symbol.as_deref(), Some("TC"));
+        assert_eq!(balance.decimals, Some(8));
+
+        // nothing is expanded with depth 0.
+        let expander = ResourceExpander::new(
+            &state,
+            ExpandConfig {
+                fungible_stores: true,
+                ..config(0)
+            },
+        );
+        assert!(expander
+            .expand_struct(holder(store, AccountAddress::random()))
+            .unwrap()
+            .expansions
+            .is_empty());
+    }
+
+    #[test]
+    fn test_expand_object() {
+        let (state, _owner, metadata, store) = setup();
+        let expand = |depth| {
+            let expander = ResourceExpander::new(
+                &state,
+                ExpandConfig {
+                    objects: true,
+                    ..config(depth)
+                },
+            );
+            match expander
+                .expand_struct(holder(store, AccountAddress::random()))
+                .unwrap()
+                .expansions
+                .pop()
+            {
+                Some((_, Expansion::Object(object))) => object,
+                _ => panic!("expect an object"),
+            }
+        };
+
+        let object = expand(1);
+        assert!(object.fungible_store.is_none());
+        let types = object
+            .resources
+            .iter()
+            .map(|resource| resource.value.type_.clone())
+            .collect::<Vec<_>>();
+        assert!(types.contains(&object_core_tag()));
+        assert!(types.contains(&FungibleStoreResource::struct_tag()));
+        // the metadata object of the store is beyond the depth.
+        assert!(object
+            .resources
+            .iter()
+            .all(|resource| resource.expansions.is_empty()));
+
+        let object = expand(2);
+        let store_resource = object
+            .resources
+            .iter()
+            .find(|resource| resource.value.type_ == FungibleStoreResource::struct_tag())
+            .unwrap();
+        match store_resource.expansions.as_slice() {
+            [(path, Expansion::Object(metadata_object))] => {
+                assert_eq!(
+                    path,
+                    &vec![PathElement::Field(Identifier::new("metadata").unwrap())]
+                );
+                assert_eq!(metadata_object.address, metadata);
+            }
+            _ => panic!("expect the metadata object expanded"),
+        }
+    }
+
+    #[test]
+    fn test_expand_table() {
+        let (state, _owner, _metadata, store) = setup();
+        let handle = AccountAddress::random();
+        let resolver = MockTableInfoResolver(BTreeMap::from([(
+            TableHandle(handle),
+            TableInfo::new(TypeTag::U64, TypeTag::Address),
+        )]));
+        let tables = ExpandConfig {
+            tables: true,
+            ..config(1)
+        };
+
+        // the table info can not be resolved without a resolver.
+        let expander = ResourceExpander::new(&state, tables);
+        assert!(expander
+            .expand_struct(holder(store, handle))
+            .unwrap()
+            .expansions
+            .is_empty());
+
+        let expander = ResourceExpander::new(&state, tables).with_table_info_resolver(&resolver);
+        let expanded = expander.expand_struct(holder(store, handle)).unwrap();
+        match expanded.expansions.as_slice() {
+            [(path, Expansion::Table(table, info))] => {
+                assert_eq!(
+                    path,
+                    &vec![
+                        PathElement::Field(Identifier::new("tables").unwrap()),
+                        PathElement::Index(0)
+                    ]
+                );
+                assert_eq!(*table, TableHandle(handle));
+                assert_eq!(info.key_type, TypeTag::U64);
+                assert_eq!(info.value_type, TypeTag::Address);
+            }
+            _ => panic!("expect the table expanded"),
+        }
+    }
+}
@@ -32,6 +32,7 @@ use std::{
     fmt::{Display, Formatter},
 };
 
+pub mod expand;
 mod fat_type;
 pub mod module_cache;
 pub mod resolver;
@@ -6,12 +6,18 @@ use jsonrpsee::{
     proc_macros::rpc,
     Methods,
 };
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use starcoin_vm2_account_api::AccountInfo;
+use starcoin_vm2_crypto::HashValue;
 use starcoin_vm2_types::{
     account_address::AccountAddress,
     sign_message::SigningMessage,
-    transaction::{RawUserTransaction, SignedUserTransaction},
-    view::{SignedMessageView, StrView, TransactionRequest},
+    transaction::{
+        authenticator::{AccountAuthenticator, Scheme},
+        RawUserTransaction, SignedUserTransaction,
+    },
+    view::{PortfolioView, SignedMessageView, StrView, TransactionRequest},
 };
 use starcoin_vm2_vm_types::token::token_code::TokenCode;
 
@@ -27,8 +33,10 @@ pub trait AccountApi {
     #[method(name = "set_default_account")]
     async fn set_default_account(&self, addr: AccountAddress) -> RpcResult<AccountInfo>;
 
+    /// Create an account whose key is generated by the node's wallet, `key_type` is default to
+    /// ed25519.
     #[method(name = "create")]
-    async fn create(&self, password: String) -> RpcResult<AccountInfo>;
+    async fn create(&self, password: String, key_type: Option<Scheme>) -> RpcResult<AccountInfo>;
 
     #[method(name = "list")]
     async fn list(&self) -> RpcResult<Vec<AccountInfo>>;
@@ -54,6 +62,26 @@ pub trait AccountApi {
         signer: AccountAddress,
     ) -> RpcResult<SignedUserTransaction>;
 
+    /// sign a txn whose gas is paid by `fee_payer` as its sender or as the fee payer, return the
+    /// authenticator of the signer.
+    #[method(name = "sign_fee_payer_txn")]
+    async fn sign_fee_payer_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        fee_payer: AccountAddress,
+        signer: AccountAddress,
+    ) -> RpcResult<AccountAuthenticator>;
+
+    /// sign a multi-agent txn as its sender or as one of `secondary_signers`, return the
+    /// authenticator of the signer.
+    #[method(name = "sign_multi_agent_txn")]
+    async fn sign_multi_agent_txn(
+        &self,
+        raw_txn: RawUserTransaction,
+        secondary_signers: Vec<AccountAddress>,
+        signer: AccountAddress,
+    ) -> RpcResult<AccountAuthenticator>;
+
     /// unlock account for duration in seconds, default to u32::max.
     #[method(name = "unlock")]
     async fn unlock(
@@ -106,6 +134,33 @@ pub trait AccountApi {
         address: AccountAddress,
         password: Option<String>,
     ) -> RpcResult<AccountInfo>;
+
+    /// Get the coin balances of the account, its primary fungible stores of the tokens and the
+    /// fungible asset metadata in the option, and the NFTs and secondary fungible stores among the
+    /// objects in the option. Objects are not indexed by their owner on chain, so the assets of
+    /// other objects are not discovered. The account does not need to be managed by the node.
+    #[method(name = "portfolio")]
+    async fn portfolio(
+        &self,
+        address: AccountAddress,
+        option: Option<PortfolioOption>,
+    ) -> RpcResult<PortfolioView>;
+}
+
+#[derive(Default, Clone, Debug, Serialize, Deserialize, Eq, PartialEq, JsonSchema)]
+#[serde(default)]
+pub struct PortfolioOption {
+    /// The state tree root, default is the latest block state root
+    pub state_root: Option<HashValue>,
+    /// The primary fungible stores are derived from the token code, the stores of the coins held
+    /// by the account, STC and the accepted tokens are always looked up.
+    pub token_codes: Vec<TokenCode>,
+    /// The primary fungible stores of the fungible assets without a token code, derived from the
+    /// address of the metadata object.
+    pub fungible_asset_metadata: Vec<AccountAddress>,
+    /// Only these objects are checked for the NFTs and the secondary fungible stores owned by the
+    /// account, other objects of the account are not discovered.
+    pub objects: Vec<AccountAddress>,
 }
 
 pub use AccountApiClient as AccountApiRpcClient;
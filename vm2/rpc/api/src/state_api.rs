@@ -141,6 +141,31 @@ pub struct PrimaryFungibleStoreOption {
     pub token_code: Option<String>,
 }
 
+/// Expand the objects, fungible stores and tables referenced by the decoded resource.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, JsonSchema)]
+#[serde(default)]
+pub struct ExpandOption {
+    /// How many levels of references are followed, at most 3.
+    pub depth: u8,
+    /// Expand `Object<T>` to the owner and the resources of the object.
+    pub objects: bool,
+    /// Expand `Object<T>` holding a fungible store to its token balance.
+    pub fungible_stores: bool,
+    /// Expand table handles to the key and value types of the table.
+    pub tables: bool,
+}
+
+impl Default for ExpandOption {
+    fn default() -> Self {
+        Self {
+            depth: 1,
+            objects: true,
+            fungible_stores: true,
+            tables: true,
+        }
+    }
+}
+
 #[derive(Default, Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, JsonSchema)]
 #[serde(default)]
 pub struct GetResourceOption {
@@ -148,6 +173,9 @@ pub struct GetResourceOption {
     pub state_root: Option<HashValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub primary_fungible_store: Option<PrimaryFungibleStoreOption>,
+    /// Only works with `decode`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expand: Option<ExpandOption>,
 }
 
 #[derive(Default, Clone, Debug, Serialize, Deserialize, Eq, Hash, PartialEq, JsonSchema)]
@@ -168,6 +196,9 @@ pub struct ListResourceOption {
     pub resource_types: Option<Vec<StructTagView>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub primary_fungible_store: Option<PrimaryFungibleStoreOption>,
+    /// Only works with `decode`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expand: Option<ExpandOption>,
 }
 
 impl Default for ListResourceOption {
@@ -179,6 +210,7 @@ impl Default for ListResourceOption {
             max_size: usize::MAX,
             resource_types: None,
             primary_fungible_store: None,
+            expand: None,
         }
     }
 }
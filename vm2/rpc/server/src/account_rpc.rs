@@ -2,17 +2,25 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::helpers::TransactionRequestFiller as TransactionRequestFiller2;
+use crate::portfolio::portfolio;
+use anyhow::format_err;
 use jsonrpsee::core::{async_trait, RpcResult};
 use starcoin_vm2_account_api::{
-    AccountAsyncService as AccountAsyncService2, AccountInfo as AccountInfo2,
+    error::AccountError as AccountError2, AccountAsyncService as AccountAsyncService2,
+    AccountInfo as AccountInfo2,
+};
+use starcoin_vm2_state_api::{
+    ChainStateAsyncService as ChainStateAsyncService2, StateNodeStore as StateNodeStore2,
 };
-use starcoin_vm2_state_api::ChainStateAsyncService as ChainStateAsyncService2;
 
 use starcoin_config::NodeConfig;
 use starcoin_txpool_api::TxPoolSyncService;
-use starcoin_vm2_rpc_api::account_api::AccountApiServer as AccountApiServer2;
+use starcoin_vm2_rpc_api::account_api::{
+    AccountApiServer as AccountApiServer2, PortfolioOption as PortfolioOption2,
+};
+use starcoin_vm2_statedb::ChainStateDB as ChainStateDB2;
 use starcoin_vm2_types::view::{
-    SignedMessageView as SignedMessageView2, StrView as StrView2,
+    PortfolioView as PortfolioView2, SignedMessageView as SignedMessageView2, StrView as StrView2,
     TransactionRequest as TransactionRequest2,
 };
 use starcoin_vm2_types::{
@@ -20,6 +28,7 @@ use starcoin_vm2_types::{
     account_config::token_code::TokenCode as TokenCode2,
     sign_message::SigningMessage as SigningMessage2,
     transaction::{
+        authenticator::{AccountAuthenticator as AccountAuthenticator2, Scheme as Scheme2},
         RawUserTransaction as RawUserTransaction2, SignedUserTransaction as SignedUserTransaction2,
     },
 };
@@ -37,6 +46,7 @@ where
     pool: Pool,
     chain_state: State,
     node_config: Arc<NodeConfig>,
+    state_store: Option<Arc<dyn StateNodeStore2>>,
 }
 
 impl<Account, Pool, State> AccountRpcImpl<Account, Pool, State>
@@ -57,8 +67,15 @@ where
             pool,
             chain_state,
             node_config,
+            state_store: None,
         }
     }
+
+    /// The state store to read the portfolio of the accounts from.
+    pub fn with_state_store(mut self, state_store: Arc<dyn StateNodeStore2>) -> Self {
+        self.state_store = Some(state_store);
+        self
+    }
     fn txn_request_filler(&self) -> TransactionRequestFiller2<Account, Pool, State> {
         TransactionRequestFiller2 {
             account: Some(self.account.clone()),
@@ -92,10 +109,10 @@ where
             .map_err(crate::map_jsonrpc_err)
     }
 
-    async fn create(&self, password: String) -> RpcResult<AccountInfo2> {
+    async fn create(&self, password: String, key_type: Option<Scheme2>) -> RpcResult<AccountInfo2> {
         let service = self.account.clone();
         service
-            .create_account(password)
+            .create_account(password, key_type.unwrap_or(Scheme2::Ed25519))
             .await
             .map_err(crate::map_jsonrpc_err)
     }
@@ -155,6 +172,32 @@ where
             .map_err(crate::map_jsonrpc_err)
     }
 
+    async fn sign_fee_payer_txn(
+        &self,
+        raw_txn: RawUserTransaction2,
+        fee_payer: AccountAddress2,
+        signer: AccountAddress2,
+    ) -> RpcResult<AccountAuthenticator2> {
+        let service = self.account.clone();
+        service
+            .sign_fee_payer_txn(raw_txn, fee_payer, signer)
+            .await
+            .map_err(crate::map_jsonrpc_err)
+    }
+
+    async fn sign_multi_agent_txn(
+        &self,
+        raw_txn: RawUserTransaction2,
+        secondary_signers: Vec<AccountAddress2>,
+        signer: AccountAddress2,
+    ) -> RpcResult<AccountAuthenticator2> {
+        let service = self.account.clone();
+        service
+            .sign_multi_agent_txn(raw_txn, secondary_signers, signer)
+            .await
+            .map_err(crate::map_jsonrpc_err)
+    }
+
     async fn unlock(
         &self,
         address: AccountAddress2,
@@ -246,4 +289,44 @@ where
             .await
             .map_err(crate::map_jsonrpc_err)
     }
+    async fn portfolio(
+        &self,
+        address: AccountAddress2,
+        option: Option<PortfolioOption2>,
+    ) -> RpcResult<PortfolioView2> {
+        let account_service = self.account.clone();
+        let chain_state = self.chain_state.clone();
+        let state_store = self.state_store.clone();
+        let option = option.unwrap_or_default();
+        async move {
+            let state_store =
+                state_store.ok_or_else(|| format_err!("The state store is not configured"))?;
+            let state_root = match option.state_root {
+                Some(state_root) => state_root,
+                None => chain_state.state_root().await?,
+            };
+            let mut token_codes = option.token_codes;
+            match account_service.accepted_tokens(address).await {
+                Ok(accepted_tokens) => token_codes.extend(accepted_tokens),
+                // the account may be not managed by the node.
+                Err(e)
+                    if matches!(
+                        e.downcast_ref::<AccountError2>(),
+                        Some(AccountError2::AccountNotExist(_))
+                    ) => {}
+                Err(e) => return Err(e),
+            }
+            let statedb = ChainStateDB2::new(state_store, Some(state_root));
+            portfolio(
+                &statedb,
+                state_root,
+                address,
+                token_codes,
+                option.fungible_asset_metadata,
+                option.objects,
+            )
+        }
+        .await
+        .map_err(crate::map_jsonrpc_err)
+    }
 }
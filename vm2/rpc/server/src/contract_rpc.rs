@@ -19,9 +19,9 @@ use starcoin_vm2_rpc_api::contract_api::ContractApiServer;
 use starcoin_vm2_state_api::ChainStateAsyncService;
 use starcoin_vm2_statedb::ChainStateDB;
 use starcoin_vm2_types::view::{
-    AnnotatedMoveStructView, AnnotatedMoveValueView, ContractCall, DryRunOutputView,
-    DryRunTransactionRequest, FunctionIdView, ModuleIdView, StrView, StructTagView,
-    TransactionOutputView, WriteOpValueView,
+    access_list_view, AnnotatedMoveStructView, AnnotatedMoveValueView, ContractCall,
+    DryRunOutputView, DryRunTransactionRequest, FunctionIdView, ModuleIdView, StrView,
+    StructTagView, TransactionOutputView, WriteOpValueView,
 };
 use starcoin_vm2_types::{
     account_address::AccountAddress,
@@ -198,6 +198,8 @@ where
                 DryRunTransaction {
                     raw_txn: txn,
                     public_key: sender_public_key.0,
+                    fee_payer: None,
+                    secondary_signers: vec![],
                 },
                 metrics,
             )
@@ -224,6 +226,8 @@ where
                 DryRunTransaction {
                     raw_txn,
                     public_key: sender_public_key.0,
+                    fee_payer: None,
+                    secondary_signers: vec![],
                 },
                 metrics,
             )
@@ -296,6 +300,7 @@ pub fn dry_run<S: StateView>(
         starcoin_vm2_dev::playground::dry_run(state_view, txn.clone(), metrics)?;
     let vm_status_explain =
         starcoin_vm2_status_translator::explain_vm_status(state_view, vm_status)?;
+    let access_list = access_list_view(&output);
     let mut txn_output: TransactionOutputView = output.into();
 
     let resolver = {
@@ -336,5 +341,6 @@ pub fn dry_run<S: StateView>(
     Ok(DryRunOutputView {
         explained_status: vm_status_explain,
         txn_output,
+        access_list: Some(access_list),
     })
 }
@@ -27,7 +27,19 @@ where
         &self,
         txn_request: TransactionRequest2,
     ) -> anyhow::Result<RawUserTransaction> {
-        let payload = if !txn_request.modules.is_empty() {
+        let payload = if !txn_request.entry_functions.is_empty() {
+            anyhow::ensure!(
+                txn_request.script.is_none() && txn_request.modules.is_empty(),
+                "invalid transaction request: entry functions can not be used with script or modules"
+            );
+            TransactionPayload::EntryFunctionBatch(
+                txn_request
+                    .entry_functions
+                    .into_iter()
+                    .map(|script_data| script_data.into_script_function())
+                    .collect::<anyhow::Result<_>>()?,
+            )
+        } else if !txn_request.modules.is_empty() {
             let modules = txn_request
                 .modules
                 .into_iter()
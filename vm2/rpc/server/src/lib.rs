@@ -16,4 +16,5 @@ pub(crate) fn map_jsonrpc_err(err: anyhow::Error) -> jsonrpsee::types::ErrorObje
 pub mod account_rpc;
 pub mod contract_rpc;
 mod helpers;
+mod portfolio;
 pub mod state_rpc;
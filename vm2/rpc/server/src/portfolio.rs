@@ -0,0 +1,429 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use bcs_ext::BCSCodec;
+use starcoin_vm2_crypto::HashValue;
+use starcoin_vm2_resource_viewer::{
+    expand::{fungible_store_balance, object_group_members, object_owner},
+    AnnotatedMoveStruct, AnnotatedMoveValue, MoveValueAnnotator,
+};
+use starcoin_vm2_statedb::{ChainStateDB, ChainStateReader};
+use starcoin_vm2_types::view::{
+    CoinBalanceView, FungibleAssetBalanceView, PortfolioView, StrView, TokenObjectView,
+};
+use starcoin_vm2_vm_types::{
+    account_address::AccountAddress,
+    account_config::{resources::primary_store, CoinStoreResource},
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag, CORE_CODE_ADDRESS},
+    state_store::{state_key::StateKey, TStateView},
+    token::{stc::G_STC_TOKEN_CODE, token_code::TokenCode},
+    transaction::authenticator::AuthenticationKey,
+};
+use std::collections::BTreeSet;
+
+/// Read the assets of `address` that can be located without an owner index. The coins are listed
+/// from the account resources, the primary fungible stores are derived from `token_codes`, STC,
+/// the coins and the `fungible_asset_metadata`. Only the `objects` owned by the account are checked
+/// for the NFTs and the secondary fungible stores, for objects are not indexed by their owner on
+/// chain.
+pub(crate) fn portfolio(
+    chain_state: &ChainStateDB,
+    state_root: HashValue,
+    address: AccountAddress,
+    token_codes: Vec<TokenCode>,
+    fungible_asset_metadata: Vec<AccountAddress>,
+    objects: Vec<AccountAddress>,
+) -> Result<PortfolioView> {
+    let annotator = MoveValueAnnotator::new(chain_state);
+    let mut primary_tokens = token_codes.into_iter().collect::<BTreeSet<_>>();
+    primary_tokens.insert(G_STC_TOKEN_CODE.clone());
+
+    let mut coins = vec![];
+    if let Some(state) = chain_state.get_account_state_set(&address)? {
+        for (key, value) in state.resource_set().cloned().unwrap_or_default().iter() {
+            let struct_tag = StructTag::decode(key.as_slice())?;
+            let token_code = match CoinStoreResource::token_code(&struct_tag) {
+                Some(token_code) => token_code,
+                None => continue,
+            };
+            let store = bcs_ext::from_bytes::<CoinStoreResource>(value)?;
+            let info = coin_info(chain_state, &annotator, &token_code)?;
+            coins.push(CoinBalanceView {
+                token_code: token_code.clone(),
+                name: info.as_ref().and_then(|i| string_field(i, "name")),
+                symbol: info.as_ref().and_then(|i| string_field(i, "symbol")),
+                decimals: info.as_ref().and_then(|i| match field(i, "decimals") {
+                    Some(AnnotatedMoveValue::U8(decimals)) => Some(*decimals),
+                    _ => None,
+                }),
+                balance: StrView(store.coin()),
+                frozen: store.frozen(),
+            });
+            primary_tokens.insert(token_code);
+        }
+    }
+
+    let mut fungible_assets = vec![];
+    for token_code in primary_tokens {
+        let store = primary_store(&address, &token_code.to_canonical_string())?;
+        let members = object_group_members(chain_state, store, &[])?;
+        if let Some(balance) = fungible_store_balance(chain_state, &members)? {
+            fungible_assets.push(FungibleAssetBalanceView {
+                store,
+                metadata: balance.metadata,
+                token_code: Some(token_code),
+                name: balance.name,
+                symbol: balance.symbol,
+                decimals: balance.decimals,
+                balance: StrView(balance.balance),
+                frozen: balance.frozen,
+            });
+        }
+    }
+    // the fungible assets without a token code, the primary store is derived from the metadata.
+    for metadata in fungible_asset_metadata.into_iter().collect::<BTreeSet<_>>() {
+        let store =
+            AuthenticationKey::object_address_from_object(&address, &metadata).derived_address();
+        if fungible_assets.iter().any(|asset| asset.store == store) {
+            continue;
+        }
+        let members = object_group_members(chain_state, store, &[])?;
+        if let Some(balance) = fungible_store_balance(chain_state, &members)? {
+            fungible_assets.push(FungibleAssetBalanceView {
+                store,
+                metadata: balance.metadata,
+                token_code: None,
+                name: balance.name,
+                symbol: balance.symbol,
+                decimals: balance.decimals,
+                balance: StrView(balance.balance),
+                frozen: balance.frozen,
+            });
+        }
+    }
+
+    let mut nfts = vec![];
+    let token_tag = token_objects_tag("object_token", "Token");
+    let token_identifiers_tag = token_objects_tag("object_token", "TokenIdentifiers");
+    let collection_tag = token_objects_tag("collection", "Collection");
+    for object in objects.into_iter().collect::<BTreeSet<_>>() {
+        let members = object_group_members(
+            chain_state,
+            object,
+            &[token_tag.clone(), token_identifiers_tag.clone()],
+        )?;
+        if object_owner(&annotator, &members)? != Some(address) {
+            continue;
+        }
+        if let Some(balance) = fungible_store_balance(chain_state, &members)? {
+            fungible_assets.push(FungibleAssetBalanceView {
+                store: object,
+                metadata: balance.metadata,
+                token_code: None,
+                name: balance.name,
+                symbol: balance.symbol,
+                decimals: balance.decimals,
+                balance: StrView(balance.balance),
+                frozen: balance.frozen,
+            });
+        }
+        let token = match members.get(&token_tag) {
+            Some(bytes) => annotator.view_struct(token_tag.clone(), bytes.as_slice())?,
+            None => continue,
+        };
+        let collection = match field(&token, "collection") {
+            Some(AnnotatedMoveValue::Struct(s)) => match field(s, "inner") {
+                Some(AnnotatedMoveValue::Address(collection)) => *collection,
+                _ => continue,
+            },
+            _ => continue,
+        };
+        // the name in Token is deprecated once TokenIdentifiers is populated.
+        let name = match members.get(&token_identifiers_tag) {
+            Some(bytes) => string_field(
+                &annotator.view_struct(token_identifiers_tag.clone(), bytes.as_slice())?,
+                "name",
+            ),
+            None => None,
+        };
+        let collection_name =
+            object_group_members(chain_state, collection, &[collection_tag.clone()])?
+                .get(&collection_tag)
+                .map(|bytes| annotator.view_struct(collection_tag.clone(), bytes.as_slice()))
+                .transpose()?
+                .and_then(|c| string_field(&c, "name"));
+        nfts.push(TokenObjectView {
+            object,
+            collection,
+            collection_name,
+            name: name
+                .or_else(|| string_field(&token, "name"))
+                .unwrap_or_default(),
+            description: string_field(&token, "description").unwrap_or_default(),
+            uri: string_field(&token, "uri").unwrap_or_default(),
+        });
+    }
+
+    Ok(PortfolioView {
+        address,
+        state_root,
+        coins,
+        fungible_assets,
+        nfts,
+    })
+}
+
+/// The `0x1::coin::CoinInfo<T>` stored at the address of the coin type.
+fn coin_info(
+    chain_state: &ChainStateDB,
+    annotator: &MoveValueAnnotator,
+    token_code: &TokenCode,
+) -> Result<Option<AnnotatedMoveStruct>> {
+    let coin_type: StructTag = token_code.clone().try_into()?;
+    let info_tag = StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new("coin")?,
+        name: Identifier::new("CoinInfo")?,
+        type_args: vec![TypeTag::Struct(Box::new(coin_type.clone()))],
+    };
+    chain_state
+        .get_state_value_bytes(&StateKey::resource(&coin_type.address, &info_tag)?)?
+        .map(|bytes| annotator.view_struct(info_tag, bytes.as_ref()))
+        .transpose()
+}
+
+fn token_objects_tag(module: &str, name: &str) -> StructTag {
+    StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new(module).expect("valid identifier"),
+        name: Identifier::new(name).expect("valid identifier"),
+        type_args: vec![],
+    }
+}
+
+fn field<'a>(value: &'a AnnotatedMoveStruct, name: &str) -> Option<&'a AnnotatedMoveValue> {
+    value
+        .value
+        .iter()
+        .find(|(field, _)| field.as_str() == name)
+        .map(|(_, v)| v)
+}
+
+fn string_field(value: &AnnotatedMoveStruct, name: &str) -> Option<String> {
+    field(value, name).and_then(as_string)
+}
+
+/// The text of a `0x1::string::String`, or of a snapshot wrapping the string in `value`.
+fn as_string(value: &AnnotatedMoveValue) -> Option<String> {
+    match value {
+        AnnotatedMoveValue::Bytes(bytes) => String::from_utf8(bytes.clone()).ok(),
+        AnnotatedMoveValue::Struct(s) => field(s, "bytes")
+            .or_else(|| field(s, "value"))
+            .and_then(as_string),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{portfolio, token_objects_tag};
+    use starcoin_vm2_state_api::ChainStateWriter;
+    use starcoin_vm2_statedb::{ChainStateDB, ChainStateReader};
+    use starcoin_vm2_test_helper::executor::prepare_genesis;
+    use starcoin_vm2_vm_types::{
+        account_address::AccountAddress,
+        account_config::{
+            resources::{primary_store, FungibleStoreResource, ObjectGroupResource},
+            CoinStoreResource,
+        },
+        event::{EventHandle, EventKey},
+        language_storage::StructTag,
+        move_resource::MoveStructType,
+        state_store::state_key::StateKey,
+        token::stc::G_STC_TOKEN_CODE,
+        transaction::authenticator::AuthenticationKey,
+        write_set::{WriteOp, WriteSetMut},
+    };
+    use std::collections::BTreeMap;
+
+    fn apply(state: &ChainStateDB, key: StateKey, bytes: Vec<u8>) {
+        let write_set = WriteSetMut::new(vec![(key, WriteOp::legacy_creation(bytes.into()))])
+            .freeze()
+            .unwrap();
+        state.apply_write_set(write_set).unwrap();
+    }
+
+    fn put_object(
+        state: &ChainStateDB,
+        address: AccountAddress,
+        owner: AccountAddress,
+        members: Vec<(StructTag, Vec<u8>)>,
+    ) {
+        // the bcs of `0x1::object::ObjectCore`.
+        let object_core = bcs_ext::to_bytes(&(0u64, owner, true, event_handle(address))).unwrap();
+        let members = members
+            .into_iter()
+            .chain([(token_objects_tag("object", "ObjectCore"), object_core)])
+            .collect::<BTreeMap<_, _>>();
+        apply(
+            state,
+            StateKey::resource_group(&address, &ObjectGroupResource::struct_tag()),
+            bcs_ext::to_bytes(&members).unwrap(),
+        );
+    }
+
+    fn event_handle(address: AccountAddress) -> EventHandle {
+        EventHandle::new(EventKey::new(0, address), 0)
+    }
+
+    fn fungible_store(metadata: AccountAddress, balance: u64) -> (StructTag, Vec<u8>) {
+        (
+            FungibleStoreResource::struct_tag(),
+            bcs_ext::to_bytes(&FungibleStoreResource::new(metadata, balance, false)).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_portfolio() {
+        let (state, _net) = prepare_genesis().unwrap();
+        let owner = AccountAddress::random();
+        let other = AccountAddress::random();
+
+        let coin_store_tag =
+            CoinStoreResource::struct_tag_for_token(G_STC_TOKEN_CODE.clone().try_into().unwrap());
+        apply(
+            &state,
+            StateKey::resource(&owner, &coin_store_tag).unwrap(),
+            bcs_ext::to_bytes(&CoinStoreResource::new(
+                10,
+                false,
+                event_handle(owner),
+                event_handle(owner),
+            ))
+            .unwrap(),
+        );
+        let metadata = AccountAddress::random();
+        put_object(
+            &state,
+            metadata,
+            other,
+            vec![(
+                token_objects_tag("fungible_asset", "Metadata"),
+                bcs_ext::to_bytes(&("Test Coin", "TC", 8u8, "", "")).unwrap(),
+            )],
+        );
+        let primary = primary_store(&owner, &G_STC_TOKEN_CODE.to_canonical_string()).unwrap();
+        put_object(&state, primary, owner, vec![fungible_store(metadata, 20)]);
+        let secondary = AccountAddress::random();
+        put_object(&state, secondary, owner, vec![fungible_store(metadata, 30)]);
+        let others_store = AccountAddress::random();
+        put_object(
+            &state,
+            others_store,
+            other,
+            vec![fungible_store(metadata, 40)],
+        );
+
+        let collection = AccountAddress::random();
+        put_object(
+            &state,
+            collection,
+            other,
+            vec![(
+                token_objects_tag("collection", "Collection"),
+                bcs_ext::to_bytes(&(other, "", "Test Collection", "", event_handle(collection)))
+                    .unwrap(),
+            )],
+        );
+        let token = |address| {
+            vec![(
+                token_objects_tag("object_token", "Token"),
+                bcs_ext::to_bytes(&(
+                    collection,
+                    1u64,
+                    "A test token",
+                    "Test Token #1",
+                    "https://example.com/1",
+                    event_handle(address),
+                ))
+                .unwrap(),
+            )]
+        };
+        let nft = AccountAddress::random();
+        put_object(&state, nft, owner, token(nft));
+        let others_nft = AccountAddress::random();
+        put_object(&state, others_nft, other, token(others_nft));
+
+        let view = portfolio(
+            &state,
+            state.state_root(),
+            owner,
+            vec![],
+            vec![],
+            vec![secondary, others_store, nft, others_nft, nft],
+        )
+        .unwrap();
+        assert_eq!(view.address, owner);
+
+        assert_eq!(view.coins.len(), 1);
+        let coin = &view.coins[0];
+        assert_eq!(coin.token_code, G_STC_TOKEN_CODE.clone());
+        assert_eq!(coin.balance.0, 10);
+        assert_eq!(coin.symbol.as_deref(), Some("STC"));
+        assert_eq!(coin.decimals, Some(9));
+
+        // the objects of other accounts are skipped.
+        assert_eq!(view.fungible_assets.len(), 2);
+        let primary_asset = &view.fungible_assets[0];
+        assert_eq!(primary_asset.store, primary);
+        assert_eq!(primary_asset.token_code, Some(G_STC_TOKEN_CODE.clone()));
+        assert_eq!(primary_asset.balance.0, 20);
+        assert_eq!(primary_asset.symbol.as_deref(), Some("TC"));
+        let secondary_asset = &view.fungible_assets[1];
+        assert_eq!(secondary_asset.store, secondary);
+        assert_eq!(secondary_asset.metadata, metadata);
+        assert_eq!(secondary_asset.token_code, None);
+        assert_eq!(secondary_asset.balance.0, 30);
+
+        assert_eq!(view.nfts.len(), 1);
+        let token = &view.nfts[0];
+        assert_eq!(token.object, nft);
+        assert_eq!(token.collection, collection);
+        assert_eq!(token.collection_name.as_deref(), Some("Test Collection"));
+        assert_eq!(token.name, "Test Token #1");
+        assert_eq!(token.description, "A test token");
+        assert_eq!(token.uri, "https://example.com/1");
+
+        // only the supplied objects are checked.
+        let view = portfolio(&state, state.state_root(), owner, vec![], vec![], vec![]).unwrap();
+        assert_eq!(view.fungible_assets.len(), 1);
+        assert!(view.nfts.is_empty());
+
+        // the primary store of a fungible asset without a token code is found by its metadata.
+        let pure_primary =
+            AuthenticationKey::object_address_from_object(&owner, &metadata).derived_address();
+        put_object(
+            &state,
+            pure_primary,
+            owner,
+            vec![fungible_store(metadata, 50)],
+        );
+        let view = portfolio(
+            &state,
+            state.state_root(),
+            owner,
+            vec![],
+            vec![metadata, metadata],
+            vec![],
+        )
+        .unwrap();
+        assert_eq!(view.fungible_assets.len(), 2);
+        let pure_asset = &view.fungible_assets[1];
+        assert_eq!(pure_asset.store, pure_primary);
+        assert_eq!(pure_asset.metadata, metadata);
+        assert_eq!(pure_asset.token_code, None);
+        assert_eq!(pure_asset.balance.0, 50);
+    }
+}
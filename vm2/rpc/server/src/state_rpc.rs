@@ -6,16 +6,25 @@ use anyhow::{bail, Result};
 use bcs_ext::BCSCodec;
 use bytes::Bytes;
 use jsonrpsee::core::{async_trait, RpcResult};
-use serde_json::Value;
+use serde_json::{json, Value};
+use starcoin_storage::table_info::TableInfoStore;
+use starcoin_types::table::{StcTableHandle, StcTableInfo};
 use starcoin_vm2_abi_decoder::DecodedMoveValue;
 use starcoin_vm2_abi_resolver::ABIResolver;
 use starcoin_vm2_crypto::HashValue;
 use starcoin_vm2_dev::playground::view_resource;
-use starcoin_vm2_resource_viewer::MoveValueAnnotator;
+use starcoin_vm2_resource_viewer::{
+    expand::{
+        ExpandConfig, ExpandedMoveStruct, Expansion, PathElement, ResourceExpander,
+        TableInfoResolver,
+    },
+    AnnotatedMoveStruct, MoveValueAnnotator,
+};
 use starcoin_vm2_vm_types::move_resource::MoveStructType;
 
 use starcoin_vm2_rpc_api::state_api::{
-    GetCodeOption, GetResourceOption, ListCodeOption, ListResourceOption, StateApiServer,
+    ExpandOption, GetCodeOption, GetResourceOption, ListCodeOption, ListResourceOption,
+    StateApiServer,
 };
 use starcoin_vm2_state_api::{ChainStateAsyncService, StateNodeStore, StateReaderExt};
 use starcoin_vm2_statedb::{ChainStateDB, ChainStateReader};
@@ -32,17 +41,27 @@ use starcoin_vm2_vm_types::{
     identifier::Identifier,
     language_storage::{struct_tag_match, ModuleId, StructTag},
     on_chain_config::Features,
-    state_store::{state_key::StateKey, table::TableHandle, TStateView},
+    state_store::{
+        state_key::StateKey,
+        table::{TableHandle, TableInfo},
+        TStateView,
+    },
     token::{stc::G_STC_TOKEN_CODE, token_code::TokenCode},
 };
 use std::{collections::BTreeMap, str::FromStr, sync::Arc};
 
+/// The max depth of the references expanded in a resource.
+const MAX_EXPAND_DEPTH: u8 = 3;
+/// The field added to the json of an expanded `Object` or `Table`.
+const EXPANDED_FIELD: &str = "expanded";
+
 pub struct StateRpcImpl<S>
 where
     S: ChainStateAsyncService + 'static,
 {
     service: S,
     state_store: Arc<dyn StateNodeStore>,
+    table_info_store: Option<Arc<dyn TableInfoStore + Send + Sync>>,
 }
 
 impl<S> StateRpcImpl<S>
@@ -54,8 +73,18 @@ where
         Self {
             service,
             state_store,
+            table_info_store: None,
         }
     }
+
+    /// Resolve the table handles in the expanded resources by the table infos recorded by chain.
+    pub fn with_table_info_store(
+        mut self,
+        table_info_store: Arc<dyn TableInfoStore + Send + Sync>,
+    ) -> Self {
+        self.table_info_store = Some(table_info_store);
+        self
+    }
 }
 
 #[async_trait]
@@ -279,12 +308,17 @@ where
     ) -> RpcResult<Option<ResourceView>> {
         let service = self.service.clone();
         let state_store = self.state_store.clone();
+        let table_info_store = self.table_info_store.clone();
         let option = option.unwrap_or_default();
         async move {
+            let expand_config = option.expand.as_ref().map(to_expand_config).transpose()?;
             let state_root = option
                 .state_root
                 .unwrap_or(service.clone().state_root().await?);
             let chain_state = ChainStateDB::new(state_store, Some(state_root));
+            let table_info_resolver = table_info_store.map(TableInfoStoreResolver);
+            let expander = expand_config
+                .map(|config| resource_expander(&chain_state, config, &table_info_resolver));
             let primary_store_opt = option.primary_fungible_store.clone();
             let data = if let Some(primary_store) = option.primary_fungible_store {
                 ensure_fungible_store_struct(&resource_type.0)?;
@@ -301,7 +335,7 @@ where
                     let mut decoded = if option.decode {
                         let struct_tag = resource_type.0.clone();
                         let value = view_resource(&chain_state, struct_tag, d.to_vec().as_slice())?;
-                        Some(value.into())
+                        Some(decode_expanded(value, expander.as_ref())?)
                     } else {
                         None
                     };
@@ -337,6 +371,7 @@ where
     ) -> RpcResult<ListResourceView> {
         let state_service = self.service.clone();
         let db = self.state_store.clone();
+        let table_info_store = self.table_info_store.clone();
         async move {
             let ListResourceOption {
                 decode,
@@ -345,9 +380,14 @@ where
                 max_size,
                 resource_types,
                 primary_fungible_store,
+                expand,
             } = option.unwrap_or_default();
+            let expand_config = expand.as_ref().map(to_expand_config).transpose()?;
             let state_root = state_root.unwrap_or(state_service.state_root().await?);
             let statedb = ChainStateDB::new(db, Some(state_root));
+            let table_info_resolver = table_info_store.map(TableInfoStoreResolver);
+            let expander = expand_config
+                .map(|config| resource_expander(&statedb, config, &table_info_resolver));
 
             let state = statedb.get_account_state_set(&addr)?;
             let filter_types = resource_types;
@@ -400,7 +440,12 @@ where
                     let mut decoded = if decode {
                         view_resource(&statedb, struct_tag.clone(), bytes.as_slice())
                             .ok()
-                            .map(Into::into)
+                            .map(|value| match expander.as_ref() {
+                                // a resource failing to expand is listed unexpanded.
+                                Some(expander) => decode_expanded(value.clone(), Some(expander))
+                                    .unwrap_or_else(|_| value.into()),
+                                None => value.into(),
+                            })
                     } else {
                         None
                     };
@@ -565,3 +610,202 @@ fn apply_primary_store_balance_override(
     }
     Ok(())
 }
+
+struct TableInfoStoreResolver(Arc<dyn TableInfoStore + Send + Sync>);
+
+impl TableInfoResolver for TableInfoStoreResolver {
+    fn resolve_table_info(&self, handle: &TableHandle) -> Result<Option<TableInfo>> {
+        Ok(match self.0.get_table_info(StcTableHandle::V2(*handle))? {
+            Some(StcTableInfo::V2(info)) => Some(info),
+            _ => None,
+        })
+    }
+}
+
+fn to_expand_config(option: &ExpandOption) -> Result<ExpandConfig> {
+    if option.depth > MAX_EXPAND_DEPTH {
+        bail!("Expand depth is limited by {}", MAX_EXPAND_DEPTH);
+    }
+    Ok(ExpandConfig {
+        depth: option.depth as usize,
+        objects: option.objects,
+        fungible_stores: option.fungible_stores,
+        tables: option.tables,
+    })
+}
+
+fn resource_expander<'a>(
+    chain_state: &'a ChainStateDB,
+    config: ExpandConfig,
+    table_info_resolver: &'a Option<TableInfoStoreResolver>,
+) -> ResourceExpander<'a> {
+    let expander = ResourceExpander::new(chain_state, config);
+    match table_info_resolver {
+        Some(resolver) => expander.with_table_info_resolver(resolver),
+        None => expander,
+    }
+}
+
+fn decode_expanded(
+    value: AnnotatedMoveStruct,
+    expander: Option<&ResourceExpander>,
+) -> Result<DecodedMoveValue> {
+    Ok(match expander {
+        Some(expander) => DecodedMoveValue(expanded_to_json(expander.expand_struct(value)?)),
+        None => value.into(),
+    })
+}
+
+/// Decode the struct to json, and add the expanded data to the `Object` or `Table` it refers to.
+fn expanded_to_json(expanded: ExpandedMoveStruct) -> Value {
+    let ExpandedMoveStruct { value, expansions } = expanded;
+    let mut json = DecodedMoveValue::from(value).0;
+    for (path, expansion) in expansions {
+        let target = path
+            .iter()
+            .try_fold(&mut json, |json, element| match element {
+                PathElement::Field(name) => json.get_mut(name.as_str()),
+                PathElement::Index(idx) => json.get_mut(*idx),
+            });
+        if let Some(Value::Object(map)) = target {
+            map.insert(EXPANDED_FIELD.to_string(), expansion_to_json(expansion));
+        }
+    }
+    json
+}
+
+fn expansion_to_json(expansion: Expansion) -> Value {
+    match expansion {
+        Expansion::Object(object) => {
+            let resources = object
+                .resources
+                .into_iter()
+                .map(|resource| (resource.value.type_.to_string(), expanded_to_json(resource)))
+                .collect::<serde_json::Map<_, _>>();
+            json!({
+                "address": object.address.to_string(),
+                "owner": object.owner.to_string(),
+                "resources": resources,
+                "fungible_store": object.fungible_store.map(|store| json!({
+                    "metadata": store.metadata.to_string(),
+                    "balance": store.balance,
+                    "frozen": store.frozen,
+                    "name": store.name,
+                    "symbol": store.symbol,
+                    "decimals": store.decimals,
+                })),
+            })
+        }
+        Expansion::Table(handle, info) => json!({
+            "handle": handle.0.to_string(),
+            "key_type": info.key_type.to_canonical_string(),
+            "value_type": info.value_type.to_canonical_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expanded_to_json;
+    use starcoin_vm2_resource_viewer::{
+        expand::{
+            ExpandedMoveStruct, ExpandedObject, Expansion, FungibleStoreBalance, PathElement,
+        },
+        AnnotatedMoveStruct, AnnotatedMoveValue,
+    };
+    use starcoin_vm2_vm_types::{
+        account_address::AccountAddress,
+        file_format::AbilitySet,
+        identifier::Identifier,
+        language_storage::{StructTag, TypeTag, CORE_CODE_ADDRESS},
+        state_store::table::{TableHandle, TableInfo},
+    };
+
+    fn reference(
+        module: &str,
+        name: &str,
+        field: &str,
+        address: AccountAddress,
+    ) -> AnnotatedMoveValue {
+        AnnotatedMoveValue::Struct(AnnotatedMoveStruct {
+            abilities: AbilitySet::EMPTY,
+            type_: StructTag {
+                address: CORE_CODE_ADDRESS,
+                module: Identifier::new(module).unwrap(),
+                name: Identifier::new(name).unwrap(),
+                type_args: vec![],
+            },
+            value: vec![(
+                Identifier::new(field).unwrap(),
+                AnnotatedMoveValue::Address(address),
+            )],
+        })
+    }
+
+    #[test]
+    fn test_expanded_to_json() {
+        let object = AccountAddress::random();
+        let owner = AccountAddress::random();
+        let handle = AccountAddress::random();
+        let value = AnnotatedMoveStruct {
+            abilities: AbilitySet::EMPTY,
+            type_: StructTag {
+                address: CORE_CODE_ADDRESS,
+                module: Identifier::new("holder").unwrap(),
+                name: Identifier::new("Holder").unwrap(),
+                type_args: vec![],
+            },
+            value: vec![
+                (
+                    Identifier::new("store").unwrap(),
+                    reference("object", "Object", "inner", object),
+                ),
+                (
+                    Identifier::new("tables").unwrap(),
+                    AnnotatedMoveValue::Vector(vec![reference("table", "Table", "handle", handle)]),
+                ),
+            ],
+        };
+        let expanded = ExpandedMoveStruct {
+            value,
+            expansions: vec![
+                (
+                    vec![PathElement::Field(Identifier::new("store").unwrap())],
+                    Expansion::Object(ExpandedObject {
+                        address: object,
+                        owner,
+                        resources: vec![],
+                        fungible_store: Some(FungibleStoreBalance {
+                            metadata: AccountAddress::ONE,
+                            balance: 100,
+                            frozen: false,
+                            name: None,
+                            symbol: Some("TC".to_string()),
+                            decimals: Some(8),
+                        }),
+                    }),
+                ),
+                (
+                    vec![
+                        PathElement::Field(Identifier::new("tables").unwrap()),
+                        PathElement::Index(0),
+                    ],
+                    Expansion::Table(
+                        TableHandle(handle),
+                        TableInfo::new(TypeTag::U64, TypeTag::Address),
+                    ),
+                ),
+            ],
+        };
+
+        let json = expanded_to_json(expanded);
+        // the expansions are added to the referring structs, the fields are kept.
+        assert_eq!(json["store"]["inner"], object.to_string());
+        assert_eq!(json["store"]["expanded"]["owner"], owner.to_string());
+        assert_eq!(json["store"]["expanded"]["fungible_store"]["balance"], 100);
+        assert_eq!(json["store"]["expanded"]["fungible_store"]["symbol"], "TC");
+        assert_eq!(json["tables"][0]["handle"], handle.to_string());
+        assert_eq!(json["tables"][0]["expanded"]["key_type"], "u64");
+        assert_eq!(json["tables"][0]["expanded"]["value_type"], "address");
+    }
+}
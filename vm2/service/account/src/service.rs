@@ -13,6 +13,7 @@ use starcoin_vm2_account_api::{
 };
 use starcoin_vm2_crypto::ValidCryptoMaterial;
 use starcoin_vm2_types::account_config::{association_address, G_STC_TOKEN_CODE};
+use starcoin_vm2_types::transaction::authenticator::Scheme;
 use starcoin_vm2_vm_types::on_chain_resource::ChainId;
 use std::any::Any;
 use std::sync::Arc;
@@ -27,7 +28,7 @@ impl AccountService {
     pub fn mock() -> Result<Self> {
         let manager = AccountManager::new(AccountStorage::mock(), ChainId::test())?;
         //auto create default account.
-        manager.create_account("")?;
+        manager.create_account("", Scheme::Ed25519)?;
         Ok(Self { manager })
     }
 }
@@ -48,7 +49,8 @@ impl ActorService for AccountService {
 
         match account {
             None => {
-                self.manager.create_account(DEFAULT_ACCOUNT_PASSWORD)?;
+                self.manager
+                    .create_account(DEFAULT_ACCOUNT_PASSWORD, Scheme::Ed25519)?;
             }
             Some(account_info) => {
                 // before v1.1, account's setting is not persistent in storage, so the is_default in the account_info may be wrong,
@@ -107,9 +109,13 @@ impl ServiceHandler<Self, AccountRequest> for AccountService {
         ctx: &mut ServiceContext<Self>,
     ) -> Result<AccountResponse> {
         let response = match msg {
-            AccountRequest::CreateAccount(password) => AccountResponse::AccountInfo(Box::new(
-                self.manager.create_account(password.as_str())?.info(),
-            )),
+            AccountRequest::CreateAccount { password, key_type } => {
+                AccountResponse::AccountInfo(Box::new(
+                    self.manager
+                        .create_account(password.as_str(), key_type)?
+                        .info(),
+                ))
+            }
             AccountRequest::GetDefaultAccount() => {
                 AccountResponse::AccountInfoOption(Box::new(self.manager.default_account_info()?))
             }
@@ -143,6 +149,22 @@ impl ServiceHandler<Self, AccountRequest> for AccountService {
                 txn: raw_txn,
                 signer,
             } => AccountResponse::SignedTxn(Box::new(self.manager.sign_txn(signer, *raw_txn)?)),
+            AccountRequest::SignFeePayerTxn {
+                txn: raw_txn,
+                fee_payer,
+                signer,
+            } => AccountResponse::AccountAuthenticator(Box::new(
+                self.manager
+                    .sign_fee_payer_txn(signer, *raw_txn, fee_payer)?,
+            )),
+            AccountRequest::SignMultiAgentTxn {
+                txn: raw_txn,
+                secondary_signers,
+                signer,
+            } => AccountResponse::AccountAuthenticator(Box::new(
+                self.manager
+                    .sign_multi_agent_txn(signer, *raw_txn, secondary_signers)?,
+            )),
             AccountRequest::SignMessage { message, signer } => AccountResponse::SignedMessage(
                 Box::new(self.manager.sign_message(signer, message)?),
             ),
@@ -33,6 +33,7 @@ use starcoin_types::{
     contract_event::StcContractEvent,
     multi_access_path::MultiAccessPath,
     startup_info::{ChainInfo, ChainStatus},
+    system_events::ChainReorgEvent,
 };
 use starcoin_vm2_rpc_api::{block_info_view2::BlockInfoView2, transaction_view2::TransactionView2};
 use starcoin_vm2_statedb::ChainStateDB;
@@ -682,6 +683,11 @@ impl ChainApiServer for MockChainApi {
         fut.await.map_err(map_err).map_err(to_rpc_err)
     }
 
+    async fn get_reorgs(&self, _since: u64) -> RpcResult<Vec<ChainReorgEvent>> {
+        let fut = async move { bail!("not implemented.") };
+        fut.await.map_err(map_err).map_err(to_rpc_err)
+    }
+
     async fn get_block_info_by_hash(&self, _id: HashValue) -> RpcResult<Option<BlockInfoView>> {
         let fut = async move {
             bail!("not implemented.");
@@ -31,6 +31,8 @@ fn dry_run_transfer_materializes_delayed_fields() {
     let txn = DryRunTransaction {
         raw_txn,
         public_key,
+        fee_payer: None,
+        secondary_signers: vec![],
     };
 
     let mut vm = StarcoinVM::new(None, &chain_state);
@@ -1,9 +1,12 @@
 // Copyright (c) The Starcoin Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::view::state_key_view::StateKeyView;
 use crate::view::transaction_output_view::TransactionOutputView;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use starcoin_vm2_vm_types::state_store::state_key::inner::StateKeyInner;
+use starcoin_vm2_vm_types::transaction::{AccessList, TransactionOutput};
 use vm_status_translator::VmStatusExplainView;
 
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
@@ -11,4 +14,16 @@ pub struct DryRunOutputView {
     pub explained_status: VmStatusExplainView,
     #[serde(flatten)]
     pub txn_output: TransactionOutputView,
+    /// The state keys written by the dry run, the predicted access list of the txn.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_list: Option<Vec<StateKeyView>>,
+}
+
+/// The access list predicted by the write set of a dry run output.
+pub fn access_list_view(output: &TransactionOutput) -> Vec<StateKeyView> {
+    AccessList::from_write_set(output.write_set())
+        .writes()
+        .filter(|state_key| !matches!(state_key.inner(), StateKeyInner::Raw(_)))
+        .map(|state_key| StateKeyView::from(state_key.clone()))
+        .collect()
 }
@@ -39,6 +39,8 @@ pub mod event_with_proof_view;
 pub use event_with_proof_view::*;
 pub mod function_arg_type_view;
 pub use function_arg_type_view::*;
+pub mod portfolio_view;
+pub use portfolio_view::*;
 pub mod raw_user_transaction_view;
 pub use raw_user_transaction_view::*;
 pub mod resource_view;
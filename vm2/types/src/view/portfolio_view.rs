@@ -0,0 +1,61 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::view::str_view::StrView;
+use move_core_types::account_address::AccountAddress;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use starcoin_crypto::HashValue;
+use starcoin_vm2_vm_types::token::token_code::TokenCode;
+
+/// The coins held by an account, with the fungible assets and NFTs found at the stores and objects
+/// looked up for it.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PortfolioView {
+    pub address: AccountAddress,
+    /// The state root the portfolio is read at.
+    pub state_root: HashValue,
+    pub coins: Vec<CoinBalanceView>,
+    pub fungible_assets: Vec<FungibleAssetBalanceView>,
+    /// The NFTs among the objects checked for the account.
+    pub nfts: Vec<TokenObjectView>,
+}
+
+/// The balance of a `0x1::coin::CoinStore<T>`, with the metadata in `0x1::coin::CoinInfo<T>`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CoinBalanceView {
+    pub token_code: TokenCode,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+    pub balance: StrView<u64>,
+    pub frozen: bool,
+}
+
+/// The balance of a `0x1::fungible_asset::FungibleStore`, with the metadata in
+/// `0x1::fungible_asset::Metadata`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct FungibleAssetBalanceView {
+    /// The address of the store object.
+    pub store: AccountAddress,
+    /// The address of the metadata object.
+    pub metadata: AccountAddress,
+    /// The token of the primary store, None for the stores not derived from a token code.
+    pub token_code: Option<TokenCode>,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+    pub balance: StrView<u64>,
+    pub frozen: bool,
+}
+
+/// A `0x1::object_token::Token` object owned by the account.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TokenObjectView {
+    pub object: AccountAddress,
+    pub collection: AccountAddress,
+    pub collection_name: Option<String>,
+    pub name: String,
+    pub description: String,
+    pub uri: String,
+}
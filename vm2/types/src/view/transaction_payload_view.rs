@@ -13,6 +13,8 @@ pub enum TransactionPayloadView {
     Package(DecodedPackageView),
     /// A transaction that executes an existing script function published on-chain.
     ScriptFunction(DecodedScriptFunctionView),
+    /// A transaction that executes script functions one by one as a batch.
+    ScriptFunctionBatch(Vec<DecodedScriptFunctionView>),
 }
 
 impl From<DecodedTransactionPayload> for TransactionPayloadView {
@@ -21,6 +23,9 @@ impl From<DecodedTransactionPayload> for TransactionPayloadView {
             DecodedTransactionPayload::Script(s) => Self::Script(s.into()),
             DecodedTransactionPayload::Package(p) => Self::Package(p.into()),
             DecodedTransactionPayload::ScriptFunction(s) => Self::ScriptFunction(s.into()),
+            DecodedTransactionPayload::ScriptFunctionBatch(s) => {
+                Self::ScriptFunctionBatch(s.into_iter().map(Into::into).collect())
+            }
         }
     }
 }
@@ -21,6 +21,9 @@ pub struct TransactionRequest {
     /// module codes.
     #[serde(default)]
     pub modules: Vec<StrView<ByteCode>>,
+    /// script functions executed one by one as a batch, the transaction fails if any of them fails.
+    #[serde(default)]
+    pub entry_functions: Vec<ScriptData>,
     // Maximal total gas specified by wallet to spend for this transaction.
     pub max_gas_amount: Option<u64>,
     // Maximal price can be paid per gas.
@@ -45,6 +48,7 @@ impl From<RawUserTransaction> for TransactionRequest {
             sequence_number: Some(raw.sequence_number()),
             script: None,
             modules: vec![],
+            entry_functions: vec![],
             max_gas_amount: Some(raw.max_gas_amount()),
             gas_unit_price: Some(raw.gas_unit_price()),
             gas_token_code: Some(raw.gas_token_code()),
@@ -63,6 +67,9 @@ impl From<RawUserTransaction> for TransactionRequest {
             TransactionPayload::EntryFunction(s) => {
                 request.script = Some(ScriptData::from(s));
             }
+            TransactionPayload::EntryFunctionBatch(s) => {
+                request.entry_functions = s.into_iter().map(ScriptData::from).collect();
+            }
         }
         request
     }
@@ -42,6 +42,8 @@ const EDEPRECATED_FUNCTION: u64 = 1019;
 
 // see stc_transaction_validation.move
 const EPROLOGUE_SIGNER_ALREADY_DELEGATED: u64 = 1200;
+const EPROLOGUE_INVALID_FEE_PAYER_AUTH_KEY: u64 = 1202;
+const EPROLOGUE_INVALID_SECONDARY_SIGNER_AUTH_KEY: u64 = 1204;
 
 // todo: how to handle these error code
 //const EPROLOGUE_SENDING_ACCOUNT_FROZEN: u64 = 10;
@@ -92,6 +94,10 @@ pub fn convert_prologue_runtime_error(error: VMError) -> Result<(), VMStatus> {
                 (INVALID_ARGUMENT, EPROLOGUE_INVALID_ACCOUNT_AUTH_KEY) => {
                     StatusCode::INVALID_AUTH_KEY
                 }
+                (INVALID_ARGUMENT, EPROLOGUE_INVALID_FEE_PAYER_AUTH_KEY)
+                | (INVALID_ARGUMENT, EPROLOGUE_INVALID_SECONDARY_SIGNER_AUTH_KEY) => {
+                    StatusCode::INVALID_AUTH_KEY
+                }
                 (INVALID_ARGUMENT, EPROLOGUE_SEQUENCE_NUMBER_TOO_OLD) => {
                     StatusCode::SEQUENCE_NUMBER_TOO_OLD
                 }
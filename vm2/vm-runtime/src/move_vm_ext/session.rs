@@ -601,10 +601,12 @@ impl SessionExt<'_, '_> {
     //        .execute_script(script, ty_args, final_args, gas_meter)
     //}
 
+    /// Put the signers of `senders` before `args` if `func` takes signers, the sender of a
+    /// multi-agent transaction goes first and is followed by the secondary signers.
     pub(crate) fn check_and_rearrange_args_by_signer_position(
         func: &LoadedFunction,
         args: Vec<Vec<u8>>,
-        sender: AccountAddress,
+        senders: Vec<AccountAddress>,
     ) -> VMResult<Vec<Vec<u8>>> {
         let signer_ref = Type::Reference(Box::new(Type::Signer));
         let has_signer = func
@@ -628,10 +630,14 @@ impl SessionExt<'_, '_> {
             .unwrap_or(Ok(false))?;
 
         if has_signer {
-            let signer = MoveValue::Signer(sender);
-            let mut final_args = vec![signer
-                .simple_serialize()
-                .expect("serialize signer should success")];
+            let mut final_args: Vec<Vec<u8>> = senders
+                .into_iter()
+                .map(|sender| {
+                    MoveValue::Signer(sender)
+                        .simple_serialize()
+                        .expect("serialize signer should success")
+                })
+                .collect();
             final_args.extend(args);
             Ok(final_args)
         } else {
@@ -767,7 +773,7 @@ impl SessionExt<'_, '_> {
         script: Vec<u8>,
         ty_args: Vec<TypeTag>,
         args: Vec<Vec<u8>>,
-        sender: AccountAddress,
+        senders: Vec<AccountAddress>,
     ) -> VMResult<()> {
         // load the script, perform verification
         let function = self.load_script(script.borrow(), ty_args.as_slice())?;
@@ -778,7 +784,7 @@ impl SessionExt<'_, '_> {
             &function,
             function.ty_args().to_vec(),
             args,
-            sender,
+            senders,
         )?;
 
         Ok(())
@@ -790,14 +796,14 @@ impl SessionExt<'_, '_> {
         function_name: &IdentStr,
         ty_args: Vec<TypeTag>,
         args: Vec<Vec<u8>>,
-        sender: AccountAddress,
+        senders: Vec<AccountAddress>,
     ) -> VMResult<()> {
         let func = self.load_function(module, function_name, &ty_args)?;
         let param_tys = func.param_tys().to_owned();
 
         Self::check_script_return(func.return_tys())?;
 
-        self.check_script_signer_and_build_args(&func, param_tys, args, sender)?;
+        self.check_script_signer_and_build_args(&func, param_tys, args, senders)?;
 
         Ok(())
     }
@@ -822,9 +828,9 @@ impl SessionExt<'_, '_> {
         func: &LoadedFunction,
         arg_tys: Vec<Type>,
         args: Vec<Vec<u8>>,
-        sender: AccountAddress,
+        senders: Vec<AccountAddress>,
     ) -> VMResult<()> {
-        let final_args = Self::check_and_rearrange_args_by_signer_position(func, args, sender)?;
+        let final_args = Self::check_and_rearrange_args_by_signer_position(func, args, senders)?;
         // let arg_tys =
         //     arg_tys
         //         .into_iter()
@@ -24,6 +24,7 @@ use starcoin_vm_types::{
 };
 
 use starcoin_vm_types::on_chain_resource::ChainId;
+use starcoin_vm_types::transaction::user_transaction_context::UserTransactionContext;
 use std::ops::Deref;
 use std::sync::Arc;
 
@@ -114,6 +115,54 @@ impl MoveVmExt {
         resolver: &'r S,
         session_id: SessionId,
     ) -> SessionExt<'r, '_> {
+        self.new_session_with_user_context(resolver, session_id, None)
+    }
+
+    /// Create a session of a user transaction, the `transaction_context` natives read the
+    /// sender, the secondary signers and the other fields of the transaction from the context.
+    pub fn new_session_with_user_context<'r, S: StarcoinMoveResolver>(
+        &self,
+        resolver: &'r S,
+        session_id: SessionId,
+        user_transaction_context_opt: Option<UserTransactionContext>,
+    ) -> SessionExt<'r, '_> {
+        let extensions = self.native_extensions(resolver, session_id, user_transaction_context_opt);
+        self.new_session_with_extensions(resolver, extensions)
+    }
+
+    /// Create a session with the native extensions of the Move unit tests too, the unit test
+    /// natives are in the VM for it is built with the `testing` feature.
+    #[cfg(feature = "testing")]
+    pub fn new_unit_test_session<'r, S: StarcoinMoveResolver>(
+        &self,
+        resolver: &'r S,
+    ) -> SessionExt<'r, '_> {
+        use starcoin_framework::natives::{
+            code::NativeCodeContext,
+            cryptography::{
+                algebra::AlgebraContext, ristretto255_point::NativeRistrettoPointContext,
+            },
+            randomness::RandomnessContext,
+        };
+
+        let mut extensions = self.native_extensions(resolver, SessionId::void(), None);
+        extensions.add(NativeCodeContext::default());
+        extensions.add(NativeRistrettoPointContext::new());
+        extensions.add(AlgebraContext::new());
+        extensions.add(RandomnessContext::new());
+        self.new_session_with_extensions(resolver, extensions)
+    }
+
+    fn native_extensions<'r, S: StarcoinMoveResolver>(
+        &self,
+        resolver: &'r S,
+        session_id: SessionId,
+        user_transaction_context_opt: Option<UserTransactionContext>,
+    ) -> NativeContextExtensions<'r> {
+        let chain_id = user_transaction_context_opt
+            .as_ref()
+            .map(|context| context.chain_id())
+            .unwrap_or_else(|| ChainId::test().id());
         let mut extensions = NativeContextExtensions::default();
         let txn_hash: [u8; 32] = session_id
             .as_uuid()
@@ -128,10 +177,17 @@ impl MoveVmExt {
         extensions.add(NativeTransactionContext::new(
             txn_hash.to_vec(),
             vec![],
-            ChainId::test().id(),
-            None,
+            chain_id,
+            user_transaction_context_opt,
         ));
+        extensions
+    }
 
+    fn new_session_with_extensions<'r, S: StarcoinMoveResolver>(
+        &self,
+        resolver: &'r S,
+        extensions: NativeContextExtensions<'r>,
+    ) -> SessionExt<'r, '_> {
         // The VM code loader has bugs around module upgrade. After a module upgrade, the internal
         // cache needs to be flushed to work around those bugs.
         self.inner.flush_loader_cache_if_invalidated();
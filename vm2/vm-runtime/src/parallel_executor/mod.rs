@@ -255,12 +255,13 @@ impl ParallelStarcoinVM {
 
         let delayed_field_cache = Arc::new(DelayedFieldCache::default());
         let exec_start = std::time::Instant::now();
-        match ParallelTransactionExecutor::<PreprocessedTransaction, StarcoinVMWrapper<S>>::new(
-            concurrency_level,
-            block_gas_limit,
-        )
-        .with_delayed_fields(delayed_fields_enabled)
-        .execute_transactions_parallel_with_delayed_fields(
+        let executor =
+            ParallelTransactionExecutor::<PreprocessedTransaction, StarcoinVMWrapper<S>>::new(
+                concurrency_level,
+                block_gas_limit,
+            )
+            .with_delayed_fields(delayed_fields_enabled);
+        match executor.execute_transactions_parallel_with_delayed_fields(
             (
                 state_view,
                 delayed_field_cache.clone(),
@@ -270,6 +271,7 @@ impl ParallelStarcoinVM {
         ) {
             Ok((results, delayed_fields)) => {
                 let exec_ms = exec_start.elapsed().as_secs_f64() * 1000.0;
+                let exec_stats = executor.last_execution_stats();
                 if !delayed_fields_enabled
                     && results
                         .iter()
@@ -292,9 +294,12 @@ impl ParallelStarcoinVM {
                 let rg_stats = take_resource_group_stats();
                 info!(
                     target: "vm-bench",
-                    "parallel execute done: exec_ms={:.3} materialize_ms={:.3} rg_accesses={} rg_cache_hits={} rg_member_calls={} rg_member_ms={:.3} rg_size_calls={} rg_size_ms={:.3}",
+                    "parallel execute done: exec_ms={:.3} materialize_ms={:.3} txns={} executions={} aborts={} rg_accesses={} rg_cache_hits={} rg_member_calls={} rg_member_ms={:.3} rg_size_calls={} rg_size_ms={:.3}",
                     exec_ms,
                     materialize_ms,
+                    exec_stats.txns,
+                    exec_stats.executions,
+                    exec_stats.aborts,
                     rg_stats.group_accesses,
                     rg_stats.group_cache_hits,
                     rg_stats.group_member_calls,
@@ -48,7 +48,8 @@ use starcoin_vm_types::{
     account_address::AccountAddress,
     account_config::{
         genesis_address, upgrade::UpgradeEvent, ModuleUpgradeStrategy, TwoPhaseUpgradeV2Resource,
-        G_EPILOGUE_NAME, G_PROLOGUE_NAME,
+        G_EPILOGUE_NAME, G_FEE_PAYER_EPILOGUE_NAME, G_FEE_PAYER_PROLOGUE_NAME,
+        G_MULTI_AGENT_PROLOGUE_NAME, G_PROLOGUE_NAME,
     },
     errors::{Location, PartialVMError, VMResult},
     file_format::{CompiledModule, CompiledScript},
@@ -86,6 +87,16 @@ pub struct StarcoinVM {
     metrics: Option<VMMetrics>,
 }
 
+/// The gas charged by [`StarcoinVM::execute_function_with_gas_usage`].
+#[cfg(feature = "testing")]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct FunctionGasUsage {
+    /// The gas of the instructions and the native functions.
+    pub execution: u64,
+    /// The gas of the global writes, charged for each account mutated by the function.
+    pub storage: u64,
+}
+
 impl StarcoinVM {
     #[cfg(feature = "metrics")]
     pub fn new<S: StateView>(metrics: Option<VMMetrics>, state: &S) -> Self {
@@ -373,9 +384,11 @@ impl StarcoinVM {
         let impl_start = Instant::now();
         let txn_data = TransactionMetadata::new(transaction)?;
         let data_cache = remote_cache.as_move_resolver();
-        let mut session = self
-            .move_vm
-            .new_session(&data_cache, SessionId::txn(transaction));
+        let mut session = self.move_vm.new_session_with_user_context(
+            &data_cache,
+            SessionId::txn(transaction),
+            txn_data.user_transaction_context(),
+        );
         let gas_params = self.get_gas_parameters()?;
         let mut gas_meter = StarcoinGasMeter::new(gas_params.clone(), txn_data.max_gas_amount());
         gas_meter.set_metering(false);
@@ -432,7 +445,7 @@ impl StarcoinVM {
                         s.code().to_vec(),
                         s.ty_args().to_vec(),
                         s.args().to_vec(),
-                        txn_data.sender(),
+                        txn_data.senders(),
                     )
                     .map_err(|e| e.into_vm_status())?;
                 "script"
@@ -444,11 +457,28 @@ impl StarcoinVM {
                         s.function(),
                         s.ty_args().to_vec(),
                         s.args().to_vec(),
-                        txn_data.sender(),
+                        txn_data.senders(),
                     )
                     .map_err(|e| e.into_vm_status())?;
                 "entry_function"
             }
+            TransactionPayload::EntryFunctionBatch(entry_functions) => {
+                if entry_functions.is_empty() {
+                    return Err(VMStatus::error(StatusCode::UNKNOWN_SCRIPT, None));
+                }
+                for s in entry_functions {
+                    session
+                        .verify_script_function_args(
+                            s.module(),
+                            s.function(),
+                            s.ty_args().to_vec(),
+                            s.args().to_vec(),
+                            txn_data.senders(),
+                        )
+                        .map_err(|e| e.into_vm_status())?;
+                }
+                "entry_function_batch"
+            }
         };
         let verify_args_dur = verify_args_start.elapsed();
 
@@ -691,7 +721,7 @@ impl StarcoinVM {
                     init_script.ty_args().to_vec(),
                     init_script.args().to_vec(),
                     gas_meter,
-                    sender,
+                    vec![sender],
                 )
                 .map_err(|e| e.into_vm_status())?;
             }
@@ -713,6 +743,12 @@ impl StarcoinVM {
         {
             gas_meter.set_metering(false);
             self.check_gas(txn_data)?;
+            // a txn skipping the verification must not execute an empty batch either.
+            if let TransactionPayload::EntryFunctionBatch(entry_functions) = payload {
+                if entry_functions.is_empty() {
+                    return Err(VMStatus::error(StatusCode::UNKNOWN_SCRIPT, None));
+                }
+            }
             self.run_prologue(&mut session, gas_meter, txn_data)?;
         }
 
@@ -736,7 +772,7 @@ impl StarcoinVM {
                         script.ty_args().to_vec(),
                         script.args().to_vec(),
                         gas_meter,
-                        txn_data.sender(),
+                        txn_data.senders(),
                     )
                 }
                 TransactionPayload::EntryFunction(script_function) => {
@@ -748,9 +784,25 @@ impl StarcoinVM {
                         script_function.ty_args().to_vec(),
                         script_function.args().to_vec(),
                         gas_meter,
-                        txn_data.sender(),
+                        txn_data.senders(),
                     )
                 }
+                TransactionPayload::EntryFunctionBatch(entry_functions) => {
+                    // all the functions share the session and the gas meter, the changes of the
+                    // executed ones are discarded with the session if a later one fails.
+                    entry_functions.iter().try_for_each(|script_function| {
+                        debug!("TransactionPayload::{:?}", script_function);
+                        Self::validate_execute_entry_function(
+                            &mut session,
+                            script_function.module(),
+                            script_function.function(),
+                            script_function.ty_args().to_vec(),
+                            script_function.args().to_vec(),
+                            gas_meter,
+                            txn_data.senders(),
+                        )
+                    })
+                }
                 TransactionPayload::Package(_) => {
                     return Err(VMStatus::error(StatusCode::UNREACHABLE, None));
                 }
@@ -773,13 +825,13 @@ impl StarcoinVM {
         ty_args: Vec<TypeTag>,
         args: Vec<impl Borrow<[u8]>>,
         gas_meter: &mut impl GasMeter,
-        sender: AccountAddress,
+        senders: Vec<AccountAddress>,
     ) -> VMResult<()> {
         let loaded_func = session.load_function(module, function_name, &ty_args)?;
 
         verifier::transaction_arg_validation::validate_combine_singer_and_args(
             session,
-            vec![sender],
+            senders.clone(),
             &args,
             &loaded_func,
         )?;
@@ -787,7 +839,7 @@ impl StarcoinVM {
         let final_args = SessionExt::check_and_rearrange_args_by_signer_position(
             loaded_func.borrow(),
             args.iter().map(|b| b.borrow().to_vec()).collect(),
-            sender,
+            senders,
         )?;
 
         let tranversal_storage = TraversalStorage::new();
@@ -805,13 +857,13 @@ impl StarcoinVM {
         ty_args: Vec<TypeTag>,
         args: Vec<impl Borrow<[u8]>>,
         gas_meter: &mut impl GasMeter,
-        sender: AccountAddress,
+        senders: Vec<AccountAddress>,
     ) -> VMResult<()> {
         let loaded_func = session.load_script(script.borrow(), ty_args.as_ref())?;
 
         verifier::transaction_arg_validation::validate_combine_singer_and_args(
             session,
-            vec![sender],
+            senders.clone(),
             &args,
             &loaded_func,
         )?;
@@ -819,7 +871,7 @@ impl StarcoinVM {
         let final_args = SessionExt::check_and_rearrange_args_by_signer_position(
             loaded_func.borrow(),
             args.iter().map(|b| b.borrow().to_vec()).collect(),
-            sender,
+            senders,
         )?;
 
         let traversal_storage = TraversalStorage::new();
@@ -870,26 +922,57 @@ impl StarcoinVM {
             ),
         };
 
+        let mut args = vec![
+            MoveValue::Signer(genesis_address),
+            MoveValue::Address(txn_data.sender),
+            MoveValue::U64(txn_sequence_number),
+            MoveValue::vector_u8(authentication_key_preimage),
+        ];
+        // The gas of a fee payer txn is checked on the fee payer, the auth keys of the secondary
+        // signers of a multi-agent txn are checked besides the sender's one.
+        let function_name = match &txn_data.fee_payer {
+            Some((fee_payer, fee_payer_authentication_key_preimage)) => {
+                args.push(MoveValue::Address(*fee_payer));
+                args.push(MoveValue::vector_u8(
+                    fee_payer_authentication_key_preimage.clone(),
+                ));
+                &G_FEE_PAYER_PROLOGUE_NAME
+            }
+            None if !txn_data.secondary_signers.is_empty() => {
+                let (addresses, preimages): (Vec<_>, Vec<_>) = txn_data
+                    .secondary_signers
+                    .iter()
+                    .map(|(address, preimage)| {
+                        (
+                            MoveValue::Address(*address),
+                            MoveValue::vector_u8(preimage.clone()),
+                        )
+                    })
+                    .unzip();
+                args.push(MoveValue::Vector(addresses));
+                args.push(MoveValue::Vector(preimages));
+                &G_MULTI_AGENT_PROLOGUE_NAME
+            }
+            None => &G_PROLOGUE_NAME,
+        };
+        args.extend([
+            MoveValue::U64(txn_gas_price),
+            MoveValue::U64(txn_max_gas_amount),
+            MoveValue::U64(txn_expiration_time),
+            MoveValue::U8(chain_id),
+            MoveValue::U8(payload_type.into()),
+            MoveValue::vector_u8(script_or_package_hash.to_vec()),
+            MoveValue::Address(package_address),
+        ]);
+
         let traversal_storage = TraversalStorage::new();
         // Run prologue by genesis account
         session
             .execute_function_bypass_visibility(
                 &account_config::G_TRANSACTION_VALIDATION_MODULE,
-                &G_PROLOGUE_NAME,
+                function_name,
                 vec![gas_token_ty],
-                serialize_values(&vec![
-                    MoveValue::Signer(genesis_address),
-                    MoveValue::Address(txn_data.sender),
-                    MoveValue::U64(txn_sequence_number),
-                    MoveValue::vector_u8(authentication_key_preimage),
-                    MoveValue::U64(txn_gas_price),
-                    MoveValue::U64(txn_max_gas_amount),
-                    MoveValue::U64(txn_expiration_time),
-                    MoveValue::U8(chain_id),
-                    MoveValue::U8(payload_type.into()),
-                    MoveValue::vector_u8(script_or_package_hash.to_vec()),
-                    MoveValue::Address(package_address),
-                ]),
+                serialize_values(&args),
                 gas_meter,
                 &mut TraversalContext::new(&traversal_storage),
             )
@@ -967,22 +1050,30 @@ impl StarcoinVM {
         //         ]),
         //     )
         // };
-        let (function_name, args) = (
-            &G_EPILOGUE_NAME,
-            serialize_values(&vec![
-                MoveValue::Signer(genesis_address),
-                MoveValue::Address(txn_data.sender),
-                MoveValue::U64(txn_sequence_number),
-                MoveValue::vector_u8(txn_authentication_key_preimage),
-                MoveValue::U64(txn_gas_price),
-                MoveValue::U64(txn_max_gas_amount),
-                MoveValue::U64(gas_remaining),
-                MoveValue::U8(payload_type.into()),
-                MoveValue::vector_u8(script_or_package_hash.to_vec()),
-                MoveValue::Address(package_address),
-                MoveValue::Bool(success),
-            ]),
-        );
+        let mut args = vec![
+            MoveValue::Signer(genesis_address),
+            MoveValue::Address(txn_data.sender),
+            MoveValue::U64(txn_sequence_number),
+            MoveValue::vector_u8(txn_authentication_key_preimage),
+        ];
+        // The gas of a fee payer txn is charged from the fee payer.
+        let function_name = match txn_data.fee_payer() {
+            Some(fee_payer) => {
+                args.push(MoveValue::Address(fee_payer));
+                &G_FEE_PAYER_EPILOGUE_NAME
+            }
+            None => &G_EPILOGUE_NAME,
+        };
+        args.extend([
+            MoveValue::U64(txn_gas_price),
+            MoveValue::U64(txn_max_gas_amount),
+            MoveValue::U64(gas_remaining),
+            MoveValue::U8(payload_type.into()),
+            MoveValue::vector_u8(script_or_package_hash.to_vec()),
+            MoveValue::Address(package_address),
+            MoveValue::Bool(success),
+        ]);
+        let args = serialize_values(&args);
 
         let traversal_storage = TraversalStorage::new();
         session
@@ -1108,9 +1199,11 @@ impl StarcoinVM {
             }
         };
 
-        let session = self
-            .move_vm
-            .new_session(storage, SessionId::txn_meta(&txn_data));
+        let session = self.move_vm.new_session_with_user_context(
+            storage,
+            SessionId::txn_meta(&txn_data),
+            txn_data.user_transaction_context(),
+        );
         let mut gas_meter = StarcoinGasMeter::new(gas_params.clone(), txn_data.max_gas_amount());
         // check signature
         let signature_checked_txn = match txn.check_signature() {
@@ -1122,7 +1215,8 @@ impl StarcoinVM {
             Ok(txn) => {
                 let result = match txn.payload() {
                     payload @ TransactionPayload::Script(_)
-                    | payload @ TransactionPayload::EntryFunction(_) => self
+                    | payload @ TransactionPayload::EntryFunction(_)
+                    | payload @ TransactionPayload::EntryFunctionBatch(_) => self
                         .execute_script_or_script_function(
                             session,
                             &mut gas_meter,
@@ -1187,21 +1281,36 @@ impl StarcoinVM {
                 }
             }
         };
-        let txn_data = match TransactionMetadata::from_raw_txn_and_preimage(
+        let mut txn_data = match TransactionMetadata::from_raw_txn_and_preimage(
             &txn.raw_txn,
             txn.public_key.authentication_key_preimage(),
         ) {
             Ok(txn_data) => txn_data,
             Err(e) => return discard_output(e),
         };
-        let session = self
-            .move_vm
-            .new_session(storage, SessionId::txn_meta(&txn_data));
+        txn_data.fee_payer = txn.fee_payer.map(|(address, public_key)| {
+            (address, public_key.authentication_key_preimage().into_vec())
+        });
+        txn_data.set_secondary_signers(
+            txn.secondary_signers
+                .into_iter()
+                .map(|(address, public_key)| {
+                    (address, public_key.authentication_key_preimage().into_vec())
+                })
+                .collect(),
+            txn.raw_txn.payload(),
+        );
+        let session = self.move_vm.new_session_with_user_context(
+            storage,
+            SessionId::txn_meta(&txn_data),
+            txn_data.user_transaction_context(),
+        );
         let mut gas_meter = StarcoinGasMeter::new(gas_params.clone(), txn_data.max_gas_amount());
         gas_meter.set_metering(false);
         let result = match txn.raw_txn.payload() {
             payload @ TransactionPayload::Script(_)
-            | payload @ TransactionPayload::EntryFunction(_) => {
+            | payload @ TransactionPayload::EntryFunction(_)
+            | payload @ TransactionPayload::EntryFunctionBatch(_) => {
                 self.execute_script_or_script_function(session, &mut gas_meter, &txn_data, payload)
             }
             TransactionPayload::Package(p) => {
@@ -1487,6 +1596,56 @@ impl StarcoinVM {
         )
     }
 
+    /// Execute a function bypassing its visibility with the gas meter of the latest gas schedule,
+    /// the state changes are discarded. The global write gas is charged for the accounts mutated
+    /// by the function as a transaction of `sender` does, it is used to measure the gas of the
+    /// Move unit tests, so the session has the natives and the extensions of the unit tests.
+    #[cfg(feature = "testing")]
+    pub fn execute_function_with_gas_usage<S: StateView>(
+        &self,
+        state_view: &S,
+        sender: &AccountAddress,
+        module: &ModuleId,
+        function_name: &IdentStr,
+        type_params: Vec<TypeTag>,
+        args: Vec<Vec<u8>>,
+    ) -> (Result<(), VMStatus>, FunctionGasUsage) {
+        let data_cache = state_view.as_move_resolver();
+        let max_gas_amount = G_LATEST_GAS_PARAMS.vm.txn.maximum_number_of_gas_units;
+        let mut gas_meter = StarcoinGasMeter::new(G_LATEST_GAS_PARAMS.clone(), max_gas_amount);
+        gas_meter.set_metering(true);
+
+        let traversal_storage = TraversalStorage::new();
+        let mut session = self.move_vm.new_unit_test_session(&data_cache);
+        let result = session
+            .execute_function_bypass_visibility(
+                module,
+                function_name,
+                type_params,
+                args,
+                &mut gas_meter,
+                &mut TraversalContext::new(&traversal_storage),
+            )
+            .map(|_| ())
+            .map_err(|e| e.into_vm_status());
+        let execution_left = gas_meter.balance();
+        let result =
+            result.and_then(|_| charge_global_write_gas_usage(&mut gas_meter, &session, sender));
+        let gas_usage = FunctionGasUsage {
+            execution: u64::from(
+                max_gas_amount
+                    .checked_sub(execution_left)
+                    .unwrap_or(max_gas_amount),
+            ),
+            storage: u64::from(
+                execution_left
+                    .checked_sub(gas_meter.balance())
+                    .unwrap_or(execution_left),
+            ),
+        };
+        (result, gas_usage)
+    }
+
     fn execute_readonly_function_internal<S: StateView>(
         &mut self,
         state_view: &S,
@@ -1607,9 +1766,11 @@ impl StarcoinVM {
                 err
             );
         }
-        let mut session = self
-            .move_vm
-            .new_session(storage, SessionId::txn_meta(txn_data));
+        let mut session = self.move_vm.new_session_with_user_context(
+            storage,
+            SessionId::txn_meta(txn_data),
+            txn_data.user_transaction_context(),
+        );
 
         // init_script doesn't need run epilogue
         if storage.is_genesis() {
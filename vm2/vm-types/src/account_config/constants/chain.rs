@@ -36,6 +36,12 @@ pub static G_TRANSACTION_VALIDATION_MODULE: Lazy<ModuleId> = Lazy::new(|| {
 
 pub static G_PROLOGUE_NAME: Lazy<Identifier> = Lazy::new(|| Identifier::new("prologue").unwrap());
 pub static G_EPILOGUE_NAME: Lazy<Identifier> = Lazy::new(|| Identifier::new("epilogue").unwrap());
+pub static G_FEE_PAYER_PROLOGUE_NAME: Lazy<Identifier> =
+    Lazy::new(|| Identifier::new("fee_payer_prologue").unwrap());
+pub static G_FEE_PAYER_EPILOGUE_NAME: Lazy<Identifier> =
+    Lazy::new(|| Identifier::new("fee_payer_epilogue").unwrap());
+pub static G_MULTI_AGENT_PROLOGUE_NAME: Lazy<Identifier> =
+    Lazy::new(|| Identifier::new("multi_agent_prologue").unwrap());
 // pub static G_EPILOGUE_V2_NAME: Lazy<Identifier> =
 //     Lazy::new(|| Identifier::new("epilogue_v2").unwrap());
 
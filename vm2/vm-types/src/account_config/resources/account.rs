@@ -57,6 +57,11 @@ impl AccountResource {
         &self.authentication_key
     }
 
+    /// Replace the authentication_key, for patching the state outside of the vm, e.g. in a forked dev chain
+    pub fn set_authentication_key(&mut self, authentication_key: Vec<u8>) {
+        self.authentication_key = authentication_key;
+    }
+
     pub fn coin_register_events(&self) -> &EventHandle {
         &self.coin_register_events
     }
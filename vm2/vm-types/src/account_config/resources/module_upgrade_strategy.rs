@@ -12,7 +12,7 @@ use serde::{Deserialize, Serialize};
 pub const _STRATEGY_ARBITRARY: u8 = 0;
 pub const STRATEGY_TWO_PHASE: u8 = 1;
 pub const STRATEGY_NEW_MODULE: u8 = 2;
-pub const _STRATEGY_FREEZE: u8 = 3;
+pub const STRATEGY_FREEZE: u8 = 3;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ModuleUpgradeStrategy {
@@ -27,6 +27,10 @@ impl ModuleUpgradeStrategy {
     pub fn two_phase(&self) -> bool {
         self.strategy == STRATEGY_TWO_PHASE
     }
+
+    pub fn freeze(&self) -> bool {
+        self.strategy == STRATEGY_FREEZE
+    }
 }
 
 impl MoveStructType for ModuleUpgradeStrategy {
@@ -114,6 +114,10 @@ impl Epoch {
         self.strategy
     }
 
+    pub fn set_strategy(&mut self, strategy: u8) {
+        self.strategy = strategy;
+    }
+
     pub fn max_transaction_per_block(&self) -> u64 {
         self.max_transaction_per_block
     }
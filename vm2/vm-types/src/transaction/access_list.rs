@@ -0,0 +1,35 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::state_store::state_key::StateKey;
+use crate::write_set::WriteSet;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// The state keys a transaction is predicted to write, e.g. by the write set of a dry run.
+/// Reads are not recorded, so it only tells write-write conflicts apart: transactions with
+/// disjoint access lists may still conflict when one reads a key the other writes.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AccessList {
+    writes: BTreeSet<StateKey>,
+}
+
+impl AccessList {
+    pub fn new(writes: impl IntoIterator<Item = StateKey>) -> Self {
+        Self {
+            writes: writes.into_iter().collect(),
+        }
+    }
+
+    pub fn from_write_set(write_set: &WriteSet) -> Self {
+        Self::new(write_set.iter().map(|(state_key, _)| state_key.clone()))
+    }
+
+    pub fn writes(&self) -> impl Iterator<Item = &StateKey> {
+        self.writes.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+}
@@ -3,7 +3,11 @@
 
 use crate::account_address::AccountAddress;
 use crate::sign_message::SigningMessage;
-use anyhow::{ensure, Error, Result};
+use crate::transaction::secp256::{
+    Secp256k1PrivateKey, Secp256k1PublicKey, Secp256k1Signature, Secp256r1PrivateKey,
+    Secp256r1PublicKey, WebAuthnAssertion, SECP256_PRIVATE_KEY_LENGTH, SECP256_PUBLIC_KEY_LENGTH,
+};
+use anyhow::{bail, ensure, Error, Result};
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
 use rand::RngExt;
@@ -12,6 +16,7 @@ use serde::{Deserialize, Serialize};
 use starcoin_crypto::ed25519::{
     Ed25519PrivateKey, ED25519_PRIVATE_KEY_LENGTH, ED25519_PUBLIC_KEY_LENGTH,
 };
+use starcoin_crypto::keygen::KeyGen;
 use starcoin_crypto::multi_ed25519::multi_shard::MultiEd25519KeyShard;
 use starcoin_crypto::{
     derive::{DeserializeKey, SerializeKey},
@@ -37,11 +42,13 @@ use std::{convert::TryFrom, fmt, str::FromStr};
 /// under the transaction's sender account address (2).
 
 // TODO: in the future, can tie these to the TransactionAuthenticator enum directly with https://github.com/rust-lang/rust/issues/60553
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[repr(u8)]
 pub enum Scheme {
     Ed25519 = 0,
     MultiEd25519 = 1,
+    Secp256k1Ecdsa = 2,
+    Secp256r1WebAuthn = 3,
     /// Scheme identifier used to derive addresses (not the authentication key) of objects and
     /// resources accounts. This application serves to domain separate hashes. Without such
     /// separation, an adversary could create (and get a signer for) a these accounts
@@ -56,6 +63,8 @@ impl fmt::Display for Scheme {
         let display = match self {
             Self::Ed25519 => "Ed25519",
             Self::MultiEd25519 => "MultiEd25519",
+            Self::Secp256k1Ecdsa => "Secp256k1Ecdsa",
+            Self::Secp256r1WebAuthn => "Secp256r1WebAuthn",
             Self::DeriveAuid => "DeriveAuid",
             Self::DeriveObjectAddressFromObject => "DeriveObjectAddressFromObject",
         };
@@ -63,6 +72,32 @@ impl fmt::Display for Scheme {
     }
 }
 
+impl FromStr for Scheme {
+    type Err = anyhow::Error;
+
+    /// Parse the scheme of an account key, e.g. `ed25519`, `secp256k1` or `secp256r1`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "ed25519" => Self::Ed25519,
+            "multi_ed25519" | "multied25519" => Self::MultiEd25519,
+            "secp256k1" | "secp256k1_ecdsa" | "secp256k1ecdsa" => Self::Secp256k1Ecdsa,
+            "secp256r1" | "secp256r1_webauthn" | "secp256r1webauthn" => Self::Secp256r1WebAuthn,
+            _ => bail!("unknown account key scheme: {}", s),
+        })
+    }
+}
+
+/// The length of a secp256 public key tagged by its scheme id in an `AccountPublicKey`.
+const TAGGED_SECP256_PUBLIC_KEY_LENGTH: usize = 1 + SECP256_PUBLIC_KEY_LENGTH;
+/// The length of a secp256 private key tagged by its scheme id in an `AccountPrivateKey`.
+const TAGGED_SECP256_PRIVATE_KEY_LENGTH: usize = 1 + SECP256_PRIVATE_KEY_LENGTH;
+
+fn tagged_bytes(scheme: Scheme, bytes: Vec<u8>) -> Vec<u8> {
+    let mut tagged = vec![scheme as u8];
+    tagged.extend(bytes);
+    tagged
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, JsonSchema)]
 pub enum TransactionAuthenticator {
     /// Single signature
@@ -80,6 +115,33 @@ pub enum TransactionAuthenticator {
         #[schemars(with = "String")]
         signature: MultiEd25519Signature,
     },
+    /// Signed by the sender and by a fee payer who pays the gas of the transaction
+    FeePayer {
+        sender: AccountAuthenticator,
+        #[schemars(with = "String")]
+        fee_payer_address: AccountAddress,
+        fee_payer_signer: AccountAuthenticator,
+    },
+    /// Signed by the sender and by secondary signers, which are all signers of the transaction
+    MultiAgent {
+        sender: AccountAuthenticator,
+        #[schemars(with = "Vec<String>")]
+        secondary_signer_addresses: Vec<AccountAddress>,
+        secondary_signers: Vec<AccountAuthenticator>,
+    },
+    /// Single secp256k1 ECDSA signature, e.g. signed by an Ethereum key
+    Secp256k1Ecdsa {
+        #[schemars(with = "String")]
+        public_key: Secp256k1PublicKey,
+        #[schemars(with = "String")]
+        signature: Secp256k1Signature,
+    },
+    /// Single secp256r1 signature of a WebAuthn assertion, e.g. signed by a passkey
+    Secp256r1WebAuthn {
+        #[schemars(with = "String")]
+        public_key: Secp256r1PublicKey,
+        assertion: WebAuthnAssertion,
+    },
     // ... add more schemes here
 }
 
@@ -89,6 +151,284 @@ impl TransactionAuthenticator {
         match self {
             Self::Ed25519 { .. } => Scheme::Ed25519,
             Self::MultiEd25519 { .. } => Scheme::MultiEd25519,
+            Self::FeePayer { sender, .. } | Self::MultiAgent { sender, .. } => sender.scheme(),
+            Self::Secp256k1Ecdsa { .. } => Scheme::Secp256k1Ecdsa,
+            Self::Secp256r1WebAuthn { .. } => Scheme::Secp256r1WebAuthn,
+        }
+    }
+
+    /// Create a single-signature ed25519 authenticator
+    pub fn ed25519(public_key: Ed25519PublicKey, signature: Ed25519Signature) -> Self {
+        Self::Ed25519 {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Create a multisignature ed25519 authenticator
+    pub fn multi_ed25519(
+        public_key: MultiEd25519PublicKey,
+        signature: MultiEd25519Signature,
+    ) -> Self {
+        Self::MultiEd25519 {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Create a single-signature secp256k1 ECDSA authenticator
+    pub fn secp256k1_ecdsa(public_key: Secp256k1PublicKey, signature: Secp256k1Signature) -> Self {
+        Self::Secp256k1Ecdsa {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Create a secp256r1 authenticator of a WebAuthn assertion
+    pub fn secp256r1_webauthn(
+        public_key: Secp256r1PublicKey,
+        assertion: WebAuthnAssertion,
+    ) -> Self {
+        Self::Secp256r1WebAuthn {
+            public_key,
+            assertion,
+        }
+    }
+
+    /// Create an authenticator of a transaction whose gas is paid by `fee_payer_address`
+    pub fn fee_payer(
+        sender: AccountAuthenticator,
+        fee_payer_address: AccountAddress,
+        fee_payer_signer: AccountAuthenticator,
+    ) -> Self {
+        Self::FeePayer {
+            sender,
+            fee_payer_address,
+            fee_payer_signer,
+        }
+    }
+
+    /// Create an authenticator of a transaction signed by the sender and `secondary_signers`
+    pub fn multi_agent(
+        sender: AccountAuthenticator,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        secondary_signers: Vec<AccountAuthenticator>,
+    ) -> Self {
+        Self::MultiAgent {
+            sender,
+            secondary_signer_addresses,
+            secondary_signers,
+        }
+    }
+
+    /// Return Ok if the authenticator's public key matches its signature, Err otherwise.
+    /// All the signers of a fee payer or multi-agent authenticator sign the same `message`.
+    pub fn verify<T: Serialize + CryptoHash>(&self, message: &T) -> Result<()> {
+        match self {
+            Self::Ed25519 {
+                public_key,
+                signature,
+            } => signature.verify(message, public_key),
+            Self::MultiEd25519 {
+                public_key,
+                signature,
+            } => signature.verify(message, public_key),
+            Self::FeePayer {
+                sender,
+                fee_payer_signer,
+                ..
+            } => {
+                sender.verify(message)?;
+                fee_payer_signer.verify(message)
+            }
+            Self::MultiAgent {
+                sender,
+                secondary_signer_addresses,
+                secondary_signers,
+            } => {
+                ensure!(
+                    secondary_signer_addresses.len() == secondary_signers.len(),
+                    "secondary signer addresses count {} mismatch secondary signers count {}",
+                    secondary_signer_addresses.len(),
+                    secondary_signers.len()
+                );
+                sender.verify(message)?;
+                secondary_signers
+                    .iter()
+                    .try_for_each(|signer| signer.verify(message))
+            }
+            Self::Secp256k1Ecdsa {
+                public_key,
+                signature,
+            } => signature.verify(message, public_key),
+            Self::Secp256r1WebAuthn {
+                public_key,
+                assertion,
+            } => assertion.verify(message, public_key),
+        }
+    }
+
+    /// The authenticator of the sender
+    pub fn sender(&self) -> AccountAuthenticator {
+        match self {
+            Self::Ed25519 {
+                public_key,
+                signature,
+            } => AccountAuthenticator::ed25519(public_key.clone(), signature.clone()),
+            Self::MultiEd25519 {
+                public_key,
+                signature,
+            } => AccountAuthenticator::multi_ed25519(public_key.clone(), signature.clone()),
+            Self::FeePayer { sender, .. } | Self::MultiAgent { sender, .. } => sender.clone(),
+            Self::Secp256k1Ecdsa {
+                public_key,
+                signature,
+            } => AccountAuthenticator::secp256k1_ecdsa(public_key.clone(), signature.clone()),
+            Self::Secp256r1WebAuthn {
+                public_key,
+                assertion,
+            } => AccountAuthenticator::secp256r1_webauthn(public_key.clone(), assertion.clone()),
+        }
+    }
+
+    /// The address of the account paying the gas if it is not the sender
+    pub fn fee_payer_address(&self) -> Option<AccountAddress> {
+        match self {
+            Self::FeePayer {
+                fee_payer_address, ..
+            } => Some(*fee_payer_address),
+            _ => None,
+        }
+    }
+
+    /// Return an authentication key preimage of the fee payer if it is not the sender
+    pub fn fee_payer_authentication_key_preimage(&self) -> Option<AuthenticationKeyPreimage> {
+        match self {
+            Self::FeePayer {
+                fee_payer_signer, ..
+            } => Some(fee_payer_signer.authentication_key_preimage()),
+            _ => None,
+        }
+    }
+
+    /// The addresses of the secondary signers, empty if it is not a multi-agent authenticator
+    pub fn secondary_signer_addresses(&self) -> Vec<AccountAddress> {
+        match self {
+            Self::MultiAgent {
+                secondary_signer_addresses,
+                ..
+            } => secondary_signer_addresses.clone(),
+            _ => vec![],
+        }
+    }
+
+    /// The authenticators of the secondary signers, empty if it is not a multi-agent authenticator
+    pub fn secondary_signers(&self) -> Vec<AccountAuthenticator> {
+        match self {
+            Self::MultiAgent {
+                secondary_signers, ..
+            } => secondary_signers.clone(),
+            _ => vec![],
+        }
+    }
+
+    /// Return the raw bytes of `self.public_key`, the sender's one for a fee payer or multi-agent
+    /// authenticator
+    pub fn public_key_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Ed25519 { public_key, .. } => public_key.to_bytes().to_vec(),
+            Self::MultiEd25519 { public_key, .. } => public_key.to_bytes().to_vec(),
+            Self::FeePayer { sender, .. } | Self::MultiAgent { sender, .. } => {
+                sender.public_key_bytes()
+            }
+            Self::Secp256k1Ecdsa { public_key, .. } => public_key.to_bytes(),
+            Self::Secp256r1WebAuthn { public_key, .. } => public_key.to_bytes(),
+        }
+    }
+
+    pub fn public_key(&self) -> AccountPublicKey {
+        match self {
+            Self::Ed25519 { public_key, .. } => AccountPublicKey::Single(public_key.clone()),
+            Self::MultiEd25519 { public_key, .. } => AccountPublicKey::Multi(public_key.clone()),
+            Self::FeePayer { sender, .. } | Self::MultiAgent { sender, .. } => sender.public_key(),
+            Self::Secp256k1Ecdsa { public_key, .. } => {
+                AccountPublicKey::Secp256k1(public_key.clone())
+            }
+            Self::Secp256r1WebAuthn { public_key, .. } => {
+                AccountPublicKey::Secp256r1(public_key.clone())
+            }
+        }
+    }
+
+    /// Return the raw bytes of `self.signature`, the sender's one for a fee payer or multi-agent
+    /// authenticator
+    pub fn signature_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Ed25519 { signature, .. } => signature.to_bytes().to_vec(),
+            Self::MultiEd25519 { signature, .. } => signature.to_bytes().to_vec(),
+            Self::FeePayer { sender, .. } | Self::MultiAgent { sender, .. } => {
+                sender.signature_bytes()
+            }
+            Self::Secp256k1Ecdsa { signature, .. } => signature.to_bytes(),
+            Self::Secp256r1WebAuthn { assertion, .. } => {
+                bcs_ext::to_bytes(assertion).expect("serialize webauthn assertion should succeed")
+            }
+        }
+    }
+
+    /// Return an authentication key preimage derived from `self`'s public key and scheme id
+    pub fn authentication_key_preimage(&self) -> AuthenticationKeyPreimage {
+        AuthenticationKeyPreimage::new(self.public_key_bytes(), self.scheme())
+    }
+
+    /// Return an authentication key derived from `self`'s public key and scheme id
+    pub fn authentication_key(&self) -> AuthenticationKey {
+        AuthenticationKey::from_preimage(&self.authentication_key_preimage())
+    }
+}
+
+/// An `AccountAuthenticator` authenticates one of the signers of a transaction signed by more
+/// than one account, e.g. the sender or the fee payer of a `TransactionAuthenticator::FeePayer`,
+/// or a secondary signer of a `TransactionAuthenticator::MultiAgent`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, JsonSchema)]
+pub enum AccountAuthenticator {
+    /// Single signature
+    Ed25519 {
+        #[schemars(with = "String")]
+        public_key: Ed25519PublicKey,
+        #[schemars(with = "String")]
+        signature: Ed25519Signature,
+    },
+    /// K-of-N multisignature
+    MultiEd25519 {
+        #[schemars(with = "String")]
+        public_key: MultiEd25519PublicKey,
+        #[schemars(with = "String")]
+        signature: MultiEd25519Signature,
+    },
+    /// Single secp256k1 ECDSA signature
+    Secp256k1Ecdsa {
+        #[schemars(with = "String")]
+        public_key: Secp256k1PublicKey,
+        #[schemars(with = "String")]
+        signature: Secp256k1Signature,
+    },
+    /// Single secp256r1 signature of a WebAuthn assertion
+    Secp256r1WebAuthn {
+        #[schemars(with = "String")]
+        public_key: Secp256r1PublicKey,
+        assertion: WebAuthnAssertion,
+    },
+}
+
+impl AccountAuthenticator {
+    /// Unique identifier for the signature scheme
+    pub fn scheme(&self) -> Scheme {
+        match self {
+            Self::Ed25519 { .. } => Scheme::Ed25519,
+            Self::MultiEd25519 { .. } => Scheme::MultiEd25519,
+            Self::Secp256k1Ecdsa { .. } => Scheme::Secp256k1Ecdsa,
+            Self::Secp256r1WebAuthn { .. } => Scheme::Secp256r1WebAuthn,
         }
     }
 
@@ -111,6 +451,25 @@ impl TransactionAuthenticator {
         }
     }
 
+    /// Create a single-signature secp256k1 ECDSA authenticator
+    pub fn secp256k1_ecdsa(public_key: Secp256k1PublicKey, signature: Secp256k1Signature) -> Self {
+        Self::Secp256k1Ecdsa {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Create a secp256r1 authenticator of a WebAuthn assertion
+    pub fn secp256r1_webauthn(
+        public_key: Secp256r1PublicKey,
+        assertion: WebAuthnAssertion,
+    ) -> Self {
+        Self::Secp256r1WebAuthn {
+            public_key,
+            assertion,
+        }
+    }
+
     /// Return Ok if the authenticator's public key matches its signature, Err otherwise
     pub fn verify<T: Serialize + CryptoHash>(&self, message: &T) -> Result<()> {
         match self {
@@ -122,6 +481,14 @@ impl TransactionAuthenticator {
                 public_key,
                 signature,
             } => signature.verify(message, public_key),
+            Self::Secp256k1Ecdsa {
+                public_key,
+                signature,
+            } => signature.verify(message, public_key),
+            Self::Secp256r1WebAuthn {
+                public_key,
+                assertion,
+            } => assertion.verify(message, public_key),
         }
     }
 
@@ -130,6 +497,8 @@ impl TransactionAuthenticator {
         match self {
             Self::Ed25519 { public_key, .. } => public_key.to_bytes().to_vec(),
             Self::MultiEd25519 { public_key, .. } => public_key.to_bytes().to_vec(),
+            Self::Secp256k1Ecdsa { public_key, .. } => public_key.to_bytes(),
+            Self::Secp256r1WebAuthn { public_key, .. } => public_key.to_bytes(),
         }
     }
 
@@ -137,6 +506,12 @@ impl TransactionAuthenticator {
         match self {
             Self::Ed25519 { public_key, .. } => AccountPublicKey::Single(public_key.clone()),
             Self::MultiEd25519 { public_key, .. } => AccountPublicKey::Multi(public_key.clone()),
+            Self::Secp256k1Ecdsa { public_key, .. } => {
+                AccountPublicKey::Secp256k1(public_key.clone())
+            }
+            Self::Secp256r1WebAuthn { public_key, .. } => {
+                AccountPublicKey::Secp256r1(public_key.clone())
+            }
         }
     }
 
@@ -145,6 +520,10 @@ impl TransactionAuthenticator {
         match self {
             Self::Ed25519 { signature, .. } => signature.to_bytes().to_vec(),
             Self::MultiEd25519 { signature, .. } => signature.to_bytes().to_vec(),
+            Self::Secp256k1Ecdsa { signature, .. } => signature.to_bytes(),
+            Self::Secp256r1WebAuthn { assertion, .. } => {
+                bcs_ext::to_bytes(assertion).expect("serialize webauthn assertion should succeed")
+            }
         }
     }
 
@@ -231,6 +610,16 @@ impl AuthenticationKey {
         Self::from_preimage(&AuthenticationKeyPreimage::multi_ed25519(public_key))
     }
 
+    /// Create an authentication key from a secp256k1 public key
+    pub fn secp256k1_ecdsa(public_key: &Secp256k1PublicKey) -> Self {
+        Self::from_preimage(&AuthenticationKeyPreimage::secp256k1_ecdsa(public_key))
+    }
+
+    /// Create an authentication key from a secp256r1 public key
+    pub fn secp256r1_webauthn(public_key: &Secp256r1PublicKey) -> Self {
+        Self::from_preimage(&AuthenticationKeyPreimage::secp256r1_webauthn(public_key))
+    }
+
     /// Return an address derived from the last `AccountAddress::LENGTH` bytes of this
     /// authentication key.
     pub fn derived_address(&self) -> AccountAddress {
@@ -301,6 +690,16 @@ impl AuthenticationKeyPreimage {
         Self::new(public_key.to_bytes(), Scheme::MultiEd25519)
     }
 
+    /// Construct a preimage from a compressed secp256k1 public key
+    pub fn secp256k1_ecdsa(public_key: &Secp256k1PublicKey) -> Self {
+        Self::new(public_key.to_bytes(), Scheme::Secp256k1Ecdsa)
+    }
+
+    /// Construct a preimage from a compressed secp256r1 public key
+    pub fn secp256r1_webauthn(public_key: &Secp256r1PublicKey) -> Self {
+        Self::new(public_key.to_bytes(), Scheme::Secp256r1WebAuthn)
+    }
+
     /// Construct a vector from this authentication key
     pub fn into_vec(self) -> Vec<u8> {
         self.0
@@ -377,19 +776,29 @@ pub enum AccountPublicKey {
     Single(Ed25519PublicKey),
     #[schemars(with = "String")]
     Multi(MultiEd25519PublicKey),
+    #[schemars(with = "String")]
+    Secp256k1(Secp256k1PublicKey),
+    #[schemars(with = "String")]
+    Secp256r1(Secp256r1PublicKey),
 }
 
 #[derive(Eq, PartialEq, Debug, DeserializeKey, SerializeKey)]
 pub enum AccountPrivateKey {
     Single(Ed25519PrivateKey),
     Multi(MultiEd25519KeyShard),
+    Secp256k1(Secp256k1PrivateKey),
+    Secp256r1(Secp256r1PrivateKey),
 }
 
+/// The secp256 keys are prefixed by their scheme id, so they can be told apart from the ed25519
+/// and multi-ed25519 keys by length.
 impl ValidCryptoMaterial for AccountPublicKey {
     fn to_bytes(&self) -> Vec<u8> {
         match self {
             Self::Single(key) => key.to_bytes().to_vec(),
             Self::Multi(key) => key.to_bytes(),
+            Self::Secp256k1(key) => tagged_bytes(Scheme::Secp256k1Ecdsa, key.to_bytes()),
+            Self::Secp256r1(key) => tagged_bytes(Scheme::Secp256r1WebAuthn, key.to_bytes()),
         }
     }
 }
@@ -406,6 +815,20 @@ impl AccountPublicKey {
         )?))
     }
 
+    /// Create a public key of `scheme` from its raw bytes without the scheme id prefix, the
+    /// secp256 keys can be either compressed or uncompressed SEC1 points.
+    pub fn from_scheme_bytes(scheme: Scheme, bytes: &[u8]) -> Result<Self> {
+        Ok(match scheme {
+            Scheme::Ed25519 => Self::Single(Ed25519PublicKey::try_from(bytes)?),
+            Scheme::MultiEd25519 => Self::Multi(MultiEd25519PublicKey::try_from(bytes)?),
+            Scheme::Secp256k1Ecdsa => Self::Secp256k1(Secp256k1PublicKey::from_sec1_bytes(bytes)?),
+            Scheme::Secp256r1WebAuthn => {
+                Self::Secp256r1(Secp256r1PublicKey::from_sec1_bytes(bytes)?)
+            }
+            _ => bail!("{} is not a scheme of account public key", scheme),
+        })
+    }
+
     pub fn derived_address(&self) -> AccountAddress {
         self.authentication_key().derived_address()
     }
@@ -414,6 +837,8 @@ impl AccountPublicKey {
         match self {
             Self::Single(p) => AuthenticationKeyPreimage::ed25519(p),
             Self::Multi(p) => AuthenticationKeyPreimage::multi_ed25519(p),
+            Self::Secp256k1(p) => AuthenticationKeyPreimage::secp256k1_ecdsa(p),
+            Self::Secp256r1(p) => AuthenticationKeyPreimage::secp256r1_webauthn(p),
         }
     }
 
@@ -427,6 +852,8 @@ impl AccountPublicKey {
         match self {
             Self::Single(public_key) => public_key.to_bytes().to_vec(),
             Self::Multi(public_key) => public_key.to_bytes().to_vec(),
+            Self::Secp256k1(public_key) => public_key.to_bytes(),
+            Self::Secp256r1(public_key) => public_key.to_bytes(),
         }
     }
 
@@ -435,6 +862,8 @@ impl AccountPublicKey {
         match self {
             Self::Single { .. } => Scheme::Ed25519,
             Self::Multi { .. } => Scheme::MultiEd25519,
+            Self::Secp256k1 { .. } => Scheme::Secp256k1Ecdsa,
+            Self::Secp256r1 { .. } => Scheme::Secp256r1WebAuthn,
         }
     }
 
@@ -457,10 +886,18 @@ impl TryFrom<&[u8]> for AccountPublicKey {
     type Error = CryptoMaterialError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
-        if value.len() == ED25519_PUBLIC_KEY_LENGTH {
-            Ed25519PublicKey::try_from(value).map(Self::Single)
-        } else {
-            MultiEd25519PublicKey::try_from(value).map(Self::Multi)
+        match value.len() {
+            ED25519_PUBLIC_KEY_LENGTH => Ed25519PublicKey::try_from(value).map(Self::Single),
+            TAGGED_SECP256_PUBLIC_KEY_LENGTH => match value[0] {
+                tag if tag == Scheme::Secp256k1Ecdsa as u8 => {
+                    Secp256k1PublicKey::try_from(&value[1..]).map(Self::Secp256k1)
+                }
+                tag if tag == Scheme::Secp256r1WebAuthn as u8 => {
+                    Secp256r1PublicKey::try_from(&value[1..]).map(Self::Secp256r1)
+                }
+                _ => Err(CryptoMaterialError::DeserializationError),
+            },
+            _ => MultiEd25519PublicKey::try_from(value).map(Self::Multi),
         }
     }
 }
@@ -484,15 +921,41 @@ impl ValidCryptoMaterial for AccountPrivateKey {
         match self {
             Self::Single(key) => key.to_bytes().to_vec(),
             Self::Multi(key) => key.to_bytes(),
+            Self::Secp256k1(key) => tagged_bytes(Scheme::Secp256k1Ecdsa, key.to_bytes()),
+            Self::Secp256r1(key) => tagged_bytes(Scheme::Secp256r1WebAuthn, key.to_bytes()),
         }
     }
 }
 
 impl AccountPrivateKey {
+    /// Generate a random private key of a single signer `scheme`.
+    pub fn generate(scheme: Scheme) -> Result<Self> {
+        Ok(match scheme {
+            Scheme::Ed25519 => Self::Single(KeyGen::from_os_rng().generate_keypair().0),
+            Scheme::Secp256k1Ecdsa => Self::Secp256k1(Secp256k1PrivateKey::generate()),
+            Scheme::Secp256r1WebAuthn => Self::Secp256r1(Secp256r1PrivateKey::generate()),
+            _ => bail!("can not generate private key of {}", scheme),
+        })
+    }
+
+    /// Create a private key of `scheme` from its raw bytes without the scheme id prefix, e.g. a
+    /// private key exported by an Ethereum wallet.
+    pub fn from_scheme_bytes(scheme: Scheme, bytes: &[u8]) -> Result<Self> {
+        Ok(match scheme {
+            Scheme::Ed25519 => Self::Single(Ed25519PrivateKey::try_from(bytes)?),
+            Scheme::MultiEd25519 => Self::Multi(MultiEd25519KeyShard::try_from(bytes)?),
+            Scheme::Secp256k1Ecdsa => Self::Secp256k1(Secp256k1PrivateKey::try_from(bytes)?),
+            Scheme::Secp256r1WebAuthn => Self::Secp256r1(Secp256r1PrivateKey::try_from(bytes)?),
+            _ => bail!("{} is not a scheme of account private key", scheme),
+        })
+    }
+
     pub fn public_key(&self) -> AccountPublicKey {
         match self {
             Self::Single(key) => AccountPublicKey::Single(key.public_key()),
             Self::Multi(key) => AccountPublicKey::Multi(key.public_key()),
+            Self::Secp256k1(key) => AccountPublicKey::Secp256k1(key.public_key()),
+            Self::Secp256r1(key) => AccountPublicKey::Secp256r1(key.public_key()),
         }
     }
 
@@ -504,12 +967,39 @@ impl AccountPrivateKey {
             Self::Multi(key) => {
                 TransactionAuthenticator::multi_ed25519(key.public_key(), key.sign(message).into())
             }
+            Self::Secp256k1(key) => {
+                TransactionAuthenticator::secp256k1_ecdsa(key.public_key(), key.sign(message))
+            }
+            Self::Secp256r1(key) => {
+                TransactionAuthenticator::secp256r1_webauthn(key.public_key(), key.sign(message))
+            }
         })
     }
 
     pub fn sign_message(&self, message: &SigningMessage) -> Result<TransactionAuthenticator> {
         self.sign(message)
     }
+
+    /// Sign `message` as one of the signers of a transaction signed by more than one account.
+    pub fn sign_account<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+    ) -> Result<AccountAuthenticator> {
+        Ok(match self {
+            Self::Single(key) => {
+                AccountAuthenticator::ed25519(key.public_key(), key.sign(message)?)
+            }
+            Self::Multi(key) => {
+                AccountAuthenticator::multi_ed25519(key.public_key(), key.sign(message).into())
+            }
+            Self::Secp256k1(key) => {
+                AccountAuthenticator::secp256k1_ecdsa(key.public_key(), key.sign(message))
+            }
+            Self::Secp256r1(key) => {
+                AccountAuthenticator::secp256r1_webauthn(key.public_key(), key.sign(message))
+            }
+        })
+    }
 }
 
 #[allow(clippy::from_over_into)]
@@ -530,19 +1020,32 @@ impl TryFrom<&[u8]> for AccountPrivateKey {
     type Error = CryptoMaterialError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
-        if value.len() == ED25519_PRIVATE_KEY_LENGTH {
-            Ed25519PrivateKey::try_from(value).map(Self::Single)
-        } else {
-            MultiEd25519KeyShard::try_from(value).map(Self::Multi)
+        match value.len() {
+            ED25519_PRIVATE_KEY_LENGTH => Ed25519PrivateKey::try_from(value).map(Self::Single),
+            TAGGED_SECP256_PRIVATE_KEY_LENGTH => match value[0] {
+                tag if tag == Scheme::Secp256k1Ecdsa as u8 => {
+                    Secp256k1PrivateKey::try_from(&value[1..]).map(Self::Secp256k1)
+                }
+                tag if tag == Scheme::Secp256r1WebAuthn as u8 => {
+                    Secp256r1PrivateKey::try_from(&value[1..]).map(Self::Secp256r1)
+                }
+                _ => Err(CryptoMaterialError::DeserializationError),
+            },
+            _ => MultiEd25519KeyShard::try_from(value).map(Self::Multi),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::transaction::authenticator::{AccountPublicKey, AuthenticationKey};
+    use crate::sign_message::SigningMessage;
+    use crate::transaction::authenticator::{
+        AccountPrivateKey, AccountPublicKey, AuthenticationKey, Scheme, TransactionAuthenticator,
+    };
     use starcoin_crypto::keygen::KeyGen;
     use starcoin_crypto::multi_ed25519::MultiEd25519PublicKey;
+    use starcoin_crypto::ValidCryptoMaterial;
+    use std::convert::TryFrom;
     use std::str::FromStr;
 
     #[test]
@@ -564,4 +1067,37 @@ mod tests {
         let auth_key2 = AuthenticationKey::multi_ed25519(&multi_pubkey);
         assert_eq!(auth_key, auth_key2);
     }
+
+    #[test]
+    fn test_secp256_authenticator() {
+        let message = SigningMessage::from_str("hello").unwrap();
+        let other_message = SigningMessage::from_str("hello world").unwrap();
+        for scheme in [Scheme::Secp256k1Ecdsa, Scheme::Secp256r1WebAuthn] {
+            let private_key = AccountPrivateKey::generate(scheme).unwrap();
+            let private_key_bytes = private_key.to_bytes();
+            assert_eq!(
+                AccountPrivateKey::try_from(private_key_bytes.as_slice()).unwrap(),
+                private_key
+            );
+            let public_key = private_key.public_key();
+            assert_eq!(public_key.scheme(), scheme);
+            assert_eq!(
+                AccountPublicKey::try_from(public_key.to_bytes().as_slice()).unwrap(),
+                public_key
+            );
+
+            let authenticator = private_key.sign(&message).unwrap();
+            assert_eq!(authenticator.public_key(), public_key);
+            assert_eq!(
+                authenticator.authentication_key(),
+                public_key.authentication_key()
+            );
+            authenticator.verify(&message).unwrap();
+            assert!(authenticator.verify(&other_message).is_err());
+
+            let bytes = bcs_ext::to_bytes(&authenticator).unwrap();
+            let decoded: TransactionAuthenticator = bcs_ext::from_bytes(&bytes).unwrap();
+            assert_eq!(decoded, authenticator);
+        }
+    }
 }
@@ -8,7 +8,9 @@ use crate::account_config::{genesis_address, STC_TOKEN_CODE_STR};
 use crate::block_metadata::BlockMetadata;
 use crate::on_chain_resource::ChainId;
 use crate::state_store::state_key::StateKey;
-use crate::transaction::authenticator::{AccountPublicKey, TransactionAuthenticator};
+use crate::transaction::authenticator::{
+    AccountAuthenticator, AccountPublicKey, TransactionAuthenticator,
+};
 use crate::write_set::WriteOp;
 use crate::{
     account_address::AccountAddress,
@@ -17,6 +19,7 @@ use crate::{
     vm_status::{StatusCode, VMStatus},
     write_set::WriteSet,
 };
+pub use access_list::AccessList;
 use anyhow::{format_err, Error, Result};
 use bcs_ext::Sample;
 pub use change_set::ChangeSet;
@@ -48,6 +51,7 @@ pub use transaction_argument::{
     parse_transaction_argument, parse_transaction_arguments, TransactionArgument,
 };
 
+mod access_list;
 pub mod authenticator;
 mod error;
 pub mod helpers;
@@ -55,6 +59,7 @@ mod module;
 mod package;
 mod pending_transaction;
 mod script;
+pub mod secp256;
 #[cfg(test)]
 mod tests;
 mod transaction_argument;
@@ -377,6 +382,39 @@ impl Sample for RawUserTransaction {
     }
 }
 
+/// The message signed by every signer of a transaction signed by more than one account, it binds
+/// the raw transaction to the addresses of the other signers.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize, CryptoHasher, CryptoHash)]
+pub enum RawUserTransactionWithData {
+    FeePayer {
+        raw_txn: RawUserTransaction,
+        fee_payer_address: AccountAddress,
+    },
+    MultiAgent {
+        raw_txn: RawUserTransaction,
+        secondary_signer_addresses: Vec<AccountAddress>,
+    },
+}
+
+impl RawUserTransactionWithData {
+    pub fn new_fee_payer(raw_txn: RawUserTransaction, fee_payer_address: AccountAddress) -> Self {
+        Self::FeePayer {
+            raw_txn,
+            fee_payer_address,
+        }
+    }
+
+    pub fn new_multi_agent(
+        raw_txn: RawUserTransaction,
+        secondary_signer_addresses: Vec<AccountAddress>,
+    ) -> Self {
+        Self::MultiAgent {
+            raw_txn,
+            secondary_signer_addresses,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum TransactionPayload {
     /// A transaction that executes code.
@@ -385,6 +423,9 @@ pub enum TransactionPayload {
     Package(Package),
     /// A transaction that executes an existing entry function published on-chain.
     EntryFunction(EntryFunction),
+    /// A transaction that executes entry functions one by one in the same session, it fails as a
+    /// whole if any of them fails.
+    EntryFunctionBatch(Vec<EntryFunction>),
 }
 
 #[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
@@ -400,7 +441,9 @@ impl TransactionPayload {
         match self {
             Self::Script(_) => TransactionPayloadType::Script,
             Self::Package(_) => TransactionPayloadType::Package,
-            Self::EntryFunction(_) => TransactionPayloadType::ScriptFunction,
+            Self::EntryFunction(_) | Self::EntryFunctionBatch(_) => {
+                TransactionPayloadType::ScriptFunction
+            }
         }
     }
 }
@@ -450,6 +493,10 @@ pub struct DryRunTransaction {
     /// The raw transaction
     pub raw_txn: RawUserTransaction,
     pub public_key: AccountPublicKey,
+    /// The address and public key of the account paying the gas instead of the sender
+    pub fee_payer: Option<(AccountAddress, AccountPublicKey)>,
+    /// The addresses and public keys of the secondary signers of a multi-agent transaction
+    pub secondary_signers: Vec<(AccountAddress, AccountPublicKey)>,
 }
 
 /// A transaction for which the signature has been verified. Created by
@@ -520,10 +567,51 @@ impl SignedUserTransaction {
         Self::new(raw_txn, authenticator)
     }
 
+    /// Create a transaction whose gas is paid by `fee_payer_address`, both `sender` and
+    /// `fee_payer_signer` sign the `RawUserTransactionWithData::FeePayer` of `raw_txn`.
+    pub fn fee_payer(
+        raw_txn: RawUserTransaction,
+        sender: AccountAuthenticator,
+        fee_payer_address: AccountAddress,
+        fee_payer_signer: AccountAuthenticator,
+    ) -> Self {
+        let authenticator =
+            TransactionAuthenticator::fee_payer(sender, fee_payer_address, fee_payer_signer);
+        Self::new(raw_txn, authenticator)
+    }
+
+    /// Create a transaction signed by `sender` and `secondary_signers`, all of them sign the
+    /// `RawUserTransactionWithData::MultiAgent` of `raw_txn`.
+    pub fn multi_agent(
+        raw_txn: RawUserTransaction,
+        sender: AccountAuthenticator,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        secondary_signers: Vec<AccountAuthenticator>,
+    ) -> Self {
+        let authenticator = TransactionAuthenticator::multi_agent(
+            sender,
+            secondary_signer_addresses,
+            secondary_signers,
+        );
+        Self::new(raw_txn, authenticator)
+    }
+
     pub fn authenticator(&self) -> TransactionAuthenticator {
         self.authenticator.clone()
     }
 
+    /// The address of the account paying the gas, the sender if there is no fee payer.
+    pub fn gas_payer(&self) -> AccountAddress {
+        self.authenticator
+            .fee_payer_address()
+            .unwrap_or_else(|| self.sender())
+    }
+
+    /// The addresses of the secondary signers, empty if it is not a multi-agent transaction.
+    pub fn secondary_signers(&self) -> Vec<AccountAddress> {
+        self.authenticator.secondary_signer_addresses()
+    }
+
     pub fn raw_txn(&self) -> &RawUserTransaction {
         &self.raw_txn
     }
@@ -571,7 +659,24 @@ impl SignedUserTransaction {
     /// Checks that the signature of given transaction. Returns `Ok(SignatureCheckedTransaction)` if
     /// the signature is valid.
     pub fn check_signature(self) -> Result<SignatureCheckedTransaction> {
-        self.authenticator.verify(&self.raw_txn)?;
+        match &self.authenticator {
+            TransactionAuthenticator::FeePayer {
+                fee_payer_address, ..
+            } => self.authenticator.verify(&RawUserTransactionWithData::new_fee_payer(
+                self.raw_txn.clone(),
+                *fee_payer_address,
+            ))?,
+            TransactionAuthenticator::MultiAgent {
+                secondary_signer_addresses,
+                ..
+            } => self
+                .authenticator
+                .verify(&RawUserTransactionWithData::new_multi_agent(
+                    self.raw_txn.clone(),
+                    secondary_signer_addresses.clone(),
+                ))?,
+            _ => self.authenticator.verify(&self.raw_txn)?,
+        }
         Ok(SignatureCheckedTransaction(self))
     }
 
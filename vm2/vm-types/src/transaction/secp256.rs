@@ -0,0 +1,416 @@
+// Copyright (c) The Starcoin Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Keys and signatures of the secp256k1 ECDSA and secp256r1 WebAuthn account schemes.
+//! Public keys are kept as 33 bytes compressed SEC1 points, signatures as 64 bytes `r | s`.
+//! Only signatures with a low `s` are accepted, so a signed transaction can not be malleated
+//! into another one with a different hash. Signatures are not normalized on decode, clients
+//! must normalize them before signing the transaction: passkeys return a high `s` about half of
+//! the time, `Secp256r1Signature::from_der` converts their DER signatures to the accepted form.
+
+use anyhow::{ensure, format_err, Result};
+use base64::Engine;
+use p256::ecdsa::signature::{Signer, Verifier};
+use rand::RngExt;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use starcoin_crypto::{
+    derive::{DeserializeKey, SerializeKey},
+    hash::CryptoHash,
+    CryptoMaterialError, ValidCryptoMaterial,
+};
+use std::{convert::TryFrom, fmt};
+
+/// The length of a compressed secp256k1 or secp256r1 public key.
+pub const SECP256_PUBLIC_KEY_LENGTH: usize = 33;
+/// The length of a secp256k1 or secp256r1 signature.
+pub const SECP256_SIGNATURE_LENGTH: usize = 64;
+/// The length of a secp256k1 or secp256r1 private key.
+pub const SECP256_PRIVATE_KEY_LENGTH: usize = 32;
+
+/// The `type` of the client data of a WebAuthn assertion.
+const WEBAUTHN_GET_TYPE: &str = "webauthn.get";
+/// The user present flag in the authenticator data of a WebAuthn assertion.
+const WEBAUTHN_USER_PRESENT_FLAG: u8 = 0x01;
+/// rpIdHash (32 bytes) | flags (1 byte) | signCount (4 bytes)
+const WEBAUTHN_AUTHENTICATOR_DATA_MIN_LENGTH: usize = 37;
+/// The relying party used by the assertions created by a local secp256r1 key.
+const LOCAL_RELYING_PARTY: &str = "starcoin";
+
+fn random_private_key_bytes() -> [u8; SECP256_PRIVATE_KEY_LENGTH] {
+    let mut rng = rand::rng();
+    let mut bytes = [0u8; SECP256_PRIVATE_KEY_LENGTH];
+    rng.fill(&mut bytes);
+    bytes
+}
+
+fn to_array<const N: usize>(bytes: &[u8]) -> Result<[u8; N], CryptoMaterialError> {
+    <[u8; N]>::try_from(bytes).map_err(|_| CryptoMaterialError::WrongLengthError)
+}
+
+/// A compressed secp256k1 public key, e.g. the public key of an Ethereum account.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, DeserializeKey, SerializeKey)]
+pub struct Secp256k1PublicKey([u8; SECP256_PUBLIC_KEY_LENGTH]);
+
+impl Secp256k1PublicKey {
+    /// Create a public key from a compressed or uncompressed SEC1 point.
+    pub fn from_sec1_bytes(bytes: &[u8]) -> Result<Self, CryptoMaterialError> {
+        let key = libsecp256k1::PublicKey::parse_slice(bytes, None)
+            .map_err(|_| CryptoMaterialError::DeserializationError)?;
+        Ok(Self(key.serialize_compressed()))
+    }
+
+    fn to_libsecp256k1(&self) -> libsecp256k1::PublicKey {
+        libsecp256k1::PublicKey::parse_compressed(&self.0)
+            .expect("secp256k1 public key is validated on creation")
+    }
+}
+
+impl ValidCryptoMaterial for Secp256k1PublicKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256k1PublicKey {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let bytes = to_array(bytes)?;
+        libsecp256k1::PublicKey::parse_compressed(&bytes)
+            .map_err(|_| CryptoMaterialError::DeserializationError)?;
+        Ok(Self(bytes))
+    }
+}
+
+/// A secp256k1 ECDSA signature over the hash of the signed message.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, DeserializeKey, SerializeKey)]
+pub struct Secp256k1Signature([u8; SECP256_SIGNATURE_LENGTH]);
+
+impl Secp256k1Signature {
+    /// Return Ok if the signature of `message` is signed by `public_key`, Err otherwise.
+    pub fn verify<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+        public_key: &Secp256k1PublicKey,
+    ) -> Result<()> {
+        let message = libsecp256k1::Message::parse(message.hash().as_ref());
+        let signature = libsecp256k1::Signature::parse_standard(&self.0)
+            .map_err(|e| format_err!("invalid secp256k1 signature: {:?}", e))?;
+        ensure!(
+            libsecp256k1::verify(&message, &signature, &public_key.to_libsecp256k1()),
+            "secp256k1 signature verification failed"
+        );
+        Ok(())
+    }
+}
+
+impl ValidCryptoMaterial for Secp256k1Signature {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256k1Signature {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let bytes = to_array(bytes)?;
+        let signature = libsecp256k1::Signature::parse_standard(&bytes)
+            .map_err(|_| CryptoMaterialError::DeserializationError)?;
+        if signature.s.is_high() {
+            return Err(CryptoMaterialError::CanonicalRepresentationError);
+        }
+        Ok(Self(bytes))
+    }
+}
+
+/// A secp256k1 private key, e.g. the private key of an Ethereum account.
+#[derive(Eq, PartialEq, DeserializeKey, SerializeKey)]
+pub struct Secp256k1PrivateKey([u8; SECP256_PRIVATE_KEY_LENGTH]);
+
+impl Secp256k1PrivateKey {
+    /// Generate a random private key.
+    pub fn generate() -> Self {
+        loop {
+            if let Ok(key) = Self::try_from(random_private_key_bytes().as_slice()) {
+                return key;
+            }
+        }
+    }
+
+    fn to_libsecp256k1(&self) -> libsecp256k1::SecretKey {
+        libsecp256k1::SecretKey::parse(&self.0)
+            .expect("secp256k1 private key is validated on creation")
+    }
+
+    pub fn public_key(&self) -> Secp256k1PublicKey {
+        Secp256k1PublicKey(
+            libsecp256k1::PublicKey::from_secret_key(&self.to_libsecp256k1())
+                .serialize_compressed(),
+        )
+    }
+
+    /// Sign the hash of `message`, the signature always has a low `s`.
+    pub fn sign<T: CryptoHash + Serialize>(&self, message: &T) -> Secp256k1Signature {
+        let message = libsecp256k1::Message::parse(message.hash().as_ref());
+        let (signature, _) = libsecp256k1::sign(&message, &self.to_libsecp256k1());
+        Secp256k1Signature(signature.serialize())
+    }
+}
+
+impl ValidCryptoMaterial for Secp256k1PrivateKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256k1PrivateKey {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let bytes = to_array(bytes)?;
+        libsecp256k1::SecretKey::parse(&bytes)
+            .map_err(|_| CryptoMaterialError::DeserializationError)?;
+        Ok(Self(bytes))
+    }
+}
+
+impl fmt::Debug for Secp256k1PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secp256k1PrivateKey(<elided secret>)")
+    }
+}
+
+/// A compressed secp256r1 public key, e.g. the public key of a passkey.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, DeserializeKey, SerializeKey)]
+pub struct Secp256r1PublicKey([u8; SECP256_PUBLIC_KEY_LENGTH]);
+
+impl Secp256r1PublicKey {
+    /// Create a public key from a compressed or uncompressed SEC1 point, WebAuthn
+    /// credentials usually expose the uncompressed one.
+    pub fn from_sec1_bytes(bytes: &[u8]) -> Result<Self, CryptoMaterialError> {
+        let key = p256::ecdsa::VerifyingKey::from_sec1_bytes(bytes)
+            .map_err(|_| CryptoMaterialError::DeserializationError)?;
+        Self::try_from(key.to_encoded_point(true).as_bytes())
+    }
+
+    fn to_verifying_key(&self) -> p256::ecdsa::VerifyingKey {
+        p256::ecdsa::VerifyingKey::from_sec1_bytes(&self.0)
+            .expect("secp256r1 public key is validated on creation")
+    }
+}
+
+impl ValidCryptoMaterial for Secp256r1PublicKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256r1PublicKey {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let bytes = to_array(bytes)?;
+        p256::ecdsa::VerifyingKey::from_sec1_bytes(&bytes)
+            .map_err(|_| CryptoMaterialError::DeserializationError)?;
+        Ok(Self(bytes))
+    }
+}
+
+/// A secp256r1 ECDSA signature.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, DeserializeKey, SerializeKey)]
+pub struct Secp256r1Signature([u8; SECP256_SIGNATURE_LENGTH]);
+
+impl Secp256r1Signature {
+    /// Create a signature from the ASN.1 DER signature returned by a passkey, a high `s` is
+    /// normalized to the low one.
+    pub fn from_der(bytes: &[u8]) -> Result<Self, CryptoMaterialError> {
+        let signature = p256::ecdsa::Signature::from_der(bytes)
+            .map_err(|_| CryptoMaterialError::DeserializationError)?;
+        let signature = signature.normalize_s().unwrap_or(signature);
+        Ok(Self(to_array(&signature.to_bytes())?))
+    }
+
+    fn to_p256(&self) -> p256::ecdsa::Signature {
+        p256::ecdsa::Signature::from_slice(&self.0)
+            .expect("secp256r1 signature is validated on creation")
+    }
+}
+
+impl ValidCryptoMaterial for Secp256r1Signature {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+}
+
+/// Decode a 64 bytes `r | s` signature, a signature with a high `s` is rejected rather than
+/// normalized.
+impl TryFrom<&[u8]> for Secp256r1Signature {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let bytes = to_array(bytes)?;
+        let signature = p256::ecdsa::Signature::from_slice(&bytes)
+            .map_err(|_| CryptoMaterialError::DeserializationError)?;
+        if signature.normalize_s().is_some() {
+            return Err(CryptoMaterialError::CanonicalRepresentationError);
+        }
+        Ok(Self(bytes))
+    }
+}
+
+#[derive(Deserialize)]
+struct CollectedClientData {
+    #[serde(rename = "type")]
+    ty: String,
+    challenge: String,
+}
+
+/// A WebAuthn assertion created by a passkey. The challenge of the assertion is the hash of the
+/// signed message, and the signature is over `authenticator_data | sha256(client_data_json)`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, JsonSchema)]
+pub struct WebAuthnAssertion {
+    pub authenticator_data: Vec<u8>,
+    pub client_data_json: Vec<u8>,
+    #[schemars(with = "String")]
+    pub signature: Secp256r1Signature,
+}
+
+impl WebAuthnAssertion {
+    pub fn new(
+        authenticator_data: Vec<u8>,
+        client_data_json: Vec<u8>,
+        signature: Secp256r1Signature,
+    ) -> Self {
+        Self {
+            authenticator_data,
+            client_data_json,
+            signature,
+        }
+    }
+
+    /// The challenge a passkey should sign to authenticate `message`, base64url encoded.
+    pub fn challenge<T: CryptoHash + Serialize>(message: &T) -> String {
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(message.hash().to_vec())
+    }
+
+    fn signed_bytes(&self) -> Vec<u8> {
+        let mut signed_bytes = self.authenticator_data.clone();
+        signed_bytes.extend(Sha256::digest(&self.client_data_json));
+        signed_bytes
+    }
+
+    /// Return Ok if the assertion is a challenge of `message` signed by `public_key`, Err otherwise.
+    pub fn verify<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+        public_key: &Secp256r1PublicKey,
+    ) -> Result<()> {
+        ensure!(
+            self.authenticator_data.len() >= WEBAUTHN_AUTHENTICATOR_DATA_MIN_LENGTH,
+            "invalid webauthn authenticator data length {}",
+            self.authenticator_data.len()
+        );
+        ensure!(
+            self.authenticator_data[32] & WEBAUTHN_USER_PRESENT_FLAG != 0,
+            "webauthn assertion is not created with the user present"
+        );
+        let client_data: CollectedClientData = serde_json::from_slice(&self.client_data_json)?;
+        ensure!(
+            client_data.ty == WEBAUTHN_GET_TYPE,
+            "invalid webauthn client data type {}",
+            client_data.ty
+        );
+        ensure!(
+            client_data.challenge == Self::challenge(message),
+            "webauthn challenge mismatch the signed message"
+        );
+        public_key
+            .to_verifying_key()
+            .verify(&self.signed_bytes(), &self.signature.to_p256())
+            .map_err(|e| format_err!("secp256r1 signature verification failed: {}", e))
+    }
+}
+
+/// A secp256r1 private key held by the wallet, it creates the same assertions as a passkey.
+#[derive(Eq, PartialEq, DeserializeKey, SerializeKey)]
+pub struct Secp256r1PrivateKey([u8; SECP256_PRIVATE_KEY_LENGTH]);
+
+impl Secp256r1PrivateKey {
+    /// Generate a random private key.
+    pub fn generate() -> Self {
+        loop {
+            if let Ok(key) = Self::try_from(random_private_key_bytes().as_slice()) {
+                return key;
+            }
+        }
+    }
+
+    fn to_signing_key(&self) -> p256::ecdsa::SigningKey {
+        p256::ecdsa::SigningKey::from_slice(&self.0)
+            .expect("secp256r1 private key is validated on creation")
+    }
+
+    pub fn public_key(&self) -> Secp256r1PublicKey {
+        Secp256r1PublicKey(
+            to_array(
+                self.to_signing_key()
+                    .verifying_key()
+                    .to_encoded_point(true)
+                    .as_bytes(),
+            )
+            .expect("compressed secp256r1 public key has a fixed length"),
+        )
+    }
+
+    /// Create a WebAuthn assertion of `message` for the local relying party.
+    pub fn sign<T: CryptoHash + Serialize>(&self, message: &T) -> WebAuthnAssertion {
+        let mut authenticator_data = Sha256::digest(LOCAL_RELYING_PARTY.as_bytes()).to_vec();
+        authenticator_data.push(WEBAUTHN_USER_PRESENT_FLAG);
+        authenticator_data.extend(0u32.to_be_bytes());
+        let client_data_json = serde_json::json!({
+            "type": WEBAUTHN_GET_TYPE,
+            "challenge": WebAuthnAssertion::challenge(message),
+            "origin": LOCAL_RELYING_PARTY,
+        })
+        .to_string()
+        .into_bytes();
+        let mut assertion = WebAuthnAssertion {
+            authenticator_data,
+            client_data_json,
+            signature: Secp256r1Signature([0u8; SECP256_SIGNATURE_LENGTH]),
+        };
+        let signature: p256::ecdsa::Signature =
+            self.to_signing_key().sign(&assertion.signed_bytes());
+        let signature = signature.normalize_s().unwrap_or(signature);
+        assertion.signature = Secp256r1Signature(
+            to_array(&signature.to_bytes()).expect("secp256r1 signature has a fixed length"),
+        );
+        assertion
+    }
+}
+
+impl ValidCryptoMaterial for Secp256r1PrivateKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256r1PrivateKey {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let bytes = to_array(bytes)?;
+        p256::ecdsa::SigningKey::from_slice(&bytes)
+            .map_err(|_| CryptoMaterialError::DeserializationError)?;
+        Ok(Self(bytes))
+    }
+}
+
+impl fmt::Debug for Secp256r1PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secp256r1PrivateKey(<elided secret>)")
+    }
+}
@@ -2,11 +2,20 @@ use crate::account_address::AccountAddress;
 use crate::account_config::genesis_address;
 use crate::block_metadata::BlockMetadata;
 use crate::on_chain_resource::ChainId;
-use crate::transaction::{Script, Transaction};
+use crate::transaction::authenticator::AccountPrivateKey;
+use crate::transaction::secp256::{Secp256r1PrivateKey, Secp256r1Signature};
+use crate::transaction::{
+    EntryFunction, RawUserTransaction, RawUserTransactionWithData, Script, SignedUserTransaction,
+    Transaction, TransactionPayload,
+};
 use crate::transaction_argument::convert_txn_args;
+use crate::transaction_metadata::TransactionMetadata;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::ModuleId;
 use move_core_types::transaction_argument::TransactionArgument;
 use move_core_types::u256;
-use starcoin_crypto::HashValue;
+use starcoin_crypto::keygen::KeyGen;
+use starcoin_crypto::{CryptoMaterialError, HashValue, ValidCryptoMaterial};
 
 #[test]
 fn test_transaction_argument_to_json() {
@@ -52,3 +61,183 @@ fn block_epilogue_id_depends_on_total_fee() {
 
     assert_ne!(epilogue_one.id(), epilogue_two.id());
 }
+
+#[test]
+fn fee_payer_txn_is_signed_with_the_fee_payer_address() {
+    let mut key_gen = KeyGen::from_os_rng();
+    let sender_key: AccountPrivateKey = key_gen.generate_keypair().0.into();
+    let fee_payer_key: AccountPrivateKey = key_gen.generate_keypair().0.into();
+    let fee_payer_address = fee_payer_key.public_key().derived_address();
+    let raw_txn = RawUserTransaction::mock_by_sender(sender_key.public_key().derived_address());
+
+    let message = RawUserTransactionWithData::new_fee_payer(raw_txn.clone(), fee_payer_address);
+    let txn = SignedUserTransaction::fee_payer(
+        raw_txn.clone(),
+        sender_key.sign_account(&message).unwrap(),
+        fee_payer_address,
+        fee_payer_key.sign_account(&message).unwrap(),
+    );
+    assert_eq!(txn.gas_payer(), fee_payer_address);
+    assert_eq!(
+        txn.authenticator().authentication_key_preimage(),
+        sender_key.public_key().authentication_key_preimage()
+    );
+    let decoded: SignedUserTransaction = bcs_ext::from_bytes(&bcs_ext::to_bytes(&txn).unwrap())
+        .expect("decode fee payer txn should success.");
+    assert_eq!(decoded.id(), txn.id());
+    assert!(txn.check_signature().is_ok());
+
+    // The signatures do not authorize another fee payer.
+    let other_fee_payer = AccountAddress::random();
+    let txn = SignedUserTransaction::fee_payer(
+        raw_txn.clone(),
+        sender_key.sign_account(&message).unwrap(),
+        other_fee_payer,
+        fee_payer_key.sign_account(&message).unwrap(),
+    );
+    assert!(txn.check_signature().is_err());
+
+    // Signing the raw txn alone is not enough.
+    let txn = SignedUserTransaction::fee_payer(
+        raw_txn.clone(),
+        sender_key.sign_account(&raw_txn).unwrap(),
+        fee_payer_address,
+        fee_payer_key.sign_account(&message).unwrap(),
+    );
+    assert!(txn.check_signature().is_err());
+}
+
+#[test]
+fn multi_agent_txn_is_signed_by_every_signer() {
+    let mut key_gen = KeyGen::from_os_rng();
+    let sender_key: AccountPrivateKey = key_gen.generate_keypair().0.into();
+    let secondary_key: AccountPrivateKey = key_gen.generate_keypair().0.into();
+    let secondary_address = secondary_key.public_key().derived_address();
+    let raw_txn = RawUserTransaction::mock_by_sender(sender_key.public_key().derived_address());
+
+    let message =
+        RawUserTransactionWithData::new_multi_agent(raw_txn.clone(), vec![secondary_address]);
+    let txn = SignedUserTransaction::multi_agent(
+        raw_txn.clone(),
+        sender_key.sign_account(&message).unwrap(),
+        vec![secondary_address],
+        vec![secondary_key.sign_account(&message).unwrap()],
+    );
+    assert_eq!(txn.secondary_signers(), vec![secondary_address]);
+    assert_eq!(txn.gas_payer(), txn.sender());
+    let decoded: SignedUserTransaction = bcs_ext::from_bytes(&bcs_ext::to_bytes(&txn).unwrap())
+        .expect("decode multi-agent txn should success.");
+    assert_eq!(decoded.id(), txn.id());
+    assert!(txn.check_signature().is_ok());
+
+    // Every secondary signer address must have a signature.
+    let txn = SignedUserTransaction::multi_agent(
+        raw_txn.clone(),
+        sender_key.sign_account(&message).unwrap(),
+        vec![secondary_address],
+        vec![],
+    );
+    assert!(txn.check_signature().is_err());
+
+    // The signatures do not authorize other secondary signers.
+    let txn = SignedUserTransaction::multi_agent(
+        raw_txn,
+        sender_key.sign_account(&message).unwrap(),
+        vec![AccountAddress::random()],
+        vec![secondary_key.sign_account(&message).unwrap()],
+    );
+    assert!(txn.check_signature().is_err());
+}
+
+#[test]
+fn only_multi_agent_and_fee_payer_txns_have_user_transaction_context() {
+    let mut key_gen = KeyGen::from_os_rng();
+    let sender_key: AccountPrivateKey = key_gen.generate_keypair().0.into();
+    let secondary_key: AccountPrivateKey = key_gen.generate_keypair().0.into();
+    let secondary_address = secondary_key.public_key().derived_address();
+    let fee_payer_key: AccountPrivateKey = key_gen.generate_keypair().0.into();
+    let fee_payer_address = fee_payer_key.public_key().derived_address();
+    let raw_txn = RawUserTransaction::new_with_default_gas_token(
+        sender_key.public_key().derived_address(),
+        0,
+        TransactionPayload::EntryFunction(EntryFunction::new(
+            ModuleId::new(genesis_address(), Identifier::new("m").unwrap()),
+            Identifier::new("f").unwrap(),
+            vec![],
+            vec![vec![1]],
+        )),
+        0,
+        0,
+        u64::MAX,
+        ChainId::new(254),
+    );
+
+    let txn = SignedUserTransaction::new(raw_txn.clone(), sender_key.sign(&raw_txn).unwrap());
+    let txn_data = TransactionMetadata::new(&txn).unwrap();
+    assert!(txn_data.user_transaction_context().is_none());
+
+    let message = RawUserTransactionWithData::new_fee_payer(raw_txn.clone(), fee_payer_address);
+    let txn = SignedUserTransaction::fee_payer(
+        raw_txn.clone(),
+        sender_key.sign_account(&message).unwrap(),
+        fee_payer_address,
+        fee_payer_key.sign_account(&message).unwrap(),
+    );
+    let context = TransactionMetadata::new(&txn)
+        .unwrap()
+        .user_transaction_context()
+        .expect("fee payer txn should have a user transaction context");
+    assert_eq!(context.sender(), txn.sender());
+    assert_eq!(context.gas_payer(), fee_payer_address);
+    assert!(context.secondary_signers().is_empty());
+    assert_eq!(
+        context.entry_function_payload().map(|payload| payload.args),
+        Some(vec![vec![1]])
+    );
+
+    let message =
+        RawUserTransactionWithData::new_multi_agent(raw_txn.clone(), vec![secondary_address]);
+    let txn = SignedUserTransaction::multi_agent(
+        raw_txn,
+        sender_key.sign_account(&message).unwrap(),
+        vec![secondary_address],
+        vec![secondary_key.sign_account(&message).unwrap()],
+    );
+    let context = TransactionMetadata::new(&txn)
+        .unwrap()
+        .user_transaction_context()
+        .expect("multi-agent txn should have a user transaction context");
+    assert_eq!(context.chain_id(), 254);
+    assert_eq!(context.secondary_signers(), vec![secondary_address]);
+    assert_eq!(
+        context.entry_function_payload().map(|payload| payload.args),
+        Some(vec![vec![1]])
+    );
+}
+
+#[test]
+fn secp256r1_signature_with_high_s_must_be_normalized() {
+    let private_key = Secp256r1PrivateKey::generate();
+    let message = RawUserTransaction::mock_by_sender(AccountAddress::random());
+    let mut assertion = private_key.sign(&message);
+    let low_s = p256::ecdsa::Signature::from_slice(&assertion.signature.to_bytes()).unwrap();
+    assert!(low_s.normalize_s().is_none());
+    let (r, s) = low_s.split_scalars();
+    let high_s = p256::ecdsa::Signature::from_scalars(r, -s).unwrap();
+
+    // The high `s` form of the same signature is rejected on decode.
+    assert!(matches!(
+        Secp256r1Signature::try_from(high_s.to_bytes().as_slice()),
+        Err(CryptoMaterialError::CanonicalRepresentationError)
+    ));
+
+    // The DER signature of a passkey is normalized, whatever its `s` is.
+    let normalized = low_s.to_bytes().to_vec();
+    for signature in [low_s, high_s] {
+        assertion.signature = Secp256r1Signature::from_der(signature.to_der().as_bytes()).unwrap();
+        assert_eq!(assertion.signature.to_bytes(), normalized);
+        assert!(assertion
+            .verify(&message, &private_key.public_key())
+            .is_ok());
+    }
+}
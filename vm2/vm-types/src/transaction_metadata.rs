@@ -4,6 +4,7 @@
 use crate::on_chain_resource::ChainId;
 use crate::token::token_code::TokenCode;
 use crate::transaction::authenticator::AuthenticationKeyPreimage;
+use crate::transaction::user_transaction_context::{EntryFunctionPayload, UserTransactionContext};
 use crate::transaction::RawUserTransaction;
 use crate::vm_status::{StatusCode, VMStatus};
 use crate::{
@@ -37,6 +38,10 @@ impl TransactionPayloadMetadata {
 pub struct TransactionMetadata {
     pub sender: AccountAddress,
     pub authentication_key_preimage: Vec<u8>,
+    /// The account paying the gas instead of the sender and its authentication key preimage.
+    pub fee_payer: Option<(AccountAddress, Vec<u8>)>,
+    /// The secondary signers of a multi-agent transaction and their authentication key preimages.
+    pub secondary_signers: Vec<(AccountAddress, Vec<u8>)>,
     pub sequence_number: u64,
     pub max_gas_amount: Gas,
     pub gas_unit_price: FeePerGasUnit,
@@ -45,14 +50,35 @@ pub struct TransactionMetadata {
     pub expiration_timestamp_secs: u64,
     pub chain_id: ChainId,
     pub payload: TransactionPayloadMetadata,
+    pub entry_function_payload: Option<EntryFunctionPayload>,
 }
 
 impl TransactionMetadata {
     pub fn new(txn: &SignedUserTransaction) -> Result<Self, VMStatus> {
-        Self::from_raw_txn_and_preimage(
+        let authenticator = txn.authenticator();
+        let mut metadata = Self::from_raw_txn_and_preimage(
             txn.raw_txn(),
-            txn.authenticator().authentication_key_preimage(),
-        )
+            authenticator.authentication_key_preimage(),
+        )?;
+        metadata.fee_payer = authenticator.fee_payer_address().zip(
+            authenticator
+                .fee_payer_authentication_key_preimage()
+                .map(|preimage| preimage.into_vec()),
+        );
+        metadata.set_secondary_signers(
+            authenticator
+                .secondary_signer_addresses()
+                .into_iter()
+                .zip(
+                    authenticator
+                        .secondary_signers()
+                        .iter()
+                        .map(|signer| signer.authentication_key_preimage().into_vec()),
+                )
+                .collect(),
+            txn.raw_txn().payload(),
+        );
+        Ok(metadata)
     }
 
     pub fn from_raw_txn_and_preimage(
@@ -73,6 +99,8 @@ impl TransactionMetadata {
         Ok(Self {
             sender: txn.sender(),
             authentication_key_preimage: auth_preimage.into_vec(),
+            fee_payer: None,
+            secondary_signers: vec![],
             sequence_number: txn.sequence_number(),
             max_gas_amount: txn.max_gas_amount().into(),
             gas_unit_price: txn.gas_unit_price().into(),
@@ -88,8 +116,12 @@ impl TransactionMetadata {
                     package.crypto_hash(),
                     package.package_address(),
                 ),
-                TransactionPayload::EntryFunction(_) => TransactionPayloadMetadata::ScriptFunction,
+                TransactionPayload::EntryFunction(_)
+                | TransactionPayload::EntryFunctionBatch(_) => {
+                    TransactionPayloadMetadata::ScriptFunction
+                }
             },
+            entry_function_payload: None,
         })
     }
     pub fn max_gas_amount(&self) -> Gas {
@@ -112,6 +144,52 @@ impl TransactionMetadata {
         &self.authentication_key_preimage
     }
 
+    pub fn fee_payer(&self) -> Option<AccountAddress> {
+        self.fee_payer.as_ref().map(|(address, _)| *address)
+    }
+
+    /// Set the secondary signers of a multi-agent transaction, the entry function payload of
+    /// `payload` is kept for the user transaction context of a multi-agent or fee payer
+    /// transaction only. The fee payer must be set before.
+    pub fn set_secondary_signers(
+        &mut self,
+        secondary_signers: Vec<(AccountAddress, Vec<u8>)>,
+        payload: &TransactionPayload,
+    ) {
+        let has_context = self.fee_payer.is_some() || !secondary_signers.is_empty();
+        self.entry_function_payload = match payload {
+            TransactionPayload::EntryFunction(entry_function) if has_context => {
+                Some(EntryFunctionPayload::new(
+                    *entry_function.module().address(),
+                    entry_function.module().name().to_string(),
+                    entry_function.function().to_string(),
+                    entry_function
+                        .ty_args()
+                        .iter()
+                        .map(|ty_arg| ty_arg.to_canonical_string())
+                        .collect(),
+                    entry_function.args().to_vec(),
+                ))
+            }
+            _ => None,
+        };
+        self.secondary_signers = secondary_signers;
+    }
+
+    pub fn secondary_signers(&self) -> Vec<AccountAddress> {
+        self.secondary_signers
+            .iter()
+            .map(|(address, _)| *address)
+            .collect()
+    }
+
+    /// The signers passed to the entry function or script, the sender goes first.
+    pub fn senders(&self) -> Vec<AccountAddress> {
+        let mut senders = vec![self.sender];
+        senders.extend(self.secondary_signers());
+        senders
+    }
+
     pub fn sequence_number(&self) -> u64 {
         self.sequence_number
     }
@@ -135,4 +213,23 @@ impl TransactionMetadata {
     pub fn payload(&self) -> &TransactionPayloadMetadata {
         &self.payload
     }
+
+    /// The context read by the `transaction_context` natives while the transaction runs. Only
+    /// multi-agent and fee payer transactions get one, the other transactions keep running with
+    /// the default native context so their execution does not change.
+    pub fn user_transaction_context(&self) -> Option<UserTransactionContext> {
+        if self.secondary_signers.is_empty() && self.fee_payer.is_none() {
+            return None;
+        }
+        Some(UserTransactionContext::new(
+            self.sender,
+            self.secondary_signers(),
+            self.fee_payer().unwrap_or(self.sender),
+            u64::from(self.max_gas_amount),
+            u64::from(self.gas_unit_price),
+            self.chain_id.id(),
+            self.entry_function_payload.clone(),
+            None,
+        ))
+    }
 }